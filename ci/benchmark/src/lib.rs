@@ -80,6 +80,32 @@ fn render_toggle_text<const N: u32>() -> impl Element<ToggleText<N>> {
     res
 }
 
+#[derive(State, Default)]
+struct ToggleWatch<const N: u32> {
+    state: Signal<bool>,
+}
+
+fn render_toggle_watch<const N: u32>() -> impl Element<ToggleWatch<N>> {
+    let mut res = e::div().child(e::button().id(BUTTON).on::<events::Click>(
+        |mut ctx: EventCtx<ToggleWatch<N>>, _| {
+            *ctx.state = !*ctx.state;
+        },
+    ));
+
+    for _ in 0..N {
+        // NOTE: Compared to `toggle text` this is the overhead of `ctx.watch` for a `Copy` value.
+        res = res.child(e::div().child(|mut ctx: RenderCtx<ToggleWatch<N>>| {
+            if ctx.watch(|ctx| *ctx.state) {
+                "ON"
+            } else {
+                "OFF"
+            }
+        }));
+    }
+
+    res
+}
+
 #[derive(State, Default)]
 struct ToggleAttr<const N: u32> {
     state: Signal<bool>,
@@ -172,6 +198,95 @@ fn render_toggle_at_once<const N: u32>() -> impl Element<ToggleAtOnce<N>> {
         })
 }
 
+#[derive(State, Default)]
+struct Label<const N: u32> {
+    count: Signal<u32>,
+    total: Signal<u32>,
+}
+
+fn label_button<const N: u32>() -> impl Element<Label<N>> {
+    e::button()
+        .id(BUTTON)
+        .on::<events::Click>(|mut ctx: EventCtx<Label<N>>, _| {
+            *ctx.count += 1;
+        })
+}
+
+fn render_label_format<const N: u32>() -> impl Element<Label<N>> {
+    let mut res = e::div().child(label_button::<N>());
+
+    for _ in 0..N {
+        res = res.child(e::p().text(|ctx: RenderCtx<Label<N>>| {
+            format!("Done {} of {} tasks", *ctx.count, *ctx.total)
+        }));
+    }
+
+    res
+}
+
+fn render_label_template<const N: u32>() -> impl Element<Label<N>> {
+    let mut res = e::div().child(label_button::<N>());
+
+    for _ in 0..N {
+        res = res.child(natrix::text_template!(
+            e::p(),
+            |ctx: RenderCtx<Label<N>>| "Done {} of {} tasks",
+            *ctx.count,
+            *ctx.total
+        ));
+    }
+
+    res
+}
+
+#[derive(Default)]
+struct Record {
+    value: u32,
+    other: u32,
+}
+
+#[derive(State, Default)]
+struct SelectFields<const N: u32> {
+    record: Signal<Record>,
+}
+
+fn render_select_fields<const N: u32>() -> impl Element<SelectFields<N>> {
+    let mut res = e::div().child(e::button().id(BUTTON).on::<events::Click>(
+        |mut ctx: EventCtx<SelectFields<N>>, _| {
+            ctx.record.other += 1;
+        },
+    ));
+
+    for _ in 0..N {
+        res = res.child(|mut ctx: RenderCtx<SelectFields<N>>| {
+            let value = ctx.select(|data| &data.record.value);
+            e::p().text(value)
+        });
+    }
+
+    res
+}
+
+#[derive(State, Default)]
+struct SplitFields<const N: u32> {
+    value: Signal<u32>,
+    other: Signal<u32>,
+}
+
+fn render_split_fields<const N: u32>() -> impl Element<SplitFields<N>> {
+    let mut res = e::div().child(e::button().id(BUTTON).on::<events::Click>(
+        |mut ctx: EventCtx<SplitFields<N>>, _| {
+            *ctx.other += 1;
+        },
+    ));
+
+    for _ in 0..N {
+        res = res.child(|ctx: RenderCtx<SplitFields<N>>| e::p().text(*ctx.value));
+    }
+
+    res
+}
+
 macro_rules! define_large_fields {
     ($($field:ident),* $(,)?) => {
         #[derive(State, Default)]
@@ -318,6 +433,17 @@ async fn run_benchmarks() {
         })
         .await;
 
+    natrix::test_utils::mount_test(
+        ToggleWatch::<10000>::default(),
+        render_toggle_watch::<10000>(),
+    );
+    bencher
+        .bench("toggle watched text", 0, |_| {
+            let button = natrix::test_utils::get(BUTTON.0);
+            button.click();
+        })
+        .await;
+
     natrix::test_utils::mount_test(
         ToggleAttr::<10000>::default(),
         render_toggle_attr::<10000>(),
@@ -362,6 +488,44 @@ async fn run_benchmarks() {
         })
         .await;
 
+    natrix::test_utils::mount_test(Label::<10000>::default(), render_label_format::<10000>());
+    bencher
+        .bench("update label format", 0, |_| {
+            let button = natrix::test_utils::get(BUTTON.0);
+            button.click();
+        })
+        .await;
+
+    natrix::test_utils::mount_test(Label::<10000>::default(), render_label_template::<10000>());
+    bencher
+        .bench("update label template", 0, |_| {
+            let button = natrix::test_utils::get(BUTTON.0);
+            button.click();
+        })
+        .await;
+
+    natrix::test_utils::mount_test(
+        SelectFields::<10000>::default(),
+        render_select_fields::<10000>(),
+    );
+    bencher
+        .bench("update unselected field", 0, |_| {
+            let button = natrix::test_utils::get(BUTTON.0);
+            button.click();
+        })
+        .await;
+
+    natrix::test_utils::mount_test(
+        SplitFields::<10000>::default(),
+        render_split_fields::<10000>(),
+    );
+    bencher
+        .bench("update unread signal", 0, |_| {
+            let button = natrix::test_utils::get(BUTTON.0);
+            button.click();
+        })
+        .await;
+
     natrix::test_utils::mount_test(LargeFields::default(), render_large_fields());
     bencher
         .bench("update large fields", 0, |_| {
@@ -8,6 +8,7 @@ const PANIC_ID: Id = natrix::id!();
 const BUTTON_ID: Id = natrix::id!();
 const RELOAD_ID: Id = natrix::id!();
 const IMG_ID: Id = natrix::id!();
+const LOG_TEXT: &str = "LOGGED ONLY IN DEV BUILDS";
 
 #[derive(State)]
 struct NotUsed;
@@ -48,6 +49,7 @@ fn render_hello_world() -> impl Element<HelloWorld> {
 }
 
 fn main() {
+    natrix::log::info!("{LOG_TEXT}");
     natrix::mount(
         HelloWorld {
             counter: Signal::new(0),
@@ -255,4 +257,24 @@ mod dist_tests {
             "There should be only one logo.png in the dist folder, even if included multiple times"
         );
     }
+
+    #[test]
+    #[ignore = "Does not have access to `dist` in current CI"]
+    fn logs_compiled_away() {
+        for file in std::fs::read_dir("./dist").unwrap() {
+            let path = file.unwrap().path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "wasm")
+            {
+                let wasm = std::fs::read(&path).unwrap();
+                assert!(
+                    !wasm
+                        .windows(super::LOG_TEXT.len())
+                        .any(|window| window == super::LOG_TEXT.as_bytes()),
+                    "`natrix::log` messages should not be in release builds"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,127 @@
+//! Report the size of the build output for `natrix build --analyze`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{compress, css, options, wasm_parser};
+use crate::prelude::*;
+
+/// Print the size of each output file, the size of each wasm section, and the css dce stats.
+pub(crate) fn print_report(
+    config: &options::BuildConfig,
+    wasm: &wasm_parser::WasmParseResult,
+    css: &css::CollectedCss,
+) -> Result<()> {
+    let mut files = Vec::new();
+    collect_files(&config.dist, &mut files)?;
+    let mut files = files
+        .into_iter()
+        .filter(|file| {
+            !file
+                .extension()
+                .is_some_and(|extension| extension == "gz" || extension == "br")
+        })
+        .map(|file| {
+            let size = fs::metadata(&file)?.len();
+            let name = file
+                .strip_prefix(&config.dist)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let compressed = fs::metadata(compress::compressed_path(&file, "br"))
+                .or_else(|_| fs::metadata(compress::compressed_path(&file, "gz")))
+                .ok()
+                .map(|metadata| metadata.len());
+            Ok((name, size, compressed))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    files.sort_by(|(_, a, _), (_, b, _)| b.cmp(a));
+
+    println!("📊 {}", "Output files".bright_blue());
+    let width = column_width(files.iter().map(|(name, _, _)| name.as_str()));
+    for (name, size, compressed) in &files {
+        let compressed = compressed
+            .map(|compressed| format!(" ({} compressed)", format_size(compressed)))
+            .unwrap_or_default();
+        println!(
+            "  {} {:>10}{}",
+            format!("{name:<width$}").cyan(),
+            format_size(*size).bold(),
+            compressed.bright_black()
+        );
+    }
+    let total = files
+        .iter()
+        .fold(0_u64, |total, (_, size, _)| total.saturating_add(*size));
+    println!(
+        "  {} {:>10}",
+        format!("{:<width$}", "total").bright_black(),
+        format_size(total).bold()
+    );
+
+    println!("📊 {}", "Wasm sections".bright_blue());
+    let mut sections = wasm.section_sizes.clone();
+    sections.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let width = column_width(sections.iter().map(|(name, _)| name.as_str()));
+    for (name, size) in &sections {
+        println!(
+            "  {} {:>10}",
+            format!("{name:<width$}").cyan(),
+            format_size(u64::try_from(*size).unwrap_or(u64::MAX)).bold()
+        );
+    }
+
+    if config.ssg && config.profile == options::BuildProfile::Release {
+        println!(
+            "🎨 {} unused css classes and ids removed",
+            css.removed_symbols.cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Recursively collect the files in the folder
+fn collect_files(folder: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The width of the widest name
+fn column_width<'a>(names: impl Iterator<Item = &'a str>) -> usize {
+    names.map(str::len).max().unwrap_or(0).max("total".len())
+}
+
+/// Format a byte count for humans
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let (tenths, unit) = if bytes < 1024 * 1024 {
+        (bytes.saturating_mul(10) / 1024, "KiB")
+    } else {
+        (bytes.saturating_mul(10) / (1024 * 1024), "MiB")
+    };
+    format!("{}.{} {unit}", tenths / 10, tenths % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_are_formatted() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
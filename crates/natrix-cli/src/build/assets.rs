@@ -10,8 +10,16 @@ use crate::prelude::*;
 /// Describes the translation from asset paths to wanted url
 #[derive(Default)]
 pub(crate) struct AssetManifest {
-    /// A mapping from runtime url to source path
-    pub(crate) mapping: HashMap<String, PathBuf>,
+    /// A mapping from runtime url to the asset
+    pub(crate) mapping: HashMap<String, ManifestEntry>,
+}
+
+/// A asset in the manifest
+pub(crate) struct ManifestEntry {
+    /// The source path
+    pub(crate) path: PathBuf,
+    /// The `Content-Type` to serve the asset with
+    pub(crate) content_type: String,
 }
 
 /// Collect the outputs of the macros
@@ -37,9 +45,13 @@ pub(crate) fn copy_assets_to_dist(
     manifest: &AssetManifest,
 ) -> Result<()> {
     let spinner = utils::create_spinner("📂 Copying Assets")?;
-    for (wanted_url, file) in &manifest.mapping {
+    for (wanted_url, entry) in &manifest.mapping {
         let target_file = config.dist.join(wanted_url);
-        if let Err(err) = fs::copy(file, target_file) {
+        let created_parent = match target_file.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        };
+        if let Err(err) = created_parent.and_then(|()| fs::copy(&entry.path, &target_file)) {
             spinner.finish();
             return Err(err.into());
         }
@@ -56,18 +68,44 @@ pub(crate) fn collect_asset_manifest(asset_files: Vec<PathBuf>) -> Result<AssetM
     let mut mapping = HashMap::with_capacity(asset_files.len());
     for file in asset_files {
         let mut file_reader = fs::File::open(file)?;
-        let natrix_shared::macros::MacroEmisson::Asset { path, emitted_path } =
-            natrix_shared::macros::bincode::decode_from_std_read(
-                &mut file_reader,
-                natrix_shared::macros::bincode_config(),
-            )?;
-        mapping.insert(emitted_path, path);
+        let emission = natrix_shared::macros::bincode::decode_from_std_read(
+            &mut file_reader,
+            natrix_shared::macros::bincode_config(),
+        )?;
+        expand_emission(&mut mapping, emission);
     }
 
     spinner.finish();
     Ok(AssetManifest { mapping })
 }
 
+/// Add the assets of a macro emission to the manifest mapping
+fn expand_emission(
+    mapping: &mut HashMap<String, ManifestEntry>,
+    emission: natrix_shared::macros::MacroEmisson,
+) {
+    match emission {
+        natrix_shared::macros::MacroEmisson::Asset {
+            path,
+            emitted_path,
+            content_type,
+        } => {
+            mapping.insert(emitted_path, ManifestEntry { path, content_type });
+        }
+        natrix_shared::macros::MacroEmisson::AssetDir { path, files } => {
+            for file in files {
+                mapping.insert(
+                    file.emitted_path,
+                    ManifestEntry {
+                        path: path.join(file.relative_path),
+                        content_type: file.content_type,
+                    },
+                );
+            }
+        }
+    }
+}
+
 /// Get all files in the sub folders of `MACRO_OUTPUT_DIR`
 pub(crate) fn get_macro_output_files(
     config: &options::BuildConfig,
@@ -77,3 +115,40 @@ pub(crate) fn get_macro_output_files(
         .flat_map(|folder| fs::read_dir(folder.path()).into_iter().flatten().flatten())
         .map(|entry| entry.path()))
 }
+
+#[cfg(test)]
+mod tests {
+    use natrix_shared::macros::{AssetDirFile, MacroEmisson};
+
+    use super::*;
+
+    #[test]
+    fn asset_dirs_expand_to_every_file() {
+        let mut mapping = HashMap::new();
+        expand_emission(
+            &mut mapping,
+            MacroEmisson::AssetDir {
+                path: PathBuf::from("/crate/public"),
+                files: vec![
+                    AssetDirFile {
+                        relative_path: String::from("font.woff2"),
+                        emitted_path: String::from("HASH-public/font.woff2"),
+                        content_type: String::from("font/woff2"),
+                    },
+                    AssetDirFile {
+                        relative_path: String::from("images/logo.png"),
+                        emitted_path: String::from("HASH-public/images/logo.png"),
+                        content_type: String::from("image/png"),
+                    },
+                ],
+            },
+        );
+
+        assert_eq!(mapping.len(), 2);
+        let logo = mapping.get("HASH-public/images/logo.png");
+        assert_eq!(
+            logo.map(|logo| (&logo.path, logo.content_type.as_str())),
+            Some((&PathBuf::from("/crate/public/images/logo.png"), "image/png"))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Precompressing the build output, so static file servers can serve it compressed without
+//! compressing it on every request.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use flate2::write::GzEncoder;
+
+use super::{options, utils};
+use crate::prelude::*;
+
+/// Write the compressed variants of the files next to them, as `file.gz` and `file.br`
+pub(crate) fn precompress(compression: options::Compression, files: &[&Path]) -> Result<()> {
+    if compression == options::Compression::None {
+        return Ok(());
+    }
+
+    let spinner = utils::create_spinner("🗜️ Compressing")?;
+    for file in files {
+        if compression.gzip() {
+            spinner.set_message(format!("gzip {}", file.display()));
+            gzip(file)?;
+        }
+        if compression.brotli() {
+            spinner.set_message(format!("brotli {}", file.display()));
+            brotli(file)?;
+        }
+    }
+    spinner.finish();
+    Ok(())
+}
+
+/// Get the path of the compressed variant of the file
+pub(crate) fn compressed_path(file: &Path, extension: &str) -> PathBuf {
+    let mut path = file.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+/// Write `file.gz`
+fn gzip(file: &Path) -> Result<()> {
+    let content = fs::read(file)?;
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&content)?;
+    fs::write(compressed_path(file, "gz"), encoder.finish()?)?;
+    Ok(())
+}
+
+/// Write `file.br`
+fn brotli(file: &Path) -> Result<()> {
+    let content = fs::read(file)?;
+    let mut compressed = Vec::new();
+    let params = brotli::enc::BrotliEncoderParams {
+        quality: 11,
+        ..Default::default()
+    };
+    brotli::BrotliCompress(&mut content.as_slice(), &mut compressed, &params)?;
+    fs::write(compressed_path(file, "br"), compressed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::process;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn compressed_path_appends_extension() {
+        assert_eq!(
+            compressed_path(Path::new("dist/code.wasm"), "br"),
+            PathBuf::from("dist/code.wasm.br")
+        );
+    }
+
+    #[test]
+    fn gzip_round_trips() -> Result<()> {
+        let directory = std::env::temp_dir().join(format!("natrix-gzip-{}", process::id()));
+        fs::create_dir_all(&directory)?;
+        let file = directory.join("code.js");
+        fs::write(&file, "console.log('hello');".repeat(100))?;
+
+        gzip(&file)?;
+
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(compressed_path(&file, "gz"))?)
+            .read_to_string(&mut decoded)?;
+        assert_eq!(decoded, fs::read_to_string(&file)?);
+
+        fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn brotli_round_trips() -> Result<()> {
+        let directory = std::env::temp_dir().join(format!("natrix-brotli-{}", process::id()));
+        fs::create_dir_all(&directory)?;
+        let file = directory.join("code.js");
+        fs::write(&file, "console.log('hello');".repeat(100))?;
+
+        brotli(&file)?;
+
+        let mut decoded = String::new();
+        brotli::Decompressor::new(fs::File::open(compressed_path(&file, "br"))?, 4096)
+            .read_to_string(&mut decoded)?;
+        assert_eq!(decoded, fs::read_to_string(&file)?);
+
+        fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+}
@@ -11,25 +11,130 @@ use super::{CSS_OUTPUT_NAME, options, utils};
 use crate::prelude::*;
 use crate::project_gen::FEATURE_BUNDLE;
 
+/// The collected css, split into what should be inlined and what should be linked
+#[derive(Default)]
+pub(crate) struct CollectedCss {
+    /// Css to inline directly in the html head
+    pub(crate) critical: Option<String>,
+    /// The css file to link to
+    pub(crate) file: Option<PathBuf>,
+    /// The number of classes and ids removed because the wasm does not use them
+    pub(crate) removed_symbols: usize,
+}
+
 /// Collect css from the stdout of a custom bundle build
 pub(crate) fn collect_css(
     config: &options::BuildConfig,
     parse_result: &super::wasm_parser::WasmParseResult,
-) -> Result<PathBuf> {
+) -> Result<CollectedCss> {
     let mut css_content = extract_css()?;
 
+    let mut removed_symbols = 0;
     if config.profile == options::BuildProfile::Release {
-        css_content = optimize_css(&css_content, parse_result)?;
+        (css_content, removed_symbols) = optimize_css(&css_content, parse_result)?;
     }
 
-    let output_path = config.dist.join(CSS_OUTPUT_NAME);
-    fs::write(&output_path, css_content)?;
+    let mut critical = None;
+    if let Some(budget) = config.critical_css {
+        let (critical_css, deferred_css) = split_critical_css(&css_content, budget)?;
+        println!(
+            "🎨 {} ({} bytes inlined, {} bytes deferred)",
+            "Split critical css".bright_blue(),
+            critical_css.len().cyan(),
+            deferred_css.len().cyan()
+        );
+        if !critical_css.is_empty() {
+            critical = Some(critical_css);
+        }
+        css_content = deferred_css;
+    }
+
+    let file = if css_content.is_empty() && critical.is_some() {
+        None
+    } else {
+        let output_path = config.dist.join(CSS_OUTPUT_NAME);
+        fs::write(&output_path, css_content)?;
+        Some(output_path)
+    };
+
+    Ok(CollectedCss {
+        critical,
+        file,
+        removed_symbols,
+    })
+}
+
+/// Split the css into a critical chunk of at most `budget` bytes, and a deferred chunk.
+///
+/// The critical chunk is the longest prefix of top level rules that fits in the budget,
+/// using a prefix means the cascade order is the same once the deferred chunk is loaded.
+fn split_critical_css(css_content: &str, budget: usize) -> Result<(String, String)> {
+    use lightningcss::traits::ToCss;
+
+    let styles = lightningcss::stylesheet::StyleSheet::parse(
+        css_content,
+        lightningcss::stylesheet::ParserOptions {
+            filename: String::from("<BUNDLED CSS>.css"),
+            ..Default::default()
+        },
+    )
+    .map_err(|err| anyhow!("Failed to parse css {err}"))?;
+
+    let mut critical = String::new();
+    let mut deferred = String::new();
+    let mut budget_exceeded = false;
+
+    for rule in &styles.rules.0 {
+        let rule = rule.to_css_string(lightningcss::printer::PrinterOptions {
+            minify: true,
+            ..Default::default()
+        })?;
+
+        let fits = critical
+            .len()
+            .checked_add(rule.len())
+            .is_some_and(|total| total <= budget);
+        if budget_exceeded || !fits {
+            budget_exceeded = true;
+            deferred.push_str(&rule);
+        } else {
+            critical.push_str(&rule);
+        }
+    }
+
+    Ok((critical, deferred))
+}
+
+/// The definition site of a `class!()` or `id!()`, as reported by bundle mode
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SymbolSite {
+    /// The generated symbol
+    pub(crate) symbol: String,
+    /// The macro that generated it, `class` or `id`
+    pub(crate) kind: String,
+    /// The file of the macro call, relative to the workspace root
+    pub(crate) file: String,
+    /// The line of the macro call, starting at 1
+    pub(crate) line: usize,
+    /// The column of the macro call, starting at 1
+    pub(crate) column: usize,
+}
 
-    Ok(output_path)
+/// The output of running the binary in bundle mode
+pub(crate) struct BundleOutput {
+    /// The collected css
+    pub(crate) css: String,
+    /// Where the classes and ids were defined
+    pub(crate) symbols: Vec<SymbolSite>,
 }
 
 /// Extract the css from the binary
-fn extract_css() -> Result<String> {
+pub(crate) fn extract_css() -> Result<String> {
+    Ok(run_bundle()?.css)
+}
+
+/// Run the binary in bundle mode, extracting the css and symbol definition sites
+pub(crate) fn run_bundle() -> Result<BundleOutput> {
     let spinner = utils::create_spinner("🎨 Extracting css")?;
 
     let mut command = Command::new("cargo");
@@ -38,14 +143,44 @@ fn extract_css() -> Result<String> {
         .args(["--features", FEATURE_BUNDLE])
         .args(["--color", "always"]);
 
-    utils::run_with_spinner(command, spinner)
+    let output = utils::run_with_spinner(command, spinner)?;
+    Ok(parse_bundle_output(&output))
+}
+
+/// Split the bundle output into the css and the symbol sites following it
+fn parse_bundle_output(output: &str) -> BundleOutput {
+    let marker = format!("\n{}\n", natrix_shared::SYMBOLS_MARKER);
+    let Some((css, symbols)) = output.split_once(&marker) else {
+        return BundleOutput {
+            css: output.to_owned(),
+            symbols: Vec::new(),
+        };
+    };
+
+    let symbols = symbols
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            Some(SymbolSite {
+                symbol: parts.next()?.to_owned(),
+                kind: parts.next()?.to_owned(),
+                file: parts.next()?.replace('\\', "/"),
+                line: parts.next()?.parse().ok()?,
+                column: parts.next()?.parse().ok()?,
+            })
+        })
+        .collect();
+    BundleOutput {
+        css: format!("{css}\n"),
+        symbols,
+    }
 }
 
-/// Optimize the given css string
+/// Optimize the given css string, returning it and the number of unused symbols removed
 fn optimize_css(
     css_content: &str,
     parse_result: &super::wasm_parser::WasmParseResult,
-) -> Result<String> {
+) -> Result<(String, usize)> {
     let mut styles = lightningcss::stylesheet::StyleSheet::parse(
         css_content,
         lightningcss::stylesheet::ParserOptions {
@@ -59,13 +194,20 @@ fn optimize_css(
     )
     .map_err(|err| anyhow!("Failed to parse css {err}"))?;
 
+    dedupe_rules(&mut styles)?;
+
     let wasm_strings = &parse_result.data_strings;
     let mut unused_symbols = get_symbols(&mut styles);
     // `wasm_strings` is a vec of data sections, so we need to check if the symbol is in any of
     // them as wasm optimizes multiple string literals to the same section
     unused_symbols.retain(|symbol| wasm_strings.iter().all(|x| !x.contains(symbol)));
+    let removed_symbols = unused_symbols.len();
 
-    let targets = lightningcss::targets::Targets::default();
+    // NOTE: Natrix emits native css nesting, which we flatten for browsers without support.
+    let targets = lightningcss::targets::Targets {
+        include: lightningcss::targets::Features::Nesting,
+        ..Default::default()
+    };
     styles.minify(lightningcss::stylesheet::MinifyOptions {
         targets,
         unused_symbols,
@@ -81,7 +223,55 @@ fn optimize_css(
 
     let css_content = css_content.code;
 
-    Ok(css_content)
+    Ok((css_content, removed_symbols))
+}
+
+/// Remove top level rules that are exact duplicates of a later rule.
+///
+/// This happens when multiple crates emit the same css, for example a shared reset.
+/// Only the *last* copy is kept, as that is the one deciding the cascade order.
+/// Rules where the first occurrence matters (such as `@layer` ordering) are left alone.
+fn dedupe_rules(styles: &mut lightningcss::stylesheet::StyleSheet) -> Result<()> {
+    use lightningcss::rules::CssRule;
+    use lightningcss::traits::ToCss;
+
+    let rules = &mut styles.rules.0;
+    let mut seen = HashSet::new();
+    let mut keep = vec![true; rules.len()];
+
+    for (rule, keep) in rules.iter().zip(keep.iter_mut()).rev() {
+        if !matches!(
+            rule,
+            CssRule::Style(_)
+                | CssRule::Media(_)
+                | CssRule::Supports(_)
+                | CssRule::Keyframes(_)
+                | CssRule::FontFace(_)
+        ) {
+            continue;
+        }
+
+        let printed = rule.to_css_string(lightningcss::printer::PrinterOptions {
+            minify: true,
+            ..Default::default()
+        })?;
+        if !seen.insert(printed) {
+            *keep = false;
+        }
+    }
+
+    let removed = keep.iter().filter(|keep| !**keep).count();
+    if removed > 0 {
+        println!(
+            "🎨 {} {} duplicate css rules",
+            "Removed".bright_blue(),
+            removed.cyan()
+        );
+    }
+
+    let mut keep = keep.into_iter();
+    rules.retain(|_| keep.next().unwrap_or(true));
+    Ok(())
 }
 
 /// Visitor to extract symbosl from a stylesheet
@@ -123,6 +313,14 @@ impl<'i> lightningcss::visitor::Visitor<'i> for SymbolVisitor {
             }
             *rule = lightningcss::rules::CssRule::Ignored;
         }
+        // NOTE: lightningcss also removes `@keyframes` named after a unused symbol,
+        // but keyframes are referenced from the css and not the wasm, so they are always kept.
+        // This also keeps a class with the same name, which is the lesser evil.
+        if let lightningcss::rules::CssRule::Keyframes(keyframes) = rule
+            && let lightningcss::rules::keyframes::KeyframesName::Ident(name) = &keyframes.name
+        {
+            self.keep.insert(name.0.to_string());
+        }
         rule.visit_children(self)
     }
 
@@ -172,3 +370,190 @@ pub(crate) fn get_symbols(
     let _ = stylesheet.visit(&mut visitor);
     visitor.symbols.difference(&visitor.keep).cloned().collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::wasm_parser::WasmParseResult;
+
+    fn parse_result(strings: &[&str]) -> WasmParseResult {
+        WasmParseResult {
+            custom_sections: std::collections::HashMap::new(),
+            data_strings: strings.iter().map(|string| (*string).to_owned()).collect(),
+            code_section_offset: 0,
+            section_sizes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bundle_output_is_split() {
+        let output = format!(
+            ".a{{color:red}}\n{}\nabc\tclass\tsrc/main.rs\t3\t21\nxyz\tid\tsrc/app.rs\t10\t5\n",
+            natrix_shared::SYMBOLS_MARKER
+        );
+        let bundle = parse_bundle_output(&output);
+        assert_eq!(bundle.css, ".a{color:red}\n");
+        assert_eq!(
+            bundle.symbols,
+            vec![
+                SymbolSite {
+                    symbol: String::from("abc"),
+                    kind: String::from("class"),
+                    file: String::from("src/main.rs"),
+                    line: 3,
+                    column: 21,
+                },
+                SymbolSite {
+                    symbol: String::from("xyz"),
+                    kind: String::from("id"),
+                    file: String::from("src/app.rs"),
+                    line: 10,
+                    column: 5,
+                },
+            ]
+        );
+
+        let bundle = parse_bundle_output(".a{color:red}\n");
+        assert_eq!(bundle.css, ".a{color:red}\n");
+        assert!(bundle.symbols.is_empty());
+    }
+
+    #[test]
+    fn unused_classes_in_supports_are_removed() -> Result<()> {
+        let css = ".used{color:red}\
+            @supports (backdrop-filter:blur(4px)){.used{color:blue}.unused{color:green}}\
+            @supports (display:grid){.unused{display:grid}}";
+
+        let (result, removed) = optimize_css(css, &parse_result(&["used"]))?;
+        assert_eq!(
+            result,
+            ".used{color:red}@supports (backdrop-filter:blur(4px)){.used{color:#00f}}"
+        );
+        assert_eq!(removed, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn nesting_is_flattened() -> Result<()> {
+        let css = ".card{color:red;&:hover{color:blue}.title{color:green}.unused{color:red}}";
+
+        let (result, _) = optimize_css(css, &parse_result(&["card", "title"]))?;
+        assert_eq!(
+            result,
+            ".card{color:red}.card:hover{color:#00f}.card .title{color:green}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn keyframes_named_like_unused_symbol_are_kept() -> Result<()> {
+        let css = ".slide{color:red}\
+            .box{animation:1s slide}\
+            @keyframes slide{0%{opacity:0}to{opacity:1}}";
+
+        let (result, _) = optimize_css(css, &parse_result(&["box"]))?;
+        assert_eq!(
+            result,
+            ".slide{color:red}.box{animation:1s slide}@keyframes slide{0%{opacity:0}to{opacity:1}}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unused_escaped_utilities_are_removed() -> Result<()> {
+        // NOTE: This is how `natrix::css::utilities` escapes `.p-4` and `.p-8`
+        let css = ".\\70 \\2d \\34 {padding:1rem}.\\70 \\2d \\38 {padding:2rem}";
+
+        let (result, removed) = optimize_css(css, &parse_result(&["p-4"]))?;
+        assert_eq!(result, ".p-4{padding:1rem}");
+        assert_eq!(removed, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn exact_duplicate_keeps_last_copy() -> Result<()> {
+        let css = ".a{color:red}.b{color:blue}.a{color:red}.b{margin:0}";
+
+        let (result, _) = optimize_css(css, &parse_result(&["a", "b"]))?;
+        assert_eq!(result, ".b{color:#00f}.a{color:red}.b{margin:0}");
+        Ok(())
+    }
+
+    #[test]
+    fn non_adjacent_duplicate_keeps_cascade() -> Result<()> {
+        // NOTE: `.a` has to keep winning over `.b` for elements with both classes
+        let css = ".a{color:red}.b{color:blue}.a{color:red}";
+
+        let (result, _) = optimize_css(css, &parse_result(&["a", "b"]))?;
+        assert_eq!(result, ".b{color:#00f}.a{color:red}");
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_conditional_rules_are_removed() -> Result<()> {
+        let css = "@media (width>=600px){.a{color:red}}\
+            @supports (display:grid){.a{display:grid}}\
+            .b{color:blue}\
+            @media (width>=600px){.a{color:red}}\
+            @supports (display:grid){.a{display:grid}}";
+
+        let (result, _) = optimize_css(css, &parse_result(&["a", "b"]))?;
+        assert_eq!(
+            result,
+            ".b{color:#00f}@media (width>=600px){.a{color:red}}@supports (display:grid){.a{display:grid}}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn declaration_order_is_not_merged() -> Result<()> {
+        let css = ".a{color:red;margin:0}.b{color:blue}.a{margin:0;color:red}";
+
+        // NOTE: Checked before minifying, as lightningcss normalizes the declarations itself
+        let mut styles = lightningcss::stylesheet::StyleSheet::parse(
+            css,
+            lightningcss::stylesheet::ParserOptions::default(),
+        )
+        .map_err(|err| anyhow!("Failed to parse css {err}"))?;
+        dedupe_rules(&mut styles)?;
+        assert_eq!(styles.rules.0.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn zero_budget_defers_everything() -> Result<()> {
+        let (critical, deferred) = split_critical_css(".a{color:red}.b{color:green}", 0)?;
+        assert_eq!(critical, "");
+        assert_eq!(deferred, ".a{color:red}.b{color:green}");
+        Ok(())
+    }
+
+    #[test]
+    fn budget_at_rule_boundary_includes_rule() -> Result<()> {
+        let first = ".a{color:red}";
+        let (critical, deferred) = split_critical_css(".a{color:red}.b{color:green}", first.len())?;
+        assert_eq!(critical, first);
+        assert_eq!(deferred, ".b{color:green}");
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_first_rule_defers_everything() -> Result<()> {
+        // NOTE: `.c` would fit on its own, but including it would break the cascade order
+        let (critical, deferred) = split_critical_css(".a{color:red;margin:0}.c{top:0}", 10)?;
+        assert_eq!(critical, "");
+        assert_eq!(deferred, ".a{color:red;margin:0}.c{top:0}");
+        Ok(())
+    }
+
+    #[test]
+    fn split_preserves_rule_order() -> Result<()> {
+        let css = ".a{color:red}@media (width>=600px){.a{color:green}}.b{margin:0}.c{top:0}";
+        for budget in 0..=css.len() {
+            let (critical, deferred) = split_critical_css(css, budget)?;
+            assert!(critical.len() <= budget);
+            assert_eq!(format!("{critical}{deferred}"), css);
+        }
+        Ok(())
+    }
+}
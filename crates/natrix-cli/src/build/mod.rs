@@ -1,14 +1,17 @@
 //! Build a natrix project
 
 use std::fs;
-use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256, Sha384};
+
 use crate::prelude::*;
-use crate::{options, utils};
+use crate::{dev_server, options, utils};
 
+mod analyze;
 pub(crate) mod assets;
-mod css;
+pub(crate) mod compress;
+pub(crate) mod css;
 pub(crate) mod sourcemap;
 mod wasm_js;
 mod wasm_parser;
@@ -19,6 +22,8 @@ const MACRO_OUTPUT_DIR: &str = "macro";
 const BINDGEN_OUTPUT_NAME: &str = "code";
 /// Name of the collected css
 const CSS_OUTPUT_NAME: &str = "styles.css";
+/// Name of the external startup script used in csp mode
+const BOOTSTRAP_OUTPUT_NAME: &str = "bootstrap.js";
 
 /// Build a project
 pub(crate) fn build(config: &options::BuildConfig) -> Result<assets::AssetManifest> {
@@ -29,7 +34,7 @@ pub(crate) fn build(config: &options::BuildConfig) -> Result<assets::AssetManife
         );
     }
 
-    println!("🧹 {}", "Cleaning dist".bright_black(),);
+    println!("🧹 {}", "Cleaning dist".bright_black());
     let _ = fs::remove_dir_all(&config.dist);
 
     if config.invalidate_cache {
@@ -37,52 +42,87 @@ pub(crate) fn build(config: &options::BuildConfig) -> Result<assets::AssetManife
     }
     let _ = fs::create_dir_all(config.temp_dir.join(MACRO_OUTPUT_DIR));
 
-    println!(
-        "🚧 {} (using profile {})",
-        "Starting Build".bright_blue(),
-        config.profile.readable().cyan()
-    );
+    if config.cargo_profile == config.profile.cargo() {
+        println!(
+            "🚧 {} (using profile {})",
+            "Starting Build".bright_blue(),
+            config.profile.readable().cyan()
+        );
+    } else {
+        println!(
+            "🚧 {} (using profile {} with cargo profile {})",
+            "Starting Build".bright_blue(),
+            config.profile.readable().cyan(),
+            config.cargo_profile.cyan()
+        );
+    }
     std::fs::create_dir_all(&config.dist).context("Creating dist")?;
 
     let source_wasm_file = wasm_js::build_wasm(config).context("Building wasm")?;
     let (wasm_file, js_file) = wasm_js::wasm_bindgen(config, &source_wasm_file)?;
-    if config.profile == options::BuildProfile::Release {
-        let rename_map = wasm_js::optimize_wasm(&wasm_file)?;
-        wasm_js::minimize_js(&js_file, rename_map)?;
-    }
+    let release_sourcemap = config.profile == options::BuildProfile::Release && config.sourcemap;
+    let js_sourcemap = if config.profile == options::BuildProfile::Release {
+        let rename_map = wasm_js::optimize_wasm(&wasm_file, release_sourcemap)?;
+        wasm_js::minimize_js(&js_file, rename_map, release_sourcemap)?
+    } else {
+        None
+    };
 
     let wasm_file = cache_bust_file(config, wasm_file)?;
     let js_file = cache_bust_file(config, js_file)?;
 
     // Parse wasm file once for both sourcemap and CSS optimization
-    let wasm_parse_result = if config.profile == options::BuildProfile::Dev || config.ssg {
+    let wasm_parse_result = if config.profile == options::BuildProfile::Dev
+        || release_sourcemap
+        || config.ssg
+        || config.analyze
+    {
         Some(wasm_parser::parse_wasm_file(&wasm_file)?)
     } else {
         None
     };
 
-    if config.profile == options::BuildProfile::Dev {
+    if config.profile == options::BuildProfile::Dev || release_sourcemap {
         println!("{}", "🗺️ Generating source map".bright_blue());
         let parse_result = wasm_parse_result
             .as_ref()
             .ok_or_else(|| anyhow!("Wasm parse result missing for sourcemap generation"))?;
-        sourcemap::create_sourcemap(&wasm_file, parse_result)?;
+        let target = if release_sourcemap {
+            sourcemap::SourcemapTarget::External
+        } else {
+            sourcemap::SourcemapTarget::Inline
+        };
+        sourcemap::create_sourcemap(&wasm_file, parse_result, target)?;
+    }
+    if let Some(js_sourcemap) = js_sourcemap {
+        sourcemap::write_js_sourcemap(&js_file, &js_sourcemap)?;
     }
 
     let asset_manifest = assets::collect_macro_output(config)?;
 
-    let css_file = if config.ssg {
+    let css = if config.ssg {
         let parse_result = wasm_parse_result
             .as_ref()
             .ok_or_else(|| anyhow!("Wasm parse result missing for CSS optimization"))?;
-        let css_file = css::collect_css(config, parse_result)?;
-        let css_file = cache_bust_file(config, css_file)?;
-        Some(css_file)
+        let mut css = css::collect_css(config, parse_result)?;
+        css.file = css
+            .file
+            .map(|css_file| cache_bust_file(config, css_file))
+            .transpose()?;
+        css
     } else {
-        None
+        css::CollectedCss::default()
     };
 
-    generate_html(config, &wasm_file, &js_file, css_file)?;
+    generate_html(config, &wasm_file, &js_file, &css)?;
+    compress::precompress(config.compress, &[&wasm_file, &js_file])?;
+
+    if config.analyze {
+        let parse_result = wasm_parse_result
+            .as_ref()
+            .ok_or_else(|| anyhow!("Wasm parse result missing for size analysis"))?;
+        analyze::print_report(config, parse_result, &css)?;
+    }
 
     println!(
         "📦 {} {}",
@@ -97,54 +137,101 @@ pub(crate) fn build(config: &options::BuildConfig) -> Result<assets::AssetManife
 pub(crate) fn generate_html(
     config: &options::BuildConfig,
     wasm_file: &Path,
-    js_file: &Path,
-    css_file: Option<PathBuf>,
+    js_path: &Path,
+    css: &css::CollectedCss,
 ) -> Result<()> {
     let base_path = &config.base_path;
 
     let html_file = config.dist.join("index.html");
-    let js_file = utils::get_filename(js_file)?;
+    let js_file = utils::get_filename(js_path)?;
     let wasm_file = utils::get_filename(wasm_file)?;
 
-    let style_link = if let Some(css_file) = css_file {
-        let css_file = utils::get_filename(&css_file)?;
-        format!(r#"<link rel="stylesheet" href="{base_path}/{css_file}"/>"#)
+    let style_link = if let Some(css_path) = &css.file {
+        let css_file = utils::get_filename(css_path)?;
+        let integrity = integrity_attributes(config, css_path)?;
+        if css.critical.is_some() {
+            // NOTE: The critical css is already inlined, so the rest can load without blocking.
+            // The `onload` swap needs javascript, so the `noscript` link is the fallback.
+            format!(
+                r#"<link rel="preload" as="style" href="{base_path}/{css_file}"{integrity} onload="this.onload=null;this.rel='stylesheet'"/>
+        <noscript><link rel="stylesheet" href="{base_path}/{css_file}"{integrity}/></noscript>"#
+            )
+        } else {
+            format!(r#"<link rel="stylesheet" href="{base_path}/{css_file}"{integrity}/>"#)
+        }
     } else {
         String::new()
     };
 
-    let js_reload = if let Some(port) = config.live_reload {
+    // NOTE: The js is imported from the bootstrap module script, which cant carry a `integrity`
+    // attribute for its imports. So we preload it with one, and also declare it in the import
+    // map for browsers supporting import map integrity. (import maps are inline scripts, so
+    // they are skipped in csp mode)
+    let js_integrity = if config.sri {
+        let hash = sri_hash(js_path)?;
+        let import_map = if config.csp {
+            String::new()
+        } else {
+            format!(
+                r#"<script type="importmap">{{"integrity":{{"{base_path}/{js_file}":"{hash}"}}}}</script>"#
+            )
+        };
         format!(
-            "
-            const reload_ws = new WebSocket(`ws://${{window.location.hostname}}:{port}`);
-            reload_ws.onmessage = (event) => {{
-                location.reload();
-            }};
-            "
+            r#"{import_map}
+        <link rel="modulepreload" href="{base_path}/{js_file}" integrity="{hash}" crossorigin="anonymous"/>"#
         )
     } else {
         String::new()
     };
+    let critical_style = if let Some(critical) = &css.critical {
+        format!("<style>{critical}</style>")
+    } else {
+        String::new()
+    };
+
+    let js_reload = live_reload_script(config);
+    let bootstrap = format!(
+        r#"
+            import init from "{base_path}/{js_file}";
+            init({{module_or_path:"{base_path}/{wasm_file}"}});
+            {js_reload}
+        "#
+    );
+    let bootstrap_script = if config.csp {
+        let bootstrap_path = config.dist.join(BOOTSTRAP_OUTPUT_NAME);
+        fs::write(&bootstrap_path, bootstrap.trim())?;
+        let bootstrap_path = cache_bust_file(config, bootstrap_path)?;
+        let bootstrap_file = utils::get_filename(&bootstrap_path)?;
+        let integrity = integrity_attributes(config, &bootstrap_path)?;
+        format!(r#"<script type="module" src="{base_path}/{bootstrap_file}"{integrity}></script>"#)
+    } else {
+        format!(r#"<script type="module">{bootstrap}</script>"#)
+    };
+
+    // NOTE: Inline `style` attributes are blocked by a strict csp as well.
+    let (noscript_style, loading_style) = if config.csp {
+        ("", "")
+    } else {
+        (r#" style="color: red""#, r#" style="color: orange""#)
+    };
 
     let content = format!(
         r#"
 <!doctype html>
 <html>
     <head>
+        {critical_style}
         {style_link}
+        {js_integrity}
         <link rel="preload" as="fetch" href="{base_path}/{wasm_file}" crossorigin="anonymous"/>
         <meta name="viewport" content="width=device-width, initial-scale=1">
     </head>
     <body>
         <noscript>
-            <h1 style="color: red">This website requires Javascript and Wasm support.</h1>
+            <h1{noscript_style}>This website requires Javascript and Wasm support.</h1>
         </noscript>
-        <div id="{}" style="color: orange">Currently loading</div>
-        <script type="module">
-            import init from "{base_path}/{js_file}";
-            init({{module_or_path:"{base_path}/{wasm_file}"}});
-            {js_reload}
-        </script>
+        <div id="{}"{loading_style}>Currently loading</div>
+        {bootstrap_script}
     </body>
 </html>
     "#,
@@ -156,6 +243,52 @@ pub(crate) fn generate_html(
     Ok(())
 }
 
+/// The script connecting to the dev server for live reloading, if enabled
+fn live_reload_script(config: &options::BuildConfig) -> String {
+    if let Some(port) = config.live_reload {
+        let css_message = dev_server::CSS_MESSAGE;
+        let style_id = natrix_shared::DEV_STYLE_ID;
+        format!(
+            r#"
+            const reload_protocol = window.location.protocol === "https:" ? "wss" : "ws";
+            const reload_ws = new WebSocket(`${{reload_protocol}}://${{window.location.hostname}}:{port}`);
+            reload_ws.onmessage = (event) => {{
+                const newline = event.data.indexOf("\n");
+                const kind = newline === -1 ? event.data : event.data.slice(0, newline);
+                const style = document.getElementById("{style_id}");
+                if (kind === "{css_message}" && style) {{
+                    style.textContent = event.data.slice(newline + 1);
+                }} else {{
+                    location.reload();
+                }}
+            }};
+            "#
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Calculate the subresource integrity hash of the given file
+fn sri_hash(file: &Path) -> Result<String> {
+    let content = fs::read(file)?;
+    let hash = Sha384::digest(&content);
+    Ok(format!("sha384-{}", data_encoding::BASE64.encode(&hash)))
+}
+
+/// Get the `integrity` and `crossorigin` attributes for the given file, if sri is enabled
+fn integrity_attributes(config: &options::BuildConfig, file: &Path) -> Result<String> {
+    if config.sri {
+        let hash = sri_hash(file)?;
+        Ok(format!(r#" integrity="{hash}" crossorigin="anonymous""#))
+    } else {
+        Ok(String::new())
+    }
+}
+
+/// How many bytes of the content hash to include in cache busted filenames
+const CONTENT_HASH_LENGTH: usize = 16;
+
 /// Moves the given file to a new location in accordane with cache busting options
 /// Returns the new file location
 pub(crate) fn cache_bust_file(
@@ -178,12 +311,13 @@ pub(crate) fn cache_bust_file(
             format!("{encoded_timestamp}-{original_filename}")
         }
         options::CacheBustOption::Content => {
+            // NOTE: `DefaultHasher` is not stable across rust versions or platforms,
+            // which would produce different filenames for the same content on different machines.
             let content = fs::read(&original_file)?;
-            let mut hasher = DefaultHasher::default();
-            content.hash(&mut hasher);
-            let hash = hasher.finish();
+            let hash = Sha256::digest(&content);
+            let hash = hash.get(..CONTENT_HASH_LENGTH).unwrap_or(hash.as_slice());
 
-            let encoded_hash = data_encoding::BASE64URL_NOPAD.encode(&hash.to_le_bytes());
+            let encoded_hash = data_encoding::BASE64URL_NOPAD.encode(hash);
             format!("{encoded_hash}-{original_filename}")
         }
     };
@@ -193,3 +327,110 @@ pub(crate) fn cache_bust_file(
     fs::rename(original_file, &new_file)?;
     Ok(new_file)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(dist: PathBuf, csp: bool) -> options::BuildConfig {
+        options::BuildConfig {
+            profile: options::BuildProfile::Release,
+            cargo_profile: String::from("release"),
+            dist: dist.clone(),
+            temp_dir: dist,
+            live_reload: None,
+            cache_bust: options::CacheBustOption::Content,
+            base_path: Box::from(""),
+            invalidate_cache: false,
+            ssg: false,
+            critical_css: None,
+            sri: false,
+            compress: options::Compression::None,
+            csp,
+            sourcemap: false,
+            analyze: false,
+        }
+    }
+
+    #[test]
+    fn csp_moves_bootstrap_to_external_file() -> Result<()> {
+        let dist = std::env::temp_dir().join(format!("natrix-csp-{}", std::process::id()));
+        fs::create_dir_all(&dist)?;
+        let config = test_config(dist.clone(), true);
+
+        generate_html(
+            &config,
+            Path::new("code_bg.wasm"),
+            Path::new("code.js"),
+            &css::CollectedCss::default(),
+        )?;
+
+        let html = fs::read_to_string(dist.join("index.html"))?;
+        assert!(!html.contains("<script type=\"module\">"));
+        assert!(!html.contains("style="));
+
+        let bootstrap = fs::read_dir(&dist)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .find(|path| path.to_string_lossy().ends_with(BOOTSTRAP_OUTPUT_NAME))
+            .ok_or_else(|| anyhow!("bootstrap.js not written"))?;
+        let bootstrap_file = utils::get_filename(&bootstrap)?;
+        assert!(html.contains(&format!(
+            r#"<script type="module" src="/{bootstrap_file}">"#
+        )));
+        assert!(fs::read_to_string(&bootstrap)?.contains(r#"import init from "/code.js";"#));
+
+        fs::remove_dir_all(dist)?;
+        Ok(())
+    }
+
+    #[test]
+    fn deferred_css_has_noscript_fallback() -> Result<()> {
+        let dist = std::env::temp_dir().join(format!("natrix-critical-{}", std::process::id()));
+        fs::create_dir_all(&dist)?;
+        let config = test_config(dist.clone(), false);
+
+        generate_html(
+            &config,
+            Path::new("code_bg.wasm"),
+            Path::new("code.js"),
+            &css::CollectedCss {
+                critical: Some(String::from("body{margin:0}")),
+                file: Some(dist.join("styles.css")),
+                removed_symbols: 0,
+            },
+        )?;
+
+        let html = fs::read_to_string(dist.join("index.html"))?;
+        assert!(html.contains(r#"<link rel="preload" as="style" href="/styles.css""#));
+        assert!(
+            html.contains(r#"<noscript><link rel="stylesheet" href="/styles.css"/></noscript>"#)
+        );
+
+        fs::remove_dir_all(dist)?;
+        Ok(())
+    }
+
+    #[test]
+    fn base_path_prefixes_urls() -> Result<()> {
+        let dist = std::env::temp_dir().join(format!("natrix-base-path-{}", std::process::id()));
+        fs::create_dir_all(&dist)?;
+        let mut config = test_config(dist.clone(), false);
+        config.base_path = Box::from("/myapp");
+
+        generate_html(
+            &config,
+            Path::new("code_bg.wasm"),
+            Path::new("code.js"),
+            &css::CollectedCss::default(),
+        )?;
+
+        let html = fs::read_to_string(dist.join("index.html"))?;
+        assert!(html.contains(r#"<link rel="preload" as="fetch" href="/myapp/code_bg.wasm""#));
+        assert!(html.contains(r#"import init from "/myapp/code.js";"#));
+        assert!(html.contains(r#"init({module_or_path:"/myapp/code_bg.wasm"});"#));
+
+        fs::remove_dir_all(dist)?;
+        Ok(())
+    }
+}
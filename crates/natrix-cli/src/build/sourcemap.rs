@@ -12,6 +12,7 @@ use anyhow::anyhow;
 use gimli::{EndianSlice, LittleEndian};
 
 use crate::prelude::*;
+use crate::utils;
 
 /// The section id for custom wasm sections
 const WASM_CUSTOM_SECTION_ID: u8 = 0;
@@ -19,18 +20,22 @@ const WASM_CUSTOM_SECTION_ID: u8 = 0;
 /// The section name for source maps
 const SOURCEMAP_SECTION_NAME: &str = "sourceMappingURL";
 
-/// Create and embed a source map in the given wasm file.
+/// Where to put a generated source map
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourcemapTarget {
+    /// Embed the source map in the wasm file as a data url
+    Inline,
+    /// Write the source map to a `.map` file next to the wasm file, and only reference it
+    External,
+}
+
+/// Create a source map for the given wasm file, and reference it from the wasm file.
 pub(crate) fn create_sourcemap(
-    wasm_file: &Path,
+    wasm_path: &Path,
     parse_result: &super::wasm_parser::WasmParseResult,
+    target: SourcemapTarget,
 ) -> Result<()> {
-    let mut sourcemap = sourcemap::SourceMapBuilder::new(Some(&wasm_file.display().to_string()));
-
-    let wasm_file = std::fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .truncate(false)
-        .open(wasm_file)?;
+    let mut sourcemap = sourcemap::SourceMapBuilder::new(Some(&wasm_path.display().to_string()));
 
     // Convert parse result to the format expected by populate_sourcemap
     let sections: HashMap<&str, &[u8]> = parse_result
@@ -40,19 +45,137 @@ pub(crate) fn create_sourcemap(
         .collect();
 
     populate_sourcemap(&mut sourcemap, &sections, parse_result.code_section_offset)?;
-    inject_sourcemap(sourcemap, wasm_file)?;
+    let sourcemap = sourcemap.into_sourcemap();
+
+    let url = match target {
+        SourcemapTarget::Inline => sourcemap.to_data_url()?,
+        SourcemapTarget::External => {
+            if sourcemap.get_token_count() == 0 {
+                println!(
+                    "{}",
+                    "No debug info survived the build, the wasm source map will be empty".yellow()
+                );
+            }
+            let map_path = map_path(wasm_path)?;
+            sourcemap.to_writer(std::fs::File::create(&map_path)?)?;
+
+            // NOTE: The debug info is only needed to generate the map, so it is not shipped.
+            let wasm = std::fs::read(wasm_path)?;
+            std::fs::write(wasm_path, strip_debug_sections(&wasm)?)?;
+
+            utils::get_filename(&map_path)?.into_owned()
+        }
+    };
+
+    let wasm_file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .truncate(false)
+        .open(wasm_path)?;
+    inject_sourcemap_url(url, wasm_file)?;
 
     Ok(())
 }
 
-/// Inject the sourcemap into the given wasm file
-fn inject_sourcemap(
-    sourcemap: sourcemap::SourceMapBuilder,
-    mut wasm_file: std::fs::File,
-) -> Result<()> {
-    let sourcemap = sourcemap.into_sourcemap();
-    let data = sourcemap.to_data_url()?;
+/// Write the source map of a js file next to it, and add a `sourceMappingURL` comment
+/// referencing it to the js file.
+pub(crate) fn write_js_sourcemap(js_file: &Path, sourcemap: &str) -> Result<()> {
+    let map_path = map_path(js_file)?;
+    std::fs::write(&map_path, sourcemap)?;
+
+    let map_file = utils::get_filename(&map_path)?;
+    let mut js_file = std::fs::OpenOptions::new().append(true).open(js_file)?;
+    writeln!(js_file, "\n//# sourceMappingURL={map_file}")?;
+    Ok(())
+}
+
+/// The path of the source map for the given file, such as `code.js.map` for `code.js`
+fn map_path(file: &Path) -> Result<PathBuf> {
+    let filename = utils::get_filename(file)?;
+    Ok(file.with_file_name(format!("{filename}.map")))
+}
+
+/// Check if the custom section is debug info, the DWARF sections or the name section
+fn is_debug_section(name: &str) -> bool {
+    name == "name" || name.starts_with(".debug_")
+}
+
+/// Remove the debug info custom sections from the given wasm module.
+///
+/// Only sections after the code section are removed, as the source map addresses are offsets
+/// into the file and would be shifted otherwise.
+fn strip_debug_sections(wasm: &[u8]) -> Result<Vec<u8>> {
+    /// The magic number and version at the start of a wasm module
+    const HEADER_LENGTH: usize = 8;
+    /// The section id of the code section
+    const CODE_SECTION_ID: u8 = 10;
+
+    let header = wasm
+        .get(..HEADER_LENGTH)
+        .ok_or(anyhow!("Wasm file is missing its header"))?;
+    let mut output = header.to_vec();
+    let mut rest = wasm.get(HEADER_LENGTH..).unwrap_or_default();
+    let mut after_code = false;
+
+    while let Some((&id, content)) = rest.split_first() {
+        let (length, content) = decode_u32_vlq(content)?;
+        let length = usize::try_from(length)?;
+        let body = content
+            .get(..length)
+            .ok_or(anyhow!("Wasm section extends past the end of the file"))?;
+        let section_length = wasm_section_length(rest, content, length)?;
+        let section = rest.get(..section_length).unwrap_or_default();
+        rest = content.get(length..).unwrap_or_default();
 
+        let is_debug = id == WASM_CUSTOM_SECTION_ID
+            && decode_u32_vlq(body).is_ok_and(|(name_length, name)| {
+                usize::try_from(name_length)
+                    .ok()
+                    .and_then(|name_length| name.get(..name_length))
+                    .and_then(|name| std::str::from_utf8(name).ok())
+                    .is_some_and(is_debug_section)
+            });
+        if !(after_code && is_debug) {
+            output.extend_from_slice(section);
+        }
+        after_code |= id == CODE_SECTION_ID;
+    }
+
+    Ok(output)
+}
+
+/// The full length of a section, including its id and length prefix
+fn wasm_section_length(section: &[u8], content: &[u8], length: usize) -> Result<usize> {
+    section
+        .len()
+        .checked_sub(content.len())
+        .and_then(|header| header.checked_add(length))
+        .ok_or(anyhow!("Invalid wasm section length"))
+}
+
+/// Decode a u32 encoded for wasm, returning it and the remaining bytes
+fn decode_u32_vlq(mut bytes: &[u8]) -> Result<(u32, &[u8])> {
+    let mut value: u32 = 0;
+    let mut shift: u32 = 0;
+    while let Some((&byte, rest)) = bytes.split_first() {
+        bytes = rest;
+        let bits = u32::from(byte & 0b0111_1111)
+            .checked_shl(shift)
+            .ok_or(anyhow!("Invalid wasm integer"))?;
+        value |= bits;
+        if byte & 0b1000_0000 == 0 {
+            return Ok((value, bytes));
+        }
+        shift = shift.saturating_add(7);
+        if shift >= 35 {
+            break;
+        }
+    }
+    Err(anyhow!("Invalid wasm integer"))
+}
+
+/// Add a `sourceMappingURL` section with the given url to the given wasm file
+fn inject_sourcemap_url(data: String, mut wasm_file: std::fs::File) -> Result<()> {
     let section_content_length = needed_space_for_u32(SOURCEMAP_SECTION_NAME.len().try_into()?)
         .saturating_add(SOURCEMAP_SECTION_NAME.len())
         .saturating_add(needed_space_for_u32(data.len().try_into()?))
@@ -191,3 +314,80 @@ fn encode_u32_vlq(mut value: u32, target: &mut Vec<u8>) {
     )]
     target.push(value as u8);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode a wasm section with the given id and content
+    fn section(id: u8, content: &[u8]) -> Vec<u8> {
+        let mut section = vec![id];
+        encode_u32_vlq(content.len().try_into().unwrap_or_default(), &mut section);
+        section.extend_from_slice(content);
+        section
+    }
+
+    /// Encode a custom section with the given name
+    fn custom_section(name: &str) -> Vec<u8> {
+        let mut content = Vec::new();
+        encode_u32_vlq(name.len().try_into().unwrap_or_default(), &mut content);
+        content.extend(name.bytes());
+        content.extend_from_slice(b"data");
+        section(WASM_CUSTOM_SECTION_ID, &content)
+    }
+
+    #[test]
+    fn vlq_roundtrip() -> Result<()> {
+        for value in [0, 1, 127, 128, 300, 16_384, u32::MAX] {
+            let mut encoded = Vec::new();
+            encode_u32_vlq(value, &mut encoded);
+            assert_eq!(encoded.len(), needed_space_for_u32(value));
+            assert_eq!(decode_u32_vlq(&encoded)?, (value, &[][..]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn debug_sections_after_code_are_stripped() -> Result<()> {
+        let header = b"\0asm\x01\0\0\0";
+        let early_debug = custom_section(".debug_info");
+        let code = section(10, &[1, 2, 3]);
+        let other = custom_section("sourceMappingURL");
+
+        let wasm = [
+            &header[..],
+            &early_debug,
+            &code,
+            &custom_section(".debug_line"),
+            &custom_section("name"),
+            &other,
+        ]
+        .concat();
+        let expected = [&header[..], &early_debug, &code, &other].concat();
+
+        assert_eq!(strip_debug_sections(&wasm)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn js_sourcemap_is_referenced() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("natrix-js-sourcemap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let js_file = dir.join("code.js");
+        std::fs::write(&js_file, "let a=1;")?;
+
+        write_js_sourcemap(&js_file, r#"{"version":3}"#)?;
+
+        assert_eq!(
+            std::fs::read_to_string(&js_file)?,
+            "let a=1;\n//# sourceMappingURL=code.js.map\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("code.js.map"))?,
+            r#"{"version":3}"#
+        );
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -62,6 +62,11 @@ pub(crate) fn wasm_bindgen(
         .arg("--omit-default-module-path");
     if config.profile == options::BuildProfile::Dev {
         command.arg("--debug").arg("--keep-debug");
+    } else if config.sourcemap {
+        // NOTE: Keep whatever debug info the cargo profile produced for the source map
+        command
+            .arg("--keep-debug")
+            .arg("--remove-producers-section");
     } else {
         command
             .arg("--remove-name-section")
@@ -79,7 +84,13 @@ pub(crate) fn wasm_bindgen(
 }
 
 /// Minimize the given js file
-pub(crate) fn minimize_js(js_file: &PathBuf, mapping: RenameMap) -> Result<(), anyhow::Error> {
+///
+/// If `sourcemap` is set the source map of the minimized code is returned as json.
+pub(crate) fn minimize_js(
+    js_file: &PathBuf,
+    mapping: RenameMap,
+    sourcemap: bool,
+) -> Result<Option<String>, anyhow::Error> {
     let spinner = utils::create_spinner("🗜️ Minimizing JS")?;
 
     let js_code = fs::read_to_string(js_file)?;
@@ -110,14 +121,15 @@ pub(crate) fn minimize_js(js_file: &PathBuf, mapping: RenameMap) -> Result<(), a
         .with_options(oxc::codegen::CodegenOptions {
             minify: true,
             comments: oxc::codegen::CommentOptions::disabled(),
+            source_map_path: sourcemap.then(|| js_file.clone()),
             ..Default::default()
         })
         .with_scoping(symbols);
-    let js_code = codegen.build(&program).code;
-    std::fs::write(js_file, js_code)?;
+    let result = codegen.build(&program);
+    std::fs::write(js_file, result.code)?;
 
     spinner.finish();
-    Ok(())
+    Ok(result.map.map(|map| map.to_json_string()))
 }
 
 /// Build the project wasm
@@ -149,8 +161,9 @@ pub(crate) fn build_wasm(config: &options::BuildConfig) -> Result<PathBuf> {
         .arg("build")
         .args(["--color", "always"])
         .args(["--target", "wasm32-unknown-unknown"])
-        .args(["--profile", config.profile.cargo()])
-        .env(natrix_shared::MACRO_SETTINGS, settings);
+        .args(["--profile", &config.cargo_profile])
+        .env(natrix_shared::MACRO_SETTINGS, settings)
+        .env(natrix_shared::BASE_PATH, &*config.base_path);
 
     if config.profile == BuildProfile::Release {
         let mut rustc_flags =
@@ -186,7 +199,7 @@ pub(crate) fn find_wasm(config: &options::BuildConfig) -> Result<PathBuf> {
     let name = utils::get_project_name()?;
     let target = target
         .join("wasm32-unknown-unknown")
-        .join(config.profile.target());
+        .join(config.cargo_target_folder());
 
     if let Some(wasm) = search_dir_for_wasm(&target, &name)? {
         return Ok(wasm);
@@ -218,7 +231,13 @@ pub(crate) fn search_dir_for_wasm(
 }
 
 /// Optimize the given wasm file
-pub(crate) fn optimize_wasm(wasm_file: &PathBuf) -> Result<RenameMap, anyhow::Error> {
+///
+/// If `keep_debug` is set the name section and dwarf are kept (as far as wasm-opt can keep them
+/// correct) for use in a source map.
+pub(crate) fn optimize_wasm(
+    wasm_file: &PathBuf,
+    keep_debug: bool,
+) -> Result<RenameMap, anyhow::Error> {
     let spinner = utils::create_spinner("🔎 Optimize wasm")?;
 
     let mut command = process::Command::new("wasm-opt");
@@ -226,9 +245,13 @@ pub(crate) fn optimize_wasm(wasm_file: &PathBuf) -> Result<RenameMap, anyhow::Er
         .arg(wasm_file)
         .arg("-o")
         .arg(wasm_file)
-        .arg("--all-features")
-        .arg("--strip-debug")
-        .arg("--strip-dwarf")
+        .arg("--all-features");
+    if keep_debug {
+        command.arg("--debuginfo");
+    } else {
+        command.arg("--strip-debug").arg("--strip-dwarf");
+    }
+    command
         .arg("--strip-producers")
         .arg("--strip-target-features");
 
@@ -15,6 +15,8 @@ pub(crate) struct WasmParseResult {
     pub data_strings: Vec<String>,
     /// Offset of the code section in the file
     pub code_section_offset: u64,
+    /// The name and size in bytes of each section, in file order
+    pub section_sizes: Vec<(String, usize)>,
 }
 
 /// Parse a WASM file from a reader using streaming mode
@@ -22,6 +24,7 @@ pub(crate) fn parse_wasm_stream<R: Read>(mut reader: R) -> Result<WasmParseResul
     let mut custom_sections = HashMap::new();
     let mut data_strings = Vec::new();
     let mut code_section_offset = 0;
+    let mut section_sizes = Vec::new();
 
     let mut parser = wasmparser::Parser::new(0);
     let mut current_data_length = 0;
@@ -59,6 +62,15 @@ pub(crate) fn parse_wasm_stream<R: Read>(mut reader: R) -> Result<WasmParseResul
             wasmparser::Chunk::Parsed { consumed, payload } => (payload, consumed),
         };
 
+        if let Some((id, range)) = payload.as_section() {
+            let name = if let wasmparser::Payload::CustomSection(reader) = &payload {
+                format!("custom \"{}\"", reader.name())
+            } else {
+                section_name(id).to_owned()
+            };
+            section_sizes.push((name, range.len()));
+        }
+
         match payload {
             wasmparser::Payload::CustomSection(reader) => {
                 custom_sections.insert(reader.name().to_string(), reader.data().to_vec());
@@ -107,11 +119,59 @@ pub(crate) fn parse_wasm_stream<R: Read>(mut reader: R) -> Result<WasmParseResul
         custom_sections,
         data_strings,
         code_section_offset,
+        section_sizes,
     })
 }
 
+/// The name of a section from its id
+///
+/// <https://webassembly.github.io/spec/core/binary/modules.html#sections>
+fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "data count",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
 /// Parse a WASM file from a file path
 pub(crate) fn parse_wasm_file(wasm_file: &Path) -> Result<WasmParseResult> {
     let file = std::fs::File::open(wasm_file)?;
     parse_wasm_stream(file)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_sizes_are_recorded() -> Result<()> {
+        let module: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x01, 0x01, 0x00, // empty type section
+            0x00, 0x06, 0x02, b'h', b'i', 0x01, 0x02, 0x03, // custom section `hi`
+        ];
+
+        let result = parse_wasm_stream(module)?;
+        assert_eq!(
+            result.section_sizes,
+            vec![
+                (String::from("type"), 1),
+                (String::from("custom \"hi\""), 6),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,490 @@
+//! Check the project for unused css without building the wasm.
+//!
+//! Running the app in bundle mode reports where each `class!()` and `id!()` was called, which is
+//! used to find the constants they were defined as.
+//! A constant is considered used if it is referenced anywhere outside of its definition, `use`
+//! statements, and the `register_*!` macros defining its styles.
+
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::build::css::{self, SymbolSite};
+use crate::prelude::*;
+
+/// The kind of a unique css symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolKind {
+    /// Created with `class!()`
+    Class,
+    /// Created with `id!()`
+    Id,
+}
+
+impl SymbolKind {
+    /// The name used in the report
+    fn readable(self) -> &'static str {
+        match self {
+            Self::Class => "class",
+            Self::Id => "id",
+        }
+    }
+}
+
+/// A constant defined with `class!()` or `id!()`
+#[derive(Debug, PartialEq, Eq)]
+struct Definition {
+    /// The name of the constant
+    name: String,
+    /// The kind of symbol
+    kind: SymbolKind,
+    /// The generated symbol
+    symbol: String,
+    /// The index of the file it is defined in
+    file: usize,
+    /// The line of the macro call, starting at 1
+    line: usize,
+}
+
+/// A source file of the project
+struct SourceFile {
+    /// The path as reported by `file!()`, relative to the workspace root
+    path: String,
+    /// The file content
+    content: String,
+}
+
+/// Run `natrix check`
+pub(crate) fn do_check() -> Result<()> {
+    let bundle = css::run_bundle()?;
+    let mut stylesheet = lightningcss::stylesheet::StyleSheet::parse(
+        &bundle.css,
+        lightningcss::stylesheet::ParserOptions::default(),
+    )
+    .map_err(|err| anyhow!("Failed to parse css {err}"))?;
+    let styled = css::get_symbols(&mut stylesheet);
+
+    let files = read_sources()?;
+    let definitions = find_definitions(&files, &bundle.symbols);
+    let warnings = report(&files, &definitions, &styled);
+    if warnings == 0 {
+        println!("{}", "✅ No unused css found".green());
+    } else {
+        println!(
+            "{} {}",
+            warnings.yellow().bold(),
+            "css warnings found".yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Print the warnings, returning the amount
+fn report(files: &[SourceFile], definitions: &[Definition], styled: &HashSet<String>) -> usize {
+    let mut warnings: usize = 0;
+    for definition in definitions {
+        let Some(file) = files.get(definition.file) else {
+            continue;
+        };
+        let location = format!("{}:{}", file.path, definition.line);
+        let is_styled = styled.contains(&definition.symbol);
+
+        if is_styled && !is_referenced(files, definitions, definition) {
+            println!(
+                "{} {} {} is styled but never used {}",
+                "⚠️ Unused".yellow().bold(),
+                definition.kind.readable(),
+                definition.name.cyan(),
+                location.dimmed()
+            );
+            warnings = warnings.saturating_add(1);
+        } else if !is_styled && definition.kind == SymbolKind::Class {
+            println!(
+                "{} {} {} has no styles {}",
+                "⚠️ Unstyled".yellow().bold(),
+                definition.kind.readable(),
+                definition.name.cyan(),
+                location.dimmed()
+            );
+            warnings = warnings.saturating_add(1);
+        }
+    }
+    warnings
+}
+
+/// Read all the rust files of the current package
+fn read_sources() -> Result<Vec<SourceFile>> {
+    let metadata = cargo_metadata::MetadataCommand::new().no_deps().exec()?;
+    let packages = metadata.workspace_default_packages();
+    let package = packages.first().ok_or(anyhow!("No package found"))?;
+    let root = PathBuf::from(&metadata.workspace_root);
+    let src = PathBuf::from(&package.manifest_path)
+        .parent()
+        .ok_or(anyhow!("Manifest has no parent"))?
+        .join("src");
+
+    let mut files = Vec::new();
+    collect_rust_files(&src, &mut files)?;
+    files
+        .into_iter()
+        .map(|file| {
+            let path = file
+                .strip_prefix(&root)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = fs::read_to_string(&file)?;
+            Ok(SourceFile { path, content })
+        })
+        .collect()
+}
+
+/// Recursively collect the `.rs` files in the folder
+fn collect_rust_files(folder: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Find the constants the reported symbols are defined as.
+///
+/// Symbols from other crates, or not defined as a constant, are skipped.
+fn find_definitions(files: &[SourceFile], sites: &[SymbolSite]) -> Vec<Definition> {
+    sites
+        .iter()
+        .filter_map(|site| {
+            let kind = match site.kind.as_str() {
+                "class" => SymbolKind::Class,
+                "id" => SymbolKind::Id,
+                _ => return None,
+            };
+            let file = files.iter().position(|file| file.path == site.file)?;
+            let content = &files.get(file)?.content;
+            let offset = line_offset(content, site.line, site.column)?;
+            let name = const_name(content.get(..offset)?)?;
+            Some(Definition {
+                name: name.to_owned(),
+                kind,
+                symbol: site.symbol.clone(),
+                file,
+                line: site.line,
+            })
+        })
+        .collect()
+}
+
+/// The byte offset of the given line and column, both starting at 1
+fn line_offset(source: &str, line: usize, column: usize) -> Option<usize> {
+    let mut offset: usize = 0;
+    for (current, content) in (1..).zip(source.split_inclusive('\n')) {
+        if current == line {
+            let (index, _) = content.char_indices().nth(column.checked_sub(1)?)?;
+            return offset.checked_add(index);
+        }
+        offset = offset.saturating_add(content.len());
+    }
+    None
+}
+
+/// The name of the constant whose initializer ends with `before`, if any.
+///
+/// The definition can span multiple lines, such as when formatting moves the macro call to the
+/// next line.
+fn const_name(before: &str) -> Option<&str> {
+    let start = before
+        .rmatch_indices("const ")
+        .map(|(index, _)| index)
+        .find(|index| {
+            !before
+                .get(..*index)
+                .and_then(|before| before.chars().next_back())
+                .is_some_and(is_identifier_char)
+        })?;
+    let rest = before.get(start..)?.strip_prefix("const ")?;
+    let (name, initializer) = rest.split_once(':')?;
+    if initializer.contains(';') || !initializer.contains('=') {
+        return None;
+    }
+    let name = name.trim();
+    name.chars()
+        .all(is_identifier_char)
+        .then_some(name)
+        .filter(|name| !name.is_empty())
+}
+
+/// Whether the character can be part of a identifier
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The name a module is referred to by in paths, `crate` for the crate root
+fn module_name(path: &str) -> &str {
+    let path = Path::new(path);
+    let stem = path.file_stem().and_then(|stem| stem.to_str());
+    match stem {
+        Some("main" | "lib") => "crate",
+        Some("mod") => path
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .unwrap_or_default(),
+        Some(stem) => stem,
+        None => "",
+    }
+}
+
+/// Whether the constant is referenced outside of its definition, `use` statements, and
+/// `register_*!` macros.
+///
+/// If multiple constants share the name, a reference only counts for this one if it is in the
+/// same file, qualified with its module, or imported by a `use` mentioning its module.
+fn is_referenced(
+    files: &[SourceFile],
+    definitions: &[Definition],
+    definition: &Definition,
+) -> bool {
+    let name = definition.name.as_str();
+    let Some(own_path) = files.get(definition.file).map(|file| file.path.as_str()) else {
+        return false;
+    };
+    let module = module_name(own_path);
+    let ambiguous = definitions
+        .iter()
+        .any(|other| other.name == name && other.file != definition.file);
+
+    files.iter().enumerate().any(|(index, file)| {
+        let content = file.content.as_str();
+        let registered = register_spans(content);
+        let imports = use_spans(content);
+        let defines_own = definitions
+            .iter()
+            .any(|other| other.name == name && other.file == index);
+        let imported = imports.iter().any(|span| {
+            content.get(span.clone()).is_some_and(|statement| {
+                word_indices(statement, name).next().is_some()
+                    && word_indices(statement, module).next().is_some()
+            })
+        });
+
+        word_indices(content, name).any(|offset| {
+            let before = content.get(..offset).unwrap_or_default();
+            let excluded = before.trim_end().ends_with("const")
+                || registered.iter().any(|span| span.contains(&offset))
+                || imports.iter().any(|span| span.contains(&offset));
+            if excluded {
+                return false;
+            }
+            if !ambiguous || before.ends_with(&format!("{module}::")) {
+                return true;
+            }
+            if before.ends_with("::") && !before.ends_with("self::") {
+                return false;
+            }
+            if defines_own {
+                return index == definition.file;
+            }
+            imported
+        })
+    })
+}
+
+/// Find the byte ranges of the `use` statements
+fn use_spans(source: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    for (start, _) in source.match_indices("use ") {
+        let line_start = source
+            .get(..start)
+            .and_then(|before| before.rfind('\n'))
+            .map_or(0, |index| index.saturating_add(1));
+        let prefix = source.get(line_start..start).unwrap_or_default().trim();
+        if !(prefix.is_empty() || prefix.starts_with("pub")) {
+            continue;
+        }
+        if let Some(end) = source.get(start..).and_then(|rest| rest.find(';')) {
+            spans.push(start..start.saturating_add(end));
+        }
+    }
+    spans
+}
+
+/// Find the byte ranges of the `register_*!(...)` macro calls
+fn register_spans(source: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    for (start, _) in source.match_indices("register_") {
+        let rest = &source[start..];
+        let Some(open) = rest.find(['(', '{', '[']) else {
+            continue;
+        };
+        if !rest[..open].trim_end().ends_with('!') {
+            continue;
+        }
+
+        let mut depth: usize = 0;
+        for (index, c) in rest[open..].char_indices() {
+            match c {
+                '(' | '{' | '[' => depth = depth.saturating_add(1),
+                ')' | '}' | ']' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        let end = start.saturating_add(open).saturating_add(index);
+                        spans.push(start..end);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    spans
+}
+
+/// Find the occurrences of `word` that are not part of a longer identifier
+fn word_indices<'a>(source: &'a str, word: &'a str) -> impl Iterator<Item = usize> + 'a {
+    source.match_indices(word).filter_map(move |(index, _)| {
+        let before = source[..index].chars().next_back();
+        let after = source[index.saturating_add(word.len())..].chars().next();
+        let is_word =
+            !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char);
+        is_word.then_some(index)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: path.to_owned(),
+            content: content.to_owned(),
+        }
+    }
+
+    fn site(symbol: &str, kind: &str, file: &str, line: usize, column: usize) -> SymbolSite {
+        SymbolSite {
+            symbol: symbol.to_owned(),
+            kind: kind.to_owned(),
+            file: file.to_owned(),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn definitions() {
+        let files = [file(
+            "src/main.rs",
+            "use natrix::prelude::*;\n\
+            const CARD: Class = natrix::class!();\n\
+            pub(crate) const MAIN: Id = id!();\n\
+            const VERY_LONG_NAME_FOR_A_CLASS: natrix::prelude::Class =\n\
+            \x20   natrix::class!();\n\
+            fn render() {\n\
+            \x20   let inline = natrix::class!();\n\
+            }\n",
+        )];
+        let sites = [
+            site("a", "class", "src/main.rs", 2, 21),
+            site("b", "id", "src/main.rs", 3, 29),
+            site("c", "class", "src/main.rs", 5, 5),
+            site("d", "class", "src/main.rs", 7, 18),
+            site("e", "class", "other/src/lib.rs", 1, 1),
+        ];
+        let names: Vec<_> = find_definitions(&files, &sites)
+            .into_iter()
+            .map(|definition| (definition.name, definition.kind, definition.symbol))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (String::from("CARD"), SymbolKind::Class, String::from("a")),
+                (String::from("MAIN"), SymbolKind::Id, String::from("b")),
+                (
+                    String::from("VERY_LONG_NAME_FOR_A_CLASS"),
+                    SymbolKind::Class,
+                    String::from("c")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn references() -> Result<()> {
+        let files = [
+            file(
+                "src/main.rs",
+                "const CARD: Class = natrix::class!();\n\
+                const CARD_TITLE: Class = natrix::class!();\n\
+                natrix::register_rule!(CARD, RuleBody::new().property(\"color\", \"red\"));\n\
+                fn render() -> impl Element<App> {\n\
+                    e::div().class(CARD_TITLE)\n\
+                }\n",
+            ),
+            file("src/other.rs", "use crate::CARD;\n"),
+        ];
+        let sites = [
+            site("a", "class", "src/main.rs", 1, 21),
+            site("b", "class", "src/main.rs", 2, 27),
+        ];
+        let definitions = find_definitions(&files, &sites);
+        let [card, card_title] = definitions.as_slice() else {
+            return Err(anyhow!("Expected two definitions, got {definitions:?}"));
+        };
+        assert!(!is_referenced(&files, &definitions, card));
+        assert!(is_referenced(&files, &definitions, card_title));
+        Ok(())
+    }
+
+    #[test]
+    fn same_named_constants_do_not_mask_each_other() -> Result<()> {
+        let files = [
+            file(
+                "src/card.rs",
+                "pub const TITLE: Class = natrix::class!();\n\
+                natrix::register_rule!(TITLE, RuleBody::new());\n",
+            ),
+            file(
+                "src/dialog.rs",
+                "pub const TITLE: Class = natrix::class!();\n\
+                natrix::register_rule!(TITLE, RuleBody::new());\n",
+            ),
+            file(
+                "src/main.rs",
+                "use crate::dialog::{\n    TITLE,\n};\n\
+                fn render() { e::div().class(TITLE) }\n",
+            ),
+        ];
+        let sites = [
+            site("a", "class", "src/card.rs", 1, 26),
+            site("b", "class", "src/dialog.rs", 1, 26),
+        ];
+        let definitions = find_definitions(&files, &sites);
+        let [card, dialog] = definitions.as_slice() else {
+            return Err(anyhow!("Expected two definitions, got {definitions:?}"));
+        };
+        assert!(!is_referenced(&files, &definitions, card));
+        assert!(is_referenced(&files, &definitions, dialog));
+        Ok(())
+    }
+
+    #[test]
+    fn unstyled_classes_are_reported() {
+        let files = [file(
+            "src/main.rs",
+            "const CARD: Class = natrix::class!();\nconst MAIN: Id = natrix::id!();\n",
+        )];
+        let sites = [
+            site("a", "class", "src/main.rs", 1, 21),
+            site("b", "id", "src/main.rs", 2, 18),
+        ];
+        let definitions = find_definitions(&files, &sites);
+        assert_eq!(report(&files, &definitions, &HashSet::new()), 1);
+    }
+}
@@ -1,19 +1,73 @@
 //! Spawn live reloading server
 
+use std::io::{Read, Write};
 use std::net::{Ipv4Addr, TcpListener};
 use std::path::PathBuf;
+use std::process::{Command, Stdio};
 use std::sync::{Arc, Mutex, mpsc};
 use std::time::Duration;
-use std::{fs, thread};
+use std::{fs, io, thread};
 
 use anyhow::Result;
 use notify::Watcher;
 use tiny_http::{Header, Response, Server};
 
 use crate::build::assets::AssetManifest;
-use crate::build::build;
+use crate::build::{build, compress, css};
 use crate::options;
 use crate::prelude::*;
+use crate::tls::{self, DevCertificate};
+
+/// The live reload message telling the page to reload
+const RELOAD_MESSAGE: &str = "reload";
+/// The live reload message carrying new css, the css follows on the next line
+pub(crate) const CSS_MESSAGE: &str = "css";
+
+/// A update pushed to the page over the live reload websocket
+#[derive(Debug, PartialEq, Eq)]
+enum DevUpdate {
+    /// Reload the page
+    Reload,
+    /// Only the css changed, swap it in without reloading
+    Css(String),
+}
+
+impl DevUpdate {
+    /// The websocket message for this update
+    fn message(&self) -> String {
+        match self {
+            Self::Reload => RELOAD_MESSAGE.to_owned(),
+            Self::Css(css) => format!("{CSS_MESSAGE}\n{css}"),
+        }
+    }
+}
+
+/// Check if all the changed files are stylesheets, in which case the css can be hot swapped
+fn is_css_only(paths: &[PathBuf]) -> bool {
+    !paths.is_empty()
+        && paths
+            .iter()
+            .all(|path| path.extension().is_some_and(|extension| extension == "css"))
+}
+
+/// Get the update to send to the page after a successful rebuild
+fn dev_update(config: &options::BuildConfig, changed: &[PathBuf]) -> DevUpdate {
+    // NOTE: The css is only injected at runtime when not doing ssg.
+    if config.ssg || !is_css_only(changed) {
+        return DevUpdate::Reload;
+    }
+
+    match css::extract_css() {
+        Ok(css) => {
+            println!("🎨 {}", "Hot swapping css".bright_blue());
+            DevUpdate::Css(css)
+        }
+        Err(err) => {
+            println!("{}", err.red());
+            DevUpdate::Reload
+        }
+    }
+}
 
 /// Find the closest gitignore
 fn find_gitignore() -> Result<ignore::gitignore::Gitignore> {
@@ -65,6 +119,13 @@ pub(crate) fn do_dev(args: &options::DevArguments) -> Result<()> {
         }
     }
 
+    let tls = if args.tls {
+        Some(DevCertificate::load_or_generate()?.rustls_config()?)
+    } else {
+        None
+    };
+    let websocket_tls = tls.clone();
+
     let ip = if args.allow_external {
         Ipv4Addr::UNSPECIFIED
     } else {
@@ -74,16 +135,22 @@ pub(crate) fn do_dev(args: &options::DevArguments) -> Result<()> {
     let dist = config.dist.clone();
     let mutex_clone = Arc::clone(&asset_manifest_mutex);
     let server_port = args.port; // Pass the user-specified port to spawn_server
-    thread::spawn(move || spawn_server(dist, mutex_clone, server_port, config.live_reload, ip));
+    let open = args.open;
+    let live_reload = config.live_reload;
+    thread::spawn(move || {
+        spawn_server(dist, mutex_clone, server_port, live_reload, ip, open, tls);
+    });
 
     if let Some(port) = config.live_reload {
-        thread::spawn(move || spawn_websocket(port, rx_reload, ip));
+        thread::spawn(move || spawn_websocket(port, rx_reload, ip, websocket_tls));
     }
 
     loop {
-        rx_notify.recv()?;
+        let mut changed = rx_notify.recv()?.paths;
         std::thread::sleep(Duration::from_millis(100));
-        while rx_notify.try_recv().is_ok() {}
+        while let Ok(event) = rx_notify.try_recv() {
+            changed.extend(event.paths);
+        }
 
         match build(&config) {
             Err(err) => {
@@ -94,19 +161,26 @@ pub(crate) fn do_dev(args: &options::DevArguments) -> Result<()> {
                     .lock()
                     .map_err(|_| anyhow!("Failed to lock mutex"))?;
                 *lock = manifest;
-                tx_reload.send(())?;
+                drop(lock);
+                tx_reload.send(dev_update(&config, &changed))?;
             }
         }
     }
 }
 
-/// Spawn a websocket server to send reload signals
-#[expect(
-    clippy::expect_used,
-    clippy::needless_pass_by_value,
-    reason = "This is running in a thread"
-)]
-fn spawn_websocket(port: u16, reload_signal: mpsc::Receiver<()>, ip: Ipv4Addr) {
+/// A connection to a live reload client, either plain or over tls
+trait ClientStream: Read + Write + Send {}
+impl<T: Read + Write + Send> ClientStream for T {}
+
+/// Spawn a websocket server to send live reload updates, accepting tls connections if `tls` is
+/// given.
+#[expect(clippy::expect_used, reason = "This is running in a thread")]
+fn spawn_websocket(
+    port: u16,
+    updates: mpsc::Receiver<DevUpdate>,
+    ip: Ipv4Addr,
+    tls: Option<Arc<rustls::ServerConfig>>,
+) {
     let server = TcpListener::bind((ip, port)).expect("Failed to bind websocket");
     let clients = Arc::new(Mutex::new(Vec::new()));
 
@@ -116,6 +190,14 @@ fn spawn_websocket(port: u16, reload_signal: mpsc::Receiver<()>, ip: Ipv4Addr) {
             let Ok(stream) = stream else {
                 continue;
             };
+            let stream: Box<dyn ClientStream> = if let Some(tls) = &tls {
+                let Ok(connection) = rustls::ServerConnection::new(Arc::clone(tls)) else {
+                    continue;
+                };
+                Box::new(rustls::StreamOwned::new(connection, stream))
+            } else {
+                Box::new(stream)
+            };
             let Ok(ws) = tungstenite::accept(stream) else {
                 continue;
             };
@@ -124,13 +206,17 @@ fn spawn_websocket(port: u16, reload_signal: mpsc::Receiver<()>, ip: Ipv4Addr) {
         }
     });
 
-    loop {
-        if let Ok(()) = reload_signal.recv() {
-            let mut clients = clients.lock().expect("Mutex gone");
-            for mut client in clients.drain(..) {
-                let _ = client.write(tungstenite::Message::from("RELOAD NOW PLS"));
-                client.flush().expect("Failed to flush");
-            }
+    for update in updates {
+        let message = update.message();
+        let mut clients = clients.lock().expect("Mutex gone");
+        clients.retain_mut(|client| {
+            client
+                .send(tungstenite::Message::from(message.as_str()))
+                .is_ok()
+        });
+        // NOTE: Reloaded pages open a new connection
+        if update == DevUpdate::Reload {
+            clients.clear();
         }
     }
 }
@@ -158,6 +244,8 @@ pub(crate) fn spawn_server(
     preferred_port: Option<u16>,
     live_reload: Option<u16>,
     ip: Ipv4Addr,
+    open: bool,
+    tls: Option<Arc<rustls::ServerConfig>>,
 ) {
     // Use the specified port if provided, otherwise start at 8000
     let port = match preferred_port {
@@ -165,15 +253,29 @@ pub(crate) fn spawn_server(
         None => get_free_port(8000).expect("Failed to find free port for server"),
     };
 
-    let server = Server::http((ip, port)).expect("Failed to start server");
-    let port = server
-        .server_addr()
-        .to_ip()
-        .expect("Failed to get ip")
-        .port();
+    let (server, port) = if let Some(tls) = &tls {
+        // NOTE: The files are served over plain http on a local port, with the public port
+        // decrypting the traffic and forwarding it there.
+        let listener = TcpListener::bind((ip, port)).expect("Failed to start server");
+        let port = listener.local_addr().expect("Failed to get ip").port();
+        let server = Server::http((Ipv4Addr::LOCALHOST, 0)).expect("Failed to start server");
+        let backend = server.server_addr().to_ip().expect("Failed to get ip");
+        let tls = Arc::clone(tls);
+        thread::spawn(move || tls::proxy(&listener, backend, &tls));
+        (server, port)
+    } else {
+        let server = Server::http((ip, port)).expect("Failed to start server");
+        let port = server
+            .server_addr()
+            .to_ip()
+            .expect("Failed to get ip")
+            .port();
+        (server, port)
+    };
+    let scheme = if tls.is_some() { "https" } else { "http" };
     println!(
         "{}{}{}{}{}",
-        "🚀 Dev server running at http://".green(),
+        format!("🚀 Dev server running at {scheme}://").green(),
         ip.bright_red(),
         ":".bright_red(),
         port.to_string().bright_red(),
@@ -185,16 +287,46 @@ pub(crate) fn spawn_server(
         .bright_black()
     );
 
+    if open {
+        // NOTE: Browsers can not open `0.0.0.0`, and this is the same machine anyway.
+        let host = if ip.is_unspecified() {
+            Ipv4Addr::LOCALHOST
+        } else {
+            ip
+        };
+        open_browser(&format!("{scheme}://{host}:{port}"));
+    }
+    if tls.is_some() {
+        println!(
+            "{}",
+            "🔐 The certificate is self signed, accept it in the browser once to continue."
+                .yellow()
+        );
+        if live_reload.is_some() {
+            println!(
+                "{}",
+                "   Live reload uses a separate port, if it does not connect open it over https and accept it there too."
+                    .yellow()
+            );
+        }
+    }
+
     for request in server.incoming_requests() {
         let asset_manifest = asset_manifest.lock().expect("Failed to lock mutex");
 
+        let accept_encoding = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Accept-Encoding"))
+            .map(|header| header.value.as_str().to_owned());
+
         let url = request.url();
         let url = url.strip_prefix("/").unwrap_or(url);
 
-        let path = if url.is_empty() {
-            folder.join("index.html")
-        } else if let Some(path) = asset_manifest.mapping.get(url) {
-            path.clone()
+        let (path, content_type) = if url.is_empty() {
+            (folder.join("index.html"), None)
+        } else if let Some(entry) = asset_manifest.mapping.get(url) {
+            (entry.path.clone(), Some(entry.content_type.clone()))
         } else {
             if url.contains("..") {
                 let response =
@@ -209,27 +341,13 @@ pub(crate) fn spawn_server(
                 );
                 return;
             }
-            folder.join(url)
+            (folder.join(url), None)
         };
 
         let response = if path.exists() && path.is_file() {
-            let content_type: &[u8] = match path.extension().and_then(|x| x.to_str()) {
-                Some("html") => b"text/html",
-                Some("js") => b"text/javascript",
-                Some("css") => b"text/css",
-                Some("wasm") => b"application/wasm",
-                None | Some(_) => b"text/plain",
-            };
-            match fs::read(path) {
-                Ok(content) => Response::from_data(content).with_header(
-                    Header::from_bytes(b"Content-Type", content_type).expect("Invalid header"),
-                ),
-                Err(err) => {
-                    println!("{}", err.red());
-                    let error_message = format!("😢 Error reading file: {err}");
-                    Response::from_string(error_message).with_status_code(500)
-                }
-            }
+            let content_type = content_type
+                .unwrap_or_else(|| natrix_shared::macros::content_type(&path).to_owned());
+            serve_file(path, content_type, accept_encoding.as_deref())
         } else {
             let not_found_message = "🚫 404 Not Found!";
             Response::from_string(not_found_message).with_status_code(404)
@@ -238,3 +356,147 @@ pub(crate) fn spawn_server(
         let _ = request.respond(response);
     }
 }
+
+/// Respond with the file, or its precompressed variant if the browser accepts it
+#[expect(clippy::expect_used, reason = "The header names and values are ascii")]
+fn serve_file(
+    path: PathBuf,
+    content_type: String,
+    accept_encoding: Option<&str>,
+) -> Response<io::Cursor<Vec<u8>>> {
+    let encoded = accepted_encodings(accept_encoding.unwrap_or_default())
+        .into_iter()
+        .map(|(encoding, extension)| (encoding, compress::compressed_path(&path, extension)))
+        .find(|(_, path)| path.is_file());
+    let (encoding, path) = match encoded {
+        Some((encoding, path)) => (Some(encoding), path),
+        None => (None, path),
+    };
+
+    match fs::read(path) {
+        Ok(content) => {
+            let mut response = Response::from_data(content).with_header(
+                Header::from_bytes(b"Content-Type", content_type).expect("Invalid header"),
+            );
+            if let Some(encoding) = encoding {
+                response.add_header(
+                    Header::from_bytes(b"Content-Encoding", encoding).expect("Invalid header"),
+                );
+                response.add_header(
+                    Header::from_bytes(b"Vary", "Accept-Encoding").expect("Invalid header"),
+                );
+            }
+            response
+        }
+        Err(err) => {
+            println!("{}", err.red());
+            let error_message = format!("😢 Error reading file: {err}");
+            Response::from_string(error_message).with_status_code(500)
+        }
+    }
+}
+
+/// The precompressed encodings accepted by the `Accept-Encoding` header, in order of preference,
+/// along with the extension of their files
+fn accepted_encodings(accept_encoding: &str) -> Vec<(&'static str, &'static str)> {
+    let accepted = |name: &str| {
+        accept_encoding.split(',').any(|encoding| {
+            let mut parts = encoding.split(';').map(str::trim);
+            let matches = parts
+                .next()
+                .is_some_and(|encoding| encoding.eq_ignore_ascii_case(name) || encoding == "*");
+            let disabled = parts.any(|part| {
+                part.strip_prefix("q=")
+                    .and_then(|quality| quality.parse::<f32>().ok())
+                    .is_some_and(|quality| quality <= 0.0)
+            });
+            matches && !disabled
+        })
+    };
+
+    [("br", "br"), ("gzip", "gz")]
+        .into_iter()
+        .filter(|(encoding, _)| accepted(encoding))
+        .collect()
+}
+
+/// The command opening the url in the default browser
+fn open_command(url: &str) -> Command {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        // NOTE: The empty argument is the window title, otherwise `start` uses the url as one.
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(url).stdout(Stdio::null()).stderr(Stdio::null());
+    command
+}
+
+/// Open the url in the default browser, warning if it fails
+fn open_browser(url: &str) {
+    let mut command = open_command(url);
+    thread::spawn(move || match command.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!(
+            "{}",
+            format!("Failed to open browser, the opener exited with {status}").yellow()
+        ),
+        Err(err) => println!("{}", format!("Failed to open browser: {err}").yellow()),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css_only_changes() {
+        assert!(is_css_only(&[
+            PathBuf::from("styles/main.css"),
+            PathBuf::from("theme.css")
+        ]));
+        assert!(!is_css_only(&[
+            PathBuf::from("styles/main.css"),
+            PathBuf::from("src/main.rs")
+        ]));
+        assert!(!is_css_only(&[PathBuf::from("Makefile")]));
+        assert!(!is_css_only(&[]));
+    }
+
+    #[test]
+    fn encoding_negotiation() {
+        assert_eq!(
+            accepted_encodings("gzip, deflate, br, zstd"),
+            vec![("br", "br"), ("gzip", "gz")]
+        );
+        assert_eq!(accepted_encodings("gzip"), vec![("gzip", "gz")]);
+        assert_eq!(
+            accepted_encodings("br;q=0, gzip;q=0.5"),
+            vec![("gzip", "gz")]
+        );
+        assert_eq!(accepted_encodings("identity"), vec![]);
+        assert_eq!(accepted_encodings(""), vec![]);
+    }
+
+    #[test]
+    fn open_command_passes_url() {
+        let command = open_command("http://127.0.0.1:8000");
+        assert_eq!(
+            command.get_args().last(),
+            Some(std::ffi::OsStr::new("http://127.0.0.1:8000"))
+        );
+    }
+
+    #[test]
+    fn update_messages() {
+        assert_eq!(DevUpdate::Reload.message(), RELOAD_MESSAGE);
+        assert_eq!(
+            DevUpdate::Css(String::from(".a{color:red}\n.b{}")).message(),
+            "css\n.a{color:red}\n.b{}"
+        );
+    }
+}
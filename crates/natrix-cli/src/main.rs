@@ -11,17 +11,24 @@ mod prelude {
 use prelude::*;
 
 mod build;
+mod check;
 mod dev_server;
 mod options;
 mod project_gen;
+mod tls;
 mod utils;
 
 fn main() -> Result<()> {
     let cli = options::Cli::parse();
 
     match cli {
-        options::Cli::New { name, stable } => project_gen::generate_project(&name, stable),
+        options::Cli::New {
+            name,
+            stable,
+            template,
+        } => project_gen::generate_project(&name, stable, template),
         options::Cli::Dev(args) => dev_server::do_dev(&args),
+        options::Cli::Check => check::do_check(),
         options::Cli::Build(args) => {
             build::build(&args.into_build_config()?).context("Building application")?;
             Ok(())
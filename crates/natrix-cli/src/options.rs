@@ -55,6 +55,20 @@ impl NatrixConfig {
 
         Ok(metadata.natrix.unwrap_or_default())
     }
+
+    /// Check `--csp` can be used with the config.
+    ///
+    /// It needs the css extracted to a stylesheet, as it is otherwise injected in a inline
+    /// `<style>` at runtime.
+    fn check_csp(&self) -> Result<()> {
+        if self.ssg {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "`--csp` requires `ssg` to be enabled, as otherwise css is injected in a inline `<style>` at runtime"
+            ))
+        }
+    }
 }
 
 /// Natrix CLI
@@ -68,15 +82,24 @@ pub(crate) enum Cli {
         /// Use Stable rust
         #[arg(short, long)]
         stable: bool,
+        /// The starter code to generate
+        #[arg(short, long, value_enum, default_value_t = Template::Hello)]
+        template: Template,
     },
     /// Spawn a dev server
     Dev(DevArguments),
     /// Build the project
     Build(BuildArguments),
+    /// Report unused css classes and ids without building the wasm
+    Check,
 }
 
 /// Arguments for the dev subcommand
 #[derive(Parser)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "These are independent cli flags, not states"
+)]
 pub(crate) struct DevArguments {
     /// Port to use for dev server
     #[arg(short, long)]
@@ -87,6 +110,12 @@ pub(crate) struct DevArguments {
     /// Disable live-reloading
     #[arg(long)]
     pub(crate) no_reload: bool,
+    /// Open the dev server in the default browser once it is running
+    #[arg(long)]
+    pub(crate) open: bool,
+    /// Serve over https with a self signed certificate, for apis that need a secure context
+    #[arg(long)]
+    pub(crate) tls: bool,
     /// The shared arguments
     #[command(flatten)]
     pub(crate) shared: SharedArguments,
@@ -98,6 +127,29 @@ pub(crate) struct BuildArguments {
     /// The target dist folder
     #[arg(short, long)]
     pub(crate) dist: Option<PathBuf>,
+    /// Inline up to this many bytes of css in the html, and load the rest without blocking
+    #[arg(
+        long,
+        value_name = "BYTES",
+        num_args = 0..=1,
+        default_missing_value = "14000",
+        conflicts_with = "csp"
+    )]
+    pub(crate) critical_css: Option<usize>,
+    /// Add subresource integrity hashes to the js and css references in the html
+    #[arg(long)]
+    pub(crate) sri: bool,
+    /// The path the app is served under, such as `/myapp`.
+    /// Overrides `base_path` in the config
+    #[arg(long, value_name = "PATH")]
+    pub(crate) base_path: Option<String>,
+    /// Write source maps for the wasm and js in release builds too.
+    /// Only debug info that survives the optimizations is mapped
+    #[arg(long)]
+    pub(crate) sourcemap: bool,
+    /// Print a breakdown of the output sizes after building
+    #[arg(long)]
+    pub(crate) analyze: bool,
     /// The shared arguments
     #[command(flatten)]
     pub(crate) shared: SharedArguments,
@@ -109,15 +161,32 @@ pub(crate) struct SharedArguments {
     /// Build profile to use
     #[arg(long, value_enum)]
     pub(crate) profile: Option<BuildProfile>,
+    /// Cargo profile to build the wasm with, such as a custom `[profile.size]`.
+    /// Defaults to the cargo profile matching `--profile`
+    #[arg(long, value_name = "NAME")]
+    pub(crate) cargo_profile: Option<String>,
     /// Invalidate the asset caches
     #[arg(long)]
     pub(crate) invalidate_cache: bool,
+    /// Write precompressed variants of the wasm and js in release builds
+    #[arg(long, value_enum, default_value_t = Compression::None)]
+    pub(crate) compress: Compression,
+    /// Do not use inline scripts or styles in the html, so it works with a strict
+    /// Content-Security-Policy. The startup code is put in a external `bootstrap.js` instead
+    #[arg(long)]
+    pub(crate) csp: bool,
 }
 
 /// Settings for building the server
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "These are independent build flags, not states"
+)]
 pub(crate) struct BuildConfig {
     /// Build profile to use
     pub(crate) profile: BuildProfile,
+    /// Cargo profile to build the wasm with
+    pub(crate) cargo_profile: String,
     /// Location to output build files
     pub(crate) dist: PathBuf,
     /// Location for the temp dir
@@ -133,13 +202,26 @@ pub(crate) struct BuildConfig {
     pub(crate) invalidate_cache: bool,
     /// Whether to do ssg
     pub(crate) ssg: bool,
+    /// Byte budget for css to inline in the html
+    pub(crate) critical_css: Option<usize>,
+    /// Whether to add subresource integrity hashes
+    pub(crate) sri: bool,
+    /// Which precompressed variants of the output to write
+    pub(crate) compress: Compression,
+    /// Whether to avoid inline scripts and styles in the html
+    pub(crate) csp: bool,
+    /// Whether to write source maps in release builds, dev builds always have them
+    pub(crate) sourcemap: bool,
+    /// Whether to print a breakdown of the output sizes
+    pub(crate) analyze: bool,
 }
 
 impl DevArguments {
     /// Create a `BuildConfig` from `DevArguments` with appropriate defaults
     pub(crate) fn get_build_config(&self) -> Result<BuildConfig> {
         let profile = self.shared.profile.unwrap_or(BuildProfile::Dev);
-        let target = utils::find_target_natrix(profile)?;
+        let cargo_profile = self.shared.cargo_profile(profile);
+        let target = utils::find_target_natrix(profile, &cargo_profile)?;
 
         let dist = target.join("dist");
 
@@ -158,15 +240,27 @@ impl DevArguments {
             None
         };
 
+        if self.shared.csp {
+            NatrixConfig::read_config()?.check_csp()?;
+        }
+
         Ok(BuildConfig {
             profile,
+            cargo_profile,
             dist,
             temp_dir: target,
             live_reload,
             cache_bust: CacheBustOption::Timestamp,
             base_path: Box::from(""),
             invalidate_cache: self.shared.invalidate_cache,
-            ssg: false,
+            // NOTE: Without ssg the css is injected in a inline `<style>` at runtime
+            ssg: self.shared.csp,
+            critical_css: None,
+            sri: false,
+            compress: self.shared.compression(profile),
+            csp: self.shared.csp,
+            sourcemap: false,
+            analyze: false,
         })
     }
 }
@@ -177,24 +271,71 @@ impl BuildArguments {
         let config = NatrixConfig::read_config()?;
 
         let profile = self.shared.profile.unwrap_or(BuildProfile::Release);
+        let cargo_profile = self.shared.cargo_profile(profile);
+        if self.shared.csp {
+            config.check_csp()?;
+        }
+
         Ok(BuildConfig {
             profile,
             dist: self.dist.unwrap_or_else(|| PathBuf::from("./dist")),
-            temp_dir: utils::find_target_natrix(profile)?,
+            temp_dir: utils::find_target_natrix(profile, &cargo_profile)?,
+            cargo_profile,
             live_reload: None,
             cache_bust: config.cache_bust,
-            base_path: config.base_path,
+            base_path: normalize_base_path(self.base_path.as_deref().unwrap_or(&config.base_path)),
             invalidate_cache: self.shared.invalidate_cache,
-            ssg: config.ssg && profile == BuildProfile::Release,
+            ssg: self.shared.csp || (config.ssg && profile == BuildProfile::Release),
+            critical_css: self.critical_css,
+            sri: self.sri,
+            compress: self.shared.compression(profile),
+            csp: self.shared.csp,
+            sourcemap: self.sourcemap,
+            analyze: self.analyze,
         })
     }
 }
 
+impl SharedArguments {
+    /// The cargo profile to use, falling back to the one matching the natrix profile
+    fn cargo_profile(&self, profile: BuildProfile) -> String {
+        self.cargo_profile
+            .clone()
+            .unwrap_or_else(|| profile.cargo().to_owned())
+    }
+
+    /// The compression to use, compression is only done for release builds
+    fn compression(&self, profile: BuildProfile) -> Compression {
+        if profile == BuildProfile::Release {
+            self.compress
+        } else {
+            Compression::None
+        }
+    }
+}
+
 impl BuildConfig {
     /// Should dev sever do direct serving
     pub(crate) fn should_direct_serve_files(&self) -> bool {
         self.profile == BuildProfile::Dev && self.live_reload.is_some()
     }
+
+    /// Return the folder cargo puts the build output in for the cargo profile
+    pub(crate) fn cargo_target_folder(&self) -> &str {
+        cargo_profile_folder(&self.cargo_profile)
+    }
+}
+
+/// Return the folder cargo puts the build output of the given profile in.
+///
+/// The built-in `dev` and `test` profiles use `debug`, `bench` uses `release`, and custom profiles
+/// use their own name.
+fn cargo_profile_folder(profile: &str) -> &str {
+    match profile {
+        "dev" | "test" => "debug",
+        "bench" => "release",
+        custom => custom,
+    }
 }
 
 /// Build profile
@@ -222,14 +363,6 @@ impl BuildProfile {
             Self::Dev => "dev",
         }
     }
-
-    /// Return the target output folder
-    pub(crate) fn target(self) -> &'static str {
-        match self {
-            Self::Release => "release",
-            Self::Dev => "debug",
-        }
-    }
 }
 
 /// Cache busting options
@@ -243,3 +376,132 @@ pub(crate) enum CacheBustOption {
     /// Create a hash based on the timestamp
     Timestamp,
 }
+
+/// Starter code for `natrix new`
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq, Debug)]
+pub(crate) enum Template {
+    /// A hello world with a counter
+    Hello,
+    /// Only a heading, and no optional features
+    Minimal,
+    /// A counter that can be incremented, decremented, and reset
+    Counter,
+    /// Multiple pages with client side routing
+    Router,
+    /// A todo list
+    Todo,
+    /// A hello world with a light and dark theme toggle
+    Theme,
+}
+
+impl Template {
+    /// The natrix features the template uses
+    pub(crate) fn features(self) -> &'static [&'static str] {
+        match self {
+            Self::Minimal => &["console_log"],
+            Self::Hello | Self::Counter | Self::Router | Self::Todo | Self::Theme => {
+                &["default_app"]
+            }
+        }
+    }
+}
+
+/// Precompression options
+#[derive(Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub(crate) enum Compression {
+    /// Do not precompress
+    None,
+    /// Write `.gz` files
+    Gzip,
+    /// Write `.br` files
+    Brotli,
+    /// Write both `.gz` and `.br` files
+    Both,
+}
+
+/// Give the base path a leading slash and no trailing slash, so `myapp/` becomes `/myapp`.
+///
+/// A base path of `/` is the same as none.
+fn normalize_base_path(base_path: &str) -> Box<str> {
+    let base_path = base_path.trim_matches('/');
+    if base_path.is_empty() {
+        Box::from("")
+    } else {
+        Box::from(format!("/{base_path}"))
+    }
+}
+
+impl Compression {
+    /// Should `.gz` files be written
+    pub(crate) fn gzip(self) -> bool {
+        matches!(self, Self::Gzip | Self::Both)
+    }
+
+    /// Should `.br` files be written
+    pub(crate) fn brotli(self) -> bool {
+        matches!(self, Self::Brotli | Self::Both)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_profile_folders() {
+        assert_eq!(cargo_profile_folder(BuildProfile::Dev.cargo()), "debug");
+        assert_eq!(
+            cargo_profile_folder(BuildProfile::Release.cargo()),
+            "release"
+        );
+        assert_eq!(cargo_profile_folder("test"), "debug");
+        assert_eq!(cargo_profile_folder("bench"), "release");
+        assert_eq!(cargo_profile_folder("size"), "size");
+    }
+
+    #[test]
+    fn base_path_normalization() {
+        assert_eq!(&*normalize_base_path(""), "");
+        assert_eq!(&*normalize_base_path("/"), "");
+        assert_eq!(&*normalize_base_path("/myapp"), "/myapp");
+        assert_eq!(&*normalize_base_path("/myapp/"), "/myapp");
+        assert_eq!(&*normalize_base_path("myapp"), "/myapp");
+        assert_eq!(&*normalize_base_path("/my/app/"), "/my/app");
+    }
+
+    #[test]
+    fn csp_requires_ssg() {
+        assert!(NatrixConfig::default().check_csp().is_ok());
+
+        let config = NatrixConfig {
+            ssg: false,
+            ..NatrixConfig::default()
+        };
+        assert!(config.check_csp().is_err());
+    }
+
+    #[test]
+    fn new_template() -> Result<()> {
+        let Cli::New { template, .. } = Cli::try_parse_from(["natrix", "new", "app"])? else {
+            return Err(anyhow!("Expected the new command"));
+        };
+        assert_eq!(template, Template::Hello);
+
+        let Cli::New { template, .. } =
+            Cli::try_parse_from(["natrix", "new", "app", "--template", "router"])?
+        else {
+            return Err(anyhow!("Expected the new command"));
+        };
+        assert_eq!(template, Template::Router);
+
+        let Err(error) = Cli::try_parse_from(["natrix", "new", "app", "--template", "blog"]) else {
+            return Err(anyhow!("Unknown templates should be rejected"));
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("hello, minimal, counter, router, todo")
+        );
+        Ok(())
+    }
+}
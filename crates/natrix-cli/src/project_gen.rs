@@ -3,6 +3,7 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::options::Template;
 use crate::prelude::*;
 
 /// Flag for extracting css
@@ -12,7 +13,7 @@ pub const FEATURE_BUNDLE: &str = "__natrix_internal_bundle";
 pub const FEATURE_NO_SGG: &str = "__natrix_internal_no_ssg";
 
 /// Generate a new project
-pub(crate) fn generate_project(name: &str, stable: bool) -> Result<()> {
+pub(crate) fn generate_project(name: &str, stable: bool, template: Template) -> Result<()> {
     let root = PathBuf::from(name);
     fs::create_dir_all(&root)?;
 
@@ -21,8 +22,8 @@ pub(crate) fn generate_project(name: &str, stable: bool) -> Result<()> {
 
     let nightly = !stable;
 
-    generate_cargo_toml(name, &root)?;
-    generate_main_rs(&src, name)?;
+    generate_cargo_toml(name, &root, template)?;
+    generate_main_rs(&src, name, template)?;
     generate_toolchain_toml(&root, nightly)?;
 
     let gitignore = "
@@ -55,16 +56,16 @@ dist
 }
 
 /// Generate the `cargo.toml`
-fn generate_cargo_toml(name: &str, root: &Path) -> Result<(), anyhow::Error> {
+fn generate_cargo_toml(name: &str, root: &Path, template: Template) -> Result<(), anyhow::Error> {
     let natrix_version = env!("CARGO_PKG_VERSION");
     let mut natrix_table = format!(r#"version = "{natrix_version}""#);
     if let Ok(path) = std::env::var("NATRIX_PATH") {
         natrix_table = format!(r#"{natrix_table}, path = "{path}""#);
     }
     let natrix_test_table = format!(r#"natrix = {{{natrix_table}, features=["test_utils"]}}"#);
-    let features = vec!["default_app"];
-    let features = features
-        .into_iter()
+    let features = template
+        .features()
+        .iter()
         .map(|feat| format!(r#""{feat}""#))
         .collect::<Vec<_>>()
         .join(",");
@@ -105,17 +106,36 @@ strip = "symbols"
     Ok(())
 }
 
-/// Generate the main.rs file for a new project
-fn generate_main_rs(src: &Path, name: &str) -> Result<(), anyhow::Error> {
-    let main_rs = format!(
-        r#"
+/// The lints at the top of every generated `main.rs`
+const MAIN_RS_LINTS: &str = "
 // Panicking in a wasm module will cause the state to be invalid
 // And it might cause UB on the next event handler execution.
 // (By default natrix uses a panic hook that blocks further event handler calls after a panic)
 #![deny(clippy::panic, clippy::unwrap_used, clippy::expect_used)]
 // These are more strict anti panic lints that you might want to enable
 // #![warn(clippy::arithmetic_side_effects, clippy::indexing_slicing, clippy::unreachable)]
+";
+
+/// Generate the main.rs file for a new project
+fn generate_main_rs(src: &Path, name: &str, template: Template) -> Result<(), anyhow::Error> {
+    let body = match template {
+        Template::Hello => hello_main_rs(name),
+        Template::Theme => theme_main_rs(name),
+        Template::Minimal => minimal_main_rs(name),
+        Template::Counter => counter_main_rs(name),
+        Template::Router => router_main_rs(name),
+        Template::Todo => todo_main_rs(name),
+    };
+    let main_rs = format!("{}\n{}", MAIN_RS_LINTS.trim_start(), body.trim_start());
+    fs::write(src.join("main.rs"), main_rs)?;
+
+    Ok(())
+}
 
+/// The hello world with a counter
+fn hello_main_rs(name: &str) -> String {
+    format!(
+        r#"
 use natrix::prelude::*;
 
 const HELLO_ID: Id = natrix::id!();
@@ -157,10 +177,344 @@ mod tests {{
     }}
 }}
 "#
-    );
-    fs::write(src.join("main.rs"), main_rs)?;
+    )
+}
 
-    Ok(())
+/// A hello world with a light and dark theme toggle
+fn theme_main_rs(name: &str) -> String {
+    format!(
+        r#"
+use natrix::prelude::*;
+use natrix::css::theme::{{self, Theme, ThemeMode, ThemeVariable}};
+
+const HELLO_ID: Id = natrix::id!();
+
+const BACKGROUND: ThemeVariable = ThemeVariable("background");
+const TEXT: ThemeVariable = ThemeVariable("text");
+
+natrix::register_theme!(Theme::new()
+    .variable(BACKGROUND, values::Color::rgb(255, 255, 255), values::Color::rgb(24, 24, 27))
+    .variable(TEXT, values::Color::rgb(24, 24, 27), values::Color::rgb(244, 244, 245))
+);
+
+const APP: Class = natrix::class!();
+natrix::register_rule!(APP, RuleBody::new()
+    .property("background-color", BACKGROUND.into_css())
+    .property("color", TEXT.into_css())
+);
+
+#[derive(State)]
+struct App;
+
+fn render_app() -> impl Element<App> {{
+    e::div()
+        .class(APP)
+        .child(e::h1().text("Hello {name}").id(HELLO_ID))
+        .child(e::button()
+            .text("Toggle theme")
+            .on::<events::Click>(|_ctx: EventCtx<App>, _| {{
+                let next = match theme::current_theme() {{
+                    ThemeMode::Dark => ThemeMode::Light,
+                    ThemeMode::Light | ThemeMode::System => ThemeMode::Dark,
+                }};
+                theme::set_theme(next);
+            }})
+        )
+}}
+
+fn main() {{
+    theme::load_theme();
+    natrix::mount(App, render_app);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use natrix::test_utils;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test() {{
+        test_utils::mount_test(App, render_app());
+        let element = test_utils::get(HELLO_ID);
+        assert_eq!(element.text_content(), Some("Hello {name}".to_string()));
+    }}
+}}
+"#
+    )
+}
+
+/// A single heading
+fn minimal_main_rs(name: &str) -> String {
+    format!(
+        r#"
+use natrix::prelude::*;
+
+const HELLO_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct App;
+
+fn render_app() -> impl Element<App> {{
+    e::h1().text("Hello {name}").id(HELLO_ID)
+}}
+
+fn main() {{
+    natrix::mount(App, render_app);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use natrix::test_utils;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test() {{
+        test_utils::mount_test(App, render_app());
+        let element = test_utils::get(HELLO_ID);
+        assert_eq!(element.text_content(), Some("Hello {name}".to_string()));
+    }}
+}}
+"#
+    )
+}
+
+/// A counter that can be incremented, decremented, and reset
+fn counter_main_rs(name: &str) -> String {
+    format!(
+        r#"
+use natrix::prelude::*;
+
+const COUNT_ID: Id = natrix::id!();
+const INCREMENT_ID: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct App {{
+    count: Signal<i32>,
+}}
+
+fn render_app() -> impl Element<App> {{
+    e::div()
+        .child(e::h1().text("{name}"))
+        .child(e::p()
+            .id(COUNT_ID)
+            .text(|ctx: RenderCtx<App>| *ctx.count)
+        )
+        .child(e::button()
+            .text("-")
+            .on::<events::Click>(|mut ctx: EventCtx<App>, _| {{
+                *ctx.count = ctx.count.saturating_sub(1);
+            }})
+        )
+        .child(e::button()
+            .id(INCREMENT_ID)
+            .text("+")
+            .on::<events::Click>(|mut ctx: EventCtx<App>, _| {{
+                *ctx.count = ctx.count.saturating_add(1);
+            }})
+        )
+        .child(e::button()
+            .text("Reset")
+            .disabled(|ctx: RenderCtx<App>| *ctx.count == 0)
+            .on::<events::Click>(|mut ctx: EventCtx<App>, _| {{
+                *ctx.count = 0;
+            }})
+        )
+}}
+
+fn main() {{
+    natrix::mount(App::default(), render_app);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use natrix::test_utils;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test() {{
+        test_utils::mount_test(App::default(), render_app());
+        let count = test_utils::get(COUNT_ID);
+        assert_eq!(count.text_content(), Some("0".to_string()));
+
+        test_utils::get(INCREMENT_ID).click();
+        assert_eq!(count.text_content(), Some("1".to_string()));
+    }}
+}}
+"#
+    )
+}
+
+/// Multiple pages using the router
+fn router_main_rs(name: &str) -> String {
+    format!(
+        r#"
+use natrix::prelude::*;
+use natrix::router::{{FromRouteParams, Location, RouteParams, Router, Routes}};
+
+const NAV_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct App {{
+    location: Location,
+}}
+
+/// Create a link to a page in the app, taking the base path into account
+fn link(path: &str, text: &'static str) -> impl Element<App> {{
+    e::a().href(format!("{{}}{{path}}", natrix::base_path())).text(text)
+}}
+
+struct UserRoute {{
+    id: u32,
+}}
+
+impl FromRouteParams for UserRoute {{
+    fn from_route_params(params: &RouteParams) -> Option<Self> {{
+        Some(Self {{
+            id: params.parse("id")?,
+        }})
+    }}
+}}
+
+fn user(UserRoute {{ id }}: UserRoute) -> impl Element<App> {{
+    e::h1().text(format!("User {{id}}"))
+}}
+
+fn render_app() -> impl Element<App> {{
+    e::div()
+        .child(e::nav()
+            .id(NAV_ID)
+            .child(link("/", "Home"))
+            .child(link("/about", "About"))
+            .child(link("/users/1", "First user"))
+        )
+        .child(Router::new(
+            |ctx: Ref<App>| field!(ctx.location),
+            Routes::new()
+                .route("/", |()| e::h1().text("Hello {name}"))
+                .route("/about", |()| e::h1().text("About {name}"))
+                .route("/users/:id", user)
+                .fallback(|path| e::h1().text(format!("{{path}} not found"))),
+        ))
+}}
+
+fn main() {{
+    natrix::mount(App {{ location: Location::current() }}, render_app);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use natrix::test_utils;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test() {{
+        test_utils::mount_test(App {{ location: Location::current() }}, render_app());
+        let nav = test_utils::get(NAV_ID);
+        assert_eq!(nav.child_element_count(), 3);
+    }}
+}}
+"#
+    )
+}
+
+/// A todo list
+fn todo_main_rs(name: &str) -> String {
+    format!(
+        r#"
+use natrix::prelude::*;
+
+const TITLE_ID: Id = natrix::id!();
+
+#[derive(Clone, PartialEq)]
+struct Todo {{
+    id: u32,
+    text: String,
+}}
+
+#[derive(State, Default)]
+struct App {{
+    new_todo: Signal<String>,
+    next_id: Signal<u32>,
+    todos: Signal<Vec<Todo>>,
+}}
+
+fn add_todo(mut ctx: EventCtx<App>) {{
+    let text = ctx.new_todo.trim().to_owned();
+    if text.is_empty() {{
+        return;
+    }}
+
+    let id = *ctx.next_id;
+    *ctx.next_id = id.wrapping_add(1);
+    ctx.todos.push(Todo {{ id, text }});
+    ctx.new_todo.clear();
+}}
+
+fn render_todo(todo: Todo) -> impl Element<App> {{
+    let id = todo.id;
+    e::li()
+        .text(todo.text)
+        .child(e::button()
+            .text("Remove")
+            .on::<events::Click>(move |mut ctx: EventCtx<App>, _| {{
+                ctx.todos.retain(|todo| todo.id != id);
+            }})
+        )
+}}
+
+fn render_app() -> impl Element<App> {{
+    e::div()
+        .child(e::h1().text("{name} todos").id(TITLE_ID))
+        .child(e::text_input().bind(|ctx: Ref<App>| field!(ctx.new_todo).deref()))
+        .child(e::button()
+            .text("Add")
+            .on::<events::Click>(|ctx: EventCtx<App>, _| add_todo(ctx))
+        )
+        .child(e::ul().children_keyed(
+            |ctx: &App| &ctx.todos,
+            |todo| todo.id,
+            |todo| render_todo(todo.clone()),
+        ))
+}}
+
+fn main() {{
+    natrix::mount(App::default(), render_app);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use natrix::test_utils;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test() {{
+        test_utils::mount_test(App::default(), render_app());
+        let element = test_utils::get(TITLE_ID);
+        assert_eq!(element.text_content(), Some("{name} todos".to_string()));
+    }}
+}}
+"#
+    )
 }
 
 /// Generate the `rust-toolchain.toml`
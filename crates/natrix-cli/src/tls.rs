@@ -0,0 +1,372 @@
+//! Self signed certificates for serving `natrix dev --tls` over https.
+//!
+//! Some browser apis are only available in a secure context, which plain http only is for
+//! `localhost`.
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::{fs, thread};
+
+use rustls::ServerConnection;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+use crate::prelude::*;
+
+/// The folder in the user cache dir the certificate is kept in.
+///
+/// The certificate is reused across restarts so the browser only has to be told to trust it
+/// once.
+const CERTIFICATE_FOLDER: &str = "natrix/dev-tls";
+/// The file name of the certificate
+const CERTIFICATE_FILE: &str = "cert.pem";
+/// The file name of the private key
+const KEY_FILE: &str = "key.pem";
+
+/// The size of the buffers used when forwarding traffic
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// A self signed certificate and its private key, in pem format
+#[derive(Clone)]
+pub(crate) struct DevCertificate {
+    /// The certificate
+    certificate: String,
+    /// The private key
+    private_key: String,
+}
+
+impl DevCertificate {
+    /// Load the certificate from the cache dir, generating and saving a new one if there is none.
+    pub(crate) fn load_or_generate() -> Result<Self> {
+        let folder = certificate_folder()?;
+        if let Some(certificate) = Self::load(&folder) {
+            return Ok(certificate);
+        }
+
+        println!("🔐 {}", "Generating self signed certificate".bright_blue());
+        let certificate = Self::generate()?;
+        certificate.save(&folder)?;
+        Ok(certificate)
+    }
+
+    /// Load a previously generated certificate
+    fn load(folder: &Path) -> Option<Self> {
+        let certificate_path = folder.join(CERTIFICATE_FILE);
+        let key_path = folder.join(KEY_FILE);
+        // NOTE: Files someone else could have written are not trusted, otherwise the browser
+        // could be told to accept a certificate for any domain.
+        if ![folder, &certificate_path, &key_path]
+            .into_iter()
+            .all(is_private)
+        {
+            return None;
+        }
+
+        let certificate = Self {
+            certificate: fs::read_to_string(certificate_path).ok()?,
+            private_key: fs::read_to_string(key_path).ok()?,
+        };
+        // NOTE: A corrupt certificate is just generated again.
+        certificate.rustls_config().is_ok().then_some(certificate)
+    }
+
+    /// Save the certificate, replacing any existing files
+    fn save(&self, folder: &Path) -> Result<()> {
+        create_private_folder(folder)?;
+        write_private_file(&folder.join(CERTIFICATE_FILE), &self.certificate)?;
+        write_private_file(&folder.join(KEY_FILE), &self.private_key)?;
+        Ok(())
+    }
+
+    /// Generate a new certificate valid for localhost
+    fn generate() -> Result<Self> {
+        let rcgen::CertifiedKey { cert, signing_key } = rcgen::generate_simple_self_signed(vec![
+            String::from("localhost"),
+            String::from("127.0.0.1"),
+        ])?;
+        Ok(Self {
+            certificate: cert.pem(),
+            private_key: signing_key.serialize_pem(),
+        })
+    }
+
+    /// The config for accepting tls connections
+    pub(crate) fn rustls_config(&self) -> Result<Arc<rustls::ServerConfig>> {
+        let certificates = CertificateDer::pem_slice_iter(self.certificate.as_bytes())
+            .collect::<Result<Vec<_>, _>>()?;
+        let private_key = PrivateKeyDer::from_pem_slice(self.private_key.as_bytes())?;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certificates, private_key)?;
+        Ok(Arc::new(config))
+    }
+}
+
+/// The folder the certificate is kept in
+fn certificate_folder() -> Result<PathBuf> {
+    let cache = dirs::cache_dir().ok_or(anyhow!("Could not find the user cache folder"))?;
+    Ok(cache.join(CERTIFICATE_FOLDER))
+}
+
+/// Check that the path is not a symlink and only accessible by its owner, which is the current
+/// user.
+#[cfg(unix)]
+fn is_private(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    let (Ok(metadata), Some(home)) = (fs::symlink_metadata(path), dirs::home_dir()) else {
+        return false;
+    };
+    let Ok(home) = fs::metadata(home) else {
+        return false;
+    };
+    // NOTE: The lowest 6 bits are the permissions of the group and others.
+    !metadata.file_type().is_symlink()
+        && metadata.uid() == home.uid()
+        && metadata.mode().trailing_zeros() >= 6
+}
+
+/// Check that the path is not a symlink, the user cache folder is already private on windows.
+#[cfg(not(unix))]
+fn is_private(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| !metadata.file_type().is_symlink())
+}
+
+/// Create the folder, making it only accessible by the current user
+fn create_private_folder(folder: &Path) -> Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        builder.mode(0o700);
+        builder.create(folder)?;
+        // NOTE: `mode` is only used for folders that did not exist yet.
+        fs::set_permissions(folder, fs::Permissions::from_mode(0o700))?;
+    }
+    #[cfg(not(unix))]
+    builder.create(folder)?;
+    Ok(())
+}
+
+/// Write a new file only readable by the current user, replacing the existing one.
+fn write_private_file(path: &Path, content: &str) -> Result<()> {
+    // NOTE: Removing first means a planted symlink is replaced instead of written through.
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        _ => {}
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Accept tls connections on `listener`, forwarding the decrypted traffic to the plain http
+/// server at `backend`.
+pub(crate) fn proxy(
+    listener: &TcpListener,
+    backend: SocketAddr,
+    config: &Arc<rustls::ServerConfig>,
+) {
+    for client in listener.incoming() {
+        let Ok(client) = client else {
+            continue;
+        };
+        let config = Arc::clone(config);
+        thread::spawn(move || {
+            let _ = forward(client, backend, config);
+        });
+    }
+}
+
+/// Forward a single tls connection to the backend
+fn forward(
+    client: TcpStream,
+    backend: SocketAddr,
+    config: Arc<rustls::ServerConfig>,
+) -> Result<()> {
+    let connection = Arc::new(Mutex::new(ServerConnection::new(config)?));
+    let server = TcpStream::connect(backend)?;
+
+    let responses = {
+        let connection = Arc::clone(&connection);
+        let server = server.try_clone()?;
+        let client = client.try_clone()?;
+        thread::spawn(move || forward_responses(&connection, server, client))
+    };
+
+    let result = forward_requests(&connection, client, &server);
+    // NOTE: On errors this also ends the response thread, which is waiting on the backend.
+    let shutdown = if result.is_ok() {
+        Shutdown::Write
+    } else {
+        Shutdown::Both
+    };
+    let _ = server.shutdown(shutdown);
+    let _ = responses.join();
+    result
+}
+
+/// Decrypt the requests from the client and send them to the backend
+fn forward_requests(
+    connection: &Mutex<ServerConnection>,
+    mut client: TcpStream,
+    mut server: &TcpStream,
+) -> Result<()> {
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = client.read(&mut buffer)?;
+        let Some(mut received) = buffer.get(..read).filter(|received| !received.is_empty()) else {
+            return Ok(());
+        };
+
+        let mut plaintext = Vec::new();
+        let mut connection = lock(connection)?;
+        while !received.is_empty() {
+            connection.read_tls(&mut received)?;
+            let state = connection.process_new_packets();
+            // NOTE: Handshake messages, or the alert if the packets were invalid.
+            write_tls(&mut connection, &mut client)?;
+            state?;
+        }
+        match connection.reader().read_to_end(&mut plaintext) {
+            Err(err) if err.kind() != io::ErrorKind::WouldBlock => return Err(err.into()),
+            _ => {}
+        }
+        drop(connection);
+
+        server.write_all(&plaintext)?;
+    }
+}
+
+/// Encrypt the responses from the backend and send them to the client
+fn forward_responses(
+    connection: &Mutex<ServerConnection>,
+    mut server: TcpStream,
+    mut client: TcpStream,
+) -> Result<()> {
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = server.read(&mut buffer)?;
+        let mut connection = lock(connection)?;
+        let Some(response) = buffer.get(..read).filter(|response| !response.is_empty()) else {
+            connection.send_close_notify();
+            write_tls(&mut connection, &mut client)?;
+            client.shutdown(Shutdown::Write)?;
+            return Ok(());
+        };
+        connection.writer().write_all(response)?;
+        write_tls(&mut connection, &mut client)?;
+    }
+}
+
+/// Send all pending tls data to the client
+fn write_tls(connection: &mut ServerConnection, client: &mut TcpStream) -> Result<()> {
+    while connection.wants_write() {
+        connection.write_tls(client)?;
+    }
+    Ok(())
+}
+
+/// Lock the connection
+fn lock(connection: &Mutex<ServerConnection>) -> Result<MutexGuard<'_, ServerConnection>> {
+    connection
+        .lock()
+        .map_err(|_| anyhow!("Failed to lock mutex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_certificate_is_usable() -> Result<()> {
+        let certificate = DevCertificate::generate()?;
+        certificate.rustls_config()?;
+        Ok(())
+    }
+
+    #[test]
+    fn proxy_forwards_decrypted_traffic() -> Result<()> {
+        let certificate = DevCertificate::generate()?;
+        let config = certificate.rustls_config()?;
+
+        let backend = TcpListener::bind("127.0.0.1:0")?;
+        let backend_addr = backend.local_addr()?;
+        thread::spawn(move || -> Result<()> {
+            let (mut stream, _) = backend.accept()?;
+            let mut request = [0; 4];
+            stream.read_exact(&mut request)?;
+            assert_eq!(&request, b"ping");
+            stream.write_all(b"pong")?;
+            Ok(())
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let proxy_addr = listener.local_addr()?;
+        thread::spawn(move || proxy(&listener, backend_addr, &config));
+
+        let mut roots = rustls::RootCertStore::empty();
+        for certificate in CertificateDer::pem_slice_iter(certificate.certificate.as_bytes()) {
+            roots.add(certificate?)?;
+        }
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let client_config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connection =
+            rustls::ClientConnection::new(Arc::new(client_config), "localhost".try_into()?)?;
+        let mut client = rustls::StreamOwned::new(connection, TcpStream::connect(proxy_addr)?);
+
+        client.write_all(b"ping")?;
+        let mut response = Vec::new();
+        client.read_to_end(&mut response)?;
+        assert_eq!(response, b"pong");
+        Ok(())
+    }
+
+    #[test]
+    fn saved_certificate_is_loaded() -> Result<()> {
+        let folder = std::env::temp_dir().join(format!("natrix-tls-test-{}", std::process::id()));
+        let certificate = DevCertificate::generate()?;
+        certificate.save(&folder)?;
+
+        let loaded = DevCertificate::load(&folder);
+        fs::remove_dir_all(&folder)?;
+        assert_eq!(
+            loaded.map(|loaded| loaded.private_key),
+            Some(certificate.private_key)
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn readable_certificate_is_not_trusted() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let folder =
+            std::env::temp_dir().join(format!("natrix-tls-planted-{}", std::process::id()));
+        DevCertificate::generate()?.save(&folder)?;
+        fs::set_permissions(folder.join(KEY_FILE), fs::Permissions::from_mode(0o644))?;
+
+        let loaded = DevCertificate::load(&folder);
+        fs::remove_dir_all(&folder)?;
+        assert!(loaded.is_none());
+        Ok(())
+    }
+}
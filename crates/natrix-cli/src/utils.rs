@@ -113,10 +113,20 @@ pub(crate) fn is_natrix_version_matching() -> Result<bool> {
 }
 
 /// Find the natrix target folder
-pub(crate) fn find_target_natrix(mode: options::BuildProfile) -> Result<PathBuf> {
+///
+/// Custom cargo profiles get their own folder, as their macro outputs might differ.
+pub(crate) fn find_target_natrix(
+    mode: options::BuildProfile,
+    cargo_profile: &str,
+) -> Result<PathBuf> {
     let target = find_target()?;
     let project = get_project_name()?;
-    Ok(target.join(format!("natrix-{project}-{}", mode.readable())))
+    let folder = if cargo_profile == mode.cargo() {
+        format!("natrix-{project}-{}", mode.readable())
+    } else {
+        format!("natrix-{project}-{}-{cargo_profile}", mode.readable())
+    };
+    Ok(target.join(folder))
 }
 
 /// Get the current target project name
@@ -0,0 +1,78 @@
+//! Runtime support for the asset macros
+
+/// A directory of assets bundled with [`dir_asset!`](crate::dir_asset).
+#[derive(Clone, Copy, Debug)]
+pub struct AssetDir {
+    /// The url of the directory
+    base: &'static str,
+    /// The paths of the files in the directory, relative to it
+    files: &'static [&'static str],
+}
+
+impl AssetDir {
+    /// Used by `dir_asset!`
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn __new(base: &'static str, files: &'static [&'static str]) -> Self {
+        Self { base, files }
+    }
+
+    /// Get the url of a file in the directory, such as `fonts/inter.woff2`.
+    ///
+    /// Returns `None` if the file is not part of the directory, including paths trying to
+    /// escape it with `..`.
+    #[must_use]
+    pub fn url(&self, file: &str) -> Option<String> {
+        let file = file.strip_prefix("./").unwrap_or(file);
+        if file.contains('\\')
+            || file
+                .split('/')
+                .any(|segment| matches!(segment, "" | "." | ".."))
+        {
+            log::warn!("Rejected asset path {file:?}");
+            return None;
+        }
+
+        if self.files.contains(&file) {
+            Some(format!("{}/{file}", self.base))
+        } else {
+            log::warn!("Asset {file:?} not found in {}", self.base);
+            None
+        }
+    }
+
+    /// The paths of the files in the directory, relative to it
+    #[must_use]
+    pub fn files(&self) -> &'static [&'static str] {
+        self.files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIR: AssetDir = AssetDir::__new("/HASH-public", &["font.woff2", "images/logo.png"]);
+
+    #[test]
+    fn resolves_files() {
+        assert_eq!(
+            DIR.url("font.woff2").as_deref(),
+            Some("/HASH-public/font.woff2")
+        );
+        assert_eq!(
+            DIR.url("./images/logo.png").as_deref(),
+            Some("/HASH-public/images/logo.png")
+        );
+        assert_eq!(DIR.url("missing.png"), None);
+    }
+
+    #[test]
+    fn rejects_path_traversal() {
+        assert_eq!(DIR.url("../secret.txt"), None);
+        assert_eq!(DIR.url("images/../font.woff2"), None);
+        assert_eq!(DIR.url("/font.woff2"), None);
+        assert_eq!(DIR.url("images\\logo.png"), None);
+        assert_eq!(DIR.url("images//logo.png"), None);
+    }
+}
@@ -1,13 +1,23 @@
 //! Variosu async versions of js callback apis
 #![cfg(feature = "async_utils")]
+use std::cell::Cell;
+use std::ops::ControlFlow;
+use std::pin::{Pin, pin};
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 
-use futures_channel::oneshot;
-use wasm_bindgen::JsValue;
+use futures_channel::{mpsc, oneshot};
+pub use futures_core::Stream;
 use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::js_sys::Function;
 
+use crate::EventCtx;
 use crate::error_handling::log_or_panic;
+use crate::reactivity::State;
+use crate::reactivity::signal::Signal;
+use crate::reactivity::state::{AsyncCtx, AsyncCtxHandle, RenderCtx};
 
 /// A guard that executes a callback when dropped.
 ///
@@ -64,57 +74,65 @@ pub fn sleep(duration: Duration) -> Option<impl Future<Output = ()>> {
 /// This function encapsulates the common logic for setting up a oneshot channel,
 /// creating a JS closure, calling a setup function (like `setTimeout` or `requestAnimationFrame`),
 /// and managing cancellation with a `DropGuard`.
+///
+/// Returns the first argument the callback was called with.
 async fn wait_with_cancellation<T: Copy + 'static>(
     setup: impl FnOnce(&Function) -> Result<T, JsValue>,
     cancel: impl FnOnce(T) + 'static,
     setup_err_msg: &str,
     recv_err_msg: &str,
-) {
+) -> JsValue {
     let (tx, rx) = oneshot::channel();
-    let closure = Closure::once(move || {
-        let _ = tx.send(());
+    let closure = Closure::once(move |value: JsValue| {
+        let _ = tx.send(value);
     });
     let function: Function = closure.as_ref().clone().into();
 
     let Ok(id) = setup(&function) else {
         log_or_panic!("{}", setup_err_msg);
-        return;
+        return JsValue::UNDEFINED;
     };
 
     let mut drop_guard = DropGuard::new(move || {
         cancel(id);
     });
 
-    if rx.await.is_err() {
+    let Ok(value) = rx.await else {
         log_or_panic!("{}", recv_err_msg);
-        return;
-    }
+        return JsValue::UNDEFINED;
+    };
 
     drop_guard.cancel();
+    value
 }
 
 /// Sleeps for the given milliseconds using js `setTimeout`.
 ///
+/// Runs on the fake clock in tests using
+/// [`test_utils::use_fake_timers`](crate::test_utils::use_fake_timers).
+///
 /// # Why `i32`?
 /// Because thats what the `web_sys` `setTimeout` binding uses.
 pub async fn sleep_milliseconds(milis: i32) {
     wait_with_cancellation(
-        |function| {
-            crate::get_window()
-                .set_timeout_with_callback_and_timeout_and_arguments_0(function, milis)
-        },
-        move |timeout_id| {
-            crate::get_window().clear_timeout_with_handle(timeout_id);
-        },
+        |function| crate::shims::set_timeout(function, milis),
+        crate::shims::clear_timeout,
         "Failed to set timeout. This is a bug in the browser or the framework.",
         "Failed to receive timeout signal",
     )
     .await;
 }
 
-/// Wait for the next browser animation frame using `requestAnimationFrame`.
+/// The timestamp-discarding form of [`next_frame`], kept for compatibility.
 pub async fn next_animation_frame() {
-    wait_with_cancellation(
+    next_frame().await;
+}
+
+/// Wait for the next browser animation frame using `requestAnimationFrame`.
+///
+/// Returns the frame timestamp in milliseconds, as passed to the `requestAnimationFrame` callback.
+pub async fn next_frame() -> f64 {
+    let timestamp = wait_with_cancellation(
         |function| crate::get_window().request_animation_frame(function),
         move |frame_id| {
             let res = crate::get_window().cancel_animation_frame(frame_id);
@@ -128,6 +146,583 @@ pub async fn next_animation_frame() {
         "Failed to receive animation frame signal",
     )
     .await;
+
+    timestamp.as_f64().unwrap_or_default()
+}
+
+/// A stream yielding every given number of milliseconds, created with [`interval_milliseconds`].
+///
+/// The interval is cleared when this is dropped.
+#[must_use = "Streams do nothing unless polled"]
+pub struct Interval {
+    /// The receiving end of the ticks
+    receiver: mpsc::UnboundedReceiver<()>,
+    /// The id of the js interval
+    id: i32,
+    /// The js callback, kept alive for as long as the interval is active
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        crate::get_window().clear_interval_with_handle(self.id);
+    }
+}
+
+/// Create a stream that yields every given milliseconds using js `setInterval`.
+///
+/// Ticks are buffered, so if the stream is not polled for a while it will yield the missed ticks
+/// in quick succession.
+pub fn interval_milliseconds(milis: i32) -> Interval {
+    let (sender, receiver) = mpsc::unbounded();
+    let closure = Closure::<dyn FnMut()>::new(move || {
+        let _ = sender.unbounded_send(());
+    });
+
+    let id = crate::get_window()
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            milis,
+        )
+        .unwrap_or_else(|_| {
+            log_or_panic!("Failed to set interval. This is a bug in the browser or the framework.");
+            0
+        });
+
+    Interval {
+        receiver,
+        id,
+        _closure: closure,
+    }
+}
+
+/// Shared state of a [`StreamHandle`]
+#[derive(Default)]
+struct CancelState {
+    /// Set once the stream should stop
+    cancelled: Cell<bool>,
+    /// The waker of the task consuming the stream, so cancelling does not have to wait for the
+    /// next item
+    waker: Cell<Option<Waker>>,
+}
+
+/// A handle to a stream consumed by [`EventCtx::for_each_stream`] or
+/// [`EventCtx::bind_stream`].
+///
+/// Dropping the handle does not stop the stream.
+#[derive(Clone)]
+pub struct StreamHandle {
+    /// The shared cancel state
+    state: Rc<CancelState>,
+}
+
+impl StreamHandle {
+    /// Stop consuming the stream and drop it, the function is not called again after this.
+    pub fn cancel(&self) {
+        self.state.cancelled.set(true);
+        if let Some(waker) = self.state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.get()
+    }
+}
+
+/// Cancels the stream when dropped, used to tie it to the lifetime of a reactive closure.
+struct CancelOnDrop(StreamHandle);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Run the future until it completes or the state is cancelled, dropping it when cancelled.
+async fn until_cancelled<F: Future<Output = Option<()>>>(
+    state: &CancelState,
+    future: F,
+) -> Option<()> {
+    let mut future = pin!(future);
+    std::future::poll_fn(|cx| {
+        if state.cancelled.get() {
+            return Poll::Ready(None);
+        }
+        state.waker.set(Some(cx.waker().clone()));
+        future.as_mut().poll(cx)
+    })
+    .await
+}
+
+/// Wait for the next item of the stream.
+async fn next_item<S: Stream>(mut stream: Pin<&mut S>) -> Option<S::Item> {
+    std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+}
+
+/// Poll the future if it hasnt completed yet, storing its output in `slot`.
+fn poll_into<F: Future>(future: Pin<&mut F>, slot: &mut Option<F::Output>, cx: &mut Context<'_>) {
+    if slot.is_none()
+        && let Poll::Ready(value) = future.poll(cx)
+    {
+        *slot = Some(value);
+    }
+}
+
+/// Run two futures concurrently, resolving once both have completed.
+///
+/// # Borrowing
+/// Each future should only access the state inside [`AsyncCtxHandle::update`] calls,
+/// and never hold a [`AsyncCtx`] across an `.await`.
+/// (The borrow checker already prevents this, as the `AsyncCtx` only lives for the closure.)
+/// This means the futures are free to interleave without causing borrow errors.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # use natrix::async_utils;
+/// # #[derive(State)]
+/// # struct App {
+/// #     user: Signal<Option<String>>,
+/// #     posts: Signal<Option<Vec<String>>>,
+/// # }
+/// # async fn fetch_user() -> String { String::new() }
+/// # async fn fetch_posts() -> Vec<String> { Vec::new() }
+/// # fn render() -> impl Element<App> {
+/// e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+///     ctx.use_async(async |ctx| {
+///         let (user, posts) = async_utils::join2(fetch_user(), fetch_posts()).await;
+///         ctx.update(|mut ctx| {
+///             *ctx.user = Some(user);
+///             *ctx.posts = Some(posts);
+///         })
+///     });
+/// })
+/// # }
+/// ```
+pub async fn join2<A: Future, B: Future>(first: A, second: B) -> (A::Output, B::Output) {
+    let mut first = pin!(first);
+    let mut second = pin!(second);
+    let (mut first_output, mut second_output) = (None, None);
+
+    std::future::poll_fn(|cx| {
+        poll_into(first.as_mut(), &mut first_output, cx);
+        poll_into(second.as_mut(), &mut second_output, cx);
+
+        if first_output.is_some()
+            && second_output.is_some()
+            && let (Some(first), Some(second)) = (first_output.take(), second_output.take())
+        {
+            return Poll::Ready((first, second));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Run three futures concurrently, resolving once all of them have completed.
+///
+/// See [`join2`] for details.
+pub async fn join3<A: Future, B: Future, C: Future>(
+    first: A,
+    second: B,
+    third: C,
+) -> (A::Output, B::Output, C::Output) {
+    let mut first = pin!(first);
+    let mut second = pin!(second);
+    let mut third = pin!(third);
+    let (mut first_output, mut second_output, mut third_output) = (None, None, None);
+
+    std::future::poll_fn(|cx| {
+        poll_into(first.as_mut(), &mut first_output, cx);
+        poll_into(second.as_mut(), &mut second_output, cx);
+        poll_into(third.as_mut(), &mut third_output, cx);
+
+        if first_output.is_some()
+            && second_output.is_some()
+            && third_output.is_some()
+            && let (Some(first), Some(second), Some(third)) = (
+                first_output.take(),
+                second_output.take(),
+                third_output.take(),
+            )
+        {
+            return Poll::Ready((first, second, third));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// The result of [`select`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+    /// The first future completed first
+    First(A),
+    /// The second future completed first
+    Second(B),
+}
+
+/// Run two futures concurrently, resolving with the output of whichever completes first.
+///
+/// The other future is dropped, which cancels any pending timers or animation frames it was
+/// waiting on. If both are ready at the same time the first one wins.
+///
+/// See [`join2`] for how to access state in the futures.
+///
+/// ```rust
+/// # use natrix::async_utils::{self, Either};
+/// # async fn fetch_data() -> String { String::new() }
+/// # async fn foo() {
+/// match async_utils::select(fetch_data(), async_utils::sleep_milliseconds(5000)).await {
+///     Either::First(data) => println!("Got {data}"),
+///     Either::Second(()) => println!("Timed out"),
+/// }
+/// # }
+/// ```
+pub async fn select<A: Future, B: Future>(first: A, second: B) -> Either<A::Output, B::Output> {
+    let mut first = pin!(first);
+    let mut second = pin!(second);
+
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(value) = first.as_mut().poll(cx) {
+            return Poll::Ready(Either::First(value));
+        }
+        if let Poll::Ready(value) = second.as_mut().poll(cx) {
+            return Poll::Ready(Either::Second(value));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+impl<T: State> EventCtx<'_, T> {
+    /// Run the given function once every animation frame, with the frame timestamp in
+    /// milliseconds.
+    ///
+    /// The loop stops when the function returns `ControlFlow::Break`, or when the state is
+    /// dropped.
+    /// Each call is its own update cycle, similar to [`AsyncCtxHandle::update`].
+    ///
+    /// ```rust
+    /// # use std::ops::ControlFlow;
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     rotation: Signal<f64>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+    ///     ctx.animation_loop(|mut ctx, time| {
+    ///         *ctx.rotation = time / 10.0;
+    ///         if *ctx.rotation > 360.0 {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     });
+    /// })
+    /// # }
+    /// ```
+    pub fn animation_loop<F>(&self, mut func: F)
+    where
+        F: FnMut(AsyncCtx<T>, f64) -> ControlFlow<()> + 'static,
+    {
+        self.use_async(async move |ctx: AsyncCtxHandle<T>| {
+            loop {
+                let time = next_frame().await;
+                if ctx.update(|ctx| func(ctx, time))?.is_break() {
+                    return Some(());
+                }
+            }
+        });
+    }
+
+    /// Run the given function for each item of the stream.
+    ///
+    /// The stream is dropped once it ends, when the function returns `ControlFlow::Break`, when
+    /// the returned handle is cancelled, or when the state is dropped.
+    /// It is not tied to any element, use [`RenderCtx::for_each_stream`] to drop it along with
+    /// the element rendering it.
+    /// Each call is its own update cycle, similar to [`AsyncCtxHandle::update`], and as with
+    /// `update` the state is not borrowed while waiting for the next item.
+    ///
+    /// ```rust
+    /// # use std::ops::ControlFlow;
+    /// # use natrix::prelude::*;
+    /// # use natrix::async_utils;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     seconds: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+    ///     ctx.for_each_stream(async_utils::interval_milliseconds(1000), |mut ctx, ()| {
+    ///         *ctx.seconds += 1;
+    ///         if *ctx.seconds >= 60 {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     });
+    /// })
+    /// # }
+    /// ```
+    pub fn for_each_stream<S, F>(&self, stream: S, mut func: F) -> StreamHandle
+    where
+        S: Stream + 'static,
+        F: FnMut(AsyncCtx<T>, S::Item) -> ControlFlow<()> + 'static,
+    {
+        let handle = StreamHandle {
+            state: Rc::new(CancelState::default()),
+        };
+        let state = Rc::clone(&handle.state);
+        self.use_async(async move |ctx: AsyncCtxHandle<T>| {
+            let loop_state = Rc::clone(&state);
+            until_cancelled(&state, async move {
+                let mut stream = pin!(stream);
+                while let Some(item) = next_item(stream.as_mut()).await {
+                    // NOTE: The function itself might have cancelled the stream
+                    if loop_state.cancelled.get() || ctx.update(|ctx| func(ctx, item))?.is_break() {
+                        break;
+                    }
+                }
+                Some(())
+            })
+            .await
+        });
+        handle
+    }
+
+    /// Set the signal returned by `signal` to each item of the stream.
+    ///
+    /// See [`for_each_stream`](Self::for_each_stream) for when the stream is dropped, and
+    /// [`RenderCtx::bind_stream`] for binding it for as long as a element is rendered.
+    ///
+    /// ```rust
+    /// # use std::pin::Pin;
+    /// # use std::task::{Context, Poll};
+    /// # use natrix::prelude::*;
+    /// # use natrix::async_utils::Stream;
+    /// # struct Messages;
+    /// # impl Stream for Messages {
+    /// #     type Item = String;
+    /// #     fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<String>> {
+    /// #         Poll::Ready(None)
+    /// #     }
+    /// # }
+    /// # fn messages() -> Messages { Messages }
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     latest: Signal<String>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+    ///     ctx.bind_stream(messages(), |app: &mut App| &mut app.latest);
+    /// })
+    /// # }
+    /// ```
+    pub fn bind_stream<S>(
+        &self,
+        stream: S,
+        signal: impl Fn(&mut T) -> &mut Signal<S::Item> + 'static,
+    ) -> StreamHandle
+    where
+        S: Stream + 'static,
+        S::Item: 'static,
+    {
+        self.for_each_stream(stream, move |mut ctx, item| {
+            **signal(&mut ctx) = item;
+            ControlFlow::Continue(())
+        })
+    }
+
+    /// Run the given function every `period`, using js `setInterval`.
+    ///
+    /// The interval is cleared when the returned handle is cancelled, or when the state is
+    /// dropped, and a app that has panicked stops ticking.
+    /// Use [`RenderCtx::use_interval`] to also clear it when a element is removed.
+    /// Each call is its own update cycle, and a tick that fires while the previous call is still
+    /// running waits for it to finish, so the function is never called while the state is
+    /// borrowed.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     seconds: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+    ///     ctx.use_interval(Duration::from_secs(1), |mut ctx| {
+    ///         *ctx.seconds += 1;
+    ///     });
+    /// })
+    /// # }
+    /// ```
+    pub fn use_interval<F>(&self, period: Duration, mut func: F) -> IntervalHandle
+    where
+        F: FnMut(AsyncCtx<T>) + 'static,
+    {
+        let milis = i32::try_from(period.as_millis()).unwrap_or(i32::MAX);
+        let interval = interval_milliseconds(milis);
+        let id = interval.id;
+
+        let stream = self.for_each_stream(interval, move |ctx, ()| {
+            func(ctx);
+            ControlFlow::Continue(())
+        });
+        IntervalHandle { stream, id }
+    }
+}
+
+impl<T: State> RenderCtx<'_, '_, T> {
+    /// Run the given function for each item of the stream, for as long as this reactive closure
+    /// is rendered.
+    ///
+    /// This is [`EventCtx::for_each_stream`], except the stream is also dropped when the
+    /// closure re-runs or its element is removed, even if the stream is idle.
+    /// So do not read signals the function modifies in the same closure, as that re-runs the
+    /// closure and starts the stream again.
+    ///
+    /// ```rust
+    /// # use std::ops::ControlFlow;
+    /// # use natrix::prelude::*;
+    /// # use natrix::async_utils;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     show_clock: Signal<bool>,
+    /// #     seconds: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// |mut ctx: RenderCtx<App>| {
+    ///     if !*ctx.show_clock {
+    ///         return None;
+    ///     }
+    ///     // Stops once `show_clock` is turned off.
+    ///     ctx.for_each_stream(async_utils::interval_milliseconds(1000), |mut ctx, ()| {
+    ///         *ctx.seconds += 1;
+    ///         ControlFlow::Continue(())
+    ///     });
+    ///     Some(e::p().text(|ctx: RenderCtx<App>| *ctx.seconds))
+    /// }
+    /// # }
+    /// ```
+    pub fn for_each_stream<S, F>(&mut self, stream: S, func: F) -> StreamHandle
+    where
+        S: Stream + 'static,
+        F: FnMut(AsyncCtx<T>, S::Item) -> ControlFlow<()> + 'static,
+    {
+        let handle = EventCtx(&mut *self.ctx).for_each_stream(stream, func);
+        self.render_state
+            .keep_alive
+            .push(Box::new(CancelOnDrop(handle.clone())));
+        handle
+    }
+
+    /// Set the signal returned by `signal` to each item of the stream, for as long as this
+    /// reactive closure is rendered.
+    ///
+    /// See [`RenderCtx::for_each_stream`] for when the stream is dropped.
+    pub fn bind_stream<S>(
+        &mut self,
+        stream: S,
+        signal: impl Fn(&mut T) -> &mut Signal<S::Item> + 'static,
+    ) -> StreamHandle
+    where
+        S: Stream + 'static,
+        S::Item: 'static,
+    {
+        let handle = EventCtx(&mut *self.ctx).bind_stream(stream, signal);
+        self.render_state
+            .keep_alive
+            .push(Box::new(CancelOnDrop(handle.clone())));
+        handle
+    }
+
+    /// Run the given function every `period`, for as long as this reactive closure is rendered.
+    ///
+    /// This is [`EventCtx::use_interval`], except the interval is also cleared when the closure
+    /// re-runs or its element is removed.
+    /// See [`RenderCtx::for_each_stream`] for which signals to avoid reading.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     show_clock: Signal<bool>,
+    /// #     seconds: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// |mut ctx: RenderCtx<App>| {
+    ///     if !*ctx.show_clock {
+    ///         return None;
+    ///     }
+    ///     ctx.use_interval(Duration::from_secs(1), |mut ctx| {
+    ///         *ctx.seconds += 1;
+    ///     });
+    ///     Some(e::p().text(|ctx: RenderCtx<App>| *ctx.seconds))
+    /// }
+    /// # }
+    /// ```
+    pub fn use_interval<F>(&mut self, period: Duration, func: F) -> IntervalHandle
+    where
+        F: FnMut(AsyncCtx<T>) + 'static,
+    {
+        let handle = EventCtx(&mut *self.ctx).use_interval(period, func);
+        self.render_state
+            .keep_alive
+            .push(Box::new(ClearOnDrop(handle.clone())));
+        handle
+    }
+}
+
+/// A handle to a interval started with [`EventCtx::use_interval`] or
+/// [`RenderCtx::use_interval`].
+///
+/// Dropping the handle does not stop the interval.
+#[derive(Clone)]
+pub struct IntervalHandle {
+    /// The handle of the stream the ticks are consumed from
+    stream: StreamHandle,
+    /// The id of the js interval
+    id: i32,
+}
+
+impl IntervalHandle {
+    /// Clear the interval, the function is not called again after this.
+    pub fn cancel(&self) {
+        if !self.stream.is_cancelled() {
+            crate::get_window().clear_interval_with_handle(self.id);
+            self.stream.cancel();
+        }
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.stream.is_cancelled()
+    }
+}
+
+/// Clears the interval when dropped, used to tie it to the lifetime of a reactive closure.
+struct ClearOnDrop(IntervalHandle);
+
+impl Drop for ClearOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +731,104 @@ mod tests {
 
     use super::*;
 
+    /// Poll the future once, without a real waker
+    fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        pin!(future).poll(&mut cx)
+    }
+
+    /// A future that is pending the first time it is polled
+    fn yield_once() -> impl Future<Output = ()> {
+        let mut yielded = false;
+        std::future::poll_fn(move |cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+    }
+
+    #[test]
+    fn join_resolves_with_all_outputs() {
+        assert_eq!(
+            poll_once(join2(async { 1 }, async { "a" })),
+            Poll::Ready((1, "a"))
+        );
+        assert_eq!(
+            poll_once(join3(async { 1 }, async { 2 }, async { 3 })),
+            Poll::Ready((1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn join_waits_for_all() {
+        assert_eq!(
+            poll_once(join2(async { 1 }, std::future::pending::<()>())),
+            Poll::Pending
+        );
+    }
+
+    #[test]
+    fn join_polls_each_future_once_to_completion() {
+        let polls = Cell::new(0);
+        let counted = async {
+            polls.set(polls.get() + 1);
+            yield_once().await;
+            polls.set(polls.get() + 1);
+        };
+
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        let mut joined = pin!(join2(counted, yield_once()));
+        assert_eq!(joined.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(joined.as_mut().poll(&mut cx), Poll::Ready(((), ())));
+        assert_eq!(polls.get(), 2);
+    }
+
+    #[test]
+    fn select_returns_first_ready() {
+        assert_eq!(
+            poll_once(select(std::future::pending::<()>(), async { 2 })),
+            Poll::Ready(Either::Second(2))
+        );
+        assert_eq!(
+            poll_once(select(async { 1 }, async { 2 })),
+            Poll::Ready(Either::<_, u8>::First(1))
+        );
+    }
+
+    #[test]
+    fn select_drops_other_future() {
+        let dropped = Cell::new(false);
+        let guarded = async {
+            let _guard = DropGuard::new(|| dropped.set(true));
+            std::future::pending::<()>().await;
+        };
+
+        assert_eq!(
+            poll_once(select(guarded, async { 1 })),
+            Poll::Ready(Either::Second(1))
+        );
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn next_item_follows_stream() {
+        let (sender, receiver) = mpsc::unbounded();
+        let mut receiver = pin!(receiver);
+
+        assert_eq!(poll_once(next_item(receiver.as_mut())), Poll::Pending);
+        let _ = sender.unbounded_send(1);
+        assert_eq!(
+            poll_once(next_item(receiver.as_mut())),
+            Poll::Ready(Some(1))
+        );
+        drop(sender);
+        assert_eq!(poll_once(next_item(receiver.as_mut())), Poll::Ready(None));
+    }
+
     #[test]
     fn test_drop_guard_basic_functionality() {
         let called = Cell::new(false);
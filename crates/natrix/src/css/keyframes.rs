@@ -5,6 +5,9 @@ use crate::css::prelude::RuleBody;
 use crate::css::values::units::Percentage;
 
 /// The name of a keyframe
+///
+/// Use [`KeyFrame::animation`] to bind it to a rule with the `animation` property,
+/// which references it by the same name as the emitted `@keyframes` rule.
 #[derive(Clone, Copy, Debug)]
 pub struct KeyFrame(pub &'static str);
 
@@ -31,7 +34,7 @@ impl KeyframeDefinition {
     pub fn to_css(self, name: &KeyFrame) -> String {
         let mut inner = String::new();
         for (frame, body) in self.0 {
-            let rule = format!("{}, {{{}}}", frame.into_css(), body.into_css());
+            let rule = format!("{} {{{}}}", frame.into_css(), body.into_css());
             inner.push_str(&rule);
         }
 
@@ -65,3 +68,72 @@ macro_rules! register_keyframe {
         });
     };
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::time::Duration;
+
+    use lightningcss::properties::Property;
+    use lightningcss::properties::animation::AnimationName;
+    use lightningcss::rules::CssRule;
+    use lightningcss::rules::keyframes::KeyframesName;
+    use lightningcss::stylesheet::{ParserOptions, StyleSheet};
+
+    use super::*;
+    use crate::css::property::RuleCollection;
+    use crate::css::values;
+    use crate::dom::html_elements::TagDiv;
+
+    const SLIDE: KeyFrame = KeyFrame("slide-in");
+
+    #[test]
+    #[expect(clippy::panic, clippy::expect_used, reason = "Its a test")]
+    fn animation_references_keyframes() {
+        let keyframes = KeyframeDefinition::new()
+            .frame(crate::unit!(0.0%), RuleBody::new().property("opacity", "0"))
+            .frame(
+                crate::unit!(100.0%),
+                RuleBody::new().property("opacity", "1"),
+            )
+            .to_css(&SLIDE);
+        let rule = RuleCollection::new()
+            .rule(
+                TagDiv,
+                RuleBody::new().animation(
+                    SLIDE
+                        .animation(Duration::from_millis(300))
+                        .easing(values::EasingFunction::CubicBezier {
+                            point1: (0.0, 0.0),
+                            point2: (0.58, 1.0),
+                        })
+                        .fill_mode(values::AnimationFillMode::Forwards),
+                ),
+            )
+            .into_css();
+        let css = format!("{keyframes}{rule}");
+        crate::css::assert_valid_css(&css);
+
+        let stylesheet =
+            StyleSheet::parse(&css, ParserOptions::default()).expect("Failed to parse css");
+        let [CssRule::Keyframes(keyframes), CssRule::Style(rule)] = stylesheet.rules.0.as_slice()
+        else {
+            panic!("Expected a keyframes and a style rule");
+        };
+        assert_eq!(keyframes.keyframes.len(), 2);
+        let KeyframesName::Ident(keyframes_name) = &keyframes.name else {
+            panic!("Expected a identifier keyframes name");
+        };
+        assert_eq!(keyframes_name.0.as_ref(), "slide-in");
+
+        let Some((Property::Animation(animations, _), _)) = rule.declarations.iter().next() else {
+            panic!("Expected a animation property");
+        };
+        let Some(animation) = animations.first() else {
+            panic!("Expected a animation");
+        };
+        let AnimationName::Ident(animation_name) = &animation.name else {
+            panic!("Expected a identifier animation name");
+        };
+        assert_eq!(animation_name.0.as_ref(), keyframes_name.0.as_ref());
+    }
+}
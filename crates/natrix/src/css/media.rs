@@ -0,0 +1,249 @@
+//! `@media` queries
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! const SIDEBAR: Class = natrix::class!();
+//!
+//! natrix::register_rules!(
+//!     property::RuleCollection::new()
+//!         .rule(SIDEBAR, RuleBody::new().property("display", "none"))
+//!         .media(
+//!             MediaQuery::min_width(values::units::Length::px(768.0)),
+//!             property::RuleCollection::new()
+//!                 .rule(SIDEBAR, RuleBody::new().property("display", "block")),
+//!         )
+//!         .media(
+//!             MediaQuery::dark_mode(),
+//!             property::RuleCollection::new()
+//!                 .rule(SIDEBAR, RuleBody::new().property("background-color", "black")),
+//!         )
+//! );
+//! ```
+
+use super::IntoCss;
+use super::property::RuleCollection;
+use super::values::units::Length;
+
+/// A preferred color scheme
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ColorScheme {
+    /// `light`
+    Light,
+    /// `dark`
+    Dark,
+}
+
+impl IntoCss for ColorScheme {
+    fn into_css(self) -> String {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+        .to_owned()
+    }
+}
+
+/// The orientation of the viewport
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Orientation {
+    /// `portrait`, the viewport is taller than it is wide
+    Portrait,
+    /// `landscape`, the viewport is wider than it is tall
+    Landscape,
+}
+
+impl IntoCss for Orientation {
+    fn into_css(self) -> String {
+        match self {
+            Self::Portrait => "portrait",
+            Self::Landscape => "landscape",
+        }
+        .to_owned()
+    }
+}
+
+/// A condition of a `@media` rule.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MediaQuery {
+    /// The condition as css
+    condition: String,
+    /// Whether the condition needs to be wrapped in parentheses to be combined with others
+    compound: bool,
+}
+
+impl MediaQuery {
+    /// A single media feature
+    fn feature(name: &str, value: &str) -> Self {
+        Self {
+            condition: format!("({name}:{value})"),
+            compound: false,
+        }
+    }
+
+    /// The viewport is at least this wide
+    pub fn min_width(width: Length) -> Self {
+        Self::feature("min-width", &width.into_css())
+    }
+
+    /// The viewport is at most this wide
+    pub fn max_width(width: Length) -> Self {
+        Self::feature("max-width", &width.into_css())
+    }
+
+    /// The viewport is at least this tall
+    pub fn min_height(height: Length) -> Self {
+        Self::feature("min-height", &height.into_css())
+    }
+
+    /// The viewport is at most this tall
+    pub fn max_height(height: Length) -> Self {
+        Self::feature("max-height", &height.into_css())
+    }
+
+    /// The user prefers the given color scheme
+    pub fn prefers_color_scheme(scheme: ColorScheme) -> Self {
+        Self::feature("prefers-color-scheme", &scheme.into_css())
+    }
+
+    /// The viewport has the given orientation
+    pub fn orientation(orientation: Orientation) -> Self {
+        Self::feature("orientation", &orientation.into_css())
+    }
+
+    /// The user prefers a dark color scheme
+    pub fn dark_mode() -> Self {
+        Self::prefers_color_scheme(ColorScheme::Dark)
+    }
+
+    /// The user prefers a light color scheme
+    pub fn light_mode() -> Self {
+        Self::prefers_color_scheme(ColorScheme::Light)
+    }
+
+    /// The user has asked for less motion, such as fewer animations
+    pub fn reduced_motion() -> Self {
+        Self::feature("prefers-reduced-motion", "reduce")
+    }
+
+    /// A raw condition, such as `(hover: hover) and (pointer: fine)`
+    pub fn raw(condition: impl Into<String>) -> Self {
+        Self {
+            condition: condition.into(),
+            compound: true,
+        }
+    }
+
+    /// The condition in a form that can be combined with other conditions
+    fn grouped(self) -> String {
+        if self.compound {
+            format!("({})", self.condition)
+        } else {
+            self.condition
+        }
+    }
+
+    /// Both conditions need to match
+    pub fn and(self, other: Self) -> Self {
+        Self::raw(format!("{} and {}", self.grouped(), other.grouped()))
+    }
+
+    /// Either of the conditions need to match
+    pub fn or(self, other: Self) -> Self {
+        Self::raw(format!("{} or {}", self.grouped(), other.grouped()))
+    }
+}
+
+impl std::ops::Not for MediaQuery {
+    type Output = Self;
+
+    /// The condition does not match
+    fn not(self) -> Self {
+        Self::raw(format!("not {}", self.grouped()))
+    }
+}
+
+impl IntoCss for MediaQuery {
+    fn into_css(self) -> String {
+        self.condition
+    }
+}
+
+impl RuleCollection {
+    /// Add rules that only apply when the given media query matches.
+    ///
+    /// Media queries can be nested, in which case the inner rules apply when both match.
+    pub fn media(mut self, query: MediaQuery, rules: RuleCollection) -> Self {
+        let section = format!("@media {}{{{}}}", query.into_css(), rules.into_css());
+        self.sections.push(section);
+        self
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::css::assert_valid_css;
+    use crate::css::property::RuleBody;
+    use crate::dom::html_elements::TagDiv;
+
+    fn with_query(query: MediaQuery) -> String {
+        RuleCollection::new()
+            .media(
+                query,
+                RuleCollection::new().rule(TagDiv, RuleBody::new().property("display", "none")),
+            )
+            .into_css()
+    }
+
+    #[test]
+    fn width() {
+        let result = with_query(MediaQuery::min_width(Length::px(600.0)));
+        assert_eq!(result, "@media (min-width:600.0px){div{display:none;}}");
+        assert_valid_css(&result);
+        assert_valid_css(&with_query(MediaQuery::max_width(Length::rem(40.5))));
+    }
+
+    #[test]
+    fn features() {
+        assert_eq!(
+            with_query(MediaQuery::dark_mode()),
+            "@media (prefers-color-scheme:dark){div{display:none;}}"
+        );
+        assert_valid_css(&with_query(MediaQuery::light_mode()));
+        assert_valid_css(&with_query(MediaQuery::orientation(Orientation::Landscape)));
+        assert_valid_css(&with_query(MediaQuery::reduced_motion()));
+        assert_valid_css(&with_query(MediaQuery::min_height(Length::px(100.0))));
+    }
+
+    #[test]
+    fn combined() {
+        let query = MediaQuery::min_width(Length::px(600.0))
+            .and(MediaQuery::max_width(Length::px(900.0)))
+            .or(!MediaQuery::dark_mode());
+        let result = with_query(query);
+        assert_eq!(
+            result,
+            "@media ((min-width:600.0px) and (max-width:900.0px)) or (not (prefers-color-scheme:dark)){div{display:none;}}"
+        );
+        assert_valid_css(&result);
+    }
+
+    #[test]
+    fn nested() {
+        let result = RuleCollection::new()
+            .media(
+                MediaQuery::dark_mode(),
+                RuleCollection::new().rule(TagDiv, RuleBody::new()).media(
+                    MediaQuery::orientation(Orientation::Portrait),
+                    RuleCollection::new().rule(TagDiv, RuleBody::new()),
+                ),
+            )
+            .into_css();
+        assert_eq!(
+            result,
+            "@media (prefers-color-scheme:dark){div{}@media (orientation:portrait){div{}}}"
+        );
+        assert_valid_css(&result);
+    }
+}
@@ -10,14 +10,21 @@ use std::fmt::Write;
 use crate::error_handling::log_or_panic_result;
 
 pub mod keyframes;
+mod known_properties;
+pub mod media;
 pub mod property;
 pub mod selectors;
+pub mod supports;
+pub mod theme;
+pub mod utilities;
 pub mod values;
 
 /// Css prelude
 /// This is auto star imported in the various `register_*` macros
 pub mod prelude {
+    pub use super::media::MediaQuery;
     pub use super::property::RuleBody;
+    pub use super::supports::SupportsCondition;
     pub use super::{IntoCss, property, selectors, values};
     pub use crate::selector_list;
 }
@@ -47,6 +54,25 @@ pub struct CssEmit(pub fn() -> String);
 #[cfg(feature = "_internal_collect_css")]
 inventory::collect!(CssEmit);
 
+/// The definition site of a `class!()` or `id!()`, emitted in bundle mode for `natrix check`.
+#[doc(hidden)]
+#[cfg(feature = "_internal_bundle")]
+pub struct SymbolSite {
+    /// The generated symbol
+    pub symbol: &'static str,
+    /// The macro that generated it, `class` or `id`
+    pub kind: &'static str,
+    /// The file of the macro call
+    pub file: &'static str,
+    /// The line of the macro call
+    pub line: u32,
+    /// The column of the macro call
+    pub column: u32,
+}
+
+#[cfg(feature = "_internal_bundle")]
+inventory::collect!(SymbolSite);
+
 #[cfg(all(feature = "_internal_no_ssg", target_arch = "wasm32"))]
 #[expect(
     unsafe_code,
@@ -90,6 +116,36 @@ macro_rules! register_raw_css {
     };
 }
 
+/// Record where the given symbol was defined, for `natrix check`.
+///
+/// This is used internally by the `class` and `id` macros, and only does anything in bundle
+/// mode.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "_internal_bundle")]
+macro_rules! register_symbol {
+    ($kind:ident, $symbol:expr) => {
+        $crate::macro_ref::inventory::submit!($crate::macro_ref::css::SymbolSite {
+            symbol: $symbol,
+            kind: stringify!($kind),
+            file: file!(),
+            line: line!(),
+            column: column!(),
+        });
+    };
+}
+
+/// Record where the given symbol was defined, for `natrix check`.
+///
+/// This is used internally by the `class` and `id` macros, and only does anything in bundle
+/// mode.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "_internal_bundle"))]
+macro_rules! register_symbol {
+    ($kind:ident, $symbol:expr) => {};
+}
+
 /// Register a `RuleCollection` to go in the bundler.
 ///
 /// Any code in here wont be included in the final wasm build.
@@ -181,6 +237,7 @@ fn css_runtime(css_string: &str) {
         .create_element("style")
         .expect("Failed to create style element");
 
+    style.set_id(natrix_shared::DEV_STYLE_ID);
     style.set_inner_html(css_string);
 
     let body = document.body().expect("No body found");
@@ -197,6 +254,14 @@ fn css_runtime(css_string: &str) {
 fn css_emit(css_string: &str) {
     log::debug!("Emitting css to bundler");
     println!("{css_string}");
+
+    println!("{}", natrix_shared::SYMBOLS_MARKER);
+    for site in inventory::iter::<SymbolSite> {
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            site.symbol, site.kind, site.file, site.line, site.column
+        );
+    }
 }
 
 /// Create a unique string
@@ -301,6 +366,18 @@ mod tests {
             let result = super::super::collect_css();
             super::super::assert_valid_css(&result);
         }
+
+        #[cfg(feature = "_internal_bundle")]
+        #[test]
+        fn symbol_sites_are_collected() {
+            const CARD: (Class, u32) = (crate::class!(), line!());
+
+            let site = crate::macro_ref::inventory::iter::<super::super::SymbolSite>
+                .into_iter()
+                .find(|site| site.symbol == CARD.0.0);
+            let site = site.map(|site| (site.file, site.line));
+            assert_eq!(site, Some((file!(), CARD.1)));
+        }
     }
 
     proptest::proptest! {
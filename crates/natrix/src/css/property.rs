@@ -1,8 +1,9 @@
 //! Css properties
 
-use super::values;
-use crate::css::selectors::IntoSelectorList;
+use super::{known_properties, values};
+use crate::css::selectors::{IntoSelectorList, SelectorList};
 use crate::css::values::IntoCss;
+use crate::error_handling::log_or_panic;
 
 /// A collection of css rules
 #[must_use]
@@ -59,6 +60,8 @@ pub trait Supports<Kind>: Property {}
 pub struct RuleBody {
     /// The properties in the rule
     pub properties: Vec<(&'static str, String)>,
+    /// The rules nested in this one
+    pub nested: Vec<(SelectorList, RuleBody)>,
 }
 
 impl RuleBody {
@@ -82,10 +85,76 @@ impl RuleBody {
         self
     }
 
-    /// Add a raw property
+    /// Add a property by name, for properties and values without typed helpers.
+    ///
+    /// The name is checked to be a known css property, a vendor prefixed property, or a custom
+    /// `--property`, so a typo panics with the location of the call instead of silently
+    /// producing css the browser ignores.
+    /// The value is not checked.
+    ///
+    /// Use [`raw_unchecked`](Self::raw_unchecked) for properties natrix does not know about.
+    /// ```rust
+    /// # use natrix::css::property::RuleBody;
+    /// let _ = RuleBody::new()
+    ///     .property("padding", "8px")
+    ///     .property("--accent", "rebeccapurple")
+    ///     .property("-webkit-line-clamp", "3");
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn property(self, name: &'static str, value: impl Into<String>) -> Self {
+        if let Err(reason) = known_properties::check_property_name(name) {
+            log_or_panic!(
+                "Invalid css property `{name}`: {reason}, use `RuleBody::raw_unchecked` if this is intended"
+            );
+        }
+        self.raw_unchecked(name, value)
+    }
+
+    /// Add a property without checking the name
+    #[inline]
+    pub fn raw_unchecked(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.properties.push((name, value.into()));
+        self
+    }
+
+    /// Add a property without checking the name
+    #[inline]
+    #[deprecated(note = "use `property`, or `raw_unchecked` for properties natrix does not know")]
+    pub fn raw(self, property: &'static str, value: impl Into<String>) -> Self {
+        self.raw_unchecked(property, value)
+    }
+
+    /// Nest a rule inside this one, using [`Parent`](crate::css::selectors::Parent) to refer to
+    /// the selector of this rule.
+    /// A selector without `Parent` matches descendants, just like in css.
+    ///
+    /// This is emitted as native css nesting, `natrix build` flattens it into plain rules for
+    /// older browsers.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::css::property::{RuleBody, RuleCollection};
+    /// use natrix::css::selectors::{Parent, PseudoClass};
+    /// const CARD: Class = natrix::class!();
+    /// const TITLE: Class = natrix::class!();
+    ///
+    /// // .card { padding: 8px; &:hover { ... } .title { ... } }
+    /// let _ = RuleCollection::new().rule(
+    ///     CARD,
+    ///     RuleBody::new()
+    ///         .property("padding", "8px")
+    ///         .nest(
+    ///             Parent.and(PseudoClass::Hover),
+    ///             RuleBody::new().property("background-color", "gray"),
+    ///         )
+    ///         .nest(TITLE, RuleBody::new().property("font-weight", "bold")),
+    /// );
+    /// ```
+    ///
+    /// <https://developer.mozilla.org/docs/Web/CSS/CSS_nesting>
     #[inline]
-    pub fn raw(mut self, property: &'static str, value: impl Into<String>) -> Self {
-        self.properties.push((property, value.into()));
+    pub fn nest(mut self, selector: impl IntoSelectorList, body: RuleBody) -> Self {
+        self.nested.push((selector.into_list(), body));
         self
     }
 }
@@ -99,6 +168,12 @@ impl IntoCss for RuleBody {
             result.push_str(&value);
             result.push(';');
         }
+        for (selector, body) in self.nested {
+            result.push_str(&selector.into_css());
+            result.push('{');
+            result.push_str(&body.into_css());
+            result.push('}');
+        }
 
         result
     }
@@ -221,3 +296,84 @@ support!(AspectRatio, (values::Auto, f32), auto_f32);
 support!(AspectRatio, (values::Auto, f64), auto_f64);
 support!(AspectRatio, (f32, values::Auto), f32_auto);
 support!(AspectRatio, (f64, values::Auto), f64_auto);
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::css::assert_valid_css;
+    use crate::css::selectors::{Class, IntoCompoundSelector, Parent, PseudoClass};
+
+    const CARD: Class = Class("card");
+    const TITLE: Class = Class("title");
+
+    fn nested_card() -> String {
+        RuleCollection::new()
+            .rule(
+                CARD,
+                RuleBody::new()
+                    .property("color", "red")
+                    .nest(
+                        Parent.and(PseudoClass::Hover),
+                        RuleBody::new()
+                            .property("color", "blue")
+                            .nest(TITLE, RuleBody::new().property("color", "green")),
+                    )
+                    .nest(TITLE, RuleBody::new().property("font-weight", "bold")),
+            )
+            .into_css()
+    }
+
+    /// Flatten the nesting like `natrix build` does for older browsers
+    #[expect(clippy::expect_used, reason = "This is meant for tests")]
+    fn flatten(css: &str) -> String {
+        use lightningcss::stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet};
+        use lightningcss::targets::{Features, Targets};
+
+        let targets = Targets {
+            include: Features::Nesting,
+            ..Targets::default()
+        };
+        let mut stylesheet =
+            StyleSheet::parse(css, ParserOptions::default()).expect("Failed to parse css");
+        stylesheet
+            .minify(MinifyOptions {
+                targets,
+                ..MinifyOptions::default()
+            })
+            .expect("Failed to minify css");
+        stylesheet
+            .to_css(PrinterOptions {
+                minify: true,
+                targets,
+                ..PrinterOptions::default()
+            })
+            .expect("Failed to print css")
+            .code
+    }
+
+    #[test]
+    fn nesting() {
+        let result = nested_card();
+        assert_eq!(
+            result,
+            ".\\63 \\61 \\72 \\64 {color:red;&:hover{color:blue;.\\74 \\69 \\74 \\6c \\65 {color:green;}}.\\74 \\69 \\74 \\6c \\65 {font-weight:bold;}}"
+        );
+        assert_valid_css(&result);
+    }
+
+    #[test]
+    fn nesting_flattened() {
+        let result = flatten(&nested_card());
+        assert_eq!(
+            result,
+            ".card{color:red}.card:hover{color:#00f}.card:hover .title{color:green}.card .title{font-weight:700}"
+        );
+        assert_valid_css(&result);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid css property `colour`")]
+    fn unknown_property_panics() {
+        let _ = RuleBody::new().property("colour", "red");
+    }
+}
@@ -136,6 +136,8 @@ pub enum SimpleSelector {
     Id(Box<str>),
     /// Pseudo Class
     Pseudo(Box<str>),
+    /// The nesting selector `&`, see [`Parent`]
+    Nesting,
 }
 
 impl IntoCss for SimpleSelector {
@@ -145,6 +147,7 @@ impl IntoCss for SimpleSelector {
             Self::Class(value) => format!(".{}", super::as_css_identifier(&value)),
             Self::Id(value) => format!("#{}", super::as_css_identifier(&value)),
             Self::Pseudo(value) => format!(":{value}"),
+            Self::Nesting => String::from("&"),
         }
     }
 }
@@ -362,7 +365,7 @@ pub struct Class(pub &'static str);
 
 impl<C: crate::reactivity::State> crate::dom::ToClass<C> for Class {
     fn calc_class(self, _node: &web_sys::Element) -> crate::dom::classes::ClassResult<C> {
-        crate::dom::classes::ClassResult::SetIt(Some(self.0.into()))
+        crate::dom::classes::ClassResult::SetIt(smallvec::smallvec![self.0.into()])
     }
 }
 
@@ -394,9 +397,11 @@ impl From<Class> for &'static str {
 /// ```
 #[macro_export]
 macro_rules! class {
-    () => {
-        $crate::prelude::Class($crate::unique_str!())
-    };
+    () => {{
+        const SYMBOL: &str = $crate::unique_str!();
+        $crate::register_symbol!(class, SYMBOL);
+        $crate::prelude::Class(SYMBOL)
+    }};
 }
 
 /// A id generate from the `id` macro
@@ -443,9 +448,34 @@ impl From<Id> for &'static str {
 /// ```
 #[macro_export]
 macro_rules! id {
-    () => {
-        $crate::prelude::Id($crate::unique_str!())
-    };
+    () => {{
+        const SYMBOL: &str = $crate::unique_str!();
+        $crate::register_symbol!(id, SYMBOL);
+        $crate::prelude::Id(SYMBOL)
+    }};
+}
+
+/// The nesting selector `&`, referring to the selector of the parent rule in a rule nested with
+/// [`RuleBody::nest`](super::property::RuleBody::nest).
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # use natrix::css::selectors::{Parent, PseudoClass};
+/// const TITLE: Class = natrix::class!();
+/// // &:hover
+/// let _ = Parent.and(PseudoClass::Hover);
+/// // & > .title
+/// let _ = Parent.child(TITLE);
+/// ```
+///
+/// <https://developer.mozilla.org/docs/Web/CSS/Nesting_selector>
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Parent;
+
+impl IntoSimpleSelector for Parent {
+    fn into_simple(self) -> SimpleSelector {
+        SimpleSelector::Nesting
+    }
 }
 
 /// For items that can be converted into compound selectors
@@ -669,6 +699,7 @@ mod tests {
     assert_impl_all!(Class: IntoSimpleSelector, IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
     assert_impl_all!(PseudoClass: IntoSimpleSelector, IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
     assert_impl_all!(PseudoClassNested<TagDiv>: IntoSimpleSelector, IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
+    assert_impl_all!(Parent: IntoSimpleSelector, IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
     assert_impl_all!(SimpleSelector: IntoSimpleSelector, IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
     assert_impl_all!(CompoundSelector: IntoCompoundSelector, IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
     assert_impl_all!(ComplexSelector: IntoComplexSelector, IntoFinalizedSelector, IntoSelectorList);
@@ -730,5 +761,8 @@ mod tests {
         assert_valid_and_snapsot!(
             TagDiv.and(PseudoClass::NthChild(NthArgument::new(2, 3).of(BTN)))
         );
+        assert_valid_and_snapsot!(Parent.and(BTN));
+        assert_valid_and_snapsot!(Parent.descendant(BTN));
+        assert_valid_and_snapsot!(BTN.descendant(Parent));
     }
 }
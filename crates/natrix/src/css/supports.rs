@@ -0,0 +1,182 @@
+//! `@supports` feature queries
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! const CARD: Class = natrix::class!();
+//!
+//! natrix::register_rules!(
+//!     property::RuleCollection::new()
+//!         .rule(CARD, RuleBody::new().property("background-color", "white"))
+//!         .supports(
+//!             SupportsCondition::raw_declaration("backdrop-filter", "blur(4px)"),
+//!             property::RuleCollection::new().rule(
+//!                 CARD,
+//!                 RuleBody::new()
+//!                     .property("background-color", "transparent")
+//!                     .property("backdrop-filter", "blur(4px)"),
+//!             ),
+//!         )
+//! );
+//! ```
+
+use super::IntoCss;
+use super::property::{Property, RuleCollection, Supports};
+use super::selectors::IntoSelectorList;
+
+/// A condition of a `@supports` rule.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct SupportsCondition {
+    /// The condition as css
+    condition: String,
+    /// Whether the condition needs to be wrapped in parentheses to be combined with others
+    compound: bool,
+}
+
+impl SupportsCondition {
+    /// A single feature, which is already wrapped in parentheses
+    fn feature(condition: String) -> Self {
+        Self {
+            condition,
+            compound: false,
+        }
+    }
+
+    /// Whether the browser supports the given property value
+    pub fn declaration<P, V>(property: P, value: V) -> Self
+    where
+        P: Property + Supports<V>,
+        V: IntoCss,
+    {
+        Self::feature(format!("({}:{})", property.name(), value.into_css()))
+    }
+
+    /// Whether the browser supports the given property value, for properties that natrix does not
+    /// have typed support for.
+    pub fn raw_declaration(property: &str, value: &str) -> Self {
+        Self::feature(format!("({property}:{value})"))
+    }
+
+    /// Whether the browser supports the given selector
+    pub fn selector(selector: impl IntoSelectorList) -> Self {
+        Self::feature(format!("selector({})", selector.into_list().into_css()))
+    }
+
+    /// A raw condition, such as `(display: grid) and (not (display: inline-grid))`
+    pub fn raw(condition: impl Into<String>) -> Self {
+        Self {
+            condition: condition.into(),
+            compound: true,
+        }
+    }
+
+    /// The condition in a form that can be combined with other conditions
+    fn grouped(self) -> String {
+        if self.compound {
+            format!("({})", self.condition)
+        } else {
+            self.condition
+        }
+    }
+
+    /// Both conditions need to be supported
+    pub fn and(self, other: Self) -> Self {
+        Self::raw(format!("{} and {}", self.grouped(), other.grouped()))
+    }
+
+    /// Either of the conditions need to be supported
+    pub fn or(self, other: Self) -> Self {
+        Self::raw(format!("{} or {}", self.grouped(), other.grouped()))
+    }
+}
+
+impl std::ops::Not for SupportsCondition {
+    type Output = Self;
+
+    /// The condition is not supported
+    fn not(self) -> Self {
+        Self::raw(format!("not {}", self.grouped()))
+    }
+}
+
+impl IntoCss for SupportsCondition {
+    fn into_css(self) -> String {
+        self.condition
+    }
+}
+
+impl RuleCollection {
+    /// Add rules that only apply if the browser supports the given condition.
+    ///
+    /// This is useful for progressive enhancement, put the fallback in the outer collection and
+    /// the enhanced version in here.
+    pub fn supports(mut self, condition: SupportsCondition, rules: RuleCollection) -> Self {
+        let section = format!("@supports {}{{{}}}", condition.into_css(), rules.into_css());
+        self.sections.push(section);
+        self
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::css::assert_valid_css;
+    use crate::css::property::{AspectRatio, RuleBody};
+    use crate::css::values::Auto;
+    use crate::dom::html_elements::TagDiv;
+
+    fn with_condition(condition: SupportsCondition) -> String {
+        RuleCollection::new()
+            .supports(
+                condition,
+                RuleCollection::new().rule(TagDiv, RuleBody::new().aspect_ratio(Auto)),
+            )
+            .into_css()
+    }
+
+    #[test]
+    fn declaration() {
+        let result = with_condition(SupportsCondition::declaration(AspectRatio, 1.5_f32));
+        assert_eq!(
+            result,
+            "@supports (aspect-ratio:1.5){div{aspect-ratio:auto;}}"
+        );
+        assert_valid_css(&result);
+    }
+
+    #[test]
+    fn selector() {
+        assert_valid_css(&with_condition(SupportsCondition::selector(TagDiv)));
+    }
+
+    #[test]
+    fn combined() {
+        let condition = SupportsCondition::raw_declaration("backdrop-filter", "blur(4px)")
+            .or(SupportsCondition::raw_declaration(
+                "-webkit-backdrop-filter",
+                "blur(4px)",
+            ))
+            .and(!SupportsCondition::declaration(AspectRatio, Auto));
+        let result = with_condition(condition);
+        assert_eq!(
+            result,
+            "@supports ((backdrop-filter:blur(4px)) or (-webkit-backdrop-filter:blur(4px))) and (not (aspect-ratio:auto)){div{aspect-ratio:auto;}}"
+        );
+        assert_valid_css(&result);
+    }
+
+    #[test]
+    fn nested() {
+        let condition = SupportsCondition::declaration(AspectRatio, Auto);
+        let result = RuleCollection::new()
+            .supports(
+                condition.clone(),
+                RuleCollection::new().supports(
+                    condition,
+                    RuleCollection::new().rule(TagDiv, RuleBody::new()),
+                ),
+            )
+            .into_css();
+        assert_valid_css(&result);
+    }
+}
@@ -0,0 +1,266 @@
+//! Light/dark themes using css custom properties.
+//!
+//! A [`Theme`] generates custom properties for both a light and dark variant.
+//! By default the variant follows the users `prefers-color-scheme`, but it can be overwritten by
+//! setting `data-theme="light"` or `data-theme="dark"` on the root element, which is what
+//! [`set_theme`] does.
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! use natrix::css::theme::{Theme, ThemeVariable};
+//!
+//! const BACKGROUND: ThemeVariable = ThemeVariable("background");
+//! const TEXT: ThemeVariable = ThemeVariable("text");
+//!
+//! natrix::register_theme!(
+//!     Theme::new()
+//!         .variable(BACKGROUND, values::Color::rgb(255, 255, 255), values::Color::rgb(20, 20, 20))
+//!         .variable(TEXT, values::Color::rgb(20, 20, 20), values::Color::rgb(230, 230, 230))
+//! );
+//!
+//! const APP: Class = natrix::class!();
+//! natrix::register_rule!(
+//!     APP,
+//!     RuleBody::new()
+//!         .property("background-color", BACKGROUND.into_css())
+//!         .property("color", TEXT.into_css())
+//! );
+//! ```
+
+use super::IntoCss;
+use super::property::{Property, Supports};
+use crate::error_handling::log_or_panic_result;
+use crate::get_document;
+
+/// The attribute set on the root element to override the color scheme
+const THEME_ATTRIBUTE: &str = "data-theme";
+
+/// The `localStorage` key the selected theme is persisted under
+const STORAGE_KEY: &str = "natrix-theme";
+
+/// A css custom property defined by a [`Theme`].
+///
+/// Using this as a value produces `var(--name)`, and as such it is accepted by every property.
+#[derive(Clone, Copy, Debug)]
+pub struct ThemeVariable(pub &'static str);
+
+impl ThemeVariable {
+    /// The name of the custom property, including the leading `--`
+    fn property_name(self) -> String {
+        format!("--{}", super::as_css_identifier(self.0))
+    }
+}
+
+impl IntoCss for ThemeVariable {
+    fn into_css(self) -> String {
+        format!("var({})", self.property_name())
+    }
+}
+
+impl<P: Property> Supports<ThemeVariable> for P {}
+
+/// A set of custom properties with a light and dark value.
+#[derive(Default, Clone)]
+#[must_use]
+pub struct Theme {
+    /// The custom property declarations for the light and dark variants
+    variables: Vec<(String, String, String)>,
+}
+
+impl Theme {
+    /// Create a new empty theme
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a variable with the given light and dark value
+    pub fn variable(
+        mut self,
+        variable: ThemeVariable,
+        light: impl IntoCss,
+        dark: impl IntoCss,
+    ) -> Self {
+        self.variables
+            .push((variable.property_name(), light.into_css(), dark.into_css()));
+        self
+    }
+
+    /// Produce the declaration block for one of the variants
+    fn body(&self, scheme: &str, dark: bool) -> String {
+        let mut result = format!("color-scheme:{scheme};");
+        for (name, light_value, dark_value) in &self.variables {
+            let value = if dark { dark_value } else { light_value };
+            result.push_str(name);
+            result.push(':');
+            result.push_str(value);
+            result.push(';');
+        }
+        result
+    }
+}
+
+impl IntoCss for Theme {
+    fn into_css(self) -> String {
+        let light = self.body("light", false);
+        let dark = self.body("dark", true);
+        format!(
+            ":root,:root[{THEME_ATTRIBUTE}=\"light\"]{{{light}}}\
+            :root[{THEME_ATTRIBUTE}=\"dark\"]{{{dark}}}\
+            @media (prefers-color-scheme: dark){{:root:not([{THEME_ATTRIBUTE}=\"light\"]){{{dark}}}}}"
+        )
+    }
+}
+
+/// Register a [`Theme`] to go in the bundler.
+///
+/// Any code in here wont be included in the final wasm build.
+/// And will be run at compile time.
+/// This macro must not be called from within a function.
+#[macro_export]
+macro_rules! register_theme {
+    ($theme:expr) => {
+        $crate::register_raw_css!({
+            use $crate::macro_ref::css::prelude::*;
+            let result: $crate::macro_ref::css::theme::Theme = $theme;
+            result.into_css()
+        });
+    };
+}
+
+/// Which theme variant to use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    /// Follow the users `prefers-color-scheme`
+    #[default]
+    System,
+    /// Always use the light variant
+    Light,
+    /// Always use the dark variant
+    Dark,
+}
+
+impl ThemeMode {
+    /// The value stored in `data-theme` and `localStorage`
+    fn as_str(self) -> Option<&'static str> {
+        match self {
+            Self::System => None,
+            Self::Light => Some("light"),
+            Self::Dark => Some("dark"),
+        }
+    }
+
+    /// Parse the value stored in `data-theme` and `localStorage`
+    fn parse(value: &str) -> Self {
+        match value {
+            "light" => Self::Light,
+            "dark" => Self::Dark,
+            _ => Self::System,
+        }
+    }
+}
+
+/// Get `localStorage` if it is available.
+///
+/// Storage might be disabled by the user, so this is not a error.
+fn local_storage() -> Option<web_sys::Storage> {
+    crate::get_window().local_storage().ok().flatten()
+}
+
+/// Apply the given mode to the root element without persisting it.
+fn apply_theme(mode: ThemeMode) {
+    let Some(root) = get_document().document_element() else {
+        return;
+    };
+    if let Some(value) = mode.as_str() {
+        log_or_panic_result!(
+            root.set_attribute(THEME_ATTRIBUTE, value),
+            "Failed to set theme attribute"
+        );
+    } else {
+        log_or_panic_result!(
+            root.remove_attribute(THEME_ATTRIBUTE),
+            "Failed to remove theme attribute"
+        );
+    }
+}
+
+/// Switch the theme, and persist the choice in `localStorage`.
+///
+/// [`ThemeMode::System`] clears the stored choice.
+pub fn set_theme(mode: ThemeMode) {
+    apply_theme(mode);
+
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let result = if let Some(value) = mode.as_str() {
+        storage.set_item(STORAGE_KEY, value)
+    } else {
+        storage.remove_item(STORAGE_KEY)
+    };
+    if result.is_err() {
+        log::warn!("Failed to persist theme choice");
+    }
+}
+
+/// Apply the theme choice persisted by [`set_theme`], returning it.
+///
+/// Call this before mounting your app to avoid a flash of the wrong theme.
+#[expect(
+    clippy::must_use_candidate,
+    reason = "Applying the theme is the main effect, the returned mode is just informational"
+)]
+pub fn load_theme() -> ThemeMode {
+    let mode = local_storage()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .map(|value| ThemeMode::parse(&value))
+        .unwrap_or_default();
+    apply_theme(mode);
+    mode
+}
+
+/// Get the currently applied theme choice.
+#[must_use]
+pub fn current_theme() -> ThemeMode {
+    get_document()
+        .document_element()
+        .and_then(|root| root.get_attribute(THEME_ATTRIBUTE))
+        .map(|value| ThemeMode::parse(&value))
+        .unwrap_or_default()
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::css::assert_valid_css;
+    use crate::css::values::Color;
+
+    #[test]
+    fn theme_is_valid_css() {
+        let theme = Theme::new()
+            .variable(
+                ThemeVariable("background"),
+                Color::rgb(255, 255, 255),
+                Color::rgb(0, 0, 0),
+            )
+            .variable(
+                ThemeVariable("text"),
+                Color::rgb(0, 0, 0),
+                Color::rgb(255, 255, 255),
+            );
+        assert_valid_css(&theme.into_css());
+    }
+
+    #[test]
+    fn variable_is_var_reference() {
+        assert_eq!(ThemeVariable("ab").into_css(), "var(--\\61 \\62 )");
+    }
+
+    #[test]
+    fn mode_roundtrip() {
+        for mode in [ThemeMode::Light, ThemeMode::Dark] {
+            assert_eq!(ThemeMode::parse(mode.as_str().unwrap_or_default()), mode);
+        }
+        assert_eq!(ThemeMode::System.as_str(), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! Utility classes generated from design tokens.
+//!
+//! Define your spacing scale and colors as [`Tokens`], and [`register_utilities!`](crate::register_utilities)
+//! generates utility classes like `.p-4` and `.text-primary` for them.
+//! The release build only keeps the utilities whose names appear in your wasm binary, so you can
+//! generate a large scale without shipping unused css.
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! use natrix::css::utilities::Tokens;
+//!
+//! const TOKENS: Tokens = Tokens {
+//!     prefix: "",
+//!     spacing: &[("1", "0.25rem"), ("2", "0.5rem"), ("4", "1rem")],
+//!     colors: &[("primary", "#3b82f6"), ("muted", "#6b7280")],
+//! };
+//! natrix::register_utilities!(TOKENS);
+//!
+//! const P_4: Class = Class("p-4");
+//! const TEXT_PRIMARY: Class = Class("text-primary");
+//!
+//! # #[derive(State)]
+//! # struct App;
+//! fn render() -> impl Element<App> {
+//!     e::div().class(P_4).class(TEXT_PRIMARY).text("Hello")
+//! }
+//! ```
+
+use super::IntoCss;
+use super::property::{RuleBody, RuleCollection};
+use super::selectors::SimpleSelector;
+
+/// The utilities generated for each entry in the spacing scale, and the properties they set
+const SPACING_UTILITIES: &[(&str, &[&str])] = &[
+    ("p", &["padding"]),
+    ("px", &["padding-left", "padding-right"]),
+    ("py", &["padding-top", "padding-bottom"]),
+    ("pt", &["padding-top"]),
+    ("pr", &["padding-right"]),
+    ("pb", &["padding-bottom"]),
+    ("pl", &["padding-left"]),
+    ("m", &["margin"]),
+    ("mx", &["margin-left", "margin-right"]),
+    ("my", &["margin-top", "margin-bottom"]),
+    ("mt", &["margin-top"]),
+    ("mr", &["margin-right"]),
+    ("mb", &["margin-bottom"]),
+    ("ml", &["margin-left"]),
+    ("gap", &["gap"]),
+];
+
+/// The utilities generated for each color, and the property they set
+const COLOR_UTILITIES: &[(&str, &str)] = &[
+    ("text", "color"),
+    ("bg", "background-color"),
+    ("border", "border-color"),
+];
+
+/// Design tokens to generate utility classes from.
+///
+/// Each token is a name and a css value, the class names are `{prefix}{utility}-{name}`.
+#[derive(Clone, Copy, Debug)]
+pub struct Tokens {
+    /// Prefix added to every class name, such as `"u-"`, to avoid clashing with your own classes
+    pub prefix: &'static str,
+    /// The spacing scale, generates padding (`p-`, `px-`, `pt-`, ...), margin (`m-`, `mx-`,
+    /// `mt-`, ...), and `gap-` utilities
+    pub spacing: &'static [(&'static str, &'static str)],
+    /// The colors, generates `text-`, `bg-`, and `border-` utilities
+    pub colors: &'static [(&'static str, &'static str)],
+}
+
+impl Tokens {
+    /// Add a utility rule setting all the properties to the value
+    fn utility(
+        &self,
+        rules: RuleCollection,
+        utility: &str,
+        name: &str,
+        properties: &[&'static str],
+        value: &str,
+    ) -> RuleCollection {
+        let class = format!("{}{utility}-{name}", self.prefix);
+        let body = properties.iter().fold(RuleBody::new(), |body, property| {
+            body.property(property, value)
+        });
+        rules.rule(SimpleSelector::Class(class.into()), body)
+    }
+}
+
+impl IntoCss for Tokens {
+    fn into_css(self) -> String {
+        let mut rules = RuleCollection::new();
+        for (utility, properties) in SPACING_UTILITIES {
+            for (name, value) in self.spacing {
+                rules = self.utility(rules, utility, name, properties, value);
+            }
+        }
+        for (utility, property) in COLOR_UTILITIES {
+            for (name, value) in self.colors {
+                rules = self.utility(rules, utility, name, &[property], value);
+            }
+        }
+        rules.into_css()
+    }
+}
+
+/// Register the utility classes for the given [`Tokens`] to go in the bundler.
+///
+/// Any code in here wont be included in the final wasm build.
+/// And will be run at compile time.
+/// This macro must not be called from within a function.
+#[macro_export]
+macro_rules! register_utilities {
+    ($tokens:expr) => {
+        $crate::register_raw_css!({
+            use $crate::macro_ref::css::prelude::*;
+            let result: $crate::macro_ref::css::utilities::Tokens = $tokens;
+            result.into_css()
+        });
+    };
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::css::{as_css_identifier, assert_valid_css};
+
+    const TOKENS: Tokens = Tokens {
+        prefix: "",
+        spacing: &[("0.5", "2px"), ("4", "1rem")],
+        colors: &[("primary", "#3b82f6")],
+    };
+
+    fn class(name: &str) -> String {
+        format!(".{}", as_css_identifier(name))
+    }
+
+    #[test]
+    fn utilities_are_valid_css() {
+        assert_valid_css(&TOKENS.into_css());
+    }
+
+    #[test]
+    fn spacing_utilities() {
+        let result = TOKENS.into_css();
+        assert!(result.contains(&format!("{}{{padding:1rem;}}", class("p-4"))));
+        assert!(result.contains(&format!(
+            "{}{{margin-left:2px;margin-right:2px;}}",
+            class("mx-0.5")
+        )));
+        assert!(result.contains(&format!("{}{{gap:1rem;}}", class("gap-4"))));
+    }
+
+    #[test]
+    fn color_utilities() {
+        let result = TOKENS.into_css();
+        assert!(result.contains(&format!("{}{{color:#3b82f6;}}", class("text-primary"))));
+        assert!(result.contains(&format!(
+            "{}{{background-color:#3b82f6;}}",
+            class("bg-primary")
+        )));
+    }
+
+    #[test]
+    fn prefix_is_applied() {
+        let result = Tokens {
+            prefix: "u-",
+            ..TOKENS
+        }
+        .into_css();
+        assert!(result.contains(&class("u-p-4")));
+        assert!(!result.contains(&format!("{}{{", class("p-4"))));
+    }
+}
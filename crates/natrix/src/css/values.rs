@@ -8,7 +8,7 @@ use std::fmt::Write;
 use std::time::Duration;
 
 pub use animations::*;
-pub use colors::Color;
+pub use colors::{Color, HexColorError};
 
 pub use super::IntoCss;
 use crate::error_handling::{log_or_panic, log_or_panic_result};
@@ -45,7 +45,127 @@ pub enum Color {
     },
 }
 
+/// Why a hex color failed to parse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexColorError {
+    /// The color did not start with a `#`
+    MissingHash,
+    /// The color was not 3, 4, 6, or 8 digits long
+    InvalidLength,
+    /// The color contained a character that is not a hex digit
+    InvalidDigit,
+}
+
+impl std::fmt::Display for HexColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHash => f.write_str("hex color should start with a `#`"),
+            Self::InvalidLength => f.write_str("hex color should have 3, 4, 6, or 8 digits"),
+            Self::InvalidDigit => {
+                f.write_str("hex color contains a character that is not a hex digit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexColorError {}
+
+/// The value of a single hex digit
+#[expect(
+    clippy::arithmetic_side_effects,
+    reason = "The match arms make sure the subtraction can not overflow"
+)]
+const fn hex_digit(digit: u8) -> Result<u8, HexColorError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(HexColorError::InvalidDigit),
+    }
+}
+
+/// The value of a two digit hex channel, such as `1e`
+const fn hex_pair(high: u8, low: u8) -> Result<u8, HexColorError> {
+    match (hex_digit(high), hex_digit(low)) {
+        (Ok(high), Ok(low)) => Ok(high << 4 | low),
+        _ => Err(HexColorError::InvalidDigit),
+    }
+}
+
+/// Convert a channel in the range 0-255 to a alpha in the range 0-1
+const fn hex_alpha(alpha: u8) -> f32 {
+    alpha as f32 / 255.0
+}
+
 impl Color {
+    /// Parse a css hex color, such as `#1e1b4b`, `#fff`, or `#1e1b4b80`.
+    ///
+    /// ```rust
+    /// # use natrix::css::values::Color;
+    /// const INDIGO: Color = match Color::hex("#1e1b4b") {
+    ///     Ok(color) => color,
+    ///     Err(_) => panic!("Invalid color"),
+    /// };
+    /// assert_eq!(INDIGO, Color::rgb(0x1e, 0x1b, 0x4b));
+    /// assert!(Color::hex("1e1b4b").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// If the color does not start with a `#`, does not have 3, 4, 6, or 8 digits, or contains
+    /// something that is not a hex digit.
+    pub const fn hex(hex: &str) -> Result<Self, HexColorError> {
+        let [b'#', digits @ ..] = hex.as_bytes() else {
+            return Err(HexColorError::MissingHash);
+        };
+
+        let (red, green, blue, alpha) = match *digits {
+            [red, green, blue] => (
+                hex_pair(red, red),
+                hex_pair(green, green),
+                hex_pair(blue, blue),
+                Ok(255),
+            ),
+            [red, green, blue, alpha] => (
+                hex_pair(red, red),
+                hex_pair(green, green),
+                hex_pair(blue, blue),
+                hex_pair(alpha, alpha),
+            ),
+            [red_1, red_2, green_1, green_2, blue_1, blue_2] => (
+                hex_pair(red_1, red_2),
+                hex_pair(green_1, green_2),
+                hex_pair(blue_1, blue_2),
+                Ok(255),
+            ),
+            [
+                red_1,
+                red_2,
+                green_1,
+                green_2,
+                blue_1,
+                blue_2,
+                alpha_1,
+                alpha_2,
+            ] => (
+                hex_pair(red_1, red_2),
+                hex_pair(green_1, green_2),
+                hex_pair(blue_1, blue_2),
+                hex_pair(alpha_1, alpha_2),
+            ),
+            _ => return Err(HexColorError::InvalidLength),
+        };
+
+        match (red, green, blue, alpha) {
+            (Ok(red), Ok(green), Ok(blue), Ok(alpha)) => Ok(Self::Rgb {
+                red,
+                green,
+                blue,
+                alpha: hex_alpha(alpha),
+            }),
+            _ => Err(HexColorError::InvalidDigit),
+        }
+    }
+
     /// Set the alpha and return a new color
     pub const fn with_alpha(self, alpha: f32) -> Self {
         debug_assert!(alpha <= 1.0 && alpha >= 0.0, "Alpha should between 0-1");
@@ -162,6 +282,314 @@ impl Color {
     }
 }
 
+/// Convert a srgb channel in the range 0-1 to linear light
+fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.040_45 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light channel in the range 0-1 to srgb
+fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert linear srgb to oklab
+fn linear_srgb_to_oklab([red, green, blue]: [f32; 3]) -> [f32; 3] {
+    let long = 0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue;
+    let medium = 0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue;
+    let short = 0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue;
+
+    let long = long.cbrt();
+    let medium = medium.cbrt();
+    let short = short.cbrt();
+
+    [
+        0.210_454_26 * long + 0.793_617_8 * medium - 0.004_072_047 * short,
+        1.977_998_5 * long - 2.428_592_2 * medium + 0.450_593_7 * short,
+        0.025_904_037 * long + 0.782_771_77 * medium - 0.808_675_77 * short,
+    ]
+}
+
+/// Convert oklab to linear srgb, the result might be out of the srgb gamut.
+fn oklab_to_linear_srgb([lightness, a, b]: [f32; 3]) -> [f32; 3] {
+    let long = lightness + 0.396_337_78 * a + 0.215_803_76 * b;
+    let medium = lightness - 0.105_561_346 * a - 0.063_854_17 * b;
+    let short = lightness - 0.089_484_18 * a - 1.291_485_5 * b;
+
+    let long = long * long * long;
+    let medium = medium * medium * medium;
+    let short = short * short * short;
+
+    [
+        4.076_741_7 * long - 3.307_711_6 * medium + 0.230_969_94 * short,
+        -1.268_438 * long + 2.609_757_4 * medium - 0.341_319_38 * short,
+        -0.004_196_086_3 * long - 0.703_418_6 * medium + 1.707_614_7 * short,
+    ]
+}
+
+/// Convert a 0-1 srgb channel to a `u8`
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "The value is clamped to 0-255 first"
+)]
+fn channel_to_u8(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl Color {
+    /// Convert this color to linear srgb, clamped to the srgb gamut.
+    /// The alpha channel is ignored.
+    fn to_linear_srgb(self) -> [f32; 3] {
+        let srgb = match self {
+            Self::Rgb {
+                red, green, blue, ..
+            } => [
+                f32::from(red) / 255.0,
+                f32::from(green) / 255.0,
+                f32::from(blue) / 255.0,
+            ],
+            Self::Hsl {
+                hue,
+                saturation,
+                lightness,
+                ..
+            } => {
+                let hue = f32::from(hue) / 60.0;
+                let saturation = f32::from(saturation) / 100.0;
+                let lightness = f32::from(lightness) / 100.0;
+
+                let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+                let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+                let offset = lightness - chroma / 2.0;
+
+                let [red, green, blue] = match hue {
+                    hue if hue < 1.0 => [chroma, second, 0.0],
+                    hue if hue < 2.0 => [second, chroma, 0.0],
+                    hue if hue < 3.0 => [0.0, chroma, second],
+                    hue if hue < 4.0 => [0.0, second, chroma],
+                    hue if hue < 5.0 => [second, 0.0, chroma],
+                    _ => [chroma, 0.0, second],
+                };
+                [red + offset, green + offset, blue + offset]
+            }
+            Self::Oklch {
+                lightness,
+                chroma,
+                hue,
+                ..
+            } => {
+                // NOTE: The hue is emitted as a plain number, so css interprets it as degrees.
+                let hue = hue.to_radians();
+                return oklab_to_linear_srgb([lightness, chroma * hue.cos(), chroma * hue.sin()])
+                    .map(|channel| channel.clamp(0.0, 1.0));
+            }
+        };
+
+        srgb.map(|channel| srgb_to_linear(channel.clamp(0.0, 1.0)))
+    }
+
+    /// The relative luminance of this color as defined by WCAG, in the range 0-1.
+    /// The alpha channel is ignored.
+    ///
+    /// <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+    #[must_use]
+    pub fn relative_luminance(self) -> f32 {
+        let [red, green, blue] = self.to_linear_srgb();
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    /// The WCAG contrast ratio between two colors, in the range 1-21.
+    /// The alpha channel is ignored.
+    ///
+    /// <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+    ///
+    /// ```rust
+    /// # use natrix::css::values::Color;
+    /// let ratio = Color::rgb(0, 0, 0).contrast_ratio(Color::rgb(255, 255, 255));
+    /// assert!((ratio - 21.0).abs() < 0.01);
+    /// ```
+    #[must_use]
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let first = self.relative_luminance();
+        let second = other.relative_luminance();
+        let (lighter, darker) = if first > second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// The lightness of this color in the range 0-1, in the colors own color space
+    fn lightness_fraction(self) -> f32 {
+        match self {
+            Self::Rgb { .. } => {
+                let [lightness, _, _] = linear_srgb_to_oklab(self.to_linear_srgb());
+                lightness
+            }
+            Self::Hsl { lightness, .. } => f32::from(lightness) / 100.0,
+            Self::Oklch { lightness, .. } => lightness,
+        }
+    }
+
+    /// Return this color with the given lightness (0-1) in the colors own color space
+    ///
+    /// `round` decides which way to round for colors with integer lightness.
+    fn with_lightness_fraction(self, new_lightness: f32, round: fn(f32) -> f32) -> Self {
+        match self {
+            Self::Rgb { alpha, .. } => {
+                let [_, a, b] = linear_srgb_to_oklab(self.to_linear_srgb());
+                let [red, green, blue] = oklab_to_linear_srgb([new_lightness, a, b])
+                    .map(|channel| channel_to_u8(linear_to_srgb(channel.clamp(0.0, 1.0))));
+                Self::Rgb {
+                    red,
+                    green,
+                    blue,
+                    alpha,
+                }
+            }
+            Self::Hsl {
+                hue,
+                saturation,
+                alpha,
+                ..
+            } => {
+                let lightness = round(new_lightness.clamp(0.0, 1.0) * 100.0);
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    reason = "The value is clamped to 0-100"
+                )]
+                let lightness = lightness as u8;
+                Self::Hsl {
+                    hue,
+                    saturation,
+                    lightness,
+                    alpha,
+                }
+            }
+            Self::Oklch {
+                chroma, hue, alpha, ..
+            } => Self::Oklch {
+                lightness: new_lightness.clamp(0.0, 1.0),
+                chroma,
+                hue,
+                alpha,
+            },
+        }
+    }
+
+    /// Find the lightness between the current one and `target` closest to the current one that
+    /// has at least `min_ratio` contrast against `against`.
+    fn search_lightness(self, against: Self, min_ratio: f32, target: f32) -> Option<Self> {
+        let round: fn(f32) -> f32 = if target > 0.5 { f32::ceil } else { f32::floor };
+        let extreme = self.with_lightness_fraction(target, round);
+        if extreme.contrast_ratio(against) < min_ratio {
+            return None;
+        }
+
+        let mut passing = target;
+        let mut failing = self.lightness_fraction();
+        for _ in 0..24 {
+            let middle = f32::midpoint(passing, failing);
+            if self
+                .with_lightness_fraction(middle, round)
+                .contrast_ratio(against)
+                >= min_ratio
+            {
+                passing = middle;
+            } else {
+                failing = middle;
+            }
+        }
+
+        Some(self.with_lightness_fraction(passing, round))
+    }
+
+    /// Make this color lighter by adding `amount` (0-1) to its lightness, keeping the hue and
+    /// saturation.
+    ///
+    /// The lightness is changed in the colors own color space, for rgb colors this is the oklab
+    /// lightness.
+    ///
+    /// ```rust
+    /// # use natrix::css::values::Color;
+    /// assert_eq!(Color::hsl(240, 50, 40).lighten(0.2), Color::hsl(240, 50, 60));
+    /// ```
+    pub fn lighten(self, amount: f32) -> Self {
+        debug_assert!(
+            (0.0..=1.0).contains(&amount),
+            "Amount should be in range 0-1"
+        );
+        self.with_lightness_fraction(self.lightness_fraction() + amount, f32::round)
+    }
+
+    /// Make this color darker by subtracting `amount` (0-1) from its lightness, keeping the hue
+    /// and saturation.
+    ///
+    /// See [`Color::lighten`].
+    pub fn darken(self, amount: f32) -> Self {
+        debug_assert!(
+            (0.0..=1.0).contains(&amount),
+            "Amount should be in range 0-1"
+        );
+        self.with_lightness_fraction(self.lightness_fraction() - amount, f32::round)
+    }
+
+    /// Adjust the lightness of this color until it has at least `min_ratio` contrast against
+    /// `against`, keeping the hue and saturation.
+    ///
+    /// The color is returned as is if it already has enough contrast, otherwise the lightness is
+    /// changed as little as needed, preferring the direction away from `against`.
+    /// If the ratio can not be reached at all, black or white is returned (whichever has the most
+    /// contrast).
+    ///
+    /// WCAG requires a ratio of `4.5` for normal text, and `3.0` for large text.
+    ///
+    /// ```rust
+    /// # use natrix::css::values::Color;
+    /// let background = Color::rgb(30, 60, 120);
+    /// let text = Color::rgb(40, 70, 130).ensure_contrast(background, 4.5);
+    /// assert!(text.contrast_ratio(background) >= 4.5);
+    /// ```
+    pub fn ensure_contrast(self, against: Self, min_ratio: f32) -> Self {
+        if self.contrast_ratio(against) >= min_ratio {
+            return self;
+        }
+
+        let lighter_first = against.relative_luminance() < 0.18;
+        let (first, second) = if lighter_first {
+            (1.0, 0.0)
+        } else {
+            (0.0, 1.0)
+        };
+
+        if let Some(color) = self.search_lightness(against, min_ratio, first) {
+            return color;
+        }
+        if let Some(color) = self.search_lightness(against, min_ratio, second) {
+            return color;
+        }
+
+        let black = Self::rgb(0, 0, 0);
+        let white = Self::rgb(255, 255, 255);
+        if black.contrast_ratio(against) > white.contrast_ratio(against) {
+            black
+        } else {
+            white
+        }
+    }
+}
+
 impl IntoCss for Color {
     fn into_css(self) -> String {
         match self {
@@ -171,7 +599,7 @@ impl IntoCss for Color {
                 blue,
                 alpha,
             } => {
-                format!("rgb({red} {green} {blue}/{alpha})",)
+                format!("rgb({red} {green} {blue}/{alpha})")
             }
             Self::Hsl {
                 hue,
@@ -239,6 +667,54 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn hex_colors() {
+        assert_eq!(Color::hex("#1e1b4b"), Ok(Color::rgb(0x1e, 0x1b, 0x4b)));
+        assert_eq!(Color::hex("#1E1B4B"), Ok(Color::rgb(0x1e, 0x1b, 0x4b)));
+        assert_eq!(Color::hex("#fa0"), Ok(Color::rgb(0xff, 0xaa, 0x00)));
+        assert_eq!(Color::hex("#fa00"), Ok(Color::rgba(0xff, 0xaa, 0x00, 0.0)));
+        assert_eq!(
+            Color::hex("#1e1b4bff"),
+            Ok(Color::rgba(0x1e, 0x1b, 0x4b, 1.0))
+        );
+    }
+
+    #[test]
+    fn hex_rejects_malformed() {
+        assert_eq!(Color::hex("1e1b4b"), Err(HexColorError::MissingHash));
+        assert_eq!(Color::hex(""), Err(HexColorError::MissingHash));
+        assert_eq!(Color::hex("#"), Err(HexColorError::InvalidLength));
+        assert_eq!(Color::hex("#1e1b4"), Err(HexColorError::InvalidLength));
+        assert_eq!(Color::hex("#1e1b4b0"), Err(HexColorError::InvalidLength));
+        assert_eq!(Color::hex("#1e1g4b"), Err(HexColorError::InvalidDigit));
+        assert_eq!(Color::hex("#ééé"), Err(HexColorError::InvalidDigit));
+        assert_eq!(Color::hex("#é1"), Err(HexColorError::InvalidDigit));
+    }
+
+    #[test]
+    fn lighten_and_darken() {
+        assert_eq!(
+            Color::hsl(240, 50, 40).lighten(0.2),
+            Color::hsl(240, 50, 60)
+        );
+        assert_eq!(Color::hsl(240, 50, 40).darken(0.3), Color::hsl(240, 50, 10));
+        assert_eq!(
+            Color::hsl(240, 50, 90).lighten(0.5),
+            Color::hsl(240, 50, 100)
+        );
+        assert_eq!(
+            Color::oklch_a(0.5, 0.1, 0.3, 0.5).darken(0.6),
+            Color::oklch_a(0.0, 0.1, 0.3, 0.5)
+        );
+
+        let base = Color::rgba(30, 60, 120, 0.5);
+        let lighter = base.lighten(0.2);
+        let darker = base.darken(0.2);
+        assert!(lighter.relative_luminance() > base.relative_luminance());
+        assert!(darker.relative_luminance() < base.relative_luminance());
+        assert!(matches!(lighter, Color::Rgb { alpha, .. } if (alpha - 0.5).abs() < f32::EPSILON));
+    }
+
     #[test]
     fn color_with_alpha() {
         let direct = Color::rgba(100, 100, 100, 0.5);
@@ -326,7 +802,97 @@ pub(crate) mod tests {
         );
     }
 
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn contrast_black_white() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+
+        assert_close(black.contrast_ratio(white), 21.0);
+        assert_close(white.contrast_ratio(black), 21.0);
+        assert_close(white.contrast_ratio(white), 1.0);
+    }
+
+    #[test]
+    fn contrast_known_pairs() {
+        let white = Color::rgb(255, 255, 255);
+
+        // The classic "darkest gray that passes AA on white"
+        assert_close(Color::rgb(0x76, 0x76, 0x76).contrast_ratio(white), 4.54);
+        assert_close(Color::rgb(0x77, 0x77, 0x77).contrast_ratio(white), 4.48);
+        assert_close(Color::rgb(0, 0, 255).contrast_ratio(white), 8.59);
+        assert_close(Color::rgb(255, 0, 0).contrast_ratio(white), 4.0);
+    }
+
+    #[test]
+    fn contrast_other_color_spaces() {
+        let white = Color::rgb(255, 255, 255);
+
+        assert_close(
+            Color::hsl(0, 100, 50).contrast_ratio(white),
+            Color::rgb(255, 0, 0).contrast_ratio(white),
+        );
+        assert_close(Color::hsl(0, 0, 100).contrast_ratio(white), 1.0);
+        assert_close(Color::oklch(1.0, 0.0, 0.0).contrast_ratio(white), 1.0);
+        assert_close(Color::oklch(0.0, 0.0, 0.0).contrast_ratio(white), 21.0);
+    }
+
+    #[test]
+    fn ensure_contrast_keeps_passing_color() {
+        let text = Color::rgb(0, 0, 0);
+        assert_eq!(text.ensure_contrast(Color::rgb(255, 255, 255), 4.5), text);
+    }
+
+    #[test]
+    fn ensure_contrast_text_on_background() {
+        let dark_background = Color::hsl(220, 60, 15);
+        let light_background = Color::hsl(50, 90, 90);
+
+        let text = Color::hsl(220, 60, 25).ensure_contrast(dark_background, 4.5);
+        assert!(text.contrast_ratio(dark_background) >= 4.5);
+        assert!(text.relative_luminance() > dark_background.relative_luminance());
+
+        let text = Color::hsl(50, 90, 80).ensure_contrast(light_background, 4.5);
+        assert!(text.contrast_ratio(light_background) >= 4.5);
+        assert!(text.relative_luminance() < light_background.relative_luminance());
+    }
+
+    #[test]
+    fn ensure_contrast_keeps_color_space() {
+        let background = Color::rgb(255, 255, 255);
+
+        assert!(matches!(
+            Color::hsl(120, 50, 90).ensure_contrast(background, 4.5),
+            Color::Hsl { .. }
+        ));
+        assert!(matches!(
+            Color::oklch(0.9, 0.1, 0.5).ensure_contrast(background, 4.5),
+            Color::Oklch { .. }
+        ));
+        assert!(matches!(
+            Color::rgb(200, 200, 250).ensure_contrast(background, 4.5),
+            Color::Rgb { .. }
+        ));
+    }
+
     proptest! {
+        #[test]
+        fn ensure_contrast_reaches_aa(color: Color, against: Color) {
+            let result = color.ensure_contrast(against, 4.5);
+            prop_assert!(result.contrast_ratio(against) >= 4.5);
+        }
+
+        #[test]
+        fn contrast_is_symmetric(first: Color, second: Color) {
+            assert_close(first.contrast_ratio(second), second.contrast_ratio(first));
+        }
+
         #[test]
         #[cfg(not(debug_assertions))]
         fn color_hsl_doesnt_crash_in_prod(hue: u16, sat: u8, lit: u8) {
@@ -339,6 +905,28 @@ pub(crate) mod tests {
             let _ = Color::oklch(lit, chroma, hue);
         }
 
+        #[test]
+        fn hex_doesnt_crash(hex: String) {
+            let _ = Color::hex(&hex);
+        }
+
+        #[test]
+        fn render_hex_colors(red: u8, green: u8, blue: u8, alpha: u8) {
+            let color = Color::hex(&format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}"));
+            prop_assert_eq!(color, Ok(Color::rgba(red, green, blue, f32::from(alpha) / 255.0)));
+
+            let color = color.map(IntoCss::into_css).unwrap_or_default();
+            assert_valid_css(&format!("h1 {{background-color: {color};}}"));
+        }
+
+        #[test]
+        fn render_adjusted_colors(color: Color, amount in 0.0f32..=1.0, alpha in 0.0f32..=1.0) {
+            for color in [color.lighten(amount), color.darken(amount), color.with_alpha(alpha)] {
+                let color = color.into_css();
+                assert_valid_css(&format!("h1 {{background-color: {color};}}"));
+            }
+        }
+
         #[test]
         fn render_colors(color: Color) {
             let color = color.into_css();
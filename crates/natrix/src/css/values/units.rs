@@ -36,6 +36,38 @@ pub struct Length {
     pub unit: super::LengthUnit,
 }
 
+impl Length {
+    /// Create a length in the given unit
+    #[must_use]
+    pub const fn new(value: f64, unit: super::LengthUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Create a length in pixels
+    #[must_use]
+    pub const fn px(value: f64) -> Self {
+        Self::new(value, super::LengthUnit::Pixel)
+    }
+
+    /// Create a length relative to the font size of the root element
+    #[must_use]
+    pub const fn rem(value: f64) -> Self {
+        Self::new(value, super::LengthUnit::RootFontSize)
+    }
+
+    /// Create a length relative to the font size of the element
+    #[must_use]
+    pub const fn em(value: f64) -> Self {
+        Self::new(value, super::LengthUnit::FontSize)
+    }
+}
+
+impl IntoCss for Length {
+    fn into_css(self) -> String {
+        format!("{}{}", self.value.into_css(), self.unit.into_css())
+    }
+}
+
 impl IntoCss for Percentage {
     fn into_css(self) -> String {
         format!("{}%", self.0)
@@ -1,8 +1,11 @@
 //! Convert various values to html attributes
 
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::{Bound, RangeBounds};
 
-use wasm_bindgen::intern;
+use wasm_bindgen::{JsCast, intern};
 
 use super::html_elements::DeferredFunc;
 use crate::error_handling::log_or_panic;
@@ -35,6 +38,213 @@ pub trait ToAttribute<C: State>: 'static {
 
     /// Return the attribute value, or a deferred function.
     fn calc_attribute(self, name: &'static str, node: &web_sys::Element) -> AttributeResult<C>;
+
+    /// Like `calc_attribute`, but applies the given check to the resulting value.
+    #[doc(hidden)]
+    #[inline]
+    fn calc_attribute_checked(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        check: AttributeCheck,
+    ) -> AttributeResult<C>
+    where
+        Self: Sized,
+    {
+        match self.calc_attribute(name, node) {
+            AttributeResult::SetIt(value) => {
+                AttributeResult::SetIt(check.apply_optional(name, node, value))
+            }
+            dynamic @ AttributeResult::IsDynamic(_) => dynamic,
+        }
+    }
+}
+
+/// A check applied to the values of a typed attribute helper.
+#[derive(Clone, Copy)]
+pub(crate) enum AttributeCheck {
+    /// Clamp the integer into the given range
+    Range(IntegerRange),
+    /// Warn about malformed BCP 47 language tags
+    LanguageTag,
+    /// Warn when making a subtree containing the focused element inert
+    Inert,
+    /// Warn about obviously malformed urls
+    Url,
+    /// Keep the `checked` property in sync, as the attribute only sets the initial state
+    Checked,
+    /// Use `"true"` and `"false"` for a bool, as aria attributes do not use html boolean semantics
+    AriaBool,
+}
+
+impl AttributeCheck {
+    /// Apply the check to the given attribute value, or lack of one
+    fn apply_optional(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        value: Option<Cow<'static, str>>,
+    ) -> Option<Cow<'static, str>> {
+        if let Self::AriaBool = self {
+            let value = if value.is_some() { "true" } else { "false" };
+            return Some(Cow::from(intern(value)));
+        }
+        if let Self::Checked = self {
+            if let Some(input) = node.dyn_ref::<web_sys::HtmlInputElement>() {
+                input.set_checked(value.is_some());
+            }
+            return value;
+        }
+        value.map(|value| self.apply(name, node, value))
+    }
+
+    /// Apply the check to the given attribute value
+    fn apply(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        value: Cow<'static, str>,
+    ) -> Cow<'static, str> {
+        match self {
+            Self::Range(range) => range.clamp(name, value),
+            Self::LanguageTag => {
+                if !is_plausible_language_tag(&value) {
+                    log::warn!("`{value}` does not look like a valid BCP 47 language tag");
+                }
+                value
+            }
+            Self::Inert => {
+                let focused = crate::get_document().active_element();
+                if focused.is_some_and(|focused| node.contains(Some(&focused))) {
+                    log::warn!(
+                        "Made a `<{}>` containing the focused element inert, move focus out of it first to not leave keyboard users stranded",
+                        node.tag_name()
+                    );
+                }
+                value
+            }
+            Self::Url => {
+                if let Some(problem) = url_problem(&value) {
+                    log::warn!("`{value}` does not look like a valid url for `{name}`: {problem}");
+                }
+                value
+            }
+            Self::Checked | Self::AriaBool => value,
+        }
+    }
+}
+
+/// Check for common typos in urls, returning a description of the problem.
+///
+/// This is not a full url parser, it only catches mistakes like stray spaces or a mangled scheme.
+fn url_problem(url: &str) -> Option<&'static str> {
+    if url.chars().any(char::is_whitespace) {
+        return Some("it contains whitespace, use `%20` for spaces");
+    }
+
+    let lowercase = url.to_ascii_lowercase();
+    if lowercase.starts_with("www.") {
+        return Some("absolute urls need a scheme, such as `https://`");
+    }
+    for scheme in ["http", "https"] {
+        if let Some(rest) = lowercase.strip_prefix(scheme) {
+            let malformed = if let Some(rest) = rest.strip_prefix(':') {
+                !rest.starts_with("//")
+            } else {
+                rest.starts_with("//")
+            };
+            if malformed {
+                return Some("the scheme should be followed by `://`");
+            }
+        }
+    }
+
+    None
+}
+
+/// Loosely check if the value is a BCP 47 language tag, such as `en`, `en-US`, or `zh-Hant-TW`.
+///
+/// This only checks the overall shape (alphanumeric subtags of 1 to 8 characters, starting with a
+/// alphabetic one), and not whether the language actually exists.
+/// The empty string is allowed, as it means the language is unknown.
+fn is_plausible_language_tag(tag: &str) -> bool {
+    if tag.is_empty() {
+        return true;
+    }
+
+    let mut subtags = tag.split('-');
+    let Some(primary) = subtags.next() else {
+        return false;
+    };
+    let primary_is_valid =
+        (1..=8).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic());
+    primary_is_valid
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Get the full name of a aria attribute, such as `aria-controls` for `controls`.
+///
+/// The prefixed names are leaked, but as the names are `'static` there is only ever a handful of
+/// them, so each is only leaked once.
+pub(crate) fn aria_attribute_name(name: &'static str) -> &'static str {
+    thread_local! {
+        static NAMES: RefCell<HashMap<&'static str, &'static str>> = RefCell::new(HashMap::new());
+    }
+
+    if name.starts_with("aria-") {
+        return name;
+    }
+    NAMES.with_borrow_mut(|names| {
+        *names
+            .entry(name)
+            .or_insert_with(|| String::leak(format!("aria-{name}")))
+    })
+}
+
+/// The valid range of a integer attribute.
+#[derive(Clone, Copy)]
+pub(crate) struct IntegerRange {
+    /// The smallest allowed value
+    pub(crate) min: i128,
+    /// The largest allowed value
+    pub(crate) max: i128,
+}
+
+impl IntegerRange {
+    /// Create a range from the given bounds
+    pub(crate) fn new(range: impl RangeBounds<i64>) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(&min) => i128::from(min),
+            Bound::Excluded(&min) => i128::from(min).saturating_add(1),
+            Bound::Unbounded => i128::MIN,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(&max) => i128::from(max),
+            Bound::Excluded(&max) => i128::from(max).saturating_sub(1),
+            Bound::Unbounded => i128::MAX,
+        };
+        Self { min, max }
+    }
+
+    /// Clamp the given attribute value into the range, warning if it was out of range.
+    ///
+    /// Browsers generally clamp (or ignore) out of range values, so this is not treated as a bug.
+    pub(crate) fn clamp(self, name: &'static str, value: Cow<'static, str>) -> Cow<'static, str> {
+        let Ok(number) = value.parse::<i128>() else {
+            return value;
+        };
+        if number < self.min || number > self.max {
+            let clamped = number.clamp(self.min, self.max);
+            log::warn!(
+                "`{name}` attribute value {number} is outside the valid range, using {clamped}"
+            );
+            Cow::from(clamped.to_string())
+        } else {
+            value
+        }
+    }
 }
 
 /// A attribute that is a integer
@@ -63,6 +273,49 @@ macro_rules! attribute_string {
 
 type_macros::strings!(attribute_string);
 
+/// A url attribute value.
+///
+/// Url attributes such as `src` and `href` also accept plain strings and `asset!` paths, this is
+/// mainly useful to make the intent clear when storing urls in state.
+/// In debug builds a warning is logged if a url attribute is set to a obviously malformed url.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Url(pub Cow<'static, str>);
+
+impl Url {
+    /// Create a new url
+    #[must_use]
+    pub fn new(url: impl Into<Cow<'static, str>>) -> Self {
+        Self(url.into())
+    }
+
+    /// Get the url as a string
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for Url {
+    fn from(url: &'static str) -> Self {
+        Self::new(url)
+    }
+}
+
+impl From<String> for Url {
+    fn from(url: String) -> Self {
+        Self::new(url)
+    }
+}
+
+impl<C: State> ToAttribute<C> for Url {
+    type AttributeKind = String;
+
+    #[inline]
+    fn calc_attribute(self, _name: &'static str, _node: &web_sys::Element) -> AttributeResult<C> {
+        AttributeResult::SetIt(Some(self.0))
+    }
+}
+
 impl<C: State> ToAttribute<C> for char {
     type AttributeKind = char;
 
@@ -115,6 +368,20 @@ impl<C: State, T: ToAttribute<C>> ToAttribute<C> for Option<T> {
             AttributeResult::SetIt(None)
         }
     }
+
+    #[inline]
+    fn calc_attribute_checked(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        check: AttributeCheck,
+    ) -> AttributeResult<C> {
+        if let Some(inner) = self {
+            inner.calc_attribute_checked(name, node, check)
+        } else {
+            AttributeResult::SetIt(None)
+        }
+    }
 }
 
 impl<C: State, T: ToAttribute<C, AttributeKind = K>, E: ToAttribute<C, AttributeKind = K>, K>
@@ -129,6 +396,19 @@ impl<C: State, T: ToAttribute<C, AttributeKind = K>, E: ToAttribute<C, Attribute
             Err(inner) => inner.calc_attribute(name, node),
         }
     }
+
+    #[inline]
+    fn calc_attribute_checked(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        check: AttributeCheck,
+    ) -> AttributeResult<C> {
+        match self {
+            Ok(inner) => inner.calc_attribute_checked(name, node, check),
+            Err(inner) => inner.calc_attribute_checked(name, node, check),
+        }
+    }
 }
 
 impl<F, C, R> ToAttribute<C> for F
@@ -141,26 +421,47 @@ where
 
     #[inline]
     fn calc_attribute(self, name: &'static str, node: &web_sys::Element) -> AttributeResult<C> {
-        let node = node.clone();
-
-        AttributeResult::IsDynamic(Box::new(move |ctx, render_state| {
-            let hook = SimpleReactive::init_new(
-                Box::new(
-                    move |ctx, node| match self(ctx).calc_attribute(name, node) {
-                        AttributeResult::SetIt(value) => {
-                            SimpleReactiveResult::Apply(ReactiveAttribute { name, data: value })
-                        }
-                        AttributeResult::IsDynamic(inner) => SimpleReactiveResult::Call(inner),
-                    },
-                ),
-                node.clone(),
-                ctx,
-            );
-            render_state.hooks.push(hook);
-        }))
+        reactive_attribute(name, node, move |ctx, node| {
+            self(ctx).calc_attribute(name, node)
+        })
+    }
+
+    #[inline]
+    fn calc_attribute_checked(
+        self,
+        name: &'static str,
+        node: &web_sys::Element,
+        check: AttributeCheck,
+    ) -> AttributeResult<C> {
+        reactive_attribute(name, node, move |ctx, node| {
+            self(ctx).calc_attribute_checked(name, node, check)
+        })
     }
 }
 
+/// Create a deferred reactive attribute from the given calculation
+fn reactive_attribute<C: State>(
+    name: &'static str,
+    node: &web_sys::Element,
+    calc: impl Fn(RenderCtx<C>, &web_sys::Element) -> AttributeResult<C> + 'static,
+) -> AttributeResult<C> {
+    let node = node.clone();
+
+    AttributeResult::IsDynamic(Box::new(move |ctx, render_state| {
+        let hook = SimpleReactive::init_new(
+            Box::new(move |ctx, node| match calc(ctx, node) {
+                AttributeResult::SetIt(value) => {
+                    SimpleReactiveResult::Apply(ReactiveAttribute { name, data: value })
+                }
+                AttributeResult::IsDynamic(inner) => SimpleReactiveResult::Call(inner),
+            }),
+            node.clone(),
+            ctx,
+        );
+        render_state.hooks.push(hook);
+    }))
+}
+
 /// Defines an enum that represents the value of an enumerated HTML attribute.
 macro_rules! define_attribute_enum {
     (
@@ -871,3 +1172,182 @@ define_attribute_enum! {
         Metadata => "metadata"
     }
 }
+
+define_attribute_enum! {
+    #[derive(Copy)]
+    enum AriaLive,
+    "aria-live",
+    "https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Attributes/aria-live",
+    {
+        Off => "off",
+        Polite => "polite",
+        Assertive => "assertive"
+    }
+}
+
+define_attribute_enum! {
+    /// Prefer the semantic html element when there is one, such as `e::button()` over
+    /// `Role::Button`, as it comes with the expected keyboard behavior.
+    enum Role,
+    "role",
+    "https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Roles",
+    {
+        // Landmarks
+        Banner => "banner",
+        Complementary => "complementary",
+        ContentInfo => "contentinfo",
+        Form => "form",
+        Main => "main",
+        Navigation => "navigation",
+        Region => "region",
+        Search => "search",
+        // Widgets
+        Button => "button",
+        Checkbox => "checkbox",
+        GridCell => "gridcell",
+        Link => "link",
+        MenuItem => "menuitem",
+        MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio",
+        Option => "option",
+        ProgressBar => "progressbar",
+        Radio => "radio",
+        ScrollBar => "scrollbar",
+        SearchBox => "searchbox",
+        Slider => "slider",
+        SpinButton => "spinbutton",
+        Switch => "switch",
+        Tab => "tab",
+        TabPanel => "tabpanel",
+        TextBox => "textbox",
+        TreeItem => "treeitem",
+        // Composite widgets
+        ComboBox => "combobox",
+        Grid => "grid",
+        Listbox => "listbox",
+        Menu => "menu",
+        MenuBar => "menubar",
+        RadioGroup => "radiogroup",
+        TabList => "tablist",
+        Tree => "tree",
+        TreeGrid => "treegrid",
+        // Live regions and windows
+        Alert => "alert",
+        Log => "log",
+        Status => "status",
+        Timer => "timer",
+        AlertDialog => "alertdialog",
+        Dialog => "dialog",
+        // Document structure
+        Group => "group",
+        List => "list",
+        ListItem => "listitem",
+        Presentation => "presentation",
+        Separator => "separator",
+        Toolbar => "toolbar",
+        Tooltip => "tooltip",
+        | Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_keeps_valid_values() {
+        let range = IntegerRange::new(1..=1000);
+        assert_eq!(range.clamp("colspan", Cow::from("1")), "1");
+        assert_eq!(range.clamp("colspan", Cow::from("1000")), "1000");
+    }
+
+    #[test]
+    fn range_clamps_invalid_values() {
+        let range = IntegerRange::new(1..=1000);
+        assert_eq!(range.clamp("colspan", Cow::from("0")), "1");
+        assert_eq!(range.clamp("colspan", Cow::from("-5")), "1");
+        assert_eq!(range.clamp("colspan", Cow::from("5000")), "1000");
+    }
+
+    #[test]
+    fn range_open_ended() {
+        let range = IntegerRange::new(0..);
+        assert_eq!(range.clamp("width", Cow::from("-1")), "0");
+        assert_eq!(
+            range.clamp("width", Cow::from("18446744073709551615")),
+            "18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn language_tags() {
+        for tag in [
+            "",
+            "en",
+            "en-US",
+            "zh-Hant-TW",
+            "de-CH-1996",
+            "x-private",
+            "i-klingon",
+        ] {
+            assert!(is_plausible_language_tag(tag), "{tag} should be valid");
+        }
+        for tag in [
+            "en_US",
+            "-en",
+            "en-",
+            "englishlanguage",
+            "e n",
+            "12",
+            "en--US",
+        ] {
+            assert!(!is_plausible_language_tag(tag), "{tag} should be invalid");
+        }
+    }
+
+    #[test]
+    fn urls() {
+        for url in [
+            "",
+            "https://example.com/a%20b",
+            "HTTP://example.com",
+            "//cdn.example.com/lib.js",
+            "/assets/logo.png",
+            "logo.png",
+            "../up",
+            "#section",
+            "?page=2",
+            "mailto:someone@example.com",
+            "data:image/png;base64,AAAA",
+            "httpdocs/index.html",
+        ] {
+            assert_eq!(url_problem(url), None, "{url} should be valid");
+        }
+        for url in [
+            "https://example.com/a b",
+            " /leading",
+            "www.example.com",
+            "https:/example.com",
+            "http:example.com",
+            "https//example.com",
+        ] {
+            assert!(url_problem(url).is_some(), "{url} should be invalid");
+        }
+    }
+
+    #[test]
+    fn aria_names() {
+        assert_eq!(aria_attribute_name("controls"), "aria-controls");
+        assert_eq!(aria_attribute_name("aria-controls"), "aria-controls");
+        assert!(std::ptr::eq(
+            aria_attribute_name("owns"),
+            aria_attribute_name("owns")
+        ));
+    }
+
+    #[test]
+    fn range_exclusive_end() {
+        let range = IntegerRange::new(0..10);
+        assert_eq!(range.clamp("x", Cow::from("10")), "9");
+    }
+}
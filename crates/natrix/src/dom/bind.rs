@@ -0,0 +1,131 @@
+//! Two way binding of form fields to the state.
+//!
+//! [`HtmlElement::bind`] keeps the value of a text field in sync with a `String` in the state,
+//! updating the field when the state changes, and the state when the user types.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! #[derive(State)]
+//! struct App {
+//!     name: Signal<String>,
+//!     age: Signal<u8>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .child(e::text_input().bind(|ctx: Ref<App>| field!(ctx.name).deref()))
+//!         .child(e::text_input().bind_parsed(|ctx: Ref<App>| field!(ctx.age).deref()))
+//!         .child(e::p().text(|ctx: RenderCtx<App>| format!("{} is {}", *ctx.name, *ctx.age)))
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use wasm_bindgen::JsCast;
+
+use super::events;
+use super::html_elements::{HtmlElement, TagTextInput, TagTextarea};
+use crate::access::{Getter, RefClosure};
+use crate::error_handling::log_or_panic;
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{ReactiveValue, SimpleReactive, SimpleReactiveResult};
+use crate::reactivity::state::EventCtx;
+
+/// A element with a text `value` that can be bound to the state
+pub trait TextField {}
+impl TextField for TagTextInput {}
+impl TextField for TagTextarea {}
+
+/// Get the current value of a text field
+fn field_value(node: &web_sys::Element) -> Option<String> {
+    if let Some(input) = node.dyn_ref::<web_sys::HtmlInputElement>() {
+        Some(input.value())
+    } else {
+        node.dyn_ref::<web_sys::HtmlTextAreaElement>()
+            .map(web_sys::HtmlTextAreaElement::value)
+    }
+}
+
+/// Set the value of a text field
+fn set_field_value(node: &web_sys::Element, value: &str) {
+    if let Some(input) = node.dyn_ref::<web_sys::HtmlInputElement>() {
+        input.set_value(value);
+    } else if let Some(textarea) = node.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        textarea.set_value(value);
+    } else {
+        log_or_panic!("`bind` used on element without a text value");
+    }
+}
+
+/// Reactively set the value of a bound text field
+struct ReactiveFieldValue<T>(T);
+
+impl<T: FromStr + Display + PartialEq> ReactiveValue for ReactiveFieldValue<T> {
+    type State = ();
+
+    fn apply(self, node: &web_sys::Element, _state: &mut Self::State) {
+        // NOTE: Leaving fields that already hold the value alone keeps the cursor position, and
+        // in progress input such as `1.` for a number.
+        if field_value(node)
+            .and_then(|current| current.parse::<T>().ok())
+            .is_some_and(|current| current == self.0)
+        {
+            return;
+        }
+        set_field_value(node, &self.0.to_string());
+    }
+}
+
+impl<C: State, T: TextField> HtmlElement<C, T> {
+    /// Bind the value of the field to the given `String`.
+    ///
+    /// The field is updated when the value changes, and the value is updated as the user types.
+    /// Input from a IME is only written back once the composition is done.
+    #[inline]
+    pub fn bind(self, value: impl Getter<C, String>) -> Self {
+        self.bind_parsed(value)
+    }
+
+    /// Bind the value of the field to a value parsed from it, such as a number.
+    ///
+    /// The value is only updated when the field contains valid input, so partial input like `-`
+    /// or `1e` is left alone until it parses.
+    pub fn bind_parsed<V>(mut self, value: impl Getter<C, V>) -> Self
+    where
+        V: FromStr + Display + PartialEq + Clone + 'static,
+    {
+        let node = self.element.clone();
+        let read = value.clone();
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = SimpleReactive::init_new(
+                Box::new(move |ctx, _node| {
+                    SimpleReactiveResult::Apply(ReactiveFieldValue(read.call_read(&ctx).clone()))
+                }),
+                node,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+
+        let node = self.element.clone();
+        let write_back = move |mut ctx: EventCtx<C>| {
+            let Some(new) = field_value(&node).and_then(|new| new.parse::<V>().ok()) else {
+                return;
+            };
+            if *value.call_read(&ctx) != new {
+                *value.call_mut(&mut ctx) = new;
+            }
+        };
+
+        let on_composition_end = write_back.clone();
+        self.on::<events::Input>(move |ctx: EventCtx<C>, event: web_sys::InputEvent| {
+            // NOTE: Writing back mid composition would set the value, clobbering the composition.
+            if !event.is_composing() {
+                write_back(ctx);
+            }
+        })
+        .on::<events::CompositionEnd>(move |ctx: EventCtx<C>, _| on_composition_end(ctx))
+    }
+}
@@ -2,15 +2,21 @@
 
 use std::borrow::Cow;
 
-use super::html_elements::DeferredFunc;
+use smallvec::SmallVec;
+
+use super::html_elements::{DeferredFunc, HtmlElement};
+use crate::css::selectors::Class;
 use crate::reactivity::State;
 use crate::reactivity::render_callbacks::{ReactiveClass, SimpleReactive, SimpleReactiveResult};
 use crate::reactivity::state::RenderCtx;
 
+/// The classes set by a single `ToClass` value, most values set zero or one class.
+pub(crate) type ClassList = SmallVec<[Cow<'static, str>; 1]>;
+
 /// The result of applying a class
 pub(crate) enum ClassResult<C: State> {
-    /// The class should be applied immedtialy
-    SetIt(Option<Cow<'static, str>>),
+    /// The classes should be applied immedtialy
+    SetIt(ClassList),
     /// The class needs access to state
     Dynamic(DeferredFunc<C>),
 }
@@ -26,7 +32,7 @@ impl<C: State, T: ToClass<C>> ToClass<C> for Option<T> {
         if let Some(inner) = self {
             inner.calc_class(node)
         } else {
-            ClassResult::SetIt(None)
+            ClassResult::SetIt(ClassList::new())
         }
     }
 }
@@ -40,6 +46,16 @@ impl<C: State, T: ToClass<C>, E: ToClass<C>> ToClass<C> for Result<T, E> {
     }
 }
 
+impl<C: State> ToClass<C> for Vec<Class> {
+    fn calc_class(self, _node: &web_sys::Element) -> ClassResult<C> {
+        ClassResult::SetIt(
+            self.into_iter()
+                .map(|class| Cow::Borrowed(class.0))
+                .collect(),
+        )
+    }
+}
+
 impl<F, C, R> ToClass<C> for F
 where
     F: Fn(RenderCtx<C>) -> R + 'static,
@@ -64,3 +80,32 @@ where
         }))
     }
 }
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Add the class while the condition is true, and remove it otherwise.
+    ///
+    /// Only this class is added or removed, so it composes with the other classes of the element.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     active: Signal<bool>,
+    /// # }
+    /// const ACTIVE: Class = natrix::class!();
+    ///
+    /// # fn render() -> impl Element<App> {
+    /// e::button().class_if(|ctx: RenderCtx<App>| *ctx.active, ACTIVE)
+    /// # }
+    /// ```
+    #[inline]
+    pub fn class_if<Cls>(
+        self,
+        condition: impl Fn(RenderCtx<C>) -> bool + 'static,
+        class: Cls,
+    ) -> Self
+    where
+        Cls: ToClass<C> + Clone + 'static,
+    {
+        self.class(move |ctx: RenderCtx<C>| condition(ctx).then(|| class.clone()))
+    }
+}
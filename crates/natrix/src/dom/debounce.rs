@@ -0,0 +1,106 @@
+//! Debounced event handlers, which only run once the events stop for a while.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+use web_sys::js_sys::Function;
+
+use super::events::{Event, EventHandler};
+use super::html_elements::HtmlElement;
+use crate::error_handling::log_or_panic;
+use crate::reactivity::State;
+use crate::reactivity::state::{EventCtx, StateHandle};
+use crate::shims;
+
+/// The state shared between the event listener and the timeout of a debounced handler
+struct Debounced<C: State, J> {
+    /// The timeout of the pending call
+    timeout: Cell<Option<i32>>,
+    /// The latest event, and the state to call the handler with
+    latest: Cell<Option<(StateHandle<C>, J)>>,
+}
+
+impl<C: State, J> Drop for Debounced<C, J> {
+    fn drop(&mut self) {
+        // NOTE: The timeout would otherwise call the handler after it has been dropped
+        if let Some(timeout) = self.timeout.take() {
+            shims::clear_timeout(timeout);
+        }
+    }
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Register a event handler that only runs once `delay` has passed without the event
+    /// firing again, such as for searching as the user types.
+    ///
+    /// The handler is called with the latest event.
+    /// If the element is removed before the delay has passed the handler is not called.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     searches: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::text_input().on_debounced::<events::Input>(
+    ///     Duration::from_millis(300),
+    ///     |mut ctx: EventCtx<App>, _| {
+    ///         *ctx.searches += 1;
+    ///     },
+    /// )
+    /// # }
+    /// ```
+    pub fn on_debounced<E: Event>(
+        mut self,
+        delay: Duration,
+        function: impl EventHandler<C, E>,
+    ) -> Self
+    where
+        E::JsEvent: 'static,
+    {
+        let function = function.func();
+        let delay = i32::try_from(delay.as_millis()).unwrap_or(i32::MAX);
+        let state: Rc<Debounced<C, E::JsEvent>> = Rc::new(Debounced {
+            timeout: Cell::new(None),
+            latest: Cell::new(None),
+        });
+
+        let fire: Closure<dyn Fn()> = Closure::new({
+            let state = state.clone();
+            move || {
+                state.timeout.set(None);
+                if let Some((handle, event)) = state.latest.take() {
+                    handle.with_mut(|ctx| function(ctx, event));
+                }
+            }
+        });
+        let fire_function: Function = fire.as_ref().unchecked_ref::<Function>().clone();
+
+        self = self.on::<E>({
+            let state = state.clone();
+            move |ctx: EventCtx<C>, event| {
+                state.latest.set(Some((ctx.weak_handle(), event)));
+
+                if let Some(timeout) = state.timeout.take() {
+                    shims::clear_timeout(timeout);
+                }
+                if let Ok(timeout) = shims::set_timeout(&fire_function, delay) {
+                    state.timeout.set(Some(timeout));
+                } else {
+                    log_or_panic!("Failed to set debounce timeout");
+                }
+            }
+        });
+
+        self.deferred.push(Box::new(move |_ctx, rendering_state| {
+            rendering_state.keep_alive.push(Box::new(fire));
+            rendering_state.keep_alive.push(Box::new(state));
+        }));
+        self
+    }
+}
@@ -0,0 +1,138 @@
+//! Modal dialogs built on the native `<dialog>` element.
+//!
+//! Toggling the `open` attribute does not make a dialog modal, it needs to be opened with
+//! `showModal()`, which gives us a backdrop, focus trapping, and `Escape` to close for free.
+//! [`HtmlElement::open_modal`] binds this to a `bool` in the state, and syncs it back when the
+//! dialog is closed by the browser.
+//! The plain `open` attribute helper shows the dialog as a non-modal one instead.
+//!
+//! # Example
+//! ```no_run
+//! # use natrix::prelude::*;
+//! #[derive(State)]
+//! struct App {
+//!     show_settings: Signal<bool>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .child(
+//!             e::button()
+//!                 .text("Settings")
+//!                 .on::<events::Click>(|mut ctx: EventCtx<App>, _| *ctx.show_settings = true),
+//!         )
+//!         .child(
+//!             e::dialog()
+//!                 .open_modal(|ctx: Ref<App>| field!(ctx.show_settings).deref())
+//!                 .close_on_backdrop_click()
+//!                 .child(e::h2().text("Settings")),
+//!         )
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+
+use super::events;
+use super::html_elements::{HtmlElement, TagDialog};
+use crate::access::{Getter, RefClosure};
+use crate::error_handling::{log_or_panic, log_or_panic_result};
+use crate::get_window;
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{ReactiveValue, SimpleReactive, SimpleReactiveResult};
+use crate::reactivity::state::EventCtx;
+
+/// Reactively open or close a dialog as a modal
+struct ReactiveDialogOpen(bool);
+
+impl ReactiveValue for ReactiveDialogOpen {
+    /// Whether the dialog should currently be open,
+    /// shared with any pending `showModal` call.
+    type State = Rc<Cell<bool>>;
+
+    fn apply(self, node: &web_sys::Element, state: &mut Self::State) {
+        let Some(dialog) = node.dyn_ref::<web_sys::HtmlDialogElement>() else {
+            log_or_panic!("`open_modal` used on non-dialog element");
+            return;
+        };
+        state.set(self.0);
+
+        if !self.0 {
+            if dialog.open() {
+                dialog.close();
+            }
+            return;
+        }
+        if dialog.open() {
+            return;
+        }
+
+        if dialog.is_connected() {
+            log_or_panic_result!(dialog.show_modal(), "Failed to open modal dialog");
+        } else {
+            // NOTE: `showModal` throws on disconnected elements, which freshly rendered elements
+            // are until the current render is done.
+            let dialog = dialog.clone();
+            let should_open = state.clone();
+            let callback = Closure::once_into_js(move || {
+                if should_open.get() && dialog.is_connected() && !dialog.open() {
+                    log_or_panic_result!(dialog.show_modal(), "Failed to open modal dialog");
+                }
+            });
+            get_window().queue_microtask(callback.unchecked_ref());
+        }
+    }
+}
+
+impl<C: State> HtmlElement<C, TagDialog> {
+    /// Open the dialog as a modal (using `showModal()`) while the given value is `true`, and close
+    /// it when it becomes `false`.
+    ///
+    /// When the dialog is closed by the browser, for example by pressing `Escape` or a
+    /// `<form method="dialog">` being submitted, the value is set back to `false`.
+    #[inline]
+    pub fn open_modal(mut self, open: impl Getter<C, bool>) -> Self {
+        let node = self.element.clone();
+        let read = open.clone();
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = SimpleReactive::init_new(
+                Box::new(move |ctx, _node| {
+                    SimpleReactiveResult::Apply(ReactiveDialogOpen(*read.call_read(&ctx)))
+                }),
+                node,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+
+        self.on::<events::Close>(move |mut ctx: EventCtx<C>, _| {
+            if *open.call_read(&ctx) {
+                *open.call_mut(&mut ctx) = false;
+            }
+        })
+    }
+
+    /// Close the dialog when the backdrop outside of it is clicked.
+    #[inline]
+    pub fn close_on_backdrop_click(self) -> Self {
+        let node = self.element.clone();
+        self.on::<events::Click>(move |_ctx: EventCtx<C>, event: web_sys::PointerEvent| {
+            // Clicks on the backdrop target the dialog itself,
+            // but so do clicks on the padding of the dialog.
+            if event.target().as_ref() != Some(node.as_ref()) {
+                return;
+            }
+            let rect = node.get_bounding_client_rect();
+            let (x, y) = (f64::from(event.client_x()), f64::from(event.client_y()));
+            let inside =
+                x >= rect.left() && x <= rect.right() && y >= rect.top() && y <= rect.bottom();
+
+            if !inside && let Some(dialog) = node.dyn_ref::<web_sys::HtmlDialogElement>() {
+                dialog.close();
+            }
+        })
+    }
+}
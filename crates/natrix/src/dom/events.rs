@@ -1,15 +1,241 @@
 //! Implementation of event traits as well as a helper trait for event handlers.
 
 use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
 
+use crate::error_handling::{log_or_panic, log_or_panic_result};
 use crate::reactivity::state::EventCtx;
 
 /// Trait for converting a struct to needed event info.
 pub trait Event {
     /// The js event the handler gets
-    type JsEvent: JsCast;
+    type JsEvent: FromEvent;
     /// The actual name
     const EVENT_NAME: &str;
+    /// Whether [`HtmlElement::on_window`](super::html_elements::HtmlElement::on_window) should
+    /// listen on the document, for events that are fired at the document rather than the window.
+    const ON_DOCUMENT: bool = false;
+}
+
+/// A type event handlers can receive, converted from the dom event.
+///
+/// This is implemented for all the `web_sys` event types, as well as wrappers such as
+/// [`KeyEvent`].
+pub trait FromEvent: Sized {
+    /// Convert the dom event, returning `None` if it is not the expected type.
+    fn from_event(event: web_sys::Event) -> Option<Self>;
+}
+
+impl<T: JsCast> FromEvent for T {
+    fn from_event(event: web_sys::Event) -> Option<Self> {
+        event.dyn_into().ok()
+    }
+}
+
+/// A key on the keyboard, as reported by [`KeyEvent::key`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// The enter or return key
+    Enter,
+    /// The escape key
+    Escape,
+    /// The tab key
+    Tab,
+    /// The backspace key
+    Backspace,
+    /// The delete key
+    Delete,
+    /// The up arrow key
+    ArrowUp,
+    /// The down arrow key
+    ArrowDown,
+    /// The left arrow key
+    ArrowLeft,
+    /// The right arrow key
+    ArrowRight,
+    /// The home key
+    Home,
+    /// The end key
+    End,
+    /// The page up key
+    PageUp,
+    /// The page down key
+    PageDown,
+    /// A key producing a printable character, such as `'a'`, `'A'`, `'1'`, or `' '`.
+    Character(char),
+    /// Any other key, with its name as used by `KeyboardEvent.key`, such as `"F1"` or `"Shift"`.
+    Other(String),
+}
+
+impl From<String> for Key {
+    fn from(key: String) -> Self {
+        match key.as_str() {
+            "Enter" => Self::Enter,
+            "Escape" => Self::Escape,
+            "Tab" => Self::Tab,
+            "Backspace" => Self::Backspace,
+            "Delete" => Self::Delete,
+            "ArrowUp" => Self::ArrowUp,
+            "ArrowDown" => Self::ArrowDown,
+            "ArrowLeft" => Self::ArrowLeft,
+            "ArrowRight" => Self::ArrowRight,
+            "Home" => Self::Home,
+            "End" => Self::End,
+            "PageUp" => Self::PageUp,
+            "PageDown" => Self::PageDown,
+            _ => {
+                let mut chars = key.chars();
+                if let (Some(character), None) = (chars.next(), chars.next()) {
+                    Self::Character(character)
+                } else {
+                    Self::Other(key)
+                }
+            }
+        }
+    }
+}
+
+/// A keyboard event, received by [`KeyDown`] and [`KeyUp`] handlers.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::dom::events::{Key, KeyEvent};
+///
+/// # #[derive(State)]
+/// # struct App {
+/// #     saved: Signal<bool>,
+/// # }
+/// # fn render() -> impl Element<App> {
+/// e::div().on::<events::KeyDown>(|mut ctx: EventCtx<App>, event: KeyEvent| {
+///     if event.ctrl() && event.key() == Key::Character('s') {
+///         event.prevent_default();
+///         *ctx.saved = true;
+///     }
+/// })
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyEvent(web_sys::KeyboardEvent);
+
+impl FromEvent for KeyEvent {
+    fn from_event(event: web_sys::Event) -> Option<Self> {
+        event.dyn_into().ok().map(Self)
+    }
+}
+
+impl KeyEvent {
+    /// The key that was pressed, taking the keyboard layout and modifiers into account.
+    #[must_use]
+    pub fn key(&self) -> Key {
+        Key::from(self.0.key())
+    }
+
+    /// The physical key that was pressed, such as `"KeyA"`, regardless of the keyboard layout.
+    #[must_use]
+    pub fn code(&self) -> String {
+        self.0.code()
+    }
+
+    /// Whether the control key was held
+    #[must_use]
+    pub fn ctrl(&self) -> bool {
+        self.0.ctrl_key()
+    }
+
+    /// Whether the shift key was held
+    #[must_use]
+    pub fn shift(&self) -> bool {
+        self.0.shift_key()
+    }
+
+    /// Whether the alt (option) key was held
+    #[must_use]
+    pub fn alt(&self) -> bool {
+        self.0.alt_key()
+    }
+
+    /// Whether the meta (command or windows) key was held
+    #[must_use]
+    pub fn meta(&self) -> bool {
+        self.0.meta_key()
+    }
+
+    /// Prevent the default action of the key press, such as typing the character
+    pub fn prevent_default(&self) {
+        self.0.prevent_default();
+    }
+
+    /// Stop the event from reaching the handlers of parent elements
+    pub fn stop_propagation(&self) {
+        self.0.stop_propagation();
+    }
+
+    /// Get the underlying `web_sys` event
+    #[must_use]
+    pub fn raw(&self) -> &web_sys::KeyboardEvent {
+        &self.0
+    }
+}
+
+impl AsRef<web_sys::Event> for KeyEvent {
+    fn as_ref(&self) -> &web_sys::Event {
+        self.0.as_ref()
+    }
+}
+
+/// Typed access to the elements of a event, implemented for all the event types handlers receive.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # #[derive(State)]
+/// # struct App {
+/// #     search: Signal<String>,
+/// # }
+/// # fn render() -> impl Element<App> {
+/// e::text_input().on::<events::Input>(|mut ctx: EventCtx<App>, event: web_sys::InputEvent| {
+///     if let Some(value) = event.target_value() {
+///         *ctx.search = value;
+///     }
+/// })
+/// # }
+/// ```
+pub trait EventExt {
+    /// The element the handler was registered on, if it is a `T`.
+    fn current_target_as<T: JsCast>(&self) -> Option<T>;
+
+    /// The element the event was dispatched to, which might be a child of the element the
+    /// handler was registered on, if it is a `T`.
+    fn target_as<T: JsCast>(&self) -> Option<T>;
+
+    /// The value of the `<input>`, `<textarea>` or `<select>` the event was dispatched to.
+    fn target_value(&self) -> Option<String> {
+        let target = self.target_as::<web_sys::Element>()?;
+        if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(input.value())
+        } else if let Some(textarea) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            Some(textarea.value())
+        } else {
+            target
+                .dyn_ref::<web_sys::HtmlSelectElement>()
+                .map(web_sys::HtmlSelectElement::value)
+        }
+    }
+
+    /// Whether the checkbox or radio button the event was dispatched to is checked.
+    fn target_checked(&self) -> Option<bool> {
+        self.target_as::<web_sys::HtmlInputElement>()
+            .map(|input| input.checked())
+    }
+}
+
+impl<E: AsRef<web_sys::Event>> EventExt for E {
+    fn current_target_as<T: JsCast>(&self) -> Option<T> {
+        self.as_ref().current_target()?.dyn_into().ok()
+    }
+
+    fn target_as<T: JsCast>(&self) -> Option<T> {
+        self.as_ref().target()?.dyn_into().ok()
+    }
 }
 /// Utility trait for defining event handler in arguments.
 ///
@@ -36,16 +262,30 @@ impl<C, E: Event, F: Fn(EventCtx<C>, E::JsEvent) + 'static> EventHandler<C, E> f
 /// Implement and define a `Event`
 macro_rules! impl_event {
     ($ty:ident => $name:literal, $handler:ident) => {
+        impl_event!($ty => $name, type web_sys::$handler);
+    };
+    ($ty:ident => $name:literal, type $handler:ty) => {
+        #[doc = $name]
+        pub struct $ty;
+
+        impl Event for $ty {
+            type JsEvent = $handler;
+            const EVENT_NAME: &str = $name;
+        }
+    };
+    ($ty:ident => $name:literal, $handler:ident, document) => {
         #[doc = $name]
         pub struct $ty;
 
         impl Event for $ty {
             type JsEvent = web_sys::$handler;
             const EVENT_NAME: &str = $name;
+            const ON_DOCUMENT: bool = true;
         }
     };
 }
 
+impl_event!(Abort => "abort", Event);
 impl_event!(AnimationCancel => "animationcancel", AnimationEvent);
 impl_event!(AnimationEnd => "animationend", AnimationEvent);
 impl_event!(AnimationIteration => "animationiteration", AnimationEvent);
@@ -53,7 +293,10 @@ impl_event!(AnimationStart => "animationstart", AnimationEvent);
 impl_event!(AuxClick => "auxclick", PointerEvent);
 impl_event!(BeforeInput => "beforeinput", InputEvent);
 impl_event!(Blur => "blur", FocusEvent);
+impl_event!(Cancel => "cancel", Event);
+impl_event!(Change => "change", Event);
 impl_event!(Click => "click", PointerEvent);
+impl_event!(Close => "close", Event);
 impl_event!(CompositionEnd => "compositionend", CompositionEvent);
 impl_event!(CompositionStart => "compositionstart", CompositionEvent);
 impl_event!(CompositionUpdate => "compositionupdate", CompositionEvent);
@@ -62,6 +305,7 @@ impl_event!(ContextMenu => "contextmenu", PointerEvent);
 impl_event!(Copy => "copy", ClipboardEvent);
 impl_event!(Cut => "cut", ClipboardEvent);
 impl_event!(DoubleClick => "dblclick", MouseEvent);
+impl_event!(Error => "error", Event);
 impl_event!(Focus => "focus", FocusEvent);
 impl_event!(FocusIn => "focusin", FocusEvent);
 impl_event!(FocusOut => "focusout", FocusEvent);
@@ -69,8 +313,9 @@ impl_event!(FullscreenChange => "fullscreenchange", Event);
 impl_event!(FullscreenError => "fullscreenerror", Event);
 impl_event!(GotPointerCapture => "gotpointercapture", PointerEvent);
 impl_event!(Input => "input", InputEvent);
-impl_event!(KeyDown => "keydown", KeyboardEvent);
-impl_event!(KeyUp => "keyup", KeyboardEvent);
+impl_event!(KeyDown => "keydown", type KeyEvent);
+impl_event!(KeyUp => "keyup", type KeyEvent);
+impl_event!(Load => "load", Event);
 impl_event!(LostPointerCapture => "lostpointercapture", PointerEvent);
 impl_event!(MouseDown => "mousedown", MouseEvent);
 impl_event!(MouseEnter => "mouseenter", MouseEvent);
@@ -88,6 +333,7 @@ impl_event!(PointerMove => "pointermove", PointerEvent);
 impl_event!(PointerOut => "pointerout", PointerEvent);
 impl_event!(PointerOver => "pointerover", PointerEvent);
 impl_event!(PointerUp => "pointerup", PointerEvent);
+impl_event!(Resize => "resize", Event);
 impl_event!(Scroll => "scroll", Event);
 impl_event!(ScrollEnd => "scrollend", Event);
 impl_event!(SecurityPolicyViolation => "securitypolicyviolation", Event);
@@ -99,4 +345,80 @@ impl_event!(TransitionCancel => "transitioncancel", TransitionEvent);
 impl_event!(TransitionEnd => "transitionend", TransitionEvent);
 impl_event!(TransitionRun => "transitionrun", TransitionEvent);
 impl_event!(TransitionStart => "transitionstart", TransitionEvent);
+impl_event!(VisibilityChange => "visibilitychange", Event, document);
 impl_event!(Wheel => "wheel", WheelEvent);
+
+/// A event listener that is removed when dropped
+pub(crate) struct EventListener {
+    /// The target the listener is attached to
+    target: web_sys::EventTarget,
+    /// The event name
+    event: &'static str,
+    /// The listener
+    callback: Closure<dyn Fn(web_sys::Event)>,
+}
+
+impl EventListener {
+    /// Attach the callback to the target
+    pub(crate) fn new(
+        target: web_sys::EventTarget,
+        event: &'static str,
+        callback: impl Fn(web_sys::Event) + 'static,
+    ) -> Option<Self> {
+        let callback: Closure<dyn Fn(web_sys::Event)> = Closure::new(callback);
+        if target
+            .add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+            .is_err()
+        {
+            log_or_panic!("Failed to attach {event} listener");
+            return None;
+        }
+        Some(Self {
+            target,
+            event,
+            callback,
+        })
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        log_or_panic_result!(
+            self.target.remove_event_listener_with_callback(
+                self.event,
+                self.callback.as_ref().unchecked_ref()
+            ),
+            "Failed to remove {} listener",
+            self.event
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_keys() {
+        assert_eq!(Key::from("Enter".to_owned()), Key::Enter);
+        assert_eq!(Key::from("ArrowLeft".to_owned()), Key::ArrowLeft);
+        assert_eq!(Key::from("PageDown".to_owned()), Key::PageDown);
+    }
+
+    #[test]
+    fn printable_characters() {
+        assert_eq!(Key::from("a".to_owned()), Key::Character('a'));
+        assert_eq!(Key::from(" ".to_owned()), Key::Character(' '));
+        assert_eq!(Key::from("ø".to_owned()), Key::Character('ø'));
+    }
+
+    #[test]
+    fn other_keys() {
+        assert_eq!(Key::from("F1".to_owned()), Key::Other("F1".to_owned()));
+        assert_eq!(
+            Key::from("Shift".to_owned()),
+            Key::Other("Shift".to_owned())
+        );
+        assert_eq!(Key::from(String::new()), Key::Other(String::new()));
+    }
+}
@@ -0,0 +1,124 @@
+//! Fragments, for rendering multiple siblings without a wrapper element.
+//!
+//! The children of a fragment are inserted directly into the parent, between two comment nodes
+//! marking where the fragment starts and ends. When a reactive closure returning a fragment
+//! reruns exactly the nodes between these comments are replaced.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! #[derive(State)]
+//! struct App {
+//!     logged_in: Signal<bool>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::ul().child(|ctx: RenderCtx<App>| {
+//!         if *ctx.logged_in {
+//!             e::fragment()
+//!                 .child(e::li().text("Profile"))
+//!                 .child(e::li().text("Log out"))
+//!         } else {
+//!             e::fragment().child(e::li().text("Log in"))
+//!         }
+//!     })
+//! }
+//! ```
+
+use smallvec::SmallVec;
+
+use super::element::{DynElement, Element, ElementRenderResult, MaybeStaticElement};
+use super::html_elements::{DeferredFunc, child_node};
+use crate::error_handling::{log_or_panic, log_or_panic_result};
+use crate::get_document;
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::RenderingState;
+use crate::reactivity::state::InnerCtx;
+
+/// A list of sibling elements rendered without a wrapper element.
+#[must_use = "Web elements are useless if not rendered"]
+pub struct Fragment<C: State> {
+    /// The nodes of the fragment, starting with the start anchor
+    fragment: web_sys::DocumentFragment,
+    /// The deferred actions of the children
+    deferred: SmallVec<[DeferredFunc<C>; 2]>,
+}
+
+impl<C: State> Default for Fragment<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: State> Fragment<C> {
+    /// Create a empty fragment
+    pub fn new() -> Self {
+        let fragment = get_document().create_document_fragment();
+        append_anchor(&fragment);
+        Self {
+            fragment,
+            deferred: SmallVec::new(),
+        }
+    }
+
+    /// Push a child to this fragment.
+    /// This accepts any valid element including closures and other fragments.
+    #[inline]
+    pub fn child<E: Element<C> + 'static>(mut self, child: E) -> Self {
+        if let Some(node) = child_node(child, &mut self.deferred) {
+            log_or_panic_result!(
+                self.fragment.append_child(&node),
+                "Failed to append child to fragment"
+            );
+        }
+        self
+    }
+
+    /// This is a simple alias for `child`
+    #[inline]
+    pub fn text<E: Element<C> + 'static>(self, text: E) -> Self {
+        self.child(text)
+    }
+
+    /// Push multiple children at once
+    #[inline]
+    pub fn children<E: Element<C>>(mut self, elements: impl IntoIterator<Item = E>) -> Self {
+        for element in elements {
+            self = self.child(element);
+        }
+        self
+    }
+}
+
+impl<C: State> Element<C> for Fragment<C> {
+    fn render(self) -> MaybeStaticElement<C> {
+        MaybeStaticElement::Dynamic(Box::new(self))
+    }
+}
+
+impl<C: State> DynElement<C> for Fragment<C> {
+    fn render(
+        self: Box<Self>,
+        ctx: &mut InnerCtx<C>,
+        render_state: &mut RenderingState,
+    ) -> ElementRenderResult {
+        let Self { fragment, deferred } = *self;
+        for modification in deferred {
+            modification(ctx, render_state);
+        }
+        append_anchor(&fragment);
+        ElementRenderResult::Node(fragment.into())
+    }
+}
+
+/// Append a comment marking the start or end of the fragment
+fn append_anchor(fragment: &web_sys::DocumentFragment) {
+    if let Ok(anchor) = web_sys::Comment::new() {
+        log_or_panic_result!(
+            fragment.append_child(&anchor),
+            "Failed to append fragment anchor"
+        );
+    } else {
+        log_or_panic!("Failed to create fragment anchor");
+    }
+}
@@ -14,30 +14,108 @@
 //! # ;
 //! ```
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 #[cfg(debug_assertions)]
 use std::collections::HashSet;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::ops::RangeBounds;
+use std::rc::Weak;
 
 use smallvec::SmallVec;
 use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::{JsCast, intern};
 
-use super::attributes::AttributeResult;
+use super::attributes::{AttributeCheck, AttributeResult, IntegerRange};
 use super::classes::ClassResult;
 use crate::css::selectors::{CompoundSelector, IntoCompoundSelector, SimpleSelector};
 use crate::dom::element::{Element, MaybeStaticElement, generate_fallback_node};
-use crate::dom::events::{Event, EventHandler};
+use crate::dom::events::{Event, EventHandler, EventListener, FromEvent};
+pub use crate::dom::fragment::Fragment;
+pub use crate::dom::svg_elements as svg;
+pub use crate::dom::svg_elements::{TagSvg, svg};
 use crate::dom::{ToAttribute, ToClass, attributes};
 use crate::error_handling::{log_or_panic, log_or_panic_result};
 use crate::prelude::Id;
 use crate::reactivity::State;
-use crate::reactivity::render_callbacks::RenderingState;
-use crate::reactivity::state::InnerCtx;
+use crate::reactivity::render_callbacks::{
+    ReactiveKeepAlive,
+    ReactiveKeyedList,
+    ReactiveMap,
+    ReactiveStyleMap,
+    RenderingState,
+    SimpleReactive,
+    SimpleReactiveResult,
+};
+use crate::reactivity::state::{InnerCtx, RenderCtx};
 use crate::{EventCtx, get_document};
 
 /// A deferred function to do something once state is available
 pub(crate) type DeferredFunc<C> = Box<dyn FnOnce(&mut InnerCtx<C>, &mut RenderingState)>;
 
+/// Create the callback for a dom event listener, which converts the event and calls the handler
+/// with the state.
+pub(crate) fn dispatch_event<C: State, E: Event>(
+    ctx_weak: Weak<RefCell<InnerCtx<C>>>,
+    function: impl Fn(EventCtx<C>, E::JsEvent) + 'static,
+) -> impl Fn(web_sys::Event) + 'static {
+    move |event| {
+        crate::panics::return_if_panic!();
+
+        let Some(event) = E::JsEvent::from_event(event) else {
+            log_or_panic!("Unexpected event type");
+            return;
+        };
+
+        let Some(ctx) = ctx_weak.upgrade() else {
+            log_or_panic!("State dropped without event handlers being cleaned up");
+            return;
+        };
+        let Ok(mut ctx) = ctx.try_borrow_mut() else {
+            log_or_panic!("State already mutably borrowed in event handler");
+            return;
+        };
+
+        ctx.track_changes(|ctx| {
+            function(EventCtx(ctx), event);
+        });
+    }
+}
+
+/// Convert a child to a node, deferring the rendering of dynamic children until state is
+/// available.
+///
+/// Returns `None` if the placeholder for a dynamic child could not be created.
+pub(crate) fn child_node<C: State>(
+    child: impl Element<C>,
+    deferred: &mut SmallVec<[DeferredFunc<C>; 2]>,
+) -> Option<web_sys::Node> {
+    let node = match child.render() {
+        MaybeStaticElement::Static(result) => result.into_node(),
+        MaybeStaticElement::Html(html) => {
+            deferred.extend(html.deferred);
+            html.element.into()
+        }
+        MaybeStaticElement::Dynamic(dynamic) => {
+            let Ok(comment) = web_sys::Comment::new() else {
+                log_or_panic!("Failed to create placeholder comment node");
+                return None;
+            };
+            let comment_clone = comment.clone();
+            deferred.push(Box::new(move |ctx, rendering_state| {
+                let node = dynamic.render(ctx, rendering_state).into_node();
+                log_or_panic_result!(
+                    comment_clone.replace_with_with_node_1(&node),
+                    "Failed to swap in child"
+                );
+            }));
+            comment.into()
+        }
+    };
+    Some(node)
+}
+
 /// Indicates the given element is allowed to children
 /// This will catch errors such as:
 /// ```compile_fail
@@ -80,6 +158,28 @@ impl<C: State, T> HtmlElement<C, T> {
             generate_fallback_node().unchecked_into()
         };
 
+        Self::from_node(node)
+    }
+
+    /// Create a new element with the specific tag in the given namespace,
+    /// such as [`svg::SVG_NAMESPACE`].
+    ///
+    /// All svg elements have a helper function in the [`svg`](mod@svg) module.
+    pub fn new_ns(namespace: &'static str, tag: &'static str) -> Self {
+        let node = if let Ok(node) =
+            get_document().create_element_ns(Some(intern(namespace)), intern(tag))
+        {
+            node
+        } else {
+            log_or_panic!("Failed to create <{tag}> in {namespace}");
+            generate_fallback_node().unchecked_into()
+        };
+
+        Self::from_node(node)
+    }
+
+    /// Wrap a newly created dom element
+    fn from_node(node: web_sys::Element) -> Self {
         Self {
             element: node,
             deferred: SmallVec::new(),
@@ -122,42 +222,60 @@ impl<C: State, T> HtmlElement<C, T> {
     /// })
     /// # }
     /// ```
+    ///
+    /// The handler receives the event, which can be used to call `.stop_propagation()` to stop
+    /// the handlers of parent elements from running, or `.prevent_default()` to stop the
+    /// browser's default action.
     /// For more information see [Reactivity](https://vivax3794.github.io/natrix/reactivity.html) in the book.
     #[inline]
-    pub fn on<E: Event>(mut self, function: impl EventHandler<C, E>) -> Self {
+    pub fn on<E: Event>(self, function: impl EventHandler<C, E>) -> Self {
+        self.add_listener::<E>(function, false)
+    }
+
+    /// Register a event handler for this element in the capture phase.
+    ///
+    /// Capture handlers run before the handlers of the element the event was dispatched to, and
+    /// before any bubbling handlers, going from the outermost element inwards.
+    /// This allows a parent to see, and stop, a event before its children.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     locked: Signal<bool>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::div()
+    ///     .on_capture::<events::Click>(|ctx: EventCtx<App>, event: web_sys::PointerEvent| {
+    ///         if *ctx.locked {
+    ///             event.stop_propagation();
+    ///         }
+    ///     })
+    ///     .child(e::button().text("Only works when unlocked"))
+    /// # }
+    /// ```
+    #[inline]
+    pub fn on_capture<E: Event>(self, function: impl EventHandler<C, E>) -> Self {
+        self.add_listener::<E>(function, true)
+    }
+
+    /// Attach a event listener to this element, in the capture phase if `capture` is set.
+    fn add_listener<E: Event>(mut self, function: impl EventHandler<C, E>, capture: bool) -> Self {
         let function = function.func();
         let element: &web_sys::Element = self.element.as_ref();
         let element = element.clone();
 
         self.deferred.push(Box::new(move |ctx, rendering_state| {
-            let ctx_weak = ctx.this.clone();
-
-            let callback: Box<dyn Fn(web_sys::Event) + 'static> = Box::new(move |event| {
-                crate::panics::return_if_panic!();
-
-                let Ok(event) = event.dyn_into() else {
-                    log_or_panic!("Unexpected event type");
-                    return;
-                };
-
-                let Some(ctx) = ctx_weak.upgrade() else {
-                    log_or_panic!("State dropped without event handlers being cleaned up");
-                    return;
-                };
-                let Ok(mut ctx) = ctx.try_borrow_mut() else {
-                    log_or_panic!("State already mutably borrowed in event handler");
-                    return;
-                };
-
-                ctx.track_changes(|ctx| {
-                    function(EventCtx(ctx), event);
-                });
-            });
+            let callback: Box<dyn Fn(web_sys::Event) + 'static> =
+                Box::new(dispatch_event::<C, E>(ctx.this.clone(), function));
             let closure = Closure::wrap(callback);
             let function = closure.as_ref().unchecked_ref();
 
             log_or_panic_result!(
-                element.add_event_listener_with_callback(intern(E::EVENT_NAME), function),
+                element.add_event_listener_with_callback_and_bool(
+                    intern(E::EVENT_NAME),
+                    function,
+                    capture
+                ),
                 "Failed to attach event handler"
             );
 
@@ -166,6 +284,41 @@ impl<C: State, T> HtmlElement<C, T> {
         self
     }
 
+    /// Register a event handler on the window (or document for events such as
+    /// [`VisibilityChange`](super::events::VisibilityChange) that are fired at it), for events that are
+    /// not about a specific element such as resizing or global keyboard shortcuts.
+    ///
+    /// The listener is removed when this element is removed.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     resizes: Signal<u32>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::div().on_window::<events::Resize>(|mut ctx: EventCtx<App>, _| {
+    ///     *ctx.resizes += 1;
+    /// })
+    /// # }
+    /// ```
+    #[inline]
+    pub fn on_window<E: Event>(mut self, function: impl EventHandler<C, E>) -> Self {
+        let function = function.func();
+
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let target: web_sys::EventTarget = if E::ON_DOCUMENT {
+                get_document().into()
+            } else {
+                crate::get_window().into()
+            };
+            let callback = dispatch_event::<C, E>(ctx.this.clone(), function);
+            if let Some(listener) = EventListener::new(target, intern(E::EVENT_NAME), callback) {
+                rendering_state.keep_alive.push(Box::new(listener));
+            }
+        }));
+        self
+    }
+
     /// Push a child to this element.
     /// This accepts any valid element including closures.
     /// ```rust
@@ -191,27 +344,8 @@ impl<C: State, T> HtmlElement<C, T> {
     where
         T: CanHaveChild,
     {
-        let node = match child.render() {
-            MaybeStaticElement::Static(result) => result.into_node(),
-            MaybeStaticElement::Html(html) => {
-                self.deferred.extend(html.deferred);
-                html.element.into()
-            }
-            MaybeStaticElement::Dynamic(dynamic) => {
-                let Ok(comment) = web_sys::Comment::new() else {
-                    log_or_panic!("Failed to create placeholder comment node");
-                    return self;
-                };
-                let comment_clone = comment.clone();
-                self.deferred.push(Box::new(move |ctx, rendering_state| {
-                    let node = dynamic.render(ctx, rendering_state).into_node();
-                    log_or_panic_result!(
-                        comment_clone.replace_with_with_node_1(&node),
-                        "Failed to swap in child"
-                    );
-                }));
-                comment.into()
-            }
+        let Some(node) = child_node(child, &mut self.deferred) else {
+            return self;
         };
 
         let element: &web_sys::Element = self.element.as_ref();
@@ -231,7 +365,50 @@ impl<C: State, T> HtmlElement<C, T> {
 
     /// Set a attribute on the node.
     #[inline]
-    pub fn attr(mut self, key: &'static str, value: impl ToAttribute<C>) -> Self {
+    pub fn attr(self, key: &'static str, value: impl ToAttribute<C>) -> Self {
+        let result = value.calc_attribute(intern(key), &self.element);
+        self.apply_attribute(key, result)
+    }
+
+    /// Set a integer attribute on the node, clamping it into the valid range for the attribute.
+    #[inline]
+    pub(crate) fn attr_in_range(
+        self,
+        key: &'static str,
+        value: impl ToAttribute<C, AttributeKind = attributes::Integer>,
+        range: impl RangeBounds<i64>,
+    ) -> Self {
+        self.attr_checked(key, value, AttributeCheck::Range(IntegerRange::new(range)))
+    }
+
+    /// Set a attribute on the node, applying the given check to its values.
+    #[inline]
+    pub(crate) fn attr_checked(
+        self,
+        key: &'static str,
+        value: impl ToAttribute<C>,
+        check: AttributeCheck,
+    ) -> Self {
+        let result = value.calc_attribute_checked(intern(key), &self.element, check);
+        self.apply_attribute(key, result)
+    }
+
+    /// Set a url attribute on the node, warning about malformed urls in debug builds.
+    #[inline]
+    pub(crate) fn attr_url(
+        self,
+        key: &'static str,
+        value: impl ToAttribute<C, AttributeKind = String>,
+    ) -> Self {
+        if cfg!(debug_assertions) {
+            self.attr_checked(key, value, AttributeCheck::Url)
+        } else {
+            self.attr(key, value)
+        }
+    }
+
+    /// Set the attribute to the result of `calc_attribute`
+    fn apply_attribute(mut self, key: &'static str, result: AttributeResult<C>) -> Self {
         #[cfg(debug_assertions)]
         {
             if self.seen_attributes.contains(key) {
@@ -243,7 +420,7 @@ impl<C: State, T> HtmlElement<C, T> {
             self.seen_attributes.insert(key);
         }
 
-        match value.calc_attribute(intern(key), &self.element) {
+        match result {
             AttributeResult::SetIt(res) => {
                 if let Some(res) = res {
                     log_or_panic_result!(
@@ -275,10 +452,10 @@ impl<C: State, T> HtmlElement<C, T> {
     #[inline]
     pub fn class(mut self, class: impl ToClass<C> + 'static) -> Self {
         match class.calc_class(&self.element) {
-            ClassResult::SetIt(res) => {
-                if let Some(res) = res {
+            ClassResult::SetIt(classes) => {
+                for class in classes {
                     log_or_panic_result!(
-                        self.element.class_list().add_1(intern(&res)),
+                        self.element.class_list().add_1(intern(&class)),
                         "Failed to add class"
                     );
                 }
@@ -291,6 +468,48 @@ impl<C: State, T> HtmlElement<C, T> {
         self
     }
 
+    /// Reactively set a collection of inline style properties.
+    ///
+    /// Only properties that changed since the last run are updated in the dom,
+    /// and properties no longer returned are removed.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct MyState {
+    /// #     highlighted: Signal<bool>,
+    /// # }
+    /// # fn render() -> impl Element<MyState> {
+    /// e::div().style_map(|ctx: RenderCtx<MyState>| {
+    ///     let mut styles = vec![("padding", String::from("4px"))];
+    ///     if *ctx.highlighted {
+    ///         styles.push(("background-color", String::from("yellow")));
+    ///     }
+    ///     styles
+    /// })
+    /// # }
+    /// ```
+    #[inline]
+    pub fn style_map<F, I>(mut self, func: F) -> Self
+    where
+        F: Fn(RenderCtx<C>) -> I + 'static,
+        I: IntoIterator<Item = (&'static str, String)>,
+    {
+        let node = self.element.clone();
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = SimpleReactive::init_new(
+                Box::new(move |ctx, _node| {
+                    SimpleReactiveResult::Apply(ReactiveStyleMap {
+                        data: func(ctx).into_iter().collect(),
+                    })
+                }),
+                node,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+        self
+    }
+
     /// Add multiple classes
     #[inline]
     pub fn classes<Cls: ToClass<C> + 'static>(
@@ -314,6 +533,202 @@ impl<C: State, T> HtmlElement<C, T> {
         }
         self
     }
+
+    /// Render a child for each entry in a map, keyed on the map keys.
+    ///
+    /// When the map changes only the entries that were added, removed, or whose value changed
+    /// are re-rendered, the other nodes are left untouched.
+    /// Entries are rendered in the order of the map keys.
+    ///
+    /// ```rust
+    /// # use std::collections::BTreeMap;
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     users: Signal<BTreeMap<u32, String>>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     e::ul().children_map(
+    ///         |ctx: &App| &*ctx.users,
+    ///         |id, name| e::li().text(format!("{id}: {name}")),
+    ///     )
+    /// }
+    /// ```
+    pub fn children_map<K, V, E>(
+        mut self,
+        map: impl Fn(&C) -> &BTreeMap<K, V> + 'static,
+        render: impl Fn(&K, &V) -> E + 'static,
+    ) -> Self
+    where
+        T: CanHaveChild,
+        K: Ord + Clone + 'static,
+        V: PartialEq + Clone + 'static,
+        E: Element<C>,
+    {
+        let Ok(anchor) = web_sys::Comment::new() else {
+            log_or_panic!("Failed to create map anchor comment node");
+            return self;
+        };
+        let anchor: web_sys::Node = anchor.into();
+        log_or_panic_result!(
+            self.element.append_child(&anchor),
+            "Failed to append map anchor"
+        );
+
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = ReactiveMap::init_new(
+                Box::new(map),
+                Box::new(move |key, value| render(key, value).render()),
+                anchor,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+        self
+    }
+
+    /// Render a child for each item in a list, keyed on the key returned by `key`.
+    ///
+    /// When the list changes existing nodes are matched up with the new items by key, so
+    /// reordering the list moves the nodes instead of recreating them, keeping dom state such as
+    /// focus and input values.
+    /// Only items that were added, or whose value changed, are re-rendered.
+    ///
+    /// Keys must be unique within the list, only the first item with a given key is rendered.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(Clone, PartialEq)]
+    /// struct Todo {
+    ///     id: u32,
+    ///     text: String,
+    /// }
+    ///
+    /// #[derive(State)]
+    /// struct App {
+    ///     todos: Signal<Vec<Todo>>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     e::ul().children_keyed(
+    ///         |ctx: &App| &ctx.todos,
+    ///         |todo| todo.id,
+    ///         |todo| e::li().text(todo.text.clone()),
+    ///     )
+    /// }
+    /// ```
+    pub fn children_keyed<I, K, E>(
+        mut self,
+        list: impl Fn(&C) -> &[I] + 'static,
+        key: impl Fn(&I) -> K + 'static,
+        render: impl Fn(&I) -> E + 'static,
+    ) -> Self
+    where
+        T: CanHaveChild,
+        I: PartialEq + Clone + 'static,
+        K: Hash + Eq + Clone + 'static,
+        E: Element<C>,
+    {
+        let Ok(anchor) = web_sys::Comment::new() else {
+            log_or_panic!("Failed to create keyed list anchor comment node");
+            return self;
+        };
+        let anchor: web_sys::Node = anchor.into();
+        log_or_panic_result!(
+            self.element.append_child(&anchor),
+            "Failed to append keyed list anchor"
+        );
+
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = ReactiveKeyedList::init_new(
+                Box::new(list),
+                Box::new(key),
+                Box::new(move |item| render(item).render()),
+                anchor,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+        self
+    }
+
+    /// Render the child selected by `key`, keeping previously shown children alive.
+    ///
+    /// When the key changes the current child is detached from the dom instead of destroyed,
+    /// and if the new key was shown before its child is re-attached as is.
+    /// This preserves dom state such as input values, scroll positions, and focus, as well as
+    /// avoiding re-rendering, which makes it useful for things like tabs.
+    ///
+    /// At most `capacity` detached children are kept, when more are cached the least recently
+    /// shown one is dropped.
+    /// Detached children keep their reactive hooks, so they keep updating in the background and
+    /// use memory for as long as they are cached. A `capacity` of `0` drops children as soon as
+    /// they are hidden, which is the same as a normal reactive closure.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(Clone, Copy, PartialEq)]
+    /// enum Tab {
+    ///     Profile,
+    ///     Settings,
+    /// }
+    ///
+    /// #[derive(State)]
+    /// struct App {
+    ///     tab: Signal<Tab>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     e::div().keep_alive(
+    ///         |ctx: &App| *ctx.tab,
+    ///         |tab| match tab {
+    ///             Tab::Profile => e::input().attr("placeholder", "Name"),
+    ///             Tab::Settings => e::input().attr("placeholder", "Email"),
+    ///         },
+    ///         4,
+    ///     )
+    /// }
+    /// ```
+    pub fn keep_alive<K, E>(
+        mut self,
+        key: impl Fn(&C) -> K + 'static,
+        render: impl Fn(&K) -> E + 'static,
+        capacity: usize,
+    ) -> Self
+    where
+        T: CanHaveChild,
+        K: PartialEq + 'static,
+        E: Element<C>,
+    {
+        let (Ok(start), Ok(end)) = (web_sys::Comment::new(), web_sys::Comment::new()) else {
+            log_or_panic!("Failed to create keep alive anchor comment nodes");
+            return self;
+        };
+        let start: web_sys::Node = start.into();
+        let end: web_sys::Node = end.into();
+        log_or_panic_result!(
+            self.element.append_child(&start),
+            "Failed to append keep alive anchor"
+        );
+        log_or_panic_result!(
+            self.element.append_child(&end),
+            "Failed to append keep alive anchor"
+        );
+
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let hook = ReactiveKeepAlive::init_new(
+                Box::new(key),
+                Box::new(move |key| render(key).render()),
+                start,
+                end,
+                capacity,
+                ctx,
+            );
+            rendering_state.hooks.push(hook);
+        }));
+        self
+    }
 }
 
 impl<C: State, T: 'static> Element<C> for HtmlElement<C, T> {
@@ -323,6 +738,12 @@ impl<C: State, T: 'static> Element<C> for HtmlElement<C, T> {
     }
 }
 
+/// A list of sibling elements rendered without a wrapper element, see [`Fragment`]
+#[inline]
+pub fn fragment<C: State>() -> Fragment<C> {
+    Fragment::new()
+}
+
 /// Implement a factory function that returns a `HtmlElement` with a tag name equal to the
 /// function.
 macro_rules! elements {
@@ -348,6 +769,39 @@ macro_rules! elements {
     };
 }
 
+/// Implement factory functions for `<input>` elements with the `type` already set, along with
+/// `attr` helpers for the attributes that apply to that type.
+///
+/// Attributes marked with `checked` also keep the `checked` property in sync.
+macro_rules! input_types {
+    ($($name:ident => $type:literal {
+        $($attr:ident($kind:path, $attr_name:literal $(, $($check:tt)+)?)),* $(,)?
+    })*) => {
+        pastey::paste! {
+            $(
+                #[doc = "`<input type=\"" $type "\">`, <https://developer.mozilla.org/docs/Web/HTML/Reference/Elements/input/" $type ">"]
+                pub struct [< Tag $name:camel >];
+
+                #[doc = "`<input type=\"" $type "\">`, <https://developer.mozilla.org/docs/Web/HTML/Reference/Elements/input/" $type ">"]
+                #[inline]
+                pub fn $name<C: State>() -> HtmlElement<C, [< Tag $name:camel >]> {
+                    HtmlElement::new("input").attr("type", $type)
+                }
+
+                impl<C: State> HtmlElement<C, [< Tag $name:camel >]> {
+                    $(
+                        #[doc = "<https://developer.mozilla.org/docs/Web/HTML/Reference/Elements/input#" $attr_name ">"]
+                        #[inline]
+                        pub fn $attr(self, value: impl ToAttribute<C, AttributeKind = $kind>) -> Self {
+                            attr_helper_set!(self, $attr_name, value $(, $($check)+)?)
+                        }
+                    )*
+                }
+            )*
+        }
+    };
+}
+
 /// Mark that the list of elements can have children
 macro_rules! can_have_children {
     ($($tag:ident),*) => {
@@ -360,22 +814,42 @@ macro_rules! can_have_children {
 }
 
 /// A macro to define `attr` helpers for the the various elements
+///
+/// Integer attributes can specify the range the spec allows, out of range values are clamped.
+/// Url attributes can be marked with `url` to warn about malformed urls in debug builds.
 macro_rules! attr_helpers {
-    ($tag:ident => $($attr:ident($kind:path, $attr_name:literal)),+) => {
+    ($tag:ident => $($attr:ident($kind:path, $attr_name:literal $(, $($check:tt)+)?)),+) => {
         pastey::paste! {
             impl<C: State> HtmlElement<C, [< Tag $tag:camel >]> {
                 $(
                     #[doc = "<https://developer.mozilla.org/docs/Web/HTML/Reference/Elements/" $tag "##" $attr_name ">"]
                     #[inline]
                     pub fn $attr(self, value: impl ToAttribute<C, AttributeKind = $kind>) -> Self {
-                        self.attr($attr_name, value)
-                       }
+                        attr_helper_set!(self, $attr_name, value $(, $($check)+)?)
+                    }
                 )+
             }
         }
     };
 }
 
+/// Set the attribute for `attr_helpers`, checking urls if `url` is given, and clamping it if a
+/// range is given
+macro_rules! attr_helper_set {
+    ($this:ident, $attr_name:literal, $value:ident) => {
+        $this.attr($attr_name, $value)
+    };
+    ($this:ident, $attr_name:literal, $value:ident,url) => {
+        $this.attr_url($attr_name, $value)
+    };
+    ($this:ident, $attr_name:literal, $value:ident,checked) => {
+        $this.attr_checked($attr_name, $value, AttributeCheck::Checked)
+    };
+    ($this:ident, $attr_name:literal, $value:ident, $range:expr) => {
+        $this.attr_in_range($attr_name, $value, $range)
+    };
+}
+
 /// Generate a `attr` helpers implementation for the global attributes
 macro_rules! global_attrs {
     ($($attr:ident($kind:path, $attr_value:literal)),*) => {
@@ -410,6 +884,25 @@ macro_rules! aria_attrs {
     };
 }
 
+/// Generate a `attr` helpers implementation for the aria attributes that are `true` or `false`.
+macro_rules! aria_bool_attrs {
+    ($($attr:ident),*) => {
+        impl<C: State, T> HtmlElement<C, T> {
+            pastey::paste! {
+                $(
+                    #[doc = "<https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Attributes/aria%2d" $attr ">"]
+                    #[doc = ""]
+                    #[doc = "Unlike html boolean attributes `false` is set as `\"false\"`, use `None` to remove the attribute."]
+                    #[inline]
+                    pub fn [<aria_$attr>](self, value: impl ToAttribute<C, AttributeKind = bool>) -> Self {
+                        self.attr_checked(concat!("aria-", stringify!($attr)), value, AttributeCheck::AriaBool)
+                    }
+                )*
+            }
+        }
+    };
+}
+
 // NOTE:
 // "sane defaults" should not be the defaults for element constructors.
 // and should instead be implemented via extra methods, such as `.secure`
@@ -422,7 +915,7 @@ elements! {
     a, abbr, b, bdi, bdo, br, cite, code, data, dfn, em, i, kbd, mark, q, rp, rt, ruby, s, samp, small, span, strong, sub, sup, time, u, var, wbr,
     area, audio, img, map, track, video,
     embed, fencedframe, iframe, object, picture, source,
-    svg, math,
+    math,
     canvas, script,
     del, ins,
     caption, col, colgroup, table, tbody, td, tfoot, th, thead, tr,
@@ -439,7 +932,7 @@ can_have_children! {
     a, abbr, b, bdi, bdo, cite, code, data, dfn, em, i, kbd, mark, q, rp, rt, ruby, s, samp, small, span, strong, sub, sup, time, u, var,
     audio, map, video,
     fencedframe, iframe, object, picture,
-    svg, math,
+    math,
     canvas, script,
     del, ins,
     caption, colgroup, table, tbody, td, tfoot, th, thead, tr,
@@ -451,31 +944,49 @@ can_have_children! {
 global_attrs! {
     access_key(char, "accesskey"), auto_focus(bool, "autofocus"), content_editable(attributes::ContentEditable, "contenteditable"),
     dir(attributes::Direction, "dir"), draggable(attributes::TrueFalse, "draggable"), enter_key_hint(attributes::EnterkeyHint, "enterkeyhint"),
-    hidden(bool, "hidden"), id(Id, "id"), inert(bool, "inert"), input_mode(attributes::InputMode, "inputmode"), lang(String, "lang"),
+    id(Id, "id"), input_mode(attributes::InputMode, "inputmode"),
     popover(attributes::PopOver, "popover"), spellcheck(bool, "spellcheck"), tab_index(attributes::Integer, "tabindex"),
     title(String, "title"), translate(attributes::YesNo, "translate"), auto_capitalize(attributes::AutoCapitalize, "autocapitalize")
 }
 
+impl<C: State, T> HtmlElement<C, T> {
+    /// <https://developer.mozilla.org/docs/Web/HTML/Reference/Global_attributes/hidden>
+    ///
+    /// Hidden elements are not rendered, and are removed from the accessibility tree.
+    #[inline]
+    pub fn hidden(self, value: impl ToAttribute<C, AttributeKind = bool>) -> Self {
+        self.attr("hidden", value)
+    }
+
+    /// <https://developer.mozilla.org/docs/Web/HTML/Reference/Global_attributes/inert>
+    ///
+    /// Inert elements, and their children, can not be focused or interacted with, and are removed
+    /// from the accessibility tree. This is useful for content behind a modal, or offscreen content.
+    ///
+    /// The browser removes the subtree from the tab order, but if it contains the focused element
+    /// focus is left stranded. In debug builds a warning is logged when that happens, move focus
+    /// somewhere sensible before making the subtree inert.
+    #[inline]
+    pub fn inert(self, value: impl ToAttribute<C, AttributeKind = bool>) -> Self {
+        if cfg!(debug_assertions) {
+            self.attr_checked("inert", value, AttributeCheck::Inert)
+        } else {
+            self.attr("inert", value)
+        }
+    }
+}
+
 aria_attrs! {
     autocomplete,
     checked,
-    disabled,
     errormessage,
-    expanded,
     haspopup,
-    hidden,
     invalid,
     label,
     level,
-    modal,
-    multiline,
-    multiselectable,
     orientation,
     placeholder,
     pressed,
-    readonly,
-    required,
-    selected,
     sort,
     valuemax,
     valuemin,
@@ -483,8 +994,48 @@ aria_attrs! {
     valuetext
 }
 
+aria_bool_attrs! {
+    atomic,
+    busy,
+    disabled,
+    expanded,
+    hidden,
+    modal,
+    multiline,
+    multiselectable,
+    readonly,
+    required,
+    selected
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Set a `aria-*` attribute that does not have its own helper, such as `.aria("controls", id)`
+    /// for `aria-controls`.
+    ///
+    /// <https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Attributes>
+    #[inline]
+    pub fn aria(self, name: &'static str, value: impl ToAttribute<C>) -> Self {
+        self.attr(attributes::aria_attribute_name(name), value)
+    }
+
+    /// <https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Attributes/aria-live>
+    #[inline]
+    pub fn aria_live(
+        self,
+        value: impl ToAttribute<C, AttributeKind = attributes::AriaLive>,
+    ) -> Self {
+        self.attr("aria-live", value)
+    }
+
+    /// <https://developer.mozilla.org/docs/Web/Accessibility/ARIA/Reference/Roles>
+    #[inline]
+    pub fn role(self, value: impl ToAttribute<C, AttributeKind = attributes::Role>) -> Self {
+        self.attr("role", value)
+    }
+}
+
 attr_helpers!(a =>
-    download(bool, "download"), href(String, "href"), href_lang(String, "hreflang"),
+    download(bool, "download"), href(String, "href", url), href_lang(String, "hreflang"),
     ping(String, "ping"), referrer_policy(attributes::ReferrerPolicy, "referrerpolicy"), rel(attributes::Rel, "rel"),
     target(attributes::Target, "target")
 );
@@ -506,45 +1057,45 @@ impl<C: State> HtmlElement<C, TagA> {
 
 attr_helpers!(area =>
     alt(String, "alt"), coords(String, "coords"), download(bool, "download"),
-    href(String, "href"), ping(String, "ping"), referrer_policy(attributes::ReferrerPolicy, "referrerpolicy"),
+    href(String, "href", url), ping(String, "ping"), referrer_policy(attributes::ReferrerPolicy, "referrerpolicy"),
     rel(attributes::Rel, "rel"), shape(attributes::Shape, "shape"), target(attributes::Target, "target")
 );
 attr_helpers!(audio =>
     auto_play(bool, "autoplay"), controls(bool, "controls"), controls_list(attributes::ControlsList, "controlslist"),
     cross_origin(attributes::CrossOrigin, "crossorigin"), disable_remote_playback(bool, "disableremoteplayback"),
-    loop_audio(bool, "loop"), muted(bool, "muted"), preload(attributes::ContentPreload, "preload"), src(String, "src")
+    loop_audio(bool, "loop"), muted(bool, "muted"), preload(attributes::ContentPreload, "preload"), src(String, "src", url)
 );
-attr_helpers!(blockquote => cite(String, "cite"));
+attr_helpers!(blockquote => cite(String, "cite", url));
 attr_helpers!(button =>
     command(attributes::Command, "command"), command_for(Id, "commandfor"),
-    disabled(bool, "disabled"), form(Id, "form"), form_action(String, "formaction"),
+    disabled(bool, "disabled"), form(Id, "form"), form_action(String, "formaction", url),
     form_encoding_type(attributes::EncodingType, "formenvtype"), form_method(attributes::FormMethod, "formmethod"),
     form_no_validate(bool, "formnovalidate"), form_target(attributes::Target, "formtarget"),
     name(String, "name"), popover_target(Id, "popovertarget"),
     popover_target_action(attributes::PopoverAction, "popovertargetaction"), button_type(attributes::ButtonType, "type"), value(String, "value")
 );
-attr_helpers!(canvas => height(attributes::Integer, "height"), width(attributes::Integer, "width"));
-attr_helpers!(col => span(attributes::Integer, "span"));
-attr_helpers!(colgroup => span(attributes::Integer, "span"));
+attr_helpers!(canvas => height(attributes::Integer, "height", 0..), width(attributes::Integer, "width", 0..));
+attr_helpers!(col => span(attributes::Integer, "span", 1..=1000));
+attr_helpers!(colgroup => span(attributes::Integer, "span", 1..=1000));
 attr_helpers!(data => value(String, "data"));
-attr_helpers!(del => cite(String, "cite"));
+attr_helpers!(del => cite(String, "cite", url));
 attr_helpers!(details => open(bool, "open"), name(String, "name"));
 attr_helpers!(dialog => open(bool, "open"));
 attr_helpers!(embed =>
-    height(attributes::Integer, "height"), width(attributes::Integer, "width"),
-    src(String, "src"), mime_type(String, "type")
+    height(attributes::Integer, "height", 0..), width(attributes::Integer, "width", 0..),
+    src(String, "src", url), mime_type(String, "type")
 );
 attr_helpers!(fieldset => disabled(bool, "disabled"), form(Id, "form"), name(String, "name"));
 attr_helpers!(form =>
     auto_complete(attributes::OnOff, "autocomplete"), name(String, "name"), rel(attributes::Rel, "rel"),
-    action(String, "action"), encoding_type(attributes::EncodingType, "enctype"), method(attributes::FormMethod, "method"),
+    action(String, "action", url), encoding_type(attributes::EncodingType, "enctype"), method(attributes::FormMethod, "method"),
     no_validate(bool, "novalidate"), target(attributes::Target, "target")
 );
 
 attr_helpers!(iframe =>
-    height(attributes::Integer, "height"), loading(attributes::Loading, "loading"),
+    height(attributes::Integer, "height", 0..), loading(attributes::Loading, "loading"),
     name(String, "name"), referrer_policy(attributes::ReferrerPolicy, "referrerpolicy"),
-    sandbox(attributes::SandboxAllow, "sandbox"), src(String, "src"), srcdoc(String, "srcdoc"), width(attributes::Integer, "width")
+    sandbox(attributes::SandboxAllow, "sandbox"), src(String, "src", url), srcdoc(String, "srcdoc"), width(attributes::Integer, "width", 0..)
 );
 
 impl<C: State> HtmlElement<C, TagIframe> {
@@ -560,12 +1111,12 @@ impl<C: State> HtmlElement<C, TagIframe> {
 
 attr_helpers!(img =>
     alt(String, "alt"), cross_origin(attributes::CrossOrigin, "crossorigin"), decoding(attributes::ImageDecoding, "decoding"),
-    fetch_priority(attributes::FetchPriority, "fetchpriority"), height(attributes::Integer, "height"), is_map(bool, "ismap"),
+    fetch_priority(attributes::FetchPriority, "fetchpriority"), height(attributes::Integer, "height", 0..), is_map(bool, "ismap"),
     loading(attributes::Loading, "loading"), referrer_policy(attributes::ReferrerPolicy, "referrerpolicy"),
-    src(String, "src"), width(attributes::Integer, "width"), use_map(String, "usemap")
+    src(String, "src", url), width(attributes::Integer, "width", 0..), use_map(String, "usemap")
 );
 
-attr_helpers!(ins => cite(String, "cite"));
+attr_helpers!(ins => cite(String, "cite", url));
 attr_helpers!(label => is_for(Id, "for"));
 attr_helpers!(li => value(attributes::Integer, "value"));
 attr_helpers!(map => name(String, "name"));
@@ -579,8 +1130,8 @@ attr_helpers!(meter =>
 );
 
 attr_helpers!(object =>
-    data(String, "data"), form(Id, "form"), height(attributes::Integer, "height"),
-    name(String, "name"), object_type(String, "type"), width(attributes::Integer, "width")
+    data(String, "data", url), form(Id, "form"), height(attributes::Integer, "height", 0..),
+    name(String, "name"), object_type(String, "type"), width(attributes::Integer, "width", 0..)
 );
 
 attr_helpers!(ol =>
@@ -594,41 +1145,128 @@ attr_helpers!(option =>
 );
 attr_helpers!(output => is_for(Vec<Id>, "for"), form(Id, "form"), name(String, "name"));
 attr_helpers!(progress => max(attributes::Float, "max"), values(attributes::Float, "value"));
-attr_helpers!(q => cite(String, "cite"));
+attr_helpers!(q => cite(String, "cite", url));
 attr_helpers!(select =>
     auto_complete(attributes::AutoComplete, "autocomplete"),
     disabled(bool, "disabled"), form(Id, "form"), multiple(bool, "multiple"),
-    name(String, "name"), required(bool, "required"), size(attributes::Integer, "size")
+    name(String, "name"), required(bool, "required"), size(attributes::Integer, "size", 1..)
 );
 
 attr_helpers!(source =>
-    source_type(String, "type"), src(String, "src"),
-    height(attributes::Integer, "height"), width(attributes::Integer, "width")
+    source_type(String, "type"), src(String, "src", url),
+    height(attributes::Integer, "height", 0..), width(attributes::Integer, "width", 0..)
 );
 attr_helpers!(textarea =>
     auto_complete(attributes::AutoComplete, "autocomplete"),
-    auto_correct(attributes::OnOff, "autocorrect"), columns(attributes::Integer, "cols"),
-    direction_name(String, "dirname"), disabled(bool, "disabled"), form(Id, "form"), max_length(attributes::Integer, "maxlength"),
-    min_length(attributes::Integer, "min_length"), name(String, "name"), placeholder(String, "placeholder"),
-    read_only(bool, "readonly"), required(bool, "required"), rows(attributes::Integer, "rows"),
+    auto_correct(attributes::OnOff, "autocorrect"), columns(attributes::Integer, "cols", 1..),
+    direction_name(String, "dirname"), disabled(bool, "disabled"), form(Id, "form"), max_length(attributes::Integer, "maxlength", 0..),
+    min_length(attributes::Integer, "minlength", 0..), name(String, "name"), placeholder(String, "placeholder"),
+    read_only(bool, "readonly"), required(bool, "required"), rows(attributes::Integer, "rows", 1..),
     wrap(attributes::Wrap, "wrap")
 );
 
+attr_helpers!(td =>
+    column_span(attributes::Integer, "colspan", 1..=1000), headers(Vec<Id>, "headers"),
+    row_span(attributes::Integer, "rowspan", 0..=65534)
+);
+
 attr_helpers!(th =>
-    abbreviated(String, "abbr"), column_span(attributes::Integer, "colspan"), headers(Vec<Id>, "headers"),
-    row_span(attributes::Integer, "row_span"), scope(attributes::TableHeadingScope, "scope")
+    abbreviated(String, "abbr"), column_span(attributes::Integer, "colspan", 1..=1000), headers(Vec<Id>, "headers"),
+    row_span(attributes::Integer, "rowspan", 0..=65534), scope(attributes::TableHeadingScope, "scope")
 );
 
 // todo: <time>
 attr_helpers!(track =>
     default(bool, "default"), kind(attributes::TrackKind, "kind"),
-    label(String, "label"), src(String, "src"), src_language(String, "srclang")
+    label(String, "label"), src(String, "src", url), src_language(String, "srclang")
 );
 
 attr_helpers!(video =>
     auto_play(bool, "autoplay"), controls(bool, "controls"), controls_list(attributes::ControlsList, "controlslist"),
     cross_origin(attributes::CrossOrigin, "crossorigin"), disable_picture_in_picture(bool, "disablepictureinpicture"), disable_remote_playback(bool, "disableremoteplayback"),
-    height(attributes::Integer, "height"), loop_video(bool, "loop"), muted(bool, "muted"),
-    plays_inline(bool, "playsinline"), poster(String, "poster"),
-    preload(attributes::ContentPreload, "preload"), src(String, "src"), width(attributes::Integer, "width")
+    height(attributes::Integer, "height", 0..), loop_video(bool, "loop"), muted(bool, "muted"),
+    plays_inline(bool, "playsinline"), poster(String, "poster", url),
+    preload(attributes::ContentPreload, "preload"), src(String, "src", url), width(attributes::Integer, "width", 0..)
 );
+
+input_types! {
+    text_input => "text" {
+        auto_complete(attributes::AutoComplete, "autocomplete"), direction_name(String, "dirname"),
+        disabled(bool, "disabled"), form(Id, "form"), list(Id, "list"),
+        max_length(attributes::Integer, "maxlength", 0..), min_length(attributes::Integer, "minlength", 0..),
+        name(String, "name"), pattern(String, "pattern"), placeholder(String, "placeholder"),
+        read_only(bool, "readonly"), required(bool, "required"), size(attributes::Integer, "size", 1..),
+        value(String, "value"),
+    }
+    checkbox => "checkbox" {
+        checked(bool, "checked", checked), disabled(bool, "disabled"), form(Id, "form"),
+        name(String, "name"), required(bool, "required"), value(String, "value"),
+    }
+    radio => "radio" {
+        checked(bool, "checked", checked), disabled(bool, "disabled"), form(Id, "form"),
+        name(String, "name"), required(bool, "required"), value(String, "value"),
+    }
+    range => "range" {
+        disabled(bool, "disabled"), form(Id, "form"), list(Id, "list"),
+        max(attributes::Float, "max"), min(attributes::Float, "min"), name(String, "name"),
+        step(attributes::Float, "step"), value(attributes::Float, "value"),
+    }
+    color_input => "color" {
+        auto_complete(attributes::AutoComplete, "autocomplete"), disabled(bool, "disabled"),
+        form(Id, "form"), list(Id, "list"), name(String, "name"), value(String, "value"),
+    }
+    number_input => "number" {
+        auto_complete(attributes::AutoComplete, "autocomplete"), disabled(bool, "disabled"),
+        form(Id, "form"), list(Id, "list"), max(attributes::Float, "max"), min(attributes::Float, "min"),
+        name(String, "name"), placeholder(String, "placeholder"), read_only(bool, "readonly"),
+        required(bool, "required"), step(attributes::Float, "step"), value(attributes::Float, "value"),
+    }
+    password_input => "password" {
+        auto_complete(attributes::AutoComplete, "autocomplete"), disabled(bool, "disabled"), form(Id, "form"),
+        max_length(attributes::Integer, "maxlength", 0..), min_length(attributes::Integer, "minlength", 0..),
+        name(String, "name"), pattern(String, "pattern"), placeholder(String, "placeholder"),
+        read_only(bool, "readonly"), required(bool, "required"), size(attributes::Integer, "size", 1..),
+        value(String, "value"),
+    }
+    email_input => "email" {
+        auto_complete(attributes::AutoComplete, "autocomplete"), disabled(bool, "disabled"),
+        form(Id, "form"), list(Id, "list"),
+        max_length(attributes::Integer, "maxlength", 0..), min_length(attributes::Integer, "minlength", 0..),
+        multiple(bool, "multiple"), name(String, "name"), pattern(String, "pattern"),
+        placeholder(String, "placeholder"), read_only(bool, "readonly"), required(bool, "required"),
+        size(attributes::Integer, "size", 1..), value(String, "value"),
+    }
+    file_input => "file" {
+        accept(String, "accept"), disabled(bool, "disabled"), form(Id, "form"),
+        multiple(bool, "multiple"), name(String, "name"), required(bool, "required"),
+    }
+}
+
+/// Attribute helpers only exist on the elements they apply to:
+/// ```compile_fail
+/// # use natrix::prelude::*;
+/// # let _: e::HtmlElement<(), _> =
+/// e::checkbox().max_length(10)
+/// # ;
+/// ```
+/// ```compile_fail
+/// # use natrix::prelude::*;
+/// # let _: e::HtmlElement<(), _> =
+/// e::text_input().step(0.5)
+/// # ;
+/// ```
+/// ```compile_fail
+/// # use natrix::prelude::*;
+/// # let _: e::HtmlElement<(), _> =
+/// e::div().accept("image/*")
+/// # ;
+/// ```
+/// And take the kind of value the attribute expects:
+/// ```compile_fail
+/// # use natrix::prelude::*;
+/// # let _: e::HtmlElement<(), _> =
+/// e::textarea().rows("5")
+/// # ;
+/// ```
+#[expect(dead_code, reason = "For compile fail tests only")]
+fn compile_fail() {}
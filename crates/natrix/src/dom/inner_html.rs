@@ -0,0 +1,96 @@
+//! Setting the inner html of a element from a trusted string.
+
+use std::borrow::Cow;
+
+use super::html_elements::{CanHaveChild, HtmlElement};
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{
+    ReactiveInnerHtml,
+    SimpleReactive,
+    SimpleReactiveResult,
+};
+use crate::reactivity::state::RenderCtx;
+use crate::type_macros;
+
+/// The html to set, either right away or from a reactive closure
+pub(crate) enum InnerHtmlResult<C: State> {
+    /// Set the html right away
+    SetIt(Cow<'static, str>),
+    /// Set the html from the closure, rerunning it when its dependencies change
+    IsDynamic(Box<dyn Fn(RenderCtx<C>) -> Cow<'static, str>>),
+}
+
+/// A value that can be used as the inner html of a element,
+/// see [`HtmlElement::dangerous_inner_html`].
+pub trait ToInnerHtml<C: State>: 'static {
+    /// Return the html, or the closure to calculate it
+    fn calc_inner_html(self) -> InnerHtmlResult<C>;
+}
+
+/// generate a `ToInnerHtml` implementation for a string type
+macro_rules! inner_html_string {
+    ($t:ty, $cow:expr) => {
+        impl<C: State> ToInnerHtml<C> for $t {
+            #[inline]
+            fn calc_inner_html(self) -> InnerHtmlResult<C> {
+                InnerHtmlResult::SetIt(($cow)(self))
+            }
+        }
+    };
+}
+
+type_macros::strings!(inner_html_string);
+
+impl<F, C, R> ToInnerHtml<C> for F
+where
+    F: Fn(RenderCtx<C>) -> R + 'static,
+    R: Into<Cow<'static, str>>,
+    C: State,
+{
+    #[inline]
+    fn calc_inner_html(self) -> InnerHtmlResult<C> {
+        InnerHtmlResult::IsDynamic(Box::new(move |ctx| self(ctx).into()))
+    }
+}
+
+impl<C: State, T: CanHaveChild> HtmlElement<C, T> {
+    /// Set the inner html of the element, parsing the string as html.
+    ///
+    /// **This is a XSS risk**, only use it with trusted html, such as html you sanitized
+    /// yourself or rendered from markdown you control.
+    /// Use [`.text`](HtmlElement::text) for anything else, which never interprets html.
+    ///
+    /// The parsed nodes are not tracked by natrix, so setting the html replaces any children
+    /// added before it, and a reactive closure replaces all the children whenever it reruns.
+    /// Do not mix this with [`.child`](HtmlElement::child) on the same element.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     rendered_markdown: Signal<String>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::article().dangerous_inner_html(|ctx: RenderCtx<App>| ctx.rendered_markdown.clone())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn dangerous_inner_html(mut self, html: impl ToInnerHtml<C>) -> Self {
+        match html.calc_inner_html() {
+            InnerHtmlResult::SetIt(html) => self.element.set_inner_html(&html),
+            InnerHtmlResult::IsDynamic(calc) => {
+                let node = self.element.clone();
+                self.deferred.push(Box::new(move |ctx, rendering_state| {
+                    let hook = SimpleReactive::init_new(
+                        Box::new(move |ctx, _node| {
+                            SimpleReactiveResult::Apply(ReactiveInnerHtml { data: calc(ctx) })
+                        }),
+                        node,
+                        ctx,
+                    );
+                    rendering_state.hooks.push(hook);
+                }));
+            }
+        }
+        self
+    }
+}
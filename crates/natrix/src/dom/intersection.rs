@@ -0,0 +1,165 @@
+//! Reacting to a element scrolling into view, using `IntersectionObserver`.
+//!
+//! This is useful for lazy loading content, or loading more items as the user scrolls to the end
+//! of a list.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! use natrix::dom::intersection::IntersectOptions;
+//!
+//! #[derive(State)]
+//! struct App {
+//!     items: Signal<Vec<u32>>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .child(|ctx: RenderCtx<App>| {
+//!             e::ul().children(ctx.items.iter().map(|item| e::li().text(*item)))
+//!         })
+//!         .child(e::div().on_intersect(
+//!             |mut ctx: EventCtx<App>, entry| {
+//!                 if entry.is_intersecting() {
+//!                     let next = ctx.items.len() as u32;
+//!                     ctx.items.extend(next..next + 20);
+//!                 }
+//!             },
+//!             // Start loading before the end of the list is visible.
+//!             IntersectOptions::new().root_margin("200px"),
+//!         ))
+//! }
+//! ```
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::js_sys::{Array, Function};
+
+use super::html_elements::HtmlElement;
+use crate::error_handling::log_or_panic;
+use crate::reactivity::State;
+use crate::reactivity::state::EventCtx;
+
+/// Options for [`HtmlElement::on_intersect`]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use]
+pub struct IntersectOptions {
+    /// Margin around the viewport, in css `margin` syntax
+    root_margin: Option<String>,
+    /// Visibility ratios to call the handler at
+    thresholds: Vec<f64>,
+}
+
+impl IntersectOptions {
+    /// Create the default options, calling the handler when the element enters or leaves the
+    /// viewport.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grow (or with negative values shrink) the viewport used for checking visibility,
+    /// in css `margin` syntax such as `"200px"` or `"0px 0px 50% 0px"`.
+    ///
+    /// A positive margin calls the handler before the element is actually visible.
+    pub fn root_margin(mut self, margin: impl Into<String>) -> Self {
+        self.root_margin = Some(margin.into());
+        self
+    }
+
+    /// Also call the handler when the visible ratio of the element crosses `threshold`,
+    /// where `0.0` is any pixel being visible and `1.0` is the whole element being visible.
+    ///
+    /// Can be called multiple times to observe multiple thresholds.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.thresholds.push(threshold);
+        self
+    }
+
+    /// Convert to the options object for the js constructor
+    fn to_js(&self) -> web_sys::IntersectionObserverInit {
+        let init = web_sys::IntersectionObserverInit::new();
+        if let Some(margin) = &self.root_margin {
+            init.set_root_margin(margin);
+        }
+        if !self.thresholds.is_empty() {
+            let thresholds: Array = self.thresholds.iter().copied().map(JsValue::from).collect();
+            init.set_threshold(&thresholds);
+        }
+        init
+    }
+}
+
+/// Disconnects the observer when dropped, and keeps its callback alive until then
+struct ObserverGuard {
+    /// The observer
+    observer: web_sys::IntersectionObserver,
+    /// The js callback of the observer
+    _callback: Closure<dyn Fn(Array)>,
+}
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Call `handler` when the element enters or leaves the viewport, or crosses one of the
+    /// thresholds given in `options`.
+    ///
+    /// The handler is also called once the element is first observed, with its initial
+    /// visibility. Use [`IntersectionObserverEntry::is_intersecting`] to check if it is visible.
+    /// The observer is disconnected when the element is removed.
+    ///
+    /// See the [module docs](super::intersection) for a infinite scroll example.
+    ///
+    /// [`IntersectionObserverEntry::is_intersecting`]: web_sys::IntersectionObserverEntry::is_intersecting
+    pub fn on_intersect(
+        mut self,
+        handler: impl Fn(EventCtx<C>, web_sys::IntersectionObserverEntry) + 'static,
+        options: IntersectOptions,
+    ) -> Self {
+        let node = self.element.clone();
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let ctx_weak = ctx.this.clone();
+            let callback: Closure<dyn Fn(Array)> = Closure::new(move |entries: Array| {
+                crate::panics::return_if_panic!();
+
+                let Some(ctx) = ctx_weak.upgrade() else {
+                    log_or_panic!("State dropped without intersection observer being cleaned up");
+                    return;
+                };
+                let Ok(mut ctx) = ctx.try_borrow_mut() else {
+                    log_or_panic!("State already mutably borrowed in intersection observer");
+                    return;
+                };
+
+                ctx.track_changes(|ctx| {
+                    for entry in entries {
+                        let Ok(entry) = entry.dyn_into::<web_sys::IntersectionObserverEntry>()
+                        else {
+                            log_or_panic!("Unexpected intersection observer entry type");
+                            continue;
+                        };
+                        handler(EventCtx(&mut *ctx), entry);
+                    }
+                });
+            });
+
+            let function: &Function = callback.as_ref().unchecked_ref();
+            let Ok(observer) =
+                web_sys::IntersectionObserver::new_with_options(function, &options.to_js())
+            else {
+                log_or_panic!("Failed to create intersection observer, is the root margin valid?");
+                return;
+            };
+            observer.observe(&node);
+
+            rendering_state.keep_alive.push(Box::new(ObserverGuard {
+                observer,
+                _callback: callback,
+            }));
+        }));
+        self
+    }
+}
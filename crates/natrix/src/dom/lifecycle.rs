@@ -0,0 +1,92 @@
+//! Running code when a element is added to and removed from the document.
+//!
+//! This is useful for imperative apis that need the element to be in the document, such as
+//! scrolling it into view or setting up a javascript widget on it.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! #[derive(State)]
+//! struct App {
+//!     mounted: Signal<bool>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .on_mount(|mut ctx: EventCtx<App>, _node| {
+//!             *ctx.mounted = true;
+//!         })
+//!         .on_unmount(|_node| {
+//!             // Tear down anything set up in `on_mount`.
+//!         })
+//! }
+//! ```
+
+use super::html_elements::HtmlElement;
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{ReactiveHook, UpdateResult};
+use crate::reactivity::state::{EventCtx, HookKey, InnerCtx};
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Call `handler` once the element has been inserted into the document.
+    ///
+    /// The handler runs after the update cycle that rendered the element, with mutable access
+    /// to the state.
+    /// It is called at most once, and never for elements that were thrown away before they
+    /// were inserted, such as when a reactive closure is rerun in the same update.
+    pub fn on_mount(
+        mut self,
+        handler: impl FnOnce(EventCtx<C>, &web_sys::Element) + 'static,
+    ) -> Self {
+        let node = self.element.clone();
+        self.deferred.push(Box::new(move |ctx, _rendering_state| {
+            ctx.post_update.push(Box::new(move |ctx| {
+                if node.is_connected() {
+                    handler(ctx, &node);
+                } else {
+                    log::trace!("Skipping `on_mount` of element that was never mounted");
+                }
+            }));
+        }));
+        self
+    }
+
+    /// Call `handler` when the element is removed by a reactive closure.
+    ///
+    /// This includes the element being removed along with one of its parents.
+    /// The state is not accessible from the handler as it is called during the update cycle.
+    pub fn on_unmount(mut self, handler: impl FnOnce(&web_sys::Element) + 'static) -> Self {
+        let node = self.element.clone();
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let me = ctx.hooks.reserve_key();
+            ctx.hooks.set_hook(
+                me,
+                Box::new(UnmountHook {
+                    node,
+                    handler: Box::new(handler),
+                }),
+            );
+            rendering_state.hooks.push(me);
+        }));
+        self
+    }
+}
+
+/// Hook calling the `on_unmount` handler when it is dropped along with the element
+struct UnmountHook {
+    /// The element to pass to the handler
+    node: web_sys::Element,
+    /// The `on_unmount` handler
+    handler: Box<dyn FnOnce(&web_sys::Element)>,
+}
+
+impl<C: State> ReactiveHook<C> for UnmountHook {
+    fn update(&mut self, _ctx: &mut InnerCtx<C>, _you: HookKey) -> UpdateResult {
+        UpdateResult::DropHooks(Vec::new())
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        (self.handler)(&self.node);
+        Vec::new()
+    }
+}
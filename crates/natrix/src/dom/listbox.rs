@@ -0,0 +1,337 @@
+//! Keyboard accessible listbox and menu primitives.
+//!
+//! These implement the WAI-ARIA [listbox](https://www.w3.org/WAI/ARIA/apg/patterns/listbox/) and
+//! [menu](https://www.w3.org/WAI/ARIA/apg/patterns/menubar/) patterns, using a roving tabindex.
+//!
+//! * `ArrowDown`/`ArrowUp` move focus to the next/previous item.
+//! * `Home`/`End` move focus to the first/last item.
+//! * `Enter`/`Space` (or a click) selects the focused item.
+//! * `Escape` calls the [`on_escape`](Listbox::on_escape) handler.
+//!
+//! The selected item can be bound to the state with [`selected`](Listbox::selected),
+//! otherwise the list keeps track of the last selected item itself.
+//!
+//! # Example
+//! ```no_run
+//! # use natrix::prelude::*;
+//! use natrix::dom::listbox::Listbox;
+//!
+//! #[derive(Clone, Copy, PartialEq)]
+//! enum Fruit {
+//!     Apple,
+//!     Banana,
+//! }
+//!
+//! #[derive(State)]
+//! struct App {
+//!     fruit: Signal<Option<Fruit>>,
+//!     fruit_index: Signal<Option<usize>>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     Listbox::new(|mut ctx: EventCtx<App>, (index, fruit)| {
+//!         *ctx.fruit = Some(fruit);
+//!         *ctx.fruit_index = Some(index);
+//!     })
+//!     .label("Fruits")
+//!     .item((0, Fruit::Apple), "Apple")
+//!     .item((1, Fruit::Banana), "Banana")
+//!     .selected(|ctx: Ref<App>| field!(ctx.fruit_index).deref())
+//! }
+//! ```
+
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+
+use super::events::{Key, KeyEvent};
+use super::html_elements::{self, HtmlElement, TagLi, TagUl};
+use super::{Element, MaybeStaticElement, events};
+use crate::access::{Getter, RefClosure};
+use crate::error_handling::log_or_panic_result;
+use crate::get_document;
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{ReactiveValue, SimpleReactive, SimpleReactiveResult};
+use crate::reactivity::state::EventCtx;
+
+/// A selection handler
+type SelectHandler<C, V> = Rc<dyn Fn(EventCtx<C>, V)>;
+/// A escape handler
+type EscapeHandler<C> = Rc<dyn Fn(EventCtx<C>)>;
+/// Reads the selected index from the state
+type SelectedGetter<C> = Rc<dyn Fn(&C) -> Option<usize>>;
+
+/// Which aria pattern to implement
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ListKind {
+    /// `role="listbox"`, items are `role="option"` with `aria-selected`
+    Listbox,
+    /// `role="menu"`, items are `role="menuitem"`
+    Menu,
+}
+
+/// A keyboard navigable list of items, see the [module docs](self).
+#[must_use = "Web elements are useless if not rendered"]
+pub struct Listbox<C: State, V> {
+    /// The kind of list
+    kind: ListKind,
+    /// The list element
+    element: HtmlElement<C, TagUl>,
+    /// The item elements, in order
+    items: Vec<HtmlElement<C, TagLi>>,
+    /// The value of each item
+    values: Vec<V>,
+    /// The selected item, if bound to the state
+    selected: Option<SelectedGetter<C>>,
+    /// Called when a item is selected
+    on_select: SelectHandler<C, V>,
+    /// Called when escape is pressed
+    on_escape: Option<EscapeHandler<C>>,
+}
+
+impl<C: State, V: Clone + 'static> Listbox<C, V> {
+    /// Create a single select listbox, calling `on_select` with the value of the selected item.
+    pub fn new(on_select: impl Fn(EventCtx<C>, V) + 'static) -> Self {
+        Self::with_kind(ListKind::Listbox, on_select)
+    }
+
+    /// Create a menu, calling `on_select` with the value of the activated item.
+    pub fn menu(on_select: impl Fn(EventCtx<C>, V) + 'static) -> Self {
+        Self::with_kind(ListKind::Menu, on_select)
+    }
+
+    /// Create a list with the given kind
+    fn with_kind(kind: ListKind, on_select: impl Fn(EventCtx<C>, V) + 'static) -> Self {
+        let role = match kind {
+            ListKind::Listbox => "listbox",
+            ListKind::Menu => "menu",
+        };
+        Self {
+            kind,
+            element: html_elements::ul().attr("role", role),
+            items: Vec::new(),
+            values: Vec::new(),
+            selected: None,
+            on_select: Rc::new(on_select),
+            on_escape: None,
+        }
+    }
+
+    /// Add a item with the given value and content.
+    pub fn item(mut self, value: V, content: impl Element<C> + 'static) -> Self {
+        let role = match self.kind {
+            ListKind::Listbox => "option",
+            ListKind::Menu => "menuitem",
+        };
+        self.items
+            .push(html_elements::li().attr("role", role).child(content));
+        self.values.push(value);
+        self
+    }
+
+    /// Set the accessible label of the list.
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.element = self.element.attr("aria-label", label);
+        self
+    }
+
+    /// Mark the item at the index read from the state as selected (and focusable),
+    /// updating `aria-selected` and `tabindex` whenever it changes.
+    ///
+    /// If the index is `None` or out of range nothing is selected, and the first item is
+    /// focusable.
+    ///
+    /// The state is the source of truth, so selecting a item only marks it as selected once
+    /// the `on_select` handler updates the index.
+    pub fn selected(mut self, index: impl Getter<C, Option<usize>>) -> Self {
+        self.selected = Some(Rc::new(move |data: &C| *index.call_read(data)));
+        self
+    }
+
+    /// Called when `Escape` is pressed while the list has focus.
+    pub fn on_escape(mut self, handler: impl Fn(EventCtx<C>) + 'static) -> Self {
+        self.on_escape = Some(Rc::new(handler));
+        self
+    }
+
+    /// Modify the list element, for example to add classes.
+    pub fn element(
+        mut self,
+        func: impl FnOnce(HtmlElement<C, TagUl>) -> HtmlElement<C, TagUl>,
+    ) -> Self {
+        self.element = func(self.element);
+        self
+    }
+}
+
+/// Set a attribute on a raw element
+fn set_attribute(element: &web_sys::Element, name: &str, value: &str) {
+    log_or_panic_result!(
+        element.set_attribute(name, value),
+        "Failed to set listbox attribute"
+    );
+}
+
+/// The item nodes of the list and the shared behaviour on them
+struct ListNodes {
+    /// The kind of list
+    kind: ListKind,
+    /// The item nodes
+    items: Vec<web_sys::Element>,
+}
+
+impl ListNodes {
+    /// Find the index of the item containing the given node
+    fn index_of(&self, node: Option<&web_sys::Node>) -> Option<usize> {
+        self.items.iter().position(|item| item.contains(node))
+    }
+
+    /// Move the roving tabindex to the given item, and optionally focus it.
+    fn make_current(&self, index: usize, focus: bool) {
+        for (item_index, item) in self.items.iter().enumerate() {
+            let tab_index = if item_index == index { "0" } else { "-1" };
+            set_attribute(item, "tabindex", tab_index);
+        }
+        if focus
+            && let Some(item) = self
+                .items
+                .get(index)
+                .and_then(|item| item.dyn_ref::<web_sys::HtmlElement>())
+        {
+            log_or_panic_result!(item.focus(), "Failed to focus listbox item");
+        }
+    }
+
+    /// Mark the given item as selected, and make it the current item.
+    ///
+    /// If the index is `None` or out of range nothing is selected, and the first item is made
+    /// current.
+    fn select(&self, index: Option<usize>) {
+        let index = index.filter(|index| *index < self.items.len());
+        if self.kind == ListKind::Listbox {
+            for (item_index, item) in self.items.iter().enumerate() {
+                let selected = if Some(item_index) == index {
+                    "true"
+                } else {
+                    "false"
+                };
+                set_attribute(item, "aria-selected", selected);
+            }
+        }
+        self.make_current(index.unwrap_or(0), false);
+    }
+}
+
+/// Reactively mark the selected item of a list
+struct ReactiveSelection {
+    /// The items of the list
+    nodes: Rc<ListNodes>,
+    /// The selected index
+    index: Option<usize>,
+}
+
+impl ReactiveValue for ReactiveSelection {
+    type State = ();
+
+    fn apply(self, _node: &web_sys::Element, _state: &mut Self::State) {
+        self.nodes.select(self.index);
+    }
+}
+
+impl<C: State, V: Clone + 'static> Element<C> for Listbox<C, V> {
+    fn render(self) -> MaybeStaticElement<C> {
+        let Self {
+            kind,
+            mut element,
+            items,
+            values,
+            selected,
+            on_select,
+            on_escape,
+        } = self;
+
+        let nodes = Rc::new(ListNodes {
+            kind,
+            items: items.iter().map(|item| item.element.clone()).collect(),
+        });
+        element = element.children(items);
+
+        // NOTE: When bound to the state the hook marks the selection instead, including when
+        // a item is selected.
+        let bound = selected.is_some();
+        if let Some(selected) = selected {
+            let node = element.element.clone();
+            let selection_nodes = nodes.clone();
+            element.deferred.push(Box::new(move |ctx, rendering_state| {
+                let hook = SimpleReactive::init_new(
+                    Box::new(move |ctx, _node| {
+                        SimpleReactiveResult::Apply(ReactiveSelection {
+                            nodes: selection_nodes.clone(),
+                            index: selected(&ctx),
+                        })
+                    }),
+                    node,
+                    ctx,
+                );
+                rendering_state.hooks.push(hook);
+            }));
+        } else {
+            nodes.select(None);
+        }
+
+        let values = Rc::new(values);
+
+        let click_nodes = nodes.clone();
+        let click_values = values.clone();
+        let click_select = on_select.clone();
+        element =
+            element.on::<events::Click>(move |ctx: EventCtx<C>, event: web_sys::PointerEvent| {
+                let target = event.target().and_then(|target| target.dyn_into().ok());
+                let Some(index) = click_nodes.index_of(target.as_ref()) else {
+                    return;
+                };
+                if let Some(value) = click_values.get(index) {
+                    if !bound {
+                        click_nodes.select(Some(index));
+                    }
+                    click_select(ctx, value.clone());
+                }
+            });
+
+        element
+            .on::<events::KeyDown>(move |ctx: EventCtx<C>, event: KeyEvent| {
+                let focused = get_document().active_element().map(web_sys::Node::from);
+                let current = nodes.index_of(focused.as_ref()).unwrap_or(0);
+                let last = nodes.items.len().saturating_sub(1);
+
+                let new_index = match event.key() {
+                    Key::ArrowDown => current.saturating_add(1).min(last),
+                    Key::ArrowUp => current.saturating_sub(1),
+                    Key::Home => 0,
+                    Key::End => last,
+                    Key::Enter | Key::Character(' ') => {
+                        event.prevent_default();
+                        if let Some(value) = values.get(current) {
+                            if !bound {
+                                nodes.select(Some(current));
+                            }
+                            on_select(ctx, value.clone());
+                        }
+                        return;
+                    }
+                    Key::Escape => {
+                        if let Some(on_escape) = &on_escape {
+                            event.prevent_default();
+                            on_escape(ctx);
+                        }
+                        return;
+                    }
+                    _ => return,
+                };
+
+                event.prevent_default();
+                nodes.make_current(new_index, true);
+            })
+            .render()
+    }
+}
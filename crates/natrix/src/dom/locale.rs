@@ -0,0 +1,87 @@
+//! The `lang` attribute, and binding the document language to the app locale.
+
+use wasm_bindgen::intern;
+
+use super::ToAttribute;
+use super::attributes::{AttributeCheck, AttributeResult};
+use super::html_elements::HtmlElement;
+use crate::error_handling::{log_or_panic, log_or_panic_result};
+use crate::get_document;
+use crate::reactivity::State;
+
+/// Calculate a `lang` attribute value, checking it looks like a language tag in debug builds.
+fn calc_lang<C: State>(
+    value: impl ToAttribute<C, AttributeKind = String>,
+    node: &web_sys::Element,
+) -> AttributeResult<C> {
+    if cfg!(debug_assertions) {
+        value.calc_attribute_checked(intern("lang"), node, AttributeCheck::LanguageTag)
+    } else {
+        value.calc_attribute(intern("lang"), node)
+    }
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// <https://developer.mozilla.org/docs/Web/HTML/Reference/Global_attributes/lang>
+    ///
+    /// The value should be a BCP 47 language tag, such as `en` or `en-US`.
+    /// In debug builds a warning is logged for values that are obviously malformed.
+    #[inline]
+    pub fn lang(self, value: impl ToAttribute<C, AttributeKind = String>) -> Self {
+        if cfg!(debug_assertions) {
+            self.attr_checked("lang", value, AttributeCheck::LanguageTag)
+        } else {
+            self.attr("lang", value)
+        }
+    }
+
+    /// Set the `lang` attribute of the document root (`<html lang>`).
+    ///
+    /// Bind this to the locale in your state on your root element, and the document language
+    /// will be updated whenever the locale changes. The binding is active for as long as this
+    /// element is mounted.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     locale: Signal<&'static str>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     e::div()
+    ///         .document_lang(|ctx: RenderCtx<App>| *ctx.locale)
+    ///         .child(e::button().text("Norsk").on::<events::Click>(
+    ///             |mut ctx: EventCtx<App>, _| {
+    ///                 *ctx.locale = "nb";
+    ///             },
+    ///         ))
+    /// }
+    /// ```
+    pub fn document_lang(mut self, value: impl ToAttribute<C, AttributeKind = String>) -> Self {
+        let Some(root) = get_document().document_element() else {
+            log_or_panic!("Document has no root element");
+            return self;
+        };
+
+        match calc_lang(value, &root) {
+            AttributeResult::SetIt(Some(lang)) => {
+                log_or_panic_result!(
+                    root.set_attribute("lang", &lang),
+                    "Failed to set document language"
+                );
+            }
+            AttributeResult::SetIt(None) => {
+                log_or_panic_result!(
+                    root.remove_attribute("lang"),
+                    "Failed to remove document language"
+                );
+            }
+            AttributeResult::IsDynamic(dynamic) => {
+                self.deferred.push(dynamic);
+            }
+        }
+
+        self
+    }
+}
@@ -1,10 +1,23 @@
 //! DOM-related modules for rendering HTML elements.
 
 pub mod attributes;
+pub mod bind;
 pub mod classes;
+pub mod debounce;
+pub mod dialog;
 pub mod element;
 pub mod events;
+pub mod fragment;
 pub mod html_elements;
+pub mod inner_html;
+pub mod intersection;
+pub mod lifecycle;
+pub mod listbox;
+mod locale;
+pub mod props;
+pub mod svg_elements;
+pub mod tooltip;
+pub mod transition;
 
 pub use attributes::ToAttribute;
 pub use classes::ToClass;
@@ -0,0 +1,184 @@
+//! Forwarding classes and attributes to the elements of reusable components.
+
+use super::attributes::AttributeResult;
+use super::classes::ClassResult;
+use super::html_elements::HtmlElement;
+use super::{ToAttribute, ToClass};
+use crate::reactivity::State;
+
+/// A type erased attribute value
+type BoxedAttribute<C> = Box<dyn FnOnce(&'static str, &web_sys::Element) -> AttributeResult<C>>;
+
+/// A type erased class
+type BoxedClass<C> = Box<dyn FnOnce(&web_sys::Element) -> ClassResult<C>>;
+
+/// A bundle of classes and attributes passed to a component.
+///
+/// This lets a component accept arbitrary pass-through classes and attributes for its root
+/// element, and merge them with its own.
+/// When merging bundles classes are concatenated, while attributes are last-wins.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # use natrix::dom::props::MergeProps;
+/// const BUTTON: Class = natrix::class!();
+/// const PRIMARY: Class = natrix::class!();
+///
+/// fn button<C: State>(label: &'static str, props: MergeProps<C>) -> impl Element<C> {
+///     let own = MergeProps::new().class(BUTTON).attr("type", "button");
+///     e::button().props(own.merge(props)).text(label)
+/// }
+///
+/// # #[derive(State)]
+/// # struct App;
+/// fn render() -> impl Element<App> {
+///     button("Save", MergeProps::new().class(PRIMARY).attr("type", "submit"))
+/// }
+/// ```
+#[must_use]
+pub struct MergeProps<C: State> {
+    /// The classes, in the order they were added
+    classes: Vec<BoxedClass<C>>,
+    /// The attributes, each key only appears once
+    attributes: Vec<(&'static str, BoxedAttribute<C>)>,
+}
+
+impl<C: State> Default for MergeProps<C> {
+    fn default() -> Self {
+        Self {
+            classes: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+}
+
+impl<C: State> MergeProps<C> {
+    /// Create a empty bundle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a class
+    pub fn class(mut self, class: impl ToClass<C> + 'static) -> Self {
+        self.classes
+            .push(Box::new(move |node| class.calc_class(node)));
+        self
+    }
+
+    /// Add multiple classes
+    pub fn classes<Cls: ToClass<C> + 'static>(
+        mut self,
+        class_list: impl IntoIterator<Item = Cls>,
+    ) -> Self {
+        for class in class_list {
+            self = self.class(class);
+        }
+        self
+    }
+
+    /// Set a attribute, replacing any previous value for the same key.
+    pub fn attr(self, key: &'static str, value: impl ToAttribute<C>) -> Self {
+        self.insert_attribute(
+            key,
+            Box::new(move |name, node| value.calc_attribute(name, node)),
+        )
+    }
+
+    /// Insert a already boxed attribute, replacing any previous value for the same key.
+    fn insert_attribute(mut self, key: &'static str, value: BoxedAttribute<C>) -> Self {
+        self.attributes.retain(|(existing, _)| *existing != key);
+        self.attributes.push((key, value));
+        self
+    }
+
+    /// Merge two bundles.
+    ///
+    /// The classes of `other` are added after the ones in `self`, and the attributes of `other`
+    /// replace attributes with the same key in `self`.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.classes.extend(other.classes);
+        for (key, value) in other.attributes {
+            self = self.insert_attribute(key, value);
+        }
+        self
+    }
+}
+
+/// A type erased attribute from a [`MergeProps`]
+struct ErasedAttribute<C: State>(BoxedAttribute<C>);
+
+impl<C: State> ToAttribute<C> for ErasedAttribute<C> {
+    type AttributeKind = ();
+
+    fn calc_attribute(self, name: &'static str, node: &web_sys::Element) -> AttributeResult<C> {
+        (self.0)(name, node)
+    }
+}
+
+/// A type erased class from a [`MergeProps`]
+struct ErasedClass<C: State>(BoxedClass<C>);
+
+impl<C: State> ToClass<C> for ErasedClass<C> {
+    fn calc_class(self, node: &web_sys::Element) -> ClassResult<C> {
+        (self.0)(node)
+    }
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Apply a bundle of classes and attributes to this element, see [`MergeProps`].
+    ///
+    /// The attributes are set in call order like [`attr`](Self::attr), so they replace
+    /// attributes set on the element before this call, and are replaced by ones set after it.
+    /// If a attribute should be overridable put it in the components own bundle and
+    /// [`MergeProps::merge`] the incoming one into it, rather than setting it directly.
+    pub fn props(mut self, props: MergeProps<C>) -> Self {
+        for class in props.classes {
+            self = self.class(ErasedClass(class));
+        }
+        for (key, value) in props.attributes {
+            self = self.attr(key, ErasedAttribute(value));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::selectors::Class;
+
+    struct Empty;
+
+    impl State for Empty {
+        fn set(&mut self, _new: Self) {}
+    }
+
+    fn keys(props: &MergeProps<Empty>) -> Vec<&'static str> {
+        props.attributes.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn classes_are_concatenated() {
+        let own = MergeProps::<Empty>::new()
+            .class(Class("a"))
+            .class(Class("b"));
+        let incoming = MergeProps::new().classes([Class("c"), Class("d")]);
+        assert_eq!(own.merge(incoming).classes.len(), 4);
+    }
+
+    #[test]
+    fn attributes_are_last_wins() {
+        let own = MergeProps::<Empty>::new()
+            .attr("type", "button")
+            .attr("role", "tab");
+        let incoming = MergeProps::new().attr("type", "submit").attr("title", "hi");
+        let merged = own.merge(incoming);
+        assert_eq!(keys(&merged), ["role", "type", "title"]);
+    }
+
+    #[test]
+    fn setting_attribute_twice_replaces_it() {
+        let props = MergeProps::<Empty>::new().attr("id", "a").attr("id", "b");
+        assert_eq!(keys(&props), ["id"]);
+    }
+}
@@ -0,0 +1,63 @@
+//! Svg elements, which are created in the svg namespace so the browser renders them.
+//!
+//! This module is generally used via its alias in the prelude, `e::svg`.
+//! Attributes are set with the generic `.attr` method, and can be reactive like any other.
+//!
+//! # Example
+//! ```no_run
+//! # use natrix::prelude::*;
+//! # let _: e::HtmlElement<(), _> =
+//! e::svg()
+//!     .attr("viewBox", "0 0 24 24")
+//!     .attr("width", "24")
+//!     .child(e::svg::circle().attr("cx", "12").attr("cy", "12").attr("r", "10"))
+//!     .child(e::svg::path().attr("d", "M8 12h8"))
+//! # ;
+//! ```
+
+use super::html_elements::{CanHaveChild, HtmlElement};
+use crate::css::selectors::{CompoundSelector, IntoCompoundSelector, SimpleSelector};
+use crate::reactivity::State;
+
+/// The namespace uri of svg elements
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Implement a factory function that returns a `HtmlElement` in the svg namespace with the given
+/// tag name.
+macro_rules! svg_elements {
+    ($($name:ident => $tag:literal),* $(,)?) => {
+        pastey::paste! {
+            $(
+                #[doc = "<https://developer.mozilla.org/docs/Web/SVG/Reference/Element/" $tag ">"]
+                pub struct [< Tag $name:camel >];
+
+                impl IntoCompoundSelector for [< Tag $name:camel >] {
+                    fn into_compound(self) -> CompoundSelector {
+                        CompoundSelector(vec![SimpleSelector::Tag($tag.into())])
+                    }
+                }
+
+                impl CanHaveChild for [< Tag $name:camel >] {}
+
+                #[doc = "<https://developer.mozilla.org/docs/Web/SVG/Reference/Element/" $tag ">"]
+                #[inline]
+                pub fn $name<C: State>() -> HtmlElement<C, [< Tag $name:camel >]> {
+                    HtmlElement::new_ns(SVG_NAMESPACE, $tag)
+                }
+            )*
+        }
+    };
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Element
+svg_elements! {
+    svg => "svg", g => "g", defs => "defs", symbol => "symbol", r#use => "use",
+    title => "title", desc => "desc",
+    path => "path", circle => "circle", ellipse => "ellipse", line => "line",
+    polyline => "polyline", polygon => "polygon", rect => "rect",
+    text => "text", text_path => "textPath", tspan => "tspan",
+    image => "image", foreign_object => "foreignObject",
+    linear_gradient => "linearGradient", radial_gradient => "radialGradient", stop => "stop",
+    pattern => "pattern", clip_path => "clipPath", mask => "mask", marker => "marker",
+    filter => "filter",
+}
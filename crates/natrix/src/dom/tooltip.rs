@@ -0,0 +1,315 @@
+//! A floating tooltip that is shown when its target is hovered or focused.
+//!
+//! For simple cases prefer the native [`title`](super::HtmlElement::title) attribute.
+//!
+//! # Example
+//! ```no_run
+//! # use natrix::prelude::*;
+//! use natrix::dom::tooltip::{Placement, Tooltip};
+//!
+//! # let _: e::HtmlElement<(), _> =
+//! e::button()
+//!     .text("Save")
+//!     .tooltip(
+//!         Tooltip::new("Save the document")
+//!             .placement(Placement::Bottom)
+//!             .delay_milliseconds(300),
+//!     )
+//! # ;
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+use web_sys::js_sys::Function;
+
+use super::html_elements::{HtmlElement, TagDiv};
+use super::{Element, html_elements};
+use crate::error_handling::{log_or_panic, log_or_panic_result};
+use crate::reactivity::State;
+use crate::{get_document, get_window, shims};
+
+/// Space between the target and the tooltip in pixels
+const TOOLTIP_GAP: f64 = 8.0;
+
+thread_local! {
+    /// Counter used to generate unique tooltip ids
+    static NEXT_TOOLTIP_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Generate a unique id for a tooltip element
+fn next_tooltip_id() -> String {
+    let id = NEXT_TOOLTIP_ID.get();
+    NEXT_TOOLTIP_ID.set(id.wrapping_add(1));
+    format!("natrix-tooltip-{id}")
+}
+
+/// Where to place the tooltip relative to its target.
+///
+/// If the tooltip does not fit in the viewport at the given placement it is flipped to the
+/// opposite side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Above the target
+    #[default]
+    Top,
+    /// Below the target
+    Bottom,
+    /// To the left of the target
+    Left,
+    /// To the right of the target
+    Right,
+}
+
+impl Placement {
+    /// Get the placement on the opposite side
+    fn flipped(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    /// Calculate the `(left, top)` position of the tooltip for this placement
+    fn position(self, target: &web_sys::DomRect, tooltip: &web_sys::DomRect) -> (f64, f64) {
+        let center_x = target.left() + target.width() / 2.0 - tooltip.width() / 2.0;
+        let center_y = target.top() + target.height() / 2.0 - tooltip.height() / 2.0;
+
+        match self {
+            Self::Top => (center_x, target.top() - tooltip.height() - TOOLTIP_GAP),
+            Self::Bottom => (center_x, target.bottom() + TOOLTIP_GAP),
+            Self::Left => (target.left() - tooltip.width() - TOOLTIP_GAP, center_y),
+            Self::Right => (target.right() + TOOLTIP_GAP, center_y),
+        }
+    }
+}
+
+/// A floating tooltip, rendered at the end of `<body>` and positioned next to its target while
+/// the target is hovered or focused.
+///
+/// The target gets `aria-describedby` pointing at the tooltip, and the tooltip gets
+/// `role="tooltip"`.
+#[must_use = "Tooltips do nothing unless attached with `.tooltip`"]
+pub struct Tooltip<C: State> {
+    /// The tooltip element
+    element: HtmlElement<C, TagDiv>,
+    /// Preferred placement
+    placement: Placement,
+    /// Delay before showing in milliseconds
+    delay: i32,
+}
+
+impl<C: State> Tooltip<C> {
+    /// Create a tooltip with the given content, the content can be reactive.
+    pub fn new(content: impl Element<C> + 'static) -> Self {
+        Self {
+            element: html_elements::div().attr("role", "tooltip").child(content),
+            placement: Placement::default(),
+            delay: 0,
+        }
+    }
+
+    /// Set the preferred placement of the tooltip.
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Set the delay before the tooltip is shown.
+    ///
+    /// # Why `i32`?
+    /// Because thats what the `web_sys` `setTimeout` binding uses.
+    pub fn delay_milliseconds(mut self, delay: i32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Modify the tooltip element, for example to add classes.
+    ///
+    /// Note that natrix sets the `position`, `display`, `left`, and `top` styles.
+    pub fn element(
+        mut self,
+        func: impl FnOnce(HtmlElement<C, TagDiv>) -> HtmlElement<C, TagDiv>,
+    ) -> Self {
+        self.element = func(self.element);
+        self
+    }
+}
+
+/// Removes the given element from the dom when dropped
+struct RemoveOnDrop(web_sys::Element);
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        self.0.remove();
+    }
+}
+
+/// Clears the pending show timeout when dropped
+struct ClearTimeoutOnDrop(Rc<Cell<Option<i32>>>);
+
+impl Drop for ClearTimeoutOnDrop {
+    fn drop(&mut self) {
+        // NOTE: The timeout would otherwise call the dropped `show` closure
+        if let Some(timeout) = self.0.take() {
+            shims::clear_timeout(timeout);
+        }
+    }
+}
+
+/// Set a inline style on the element
+fn set_style(element: &web_sys::HtmlElement, property: &str, value: &str) {
+    log_or_panic_result!(
+        element.style().set_property(property, value),
+        "Failed to set tooltip style"
+    );
+}
+
+/// Show the tooltip and position it relative to the target
+fn show_tooltip(target: &web_sys::Element, tooltip: &web_sys::HtmlElement, placement: Placement) {
+    // NOTE: The tooltip needs to be displayed to be measured
+    set_style(tooltip, "display", "block");
+
+    let target_rect = target.get_bounding_client_rect();
+    let tooltip_rect = tooltip.get_bounding_client_rect();
+
+    let window = get_window();
+    let viewport_width = window
+        .inner_width()
+        .ok()
+        .and_then(|width| width.as_f64())
+        .unwrap_or(f64::INFINITY);
+    let viewport_height = window
+        .inner_height()
+        .ok()
+        .and_then(|height| height.as_f64())
+        .unwrap_or(f64::INFINITY);
+
+    let fits = |(left, top): (f64, f64)| {
+        left >= 0.0
+            && top >= 0.0
+            && left + tooltip_rect.width() <= viewport_width
+            && top + tooltip_rect.height() <= viewport_height
+    };
+
+    let mut position = placement.position(&target_rect, &tooltip_rect);
+    if !fits(position) {
+        let flipped = placement.flipped().position(&target_rect, &tooltip_rect);
+        if fits(flipped) {
+            position = flipped;
+        }
+    }
+
+    let (left, top) = position;
+    set_style(tooltip, "left", &format!("{left}px"));
+    set_style(tooltip, "top", &format!("{top}px"));
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Attach a floating tooltip to this element, shown on hover and focus.
+    ///
+    /// See [`Tooltip`] for details.
+    pub fn tooltip(mut self, tooltip: Tooltip<C>) -> Self {
+        let Tooltip {
+            element,
+            placement,
+            delay,
+        } = tooltip;
+        let HtmlElement {
+            element: tooltip_node,
+            deferred,
+            ..
+        } = element;
+        self.deferred.extend(deferred);
+
+        let Ok(tooltip_node) = tooltip_node.dyn_into::<web_sys::HtmlElement>() else {
+            log_or_panic!("Tooltip element was not a html element");
+            return self;
+        };
+
+        let id = next_tooltip_id();
+        tooltip_node.set_id(&id);
+        set_style(&tooltip_node, "position", "fixed");
+        set_style(&tooltip_node, "display", "none");
+        set_style(&tooltip_node, "pointer-events", "none");
+        self = self.attr("aria-describedby", id);
+
+        let target = self.element.clone();
+        self.deferred.push(Box::new(move |_ctx, rendering_state| {
+            let Some(body) = get_document().body() else {
+                log_or_panic!("Document body not found");
+                return;
+            };
+            log_or_panic_result!(
+                body.append_child(&tooltip_node),
+                "Failed to append tooltip to body"
+            );
+
+            let pending_timeout: Rc<Cell<Option<i32>>> = Rc::new(Cell::new(None));
+
+            let show: Closure<dyn Fn()> = Closure::new({
+                let target = target.clone();
+                let tooltip_node = tooltip_node.clone();
+                let pending_timeout = pending_timeout.clone();
+                move || {
+                    pending_timeout.set(None);
+                    show_tooltip(&target, &tooltip_node, placement);
+                }
+            });
+            let show_function: Function = show.as_ref().unchecked_ref::<Function>().clone();
+
+            let on_enter: Closure<dyn Fn()> = Closure::new({
+                let pending_timeout = pending_timeout.clone();
+                move || {
+                    if let Some(timeout) = pending_timeout.take() {
+                        shims::clear_timeout(timeout);
+                    }
+                    if let Ok(timeout) = shims::set_timeout(&show_function, delay) {
+                        pending_timeout.set(Some(timeout));
+                    } else {
+                        log_or_panic!("Failed to set tooltip timeout");
+                    }
+                }
+            });
+
+            let on_leave: Closure<dyn Fn()> = Closure::new({
+                let tooltip_node = tooltip_node.clone();
+                let pending_timeout = pending_timeout.clone();
+                move || {
+                    if let Some(timeout) = pending_timeout.take() {
+                        shims::clear_timeout(timeout);
+                    }
+                    set_style(&tooltip_node, "display", "none");
+                }
+            });
+
+            for (event, callback) in [
+                ("mouseenter", &on_enter),
+                ("focusin", &on_enter),
+                ("mouseleave", &on_leave),
+                ("focusout", &on_leave),
+            ] {
+                log_or_panic_result!(
+                    target
+                        .add_event_listener_with_callback(event, callback.as_ref().unchecked_ref()),
+                    "Failed to attach tooltip event handler"
+                );
+            }
+
+            rendering_state
+                .keep_alive
+                .push(Box::new(ClearTimeoutOnDrop(pending_timeout)));
+            rendering_state.keep_alive.push(Box::new(show));
+            rendering_state.keep_alive.push(Box::new(on_enter));
+            rendering_state.keep_alive.push(Box::new(on_leave));
+            rendering_state
+                .keep_alive
+                .push(Box::new(RemoveOnDrop(tooltip_node.into())));
+        }));
+        self
+    }
+}
@@ -0,0 +1,215 @@
+//! Enter and leave animations for conditionally rendered elements.
+//!
+//! [`HtmlElement::transition`] applies a class when the element is rendered, and delays removing
+//! it until the leave animation has played.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! const FADE_IN: Class = natrix::class!();
+//! const FADE_OUT: Class = natrix::class!();
+//!
+//! natrix::register_keyframe!(
+//!     const FADE = KeyframeDefinition::new()
+//!         .frame(natrix::unit!(0.0%), RuleBody::new().property("opacity", "0"))
+//! );
+//! natrix::register_rules!(
+//!     property::RuleCollection::new()
+//!         .rule(FADE_IN, RuleBody::new().property("animation", format!("{} 200ms", FADE.into_css())))
+//!         .rule(
+//!             FADE_OUT,
+//!             RuleBody::new().property("animation", format!("{} 200ms reverse", FADE.into_css())),
+//!         )
+//! );
+//!
+//! #[derive(State)]
+//! struct App {
+//!     show: Signal<bool>,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div().child(|ctx: RenderCtx<App>| {
+//!         if *ctx.show {
+//!             Some(e::p().text("Hello").transition(FADE_IN, FADE_OUT))
+//!         } else {
+//!             None
+//!         }
+//!     })
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::html_elements::HtmlElement;
+use crate::css::selectors::Class;
+use crate::error_handling::log_or_panic_result;
+use crate::reactivity::State;
+use crate::{get_window, shims};
+
+/// The attribute storing the enter and leave class of a element, separated by a space
+const TRANSITION_ATTRIBUTE: &str = "data-natrix-transition";
+
+/// The events that mark the end of a animation or transition
+const END_EVENTS: [&str; 4] = [
+    "animationend",
+    "animationcancel",
+    "transitionend",
+    "transitioncancel",
+];
+
+/// Extra time given to the animations before removing the element regardless of end events
+const FALLBACK_SLACK_MILLISECONDS: f64 = 50.0;
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Animate this element when it is added and removed by a reactive closure.
+    ///
+    /// `enter` is applied when the element is rendered.
+    /// When a reactive closure removes the element `enter` is swapped for `leave`, and the
+    /// element is kept in the dom until the animations and transitions `leave` starts are done.
+    ///
+    /// This only applies to the element a reactive closure returns, elements that are removed
+    /// along with their parent are removed right away.
+    ///
+    /// The event handlers and reactive closures of the element keep working while it animates
+    /// out, so they should not assume the state that removed the element has not changed.
+    pub fn transition(self, enter: Class, leave: Class) -> Self {
+        self.class(enter)
+            .attr(TRANSITION_ATTRIBUTE, format!("{} {}", enter.0, leave.0))
+    }
+}
+
+/// Start the leave animation of the node if it has a [`HtmlElement::transition`].
+///
+/// Returns `retained` back if the node should be removed right away, otherwise the node will
+/// remove itself once the animation is done, and `retained` is dropped along with it.
+/// This is used to keep the event handlers and reactive hooks of the node alive while it animates
+/// out.
+pub(crate) fn start_leave<R: 'static>(node: &web_sys::Node, retained: R) -> Result<(), R> {
+    let Some(element) = node.dyn_ref::<web_sys::Element>() else {
+        return Err(retained);
+    };
+    let Some(classes) = element.get_attribute(TRANSITION_ATTRIBUTE) else {
+        return Err(retained);
+    };
+    let Some((enter, leave)) = classes.split_once(' ') else {
+        return Err(retained);
+    };
+
+    // NOTE: Removing the attribute makes sure a element is never left twice
+    log_or_panic_result!(
+        element.remove_attribute(TRANSITION_ATTRIBUTE),
+        "Failed to remove transition attribute"
+    );
+    let class_list = element.class_list();
+    log_or_panic_result!(class_list.remove_1(enter), "Failed to remove enter class");
+    log_or_panic_result!(class_list.add_1(leave), "Failed to add leave class");
+
+    let duration = motion_duration(element);
+    if duration <= 0.0 {
+        return Err(retained);
+    }
+
+    // NOTE: Whichever of the end event and the fallback timeout happens first removes the node
+    let retained = Rc::new(RefCell::new(Some(retained)));
+    let remove = {
+        let target = element.clone();
+        move || {
+            target.remove();
+            if let Ok(mut retained) = retained.try_borrow_mut() {
+                drop(retained.take());
+            }
+        }
+    };
+
+    let target = element.clone();
+    let remove_on_end = remove.clone();
+    let on_end = Closure::<dyn Fn(web_sys::Event)>::new(move |event: web_sys::Event| {
+        // NOTE: The end events of animations on children bubble up to us
+        let target_value: &JsValue = target.as_ref();
+        if event
+            .target()
+            .is_some_and(|event_target| AsRef::<JsValue>::as_ref(&event_target) == target_value)
+        {
+            remove_on_end();
+        }
+    })
+    .into_js_value();
+    for event in END_EVENTS {
+        log_or_panic_result!(
+            element.add_event_listener_with_callback(event, on_end.unchecked_ref()),
+            "Failed to add animation end listener"
+        );
+    }
+
+    // NOTE: A transition whose properties do not change never fires any events.
+    let fallback = Closure::once_into_js(remove);
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Durations are far from the limits of `i32` milliseconds"
+    )]
+    let timeout = (duration + FALLBACK_SLACK_MILLISECONDS) as i32;
+    log_or_panic_result!(
+        shims::set_timeout(fallback.unchecked_ref(), timeout),
+        "Failed to set timeout"
+    );
+
+    Ok(())
+}
+
+/// The longest the animations and transitions of the element take, in milliseconds.
+fn motion_duration(element: &web_sys::Element) -> f64 {
+    let Ok(Some(style)) = get_window().get_computed_style(element) else {
+        return 0.0;
+    };
+    let longest = |property: &str| {
+        style
+            .get_property_value(property)
+            .map_or(0.0, |value| longest_time(&value))
+    };
+
+    let animated = style
+        .get_property_value("animation-name")
+        .is_ok_and(|names| names.split(',').any(|name| name.trim() != "none"));
+    let animation = if animated {
+        longest("animation-duration") + longest("animation-delay")
+    } else {
+        0.0
+    };
+    let transition = longest("transition-duration") + longest("transition-delay");
+
+    animation.max(transition)
+}
+
+/// Get the longest time in a comma separated list of css times, in milliseconds.
+fn longest_time(times: &str) -> f64 {
+    times
+        .split(',')
+        .filter_map(|time| {
+            let time = time.trim();
+            if let Some(milliseconds) = time.strip_suffix("ms") {
+                milliseconds.parse().ok()
+            } else {
+                time.strip_suffix('s')
+                    .and_then(|seconds| seconds.parse::<f64>().ok())
+                    .map(|seconds| seconds * 1000.0)
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_times() {
+        assert!((longest_time("0s") - 0.0).abs() < f64::EPSILON);
+        assert!((longest_time("0.2s, 0.5s") - 500.0).abs() < f64::EPSILON);
+        assert!((longest_time("150ms, 0.1s") - 150.0).abs() < f64::EPSILON);
+        assert!((longest_time("") - 0.0).abs() < f64::EPSILON);
+    }
+}
@@ -69,8 +69,6 @@ pub(crate) use {log_or_panic, log_or_panic_assert, log_or_panic_result};
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     #[should_panic(expected = "Error in release mode")]
     fn test_debug_expect() {
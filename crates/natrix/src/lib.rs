@@ -2,13 +2,19 @@
 #![cfg_attr(not(feature = "_internal_no_ssg"), forbid(unsafe_code))]
 
 pub mod access;
+mod assets;
 
 pub mod async_utils;
 pub mod css;
 pub mod dom;
 mod error_handling;
+pub mod log;
+pub mod media;
 pub mod panics;
 pub mod reactivity;
+pub mod router;
+pub mod shims;
+pub mod storage;
 pub mod test_utils;
 mod type_macros;
 
@@ -54,6 +60,18 @@ pub(crate) fn get_window() -> web_sys::Window {
     WINDOW.with(Clone::clone)
 }
 
+/// The path the app is served under, such as `/myapp`, set with `natrix build --base-path` or
+/// `base_path` in the config.
+///
+/// This is empty when the app is served at the root, and never ends with a `/`, so links can be
+/// built with `format!("{}/about", natrix::base_path())`.
+/// [`asset!`] urls and the [`router`] already include it.
+#[must_use]
+pub fn base_path() -> &'static str {
+    // NOTE: Must match `natrix_shared::BASE_PATH`, `option_env!` only takes a literal.
+    option_env!("NATRIX_BASE_PATH").unwrap_or_default()
+}
+
 /// Commonly used types and traits.
 pub mod prelude {
     pub use natrix_macros::State;
@@ -66,6 +84,7 @@ pub mod prelude {
         IntoCompoundSelector,
         IntoFinalizedSelector,
     };
+    pub use super::dom::events::EventExt;
     pub use super::dom::{Element, events, html_elements as e};
     pub use super::reactivity::State;
     pub use super::reactivity::signal::Signal;
@@ -73,8 +92,17 @@ pub mod prelude {
     pub use super::{field, with};
 }
 
+pub use assets::AssetDir;
 pub use dom::Element;
-pub use natrix_macros::{State, asset, data, format_elements};
+pub use natrix_macros::{
+    Snapshot,
+    State,
+    asset,
+    component,
+    dir_asset,
+    format_elements,
+    text_template,
+};
 pub use reactivity::mount::mount;
 pub use reactivity::state::{EventCtx, RenderCtx};
 
@@ -87,5 +115,6 @@ pub mod macro_ref {
 
     pub use super::css;
     pub use super::dom::element::Element;
-    pub use super::reactivity::state::State;
+    pub use super::reactivity::state::guards::guard_match_failed;
+    pub use super::reactivity::state::{SkippedField, SkippedNotState, Snapshot, State};
 }
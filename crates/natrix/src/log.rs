@@ -0,0 +1,103 @@
+//! Leveled logging macros that compile away in release builds.
+//!
+//! These forward to the [`log`](https://docs.rs/log) crate, which the `console_log` feature sets
+//! up to log to the browser console with the file and line of the call on
+//! [`mount`](crate::reactivity::mount::mount).
+//!
+//! In release builds the calls expand to dead code, so the messages are neither formatted nor
+//! included in the wasm binary, unless the `release_logs` feature is enabled.
+//! The arguments are still type checked, so a variable only used in a log does not cause a unused
+//! warning in release builds.
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! # #[derive(State)]
+//! # struct App {
+//! #     counter: Signal<u32>,
+//! # }
+//! # fn render() -> impl Element<App> {
+//! e::button().on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+//!     *ctx.counter += 1;
+//!     natrix::log::debug!("Counter is now {}", *ctx.counter);
+//! })
+//! # }
+//! ```
+
+#[doc(hidden)]
+pub use ::log as __log;
+
+/// Forward the call to the `log` crate
+#[cfg(any(debug_assertions, feature = "release_logs"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_log {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::log::__log::$level!($($arg)+)
+    };
+}
+
+/// Type check the call, but never run it, so it is removed from the binary
+#[cfg(not(any(debug_assertions, feature = "release_logs")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_log {
+    ($level:ident, $($arg:tt)+) => {
+        if false {
+            $crate::log::__log::$level!($($arg)+)
+        }
+    };
+}
+
+/// Log a message at the `error` level, see [`log::error`](https://docs.rs/log/latest/log/macro.error.html)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_error {
+    ($($arg:tt)+) => {
+        $crate::__natrix_log!(error, $($arg)+)
+    };
+}
+
+/// Log a message at the `warn` level, see [`log::warn`](https://docs.rs/log/latest/log/macro.warn.html)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_warn {
+    ($($arg:tt)+) => {
+        $crate::__natrix_log!(warn, $($arg)+)
+    };
+}
+
+/// Log a message at the `info` level, see [`log::info`](https://docs.rs/log/latest/log/macro.info.html)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_info {
+    ($($arg:tt)+) => {
+        $crate::__natrix_log!(info, $($arg)+)
+    };
+}
+
+/// Log a message at the `debug` level, see [`log::debug`](https://docs.rs/log/latest/log/macro.debug.html)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_debug {
+    ($($arg:tt)+) => {
+        $crate::__natrix_log!(debug, $($arg)+)
+    };
+}
+
+/// Log a message at the `trace` level, see [`log::trace`](https://docs.rs/log/latest/log/macro.trace.html)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __natrix_trace {
+    ($($arg:tt)+) => {
+        $crate::__natrix_log!(trace, $($arg)+)
+    };
+}
+
+#[doc(inline)]
+pub use crate::{
+    __natrix_debug as debug,
+    __natrix_error as error,
+    __natrix_info as info,
+    __natrix_trace as trace,
+    __natrix_warn as warn,
+};
@@ -0,0 +1,160 @@
+//! Reacting to `@media` queries at runtime.
+//!
+//! Put a [`MediaMatch`] in your state, and call [`HtmlElement::watch_media`] on a element that
+//! lives for as long as you need it to stay up to date, such as the root element.
+//! Reading [`MediaMatch::matches`] is tracked like reading a signal, so closures reading it are
+//! rerun when the query starts or stops matching.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! use natrix::media::MediaMatch;
+//!
+//! #[derive(State)]
+//! struct App {
+//!     reduced_motion: MediaMatch,
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .watch_media(|ctx: Ref<App>| field!(ctx.reduced_motion))
+//!         .text(|ctx: RenderCtx<App>| {
+//!             if ctx.reduced_motion.matches() {
+//!                 "Animations are disabled"
+//!             } else {
+//!                 "Animations are enabled"
+//!             }
+//!         })
+//! }
+//! ```
+//!
+//! The state is then created with `MediaMatch::reduced_motion()`.
+
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+
+use crate::access::{Getter, RefClosure};
+use crate::css::IntoCss;
+use crate::css::media::MediaQuery;
+use crate::dom::events::EventListener;
+use crate::dom::html_elements::HtmlElement;
+use crate::error_handling::log_or_panic;
+use crate::get_window;
+use crate::reactivity::State;
+use crate::reactivity::signal::Signal;
+use crate::reactivity::state::{EventCtx, Snapshot};
+
+/// Whether a media query currently matches.
+///
+/// Reading it is tracked like reading a signal.
+/// It is only kept up to date while a element with [`HtmlElement::watch_media`] for it is
+/// rendered.
+pub struct MediaMatch {
+    /// The media query
+    query: String,
+    /// Whether the query currently matches
+    matches: Signal<bool>,
+}
+
+impl State for MediaMatch {
+    fn set(&mut self, new: Self) {
+        self.query = new.query;
+        self.matches.set(new.matches);
+    }
+}
+
+impl Snapshot for MediaMatch {
+    fn snapshot(&self) -> Self {
+        Self {
+            query: self.query.clone(),
+            matches: self.matches.snapshot(),
+        }
+    }
+}
+
+impl MediaMatch {
+    /// Start tracking the given media query, using whether it matches right now
+    #[must_use]
+    pub fn new(query: MediaQuery) -> Self {
+        let query = query.into_css();
+        let matches = media_query_list(&query).is_some_and(|list| list.matches());
+        Self {
+            query,
+            matches: Signal::new(matches),
+        }
+    }
+
+    /// Track whether the user has asked for less motion, such as fewer animations
+    #[must_use]
+    pub fn reduced_motion() -> Self {
+        Self::new(MediaQuery::reduced_motion())
+    }
+
+    /// Track whether the user prefers a dark color scheme
+    #[must_use]
+    pub fn dark_mode() -> Self {
+        Self::new(MediaQuery::dark_mode())
+    }
+
+    /// Whether the media query currently matches
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        *self.matches
+    }
+
+    /// The media query as css, such as `(prefers-reduced-motion:reduce)`
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+impl<C: State, T> HtmlElement<C, T> {
+    /// Keep the [`MediaMatch`] returned by the getter up to date while this element is rendered.
+    ///
+    /// The change listener is removed when the element is removed.
+    pub fn watch_media(mut self, media: impl Getter<C, MediaMatch>) -> Self {
+        self.deferred.push(Box::new(move |ctx, rendering_state| {
+            let mut ctx = EventCtx(ctx);
+            let handle = ctx.weak_handle();
+            let current = media.call_mut(&mut ctx);
+            let Some(list) = media_query_list(&current.query) else {
+                return;
+            };
+            let stale = *current.matches != list.matches();
+
+            let update_list = list.clone();
+            let update = Rc::new(move || {
+                let matches = update_list.matches();
+                handle.with_mut(|mut ctx| {
+                    media.call_mut(&mut ctx).matches.update_if_changed(matches);
+                });
+            });
+
+            // NOTE: Whether it matches might have changed between creating the state and
+            // rendering, which is synced after the render as signals must not be modified in it.
+            if stale {
+                let update = Rc::clone(&update);
+                let callback = Closure::once_into_js(move || update());
+                get_window().queue_microtask(callback.unchecked_ref());
+            }
+
+            if let Some(listener) = EventListener::new(list.into(), "change", move |_| update()) {
+                rendering_state.keep_alive.push(Box::new(listener));
+            }
+        }));
+        self
+    }
+}
+
+/// Get the `MediaQueryList` for the query
+fn media_query_list(query: &str) -> Option<web_sys::MediaQueryList> {
+    if let Ok(Some(list)) = get_window().match_media(query) {
+        Some(list)
+    } else {
+        log_or_panic!("Failed to evaluate media query {query}");
+        None
+    }
+}
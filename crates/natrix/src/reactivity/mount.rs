@@ -118,6 +118,9 @@ pub fn render_state<C: State>(
         .replace_with_with_node_1(&node)
         .map_err(|_| "Failed to replace mount point")?;
 
+    // NOTE: Runs the callbacks queued during the initial render, such as `on_mount` handlers,
+    // now that the tree is in the document.
+    borrow_data.track_changes(|_| ());
     drop(borrow_data);
 
     Ok(RenderResult { data, keep_alive })
@@ -1,10 +1,16 @@
 //! Implements the reactive hooks for updating the dom in response to signal changessz.
 
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::rc::Weak;
 
 use wasm_bindgen::JsCast;
 
+use crate::dom::classes::ClassList;
 use crate::dom::element::{ElementRenderResult, MaybeStaticElement, generate_fallback_node};
+use crate::dom::transition;
 use crate::error_handling::{log_or_panic, log_or_panic_result};
 use crate::get_document;
 use crate::reactivity::state::{HookKey, InnerCtx, RenderCtx};
@@ -42,12 +48,81 @@ pub(crate) enum UpdateResult {
     RunHook(HookKey, Vec<HookKey>),
 }
 
+/// The nodes a rendered element was inserted as.
+///
+/// Most elements are a single node, but a fragment inserts all of its children, which start and
+/// end with a anchor comment.
+struct NodeRange {
+    /// The first node
+    first: web_sys::Node,
+    /// The last node, the same as `first` for a single node
+    last: web_sys::Node,
+}
+
+impl NodeRange {
+    /// Get the nodes the node is inserted as.
+    ///
+    /// This has to be called before the node is inserted, as inserting a fragment empties it.
+    fn new(node: &web_sys::Node) -> Self {
+        if let Some(fragment) = node.dyn_ref::<web_sys::DocumentFragment>()
+            && let (Some(first), Some(last)) = (fragment.first_child(), fragment.last_child())
+        {
+            Self { first, last }
+        } else {
+            Self {
+                first: node.clone(),
+                last: node.clone(),
+            }
+        }
+    }
+
+    /// Whether this is a single node
+    fn is_single(&self) -> bool {
+        self.first == self.last
+    }
+
+    /// The nodes in the range, in order
+    fn nodes(&self) -> Vec<web_sys::Node> {
+        let mut nodes = vec![self.first.clone()];
+        let mut current = self.first.clone();
+        while current != self.last {
+            let Some(next) = current.next_sibling() else {
+                log_or_panic!("End of node range not found.");
+                break;
+            };
+            nodes.push(next.clone());
+            current = next;
+        }
+        nodes
+    }
+
+    /// Get a node that inserts all the nodes of the range, moving them from where they are
+    fn take(&self) -> web_sys::Node {
+        if self.is_single() {
+            return self.first.clone();
+        }
+
+        let fragment = get_document().create_document_fragment();
+        for node in self.nodes() {
+            log_or_panic_result!(fragment.append_child(&node), "Failed to move node");
+        }
+        fragment.into()
+    }
+
+    /// Remove the nodes from the parent
+    fn remove(&self, parent: &web_sys::Node) {
+        for node in self.nodes() {
+            log_or_panic_result!(parent.remove_child(&node), "Failed to remove node");
+        }
+    }
+}
+
 /// Reactive hook for swapping out a entire dom node.
 pub(crate) struct ReactiveNode<C: State> {
     /// The callback to produce nodes
     callback: Box<dyn Fn(RenderCtx<C>) -> MaybeStaticElement<C>>,
-    /// The current rendered node to replace
-    target_node: web_sys::Node,
+    /// The currently rendered nodes to replace
+    target: NodeRange,
     /// Vector of various objects to be kept alive for the duration of the rendered content
     keep_alive: Vec<KeepAlive>,
     /// Hooks that are a child of this
@@ -58,7 +133,7 @@ impl<C: State> ReactiveNode<C> {
     /// Render this hook and simply return the node
     ///
     /// INVARIANT: This function works with the assumption what it returns will be put in its
-    /// `target_node` field. This function is split out to facilitate `Self::create_initial`
+    /// `target` field. This function is split out to facilitate `Self::create_initial`
     fn render(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> ElementRenderResult {
         let element = ctx.track_reads(you, |ctx| {
             (self.callback)(RenderCtx {
@@ -93,12 +168,12 @@ impl<C: State> ReactiveNode<C> {
 
         let mut this = Self {
             callback,
-            target_node: dummy_node,
+            target: NodeRange::new(&dummy_node),
             keep_alive: Vec::new(),
             hooks: Vec::new(),
         };
         let node = this.render(ctx, me).into_node();
-        this.target_node = node.clone();
+        this.target = NodeRange::new(&node);
         ctx.hooks.set_hook(me, Box::new(this));
 
         (me, node)
@@ -109,12 +184,15 @@ impl<C: State> ReactiveHook<C> for ReactiveNode<C> {
     fn update(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> UpdateResult {
         let this = &mut *self;
         let hooks = std::mem::take(&mut this.hooks);
+        let keep_alive = std::mem::take(&mut this.keep_alive);
         let new_node = this.render(ctx, you);
 
         let new_node = match new_node {
             ElementRenderResult::Node(new_node) => new_node,
             ElementRenderResult::Text(new_text) => {
-                if let Some(target_node) = this.target_node.dyn_ref::<web_sys::Text>() {
+                if this.target.is_single()
+                    && let Some(target_node) = this.target.first.dyn_ref::<web_sys::Text>()
+                {
                     target_node.set_text_content(Some(&new_text));
                     return UpdateResult::DropHooks(hooks);
                 }
@@ -123,16 +201,641 @@ impl<C: State> ReactiveHook<C> for ReactiveNode<C> {
             }
         };
 
-        let Some(parent) = this.target_node.parent_node() else {
+        let Some(parent) = this.target.first.parent_node() else {
             log_or_panic!("Parent node of target node not found.");
             return UpdateResult::DropHooks(hooks);
         };
 
+        let new_target = NodeRange::new(&new_node);
+        let dropped_hooks = if this.target.is_single() {
+            let leaving = LeavingContent {
+                state: ctx.this.clone(),
+                hooks,
+                _keep_alive: keep_alive,
+            };
+            match transition::start_leave(&this.target.first, leaving) {
+                Ok(()) => {
+                    // NOTE: The old node removes itself once its leave animation is done
+                    log_or_panic_result!(
+                        parent.insert_before(&new_node, Some(&this.target.first)),
+                        "Failed to insert node"
+                    );
+                    Vec::new()
+                }
+                Err(mut leaving) => {
+                    log_or_panic_result!(
+                        parent.replace_child(&new_node, &this.target.first),
+                        "Failed to replace parent"
+                    );
+                    std::mem::take(&mut leaving.hooks)
+                }
+            }
+        } else {
+            log_or_panic_result!(
+                parent.insert_before(&new_node, Some(&this.target.first)),
+                "Failed to insert node"
+            );
+            this.target.remove(&parent);
+            hooks
+        };
+        this.target = new_target;
+
+        UpdateResult::DropHooks(dropped_hooks)
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        self.hooks
+    }
+}
+
+/// The hooks and kept alive objects of a node that is animating out.
+///
+/// These are dropped once the node is removed, so its event handlers and reactive bindings keep
+/// working while the leave animation plays.
+struct LeavingContent<C: State> {
+    /// The state the hooks belong to
+    state: Weak<RefCell<InnerCtx<C>>>,
+    /// The hooks rendered as part of the node
+    hooks: Vec<HookKey>,
+    /// The objects kept alive for the node, such as event handlers
+    _keep_alive: Vec<KeepAlive>,
+}
+
+impl<C: State> Drop for LeavingContent<C> {
+    fn drop(&mut self) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        let Ok(mut state) = state.try_borrow_mut() else {
+            log_or_panic!("State already borrowed when removing a left node");
+            return;
+        };
+        for hook in self.hooks.drain(..) {
+            state.hooks.drop_hook(hook);
+        }
+    }
+}
+
+/// Getter for the map rendered by a `ReactiveMap`
+pub(crate) type MapGetter<C, K, V> = Box<dyn for<'a> Fn(&'a C) -> &'a BTreeMap<K, V>>;
+/// Render function for a entry of a `ReactiveMap`
+pub(crate) type MapRender<C, K, V> = Box<dyn Fn(&K, &V) -> MaybeStaticElement<C>>;
+
+/// A rendered entry of a `ReactiveMap`
+struct MapEntry<V> {
+    /// The value this entry was rendered with
+    value: V,
+    /// The rendered nodes
+    nodes: NodeRange,
+    /// Vector of various objects to be kept alive for the duration of the entry
+    #[expect(
+        dead_code,
+        reason = "This is used to keep the entry alive and we do not need to use it"
+    )]
+    keep_alive: Vec<KeepAlive>,
+    /// Hooks that are a child of this entry
+    hooks: Vec<HookKey>,
+}
+
+/// Reactive hook for rendering the entries of a map, keyed on the map keys.
+///
+/// Only entries that are added, removed, or had their value changed touch the dom.
+pub(crate) struct ReactiveMap<C: State, K, V> {
+    /// Get the map to render
+    getter: MapGetter<C, K, V>,
+    /// Render a entry
+    render: MapRender<C, K, V>,
+    /// The entries are rendered directly after this node
+    anchor: web_sys::Node,
+    /// The currently rendered entries
+    entries: BTreeMap<K, MapEntry<V>>,
+}
+
+impl<C: State, K: Ord + Clone + 'static, V: PartialEq + Clone + 'static> ReactiveMap<C, K, V> {
+    /// Create a new `ReactiveMap`, rendering the initial entries after `anchor`.
+    /// Returns the hook key of the hook.
+    pub(crate) fn init_new(
+        getter: MapGetter<C, K, V>,
+        render: MapRender<C, K, V>,
+        anchor: web_sys::Node,
+        ctx: &mut InnerCtx<C>,
+    ) -> HookKey {
+        let me = ctx.hooks.reserve_key();
+
+        let mut this = Self {
+            getter,
+            render,
+            anchor,
+            entries: BTreeMap::new(),
+        };
+        this.update(ctx, me);
+
+        ctx.hooks.set_hook(me, Box::new(this));
+        me
+    }
+
+    /// Render a new entry
+    fn render_entry(&self, ctx: &mut InnerCtx<C>, key: &K, value: V) -> MapEntry<V> {
+        render_map_entry(ctx, (self.render)(key, &value), value)
+    }
+}
+
+/// Render the element of a map entry
+fn render_map_entry<C: State, V>(
+    ctx: &mut InnerCtx<C>,
+    element: MaybeStaticElement<C>,
+    value: V,
+) -> MapEntry<V> {
+    let mut keep_alive = Vec::new();
+    let mut hooks = Vec::new();
+    let node = element
+        .render(
+            ctx,
+            &mut RenderingState {
+                keep_alive: &mut keep_alive,
+                hooks: &mut hooks,
+            },
+        )
+        .into_node();
+
+    MapEntry {
+        value,
+        nodes: NodeRange::new(&node),
+        keep_alive,
+        hooks,
+    }
+}
+
+impl<C: State, K: Ord + Clone + 'static, V: PartialEq + Clone + 'static> ReactiveHook<C>
+    for ReactiveMap<C, K, V>
+{
+    fn update(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> UpdateResult {
+        // Only clone the values that actually need to be rendered.
+        let items = ctx.track_reads(you, |ctx| {
+            (self.getter)(&ctx.data)
+                .iter()
+                .map(|(key, value)| {
+                    let unchanged = self
+                        .entries
+                        .get(key)
+                        .is_some_and(|entry| entry.value == *value);
+                    (key.clone(), (!unchanged).then(|| value.clone()))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let Some(parent) = self.anchor.parent_node() else {
+            log_or_panic!("Parent node of map anchor not found.");
+            return UpdateResult::DropHooks(Vec::new());
+        };
+
+        let mut dropped_hooks = Vec::new();
+        let mut old_entries = std::mem::take(&mut self.entries);
+
+        // Remove the old entries first so that kept entries are already next to each other.
+        old_entries.retain(|key, entry| {
+            let kept = items
+                .binary_search_by(|(new_key, _)| new_key.cmp(key))
+                .is_ok();
+            if !kept {
+                entry.nodes.remove(&parent);
+                dropped_hooks.append(&mut entry.hooks);
+            }
+            kept
+        });
+
+        let mut cursor = self.anchor.clone();
+        for (key, value) in items {
+            let entry = match value {
+                None => {
+                    let Some(entry) = old_entries.remove(&key) else {
+                        log_or_panic!("Unchanged map entry not found.");
+                        continue;
+                    };
+                    entry
+                }
+                Some(value) => {
+                    if let Some(mut old_entry) = old_entries.remove(&key) {
+                        old_entry.nodes.remove(&parent);
+                        dropped_hooks.append(&mut old_entry.hooks);
+                    }
+                    self.render_entry(ctx, &key, value)
+                }
+            };
+
+            let next = cursor.next_sibling();
+            if next.as_ref() != Some(&entry.nodes.first) {
+                log_or_panic_result!(
+                    parent.insert_before(&entry.nodes.take(), next.as_ref()),
+                    "Failed to insert map entry"
+                );
+            }
+            cursor = entry.nodes.last.clone();
+            self.entries.insert(key, entry);
+        }
+
+        UpdateResult::DropHooks(dropped_hooks)
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        self.entries
+            .into_values()
+            .flat_map(|entry| entry.hooks)
+            .collect()
+    }
+}
+
+/// Getter for the items rendered by a `ReactiveKeyedList`
+pub(crate) type ListGetter<C, T> = Box<dyn for<'a> Fn(&'a C) -> &'a [T]>;
+/// Get the key of a item of a `ReactiveKeyedList`
+pub(crate) type ListKey<T, K> = Box<dyn Fn(&T) -> K>;
+/// Render function for a item of a `ReactiveKeyedList`
+pub(crate) type ListRender<C, T> = Box<dyn Fn(&T) -> MaybeStaticElement<C>>;
+
+/// Reactive hook for rendering the items of a list, keyed on a key derived from each item.
+///
+/// Existing nodes are matched up with the new items by key, so reordering the list moves the
+/// nodes instead of recreating them.
+/// Only items that are added, or had their value changed, are rendered.
+pub(crate) struct ReactiveKeyedList<C: State, T, K> {
+    /// Get the items to render
+    getter: ListGetter<C, T>,
+    /// Get the key of a item
+    key: ListKey<T, K>,
+    /// Render a item
+    render: ListRender<C, T>,
+    /// The items are rendered directly after this node
+    anchor: web_sys::Node,
+    /// The currently rendered items, in order
+    entries: Vec<(K, MapEntry<T>)>,
+}
+
+impl<C: State, T: PartialEq + Clone + 'static, K: Hash + Eq + Clone + 'static>
+    ReactiveKeyedList<C, T, K>
+{
+    /// Create a new `ReactiveKeyedList`, rendering the initial items after `anchor`.
+    /// Returns the hook key of the hook.
+    pub(crate) fn init_new(
+        getter: ListGetter<C, T>,
+        key: ListKey<T, K>,
+        render: ListRender<C, T>,
+        anchor: web_sys::Node,
+        ctx: &mut InnerCtx<C>,
+    ) -> HookKey {
+        let me = ctx.hooks.reserve_key();
+
+        let mut this = Self {
+            getter,
+            key,
+            render,
+            anchor,
+            entries: Vec::new(),
+        };
+        this.update(ctx, me);
+
+        ctx.hooks.set_hook(me, Box::new(this));
+        me
+    }
+}
+
+impl<C: State, T: PartialEq + Clone + 'static, K: Hash + Eq + Clone + 'static> ReactiveHook<C>
+    for ReactiveKeyedList<C, T, K>
+{
+    fn update(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> UpdateResult {
+        let Some(parent) = self.anchor.parent_node() else {
+            log_or_panic!("Parent node of keyed list anchor not found.");
+            return UpdateResult::DropHooks(Vec::new());
+        };
+
+        // The old entries along with their old position
+        let mut old_entries = std::mem::take(&mut self.entries)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, entry))| (key, (index, entry)))
+            .collect::<HashMap<_, _>>();
+
+        // Only clone the values that actually need to be rendered.
+        let items = ctx.track_reads(you, |ctx| {
+            let mut seen = HashSet::new();
+            (self.getter)(&ctx.data)
+                .iter()
+                .filter_map(|item| {
+                    let key = (self.key)(item);
+                    if !seen.insert(key.clone()) {
+                        log_or_panic!(
+                            "Duplicate key in keyed list, only the first item is rendered."
+                        );
+                        return None;
+                    }
+                    let unchanged = old_entries
+                        .get(&key)
+                        .is_some_and(|(_, entry)| entry.value == *item);
+                    Some((key, (!unchanged).then(|| item.clone())))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut entries = Vec::with_capacity(items.len());
+        let mut old_positions = Vec::with_capacity(items.len());
+        for (key, value) in items {
+            let (old_position, entry) = match value {
+                None => {
+                    let Some((index, entry)) = old_entries.remove(&key) else {
+                        log_or_panic!("Unchanged keyed list entry not found.");
+                        continue;
+                    };
+                    (Some(index), entry)
+                }
+                Some(value) => (None, render_map_entry(ctx, (self.render)(&value), value)),
+            };
+            old_positions.push(old_position);
+            entries.push((key, entry));
+        }
+
+        // Anything left is either removed, or was re-rendered.
+        let mut dropped_hooks = Vec::new();
+        for (_, mut entry) in old_entries.into_values() {
+            entry.nodes.remove(&parent);
+            dropped_hooks.append(&mut entry.hooks);
+        }
+
+        // The longest run of entries that are already in the right order stay where they are,
+        // every other entry is moved after the entry before it.
+        let stable = longest_increasing_subsequence(&old_positions);
+        let mut cursor = self.anchor.clone();
+        for ((_, entry), stable) in entries.iter().zip(stable) {
+            if !stable {
+                log_or_panic_result!(
+                    parent.insert_before(&entry.nodes.take(), cursor.next_sibling().as_ref()),
+                    "Failed to insert keyed list entry"
+                );
+            }
+            cursor = entry.nodes.last.clone();
+        }
+        self.entries = entries;
+
+        UpdateResult::DropHooks(dropped_hooks)
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        self.entries
+            .into_iter()
+            .flat_map(|(_, entry)| entry.hooks)
+            .collect()
+    }
+}
+
+/// Find the longest strictly increasing subsequence of the values, ignoring `None`s.
+///
+/// Returns whether each value is part of the subsequence.
+fn longest_increasing_subsequence(values: &[Option<usize>]) -> Vec<bool> {
+    // The smallest last value of a increasing subsequence of each length, and its index
+    let mut tails: Vec<(usize, usize)> = Vec::new();
+    // The index of the previous value in the subsequence ending at each value
+    let mut previous = vec![None; values.len()];
+
+    for (index, value) in values.iter().enumerate() {
+        let Some(value) = *value else {
+            continue;
+        };
+
+        let length = tails.partition_point(|(tail, _)| *tail < value);
+        if let Some(slot) = previous.get_mut(index) {
+            *slot = length
+                .checked_sub(1)
+                .and_then(|before| tails.get(before))
+                .map(|(_, before)| *before);
+        }
+        if let Some(tail) = tails.get_mut(length) {
+            *tail = (value, index);
+        } else {
+            tails.push((value, index));
+        }
+    }
+
+    let mut result = vec![false; values.len()];
+    let mut current = tails.last().map(|(_, index)| *index);
+    while let Some(index) = current {
+        if let Some(slot) = result.get_mut(index) {
+            *slot = true;
+        }
+        current = previous.get(index).copied().flatten();
+    }
+    result
+}
+
+/// Getter for the key selecting the subtree rendered by a `ReactiveKeepAlive`
+pub(crate) type KeepAliveKey<C, K> = Box<dyn Fn(&C) -> K>;
+/// Render function for a subtree of a `ReactiveKeepAlive`
+pub(crate) type KeepAliveRender<C, K> = Box<dyn Fn(&K) -> MaybeStaticElement<C>>;
+
+/// A rendered subtree of a `ReactiveKeepAlive`
+struct KeptSubtree<K> {
+    /// The key this subtree was rendered for
+    key: K,
+    /// Holds the nodes of the subtree while it is detached.
+    ///
+    /// Keeping the nodes in a fragment means they still have a parent, so reactive nodes in the
+    /// subtree can keep updating while it is not in the document.
+    fragment: web_sys::DocumentFragment,
+    /// Vector of various objects to be kept alive for the duration of the subtree
+    #[expect(
+        dead_code,
+        reason = "This is used to keep the subtree alive and we do not need to use it"
+    )]
+    keep_alive: Vec<KeepAlive>,
+    /// Hooks that are a child of this subtree
+    hooks: Vec<HookKey>,
+}
+
+/// Reactive hook for rendering one of multiple subtrees selected by a key, keeping the
+/// previously shown subtrees alive (but detached) so they can be re-attached with their state.
+///
+/// At most `capacity` detached subtrees are kept, when there are more the least recently shown
+/// one is dropped.
+pub(crate) struct ReactiveKeepAlive<C: State, K> {
+    /// Get the key of the subtree to show
+    key: KeepAliveKey<C, K>,
+    /// Render a subtree
+    render: KeepAliveRender<C, K>,
+    /// The subtree is rendered directly after this node
+    start: web_sys::Node,
+    /// The subtree is rendered directly before this node
+    end: web_sys::Node,
+    /// The subtree currently in the dom
+    current: Option<KeptSubtree<K>>,
+    /// The detached subtrees, the most recently shown is last
+    cached: Vec<KeptSubtree<K>>,
+    /// The maximum amount of detached subtrees to keep
+    capacity: usize,
+}
+
+impl<C: State, K: PartialEq + 'static> ReactiveKeepAlive<C, K> {
+    /// Create a new `ReactiveKeepAlive`, rendering the initial subtree between `start` and `end`.
+    /// Returns the hook key of the hook.
+    pub(crate) fn init_new(
+        key: KeepAliveKey<C, K>,
+        render: KeepAliveRender<C, K>,
+        start: web_sys::Node,
+        end: web_sys::Node,
+        capacity: usize,
+        ctx: &mut InnerCtx<C>,
+    ) -> HookKey {
+        let me = ctx.hooks.reserve_key();
+
+        let mut this = Self {
+            key,
+            render,
+            start,
+            end,
+            current: None,
+            cached: Vec::new(),
+            capacity,
+        };
+        this.update(ctx, me);
+
+        ctx.hooks.set_hook(me, Box::new(this));
+        me
+    }
+
+    /// Render a new subtree into a fragment
+    fn render_subtree(&self, ctx: &mut InnerCtx<C>, key: K) -> KeptSubtree<K> {
+        let mut keep_alive = Vec::new();
+        let mut hooks = Vec::new();
+        let node = (self.render)(&key)
+            .render(
+                ctx,
+                &mut RenderingState {
+                    keep_alive: &mut keep_alive,
+                    hooks: &mut hooks,
+                },
+            )
+            .into_node();
+
+        let fragment = get_document().create_document_fragment();
+        log_or_panic_result!(
+            fragment.append_child(&node),
+            "Failed to append keep alive subtree to fragment"
+        );
+
+        KeptSubtree {
+            key,
+            fragment,
+            keep_alive,
+            hooks,
+        }
+    }
+
+    /// Move the nodes between the anchors into the fragment of the subtree
+    fn detach(&self, subtree: &KeptSubtree<K>) {
+        while let Some(node) = self.start.next_sibling()
+            && node != self.end
+        {
+            log_or_panic_result!(
+                subtree.fragment.append_child(&node),
+                "Failed to detach keep alive subtree"
+            );
+        }
+    }
+}
+
+impl<C: State, K: PartialEq + 'static> ReactiveHook<C> for ReactiveKeepAlive<C, K> {
+    fn update(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> UpdateResult {
+        let key = ctx.track_reads(you, |ctx| (self.key)(&ctx.data));
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.key == key)
+        {
+            return UpdateResult::DropHooks(Vec::new());
+        }
+
+        let Some(parent) = self.end.parent_node() else {
+            log_or_panic!("Parent node of keep alive anchor not found.");
+            return UpdateResult::DropHooks(Vec::new());
+        };
+
+        let mut dropped_hooks = Vec::new();
+        if let Some(current) = self.current.take() {
+            self.detach(&current);
+            self.cached.push(current);
+        }
+
+        let subtree = if let Some(index) = self.cached.iter().position(|subtree| subtree.key == key)
+        {
+            self.cached.remove(index)
+        } else {
+            self.render_subtree(ctx, key)
+        };
+
         log_or_panic_result!(
-            parent.replace_child(&new_node, &this.target_node),
-            "Failed to replace parent"
+            parent.insert_before(&subtree.fragment, Some(&self.end)),
+            "Failed to insert keep alive subtree"
         );
-        this.target_node = new_node;
+        self.current = Some(subtree);
+
+        let overflow = self.cached.len().saturating_sub(self.capacity);
+        for mut evicted in self.cached.drain(..overflow) {
+            dropped_hooks.append(&mut evicted.hooks);
+        }
+
+        UpdateResult::DropHooks(dropped_hooks)
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        self.current
+            .into_iter()
+            .chain(self.cached)
+            .flat_map(|subtree| subtree.hooks)
+            .collect()
+    }
+}
+
+/// Hook re-running a side effect whenever the signals it reads change
+pub(crate) struct EffectHook<C: State> {
+    /// The side effect to run
+    callback: Box<dyn Fn(RenderCtx<C>)>,
+    /// Vector of various objects to be kept alive until the effect is re-run
+    keep_alive: Vec<KeepAlive>,
+    /// Hooks created by the effect
+    hooks: Vec<HookKey>,
+}
+
+impl<C: State> EffectHook<C> {
+    /// Create the hook, running the effect once to register its dependencies.
+    /// Returns the hookkey of the hook
+    pub(crate) fn init_new(callback: Box<dyn Fn(RenderCtx<C>)>, ctx: &mut InnerCtx<C>) -> HookKey {
+        let me = ctx.hooks.reserve_key();
+
+        let mut this = Self {
+            callback,
+            keep_alive: Vec::new(),
+            hooks: Vec::new(),
+        };
+        this.update(ctx, me);
+        ctx.hooks.set_hook(me, Box::new(this));
+
+        me
+    }
+}
+
+impl<C: State> ReactiveHook<C> for EffectHook<C> {
+    fn update(&mut self, ctx: &mut InnerCtx<C>, you: HookKey) -> UpdateResult {
+        let hooks = std::mem::take(&mut self.hooks);
+        self.keep_alive.clear();
+
+        ctx.track_reads(you, |ctx| {
+            (self.callback)(RenderCtx {
+                ctx,
+                render_state: RenderingState {
+                    keep_alive: &mut self.keep_alive,
+                    hooks: &mut self.hooks,
+                },
+            });
+        });
 
         UpdateResult::DropHooks(hooks)
     }
@@ -266,30 +969,145 @@ impl ReactiveValue for ReactiveAttribute {
     }
 }
 
+/// Reactively set the inner html of a element
+pub(crate) struct ReactiveInnerHtml {
+    /// The html to set
+    pub(crate) data: Cow<'static, str>,
+}
+
+impl ReactiveValue for ReactiveInnerHtml {
+    type State = ();
+
+    fn apply(self, node: &web_sys::Element, _state: &mut Self::State) {
+        node.set_inner_html(&self.data);
+    }
+}
+
 /// Reactively set a element class
 pub(crate) struct ReactiveClass {
-    /// The class value to apply
-    pub(crate) data: Option<Cow<'static, str>>,
+    /// The classes to apply
+    pub(crate) data: ClassList,
 }
 
 impl ReactiveValue for ReactiveClass {
-    type State = Option<Cow<'static, str>>;
+    type State = ClassList;
 
     fn apply(self, node: &web_sys::Element, state: &mut Self::State) {
         let class_list = node.class_list();
 
-        match (&state, &self.data) {
-            (None, None) => {}
-            (Some(prev), None) => {
+        // NOTE: Only the classes this value set before are touched, so classes from other
+        // sources, including ones added outside natrix, are kept.
+        if let ([prev], [new]) = (state.as_slice(), self.data.as_slice()) {
+            // NOTE: Replacing keeps the position of the class in the class list.
+            // It does nothing if the previous class was already removed, so add it then.
+            match class_list.replace(prev, new) {
+                Ok(true) => {}
+                Ok(false) => {
+                    log_or_panic_result!(class_list.add_1(new), "Failed to add class");
+                }
+                Err(_) => {
+                    log_or_panic!("Failed to replace class");
+                }
+            }
+        } else {
+            for prev in state.iter().filter(|prev| !self.data.contains(prev)) {
                 log_or_panic_result!(class_list.remove_1(prev), "Failed to remove class");
             }
-            (None, Some(new)) => {
+            for new in self.data.iter().filter(|new| !state.contains(new)) {
                 log_or_panic_result!(class_list.add_1(new), "Failed to add class");
             }
-            (Some(prev), Some(new)) => {
-                log_or_panic_result!(class_list.replace(prev, new), "Failed to replace class");
+        }
+        *state = self.data;
+    }
+}
+
+/// Reactively set a list of inline styles
+pub(crate) struct ReactiveStyleMap {
+    /// The style properties to apply
+    pub(crate) data: Vec<(&'static str, String)>,
+}
+
+impl ReactiveValue for ReactiveStyleMap {
+    type State = Vec<(&'static str, String)>;
+
+    fn apply(self, node: &web_sys::Element, state: &mut Self::State) {
+        let Some(node) = node.dyn_ref::<web_sys::HtmlElement>() else {
+            log_or_panic!("`style_map` used on non-html element");
+            return;
+        };
+        let style = node.style();
+
+        for (property, _) in state.iter() {
+            if !self
+                .data
+                .iter()
+                .any(|(new_property, _)| new_property == property)
+            {
+                log_or_panic_result!(
+                    style.remove_property(property),
+                    "Failed to remove style property"
+                );
+            }
+        }
+        for (property, value) in &self.data {
+            let previous = state
+                .iter()
+                .find(|(old_property, _)| old_property == property)
+                .map(|(_, old_value)| old_value);
+            if previous != Some(value) {
+                log_or_panic_result!(
+                    style.set_property(property, value),
+                    "Failed to set style property"
+                );
             }
         }
+
         *state = self.data;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable(values: &[Option<usize>]) -> Vec<usize> {
+        longest_increasing_subsequence(values)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, stable)| stable.then_some(index))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_order_is_stable() {
+        assert_eq!(stable(&[Some(0), Some(1), Some(2)]), [0, 1, 2]);
+    }
+
+    #[test]
+    fn moved_item_is_not_stable() {
+        // The last item moved to the front
+        assert_eq!(stable(&[Some(2), Some(0), Some(1)]), [1, 2]);
+        // The first item moved to the back
+        assert_eq!(stable(&[Some(1), Some(2), Some(0)]), [0, 1]);
+    }
+
+    #[test]
+    fn new_items_are_skipped() {
+        assert_eq!(stable(&[None, Some(0), None, Some(1)]), [1, 3]);
+        assert_eq!(stable(&[None, None]), Vec::<usize>::new());
+        assert_eq!(stable(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn reversed_keeps_one() {
+        assert_eq!(stable(&[Some(3), Some(2), Some(1), Some(0)]).len(), 1);
+    }
+
+    #[test]
+    fn picks_longest_run() {
+        assert_eq!(
+            stable(&[Some(4), Some(0), Some(1), Some(5), Some(2), Some(3)]),
+            [1, 2, 4, 5]
+        );
+    }
+}
@@ -1,12 +1,16 @@
 //! Signals for tracking reactive dependencies and modifications.
 
 use std::cell::RefCell;
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
-use crate::access::{Downgrade, Project, Ref, RefClosure};
+use crate::access::{Downgrade, Getter, Project, Ref, RefClosure};
+use crate::dom::attributes::AttributeResult;
+use crate::dom::element::MaybeStaticElement;
+use crate::dom::{Element, ToAttribute};
 use crate::error_handling::log_or_panic;
 use crate::prelude::State;
-use crate::reactivity::state::SignalDepList;
+use crate::reactivity::state::{RenderCtx, SignalDepList, Snapshot};
 use crate::reactivity::statics;
 
 /// A signal tracks reads and writes to a value, as well as dependencies.
@@ -48,6 +52,12 @@ impl<T: 'static> State for Signal<T> {
     }
 }
 
+impl<T: Clone + 'static> Snapshot for Signal<T> {
+    fn snapshot(&self) -> Self {
+        Self::new(self.data.clone())
+    }
+}
+
 impl<T> Deref for Signal<T> {
     type Target = T;
 
@@ -67,7 +77,7 @@ impl<T> Deref for Signal<T> {
 impl<T> DerefMut for Signal<T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        statics::reg_dirty_list(|| self.deps.get_mut().create_iter_and_clear());
+        self.mark_changed();
 
         &mut self.data
     }
@@ -80,6 +90,362 @@ impl<T: Default> Default for Signal<T> {
     }
 }
 
+impl<T> Signal<T> {
+    /// Get a read only view of this signal.
+    #[inline]
+    #[must_use]
+    pub fn read_only(&self) -> ReadSignal<'_, T> {
+        ReadSignal(self)
+    }
+}
+
+impl<T: PartialEq> Signal<T> {
+    /// Set the value, only marking the signal as changed if it differs from the current one.
+    ///
+    /// Returns whether the value changed.
+    /// Use this when setting a value that is often the same as before, such as a computed
+    /// result, to avoid re-running the hooks that depend on it.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     text: Signal<String>,
+    ///     is_empty: Signal<bool>,
+    /// }
+    ///
+    /// fn on_input(mut ctx: EventCtx<App>, text: String) {
+    ///     let is_empty = text.is_empty();
+    ///     *ctx.text = text;
+    ///     ctx.is_empty.update_if_changed(is_empty);
+    /// }
+    /// ```
+    pub fn update_if_changed(&mut self, new: T) -> bool {
+        // NOTE: Comparing against the field directly does not subscribe the current hook.
+        if self.data == new {
+            return false;
+        }
+        **self = new;
+        true
+    }
+
+    /// Modify the value in place, only marking the signal as changed if the value differs
+    /// afterwards.
+    ///
+    /// Returns whether the value changed.
+    /// This clones the value to compare against, so for large values prefer
+    /// [`with_mut`](Self::with_mut) or `DerefMut` when you know the closure modifies it.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     tags: Signal<Vec<String>>,
+    /// }
+    ///
+    /// fn normalize(mut ctx: EventCtx<App>) {
+    ///     // Only reruns the hooks reading `tags` if a tag was not lowercase already.
+    ///     ctx.tags.with_mut_checked(|tags| {
+    ///         for tag in tags {
+    ///             *tag = tag.to_lowercase();
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn with_mut_checked(&mut self, func: impl FnOnce(&mut T)) -> bool
+    where
+        T: Clone,
+    {
+        let before = self.data.clone();
+        func(&mut self.data);
+        if self.data == before {
+            return false;
+        }
+        self.mark_changed();
+        true
+    }
+}
+
+impl<T> Signal<T> {
+    /// Modify the value in place with the closure, marking the signal as changed.
+    ///
+    /// This is the same as modifying it through `DerefMut`, but keeps the mutation in one place.
+    /// Use [`with_mut_checked`](Self::with_mut_checked) if the closure might leave the value
+    /// unchanged.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     items: Signal<Vec<u32>>,
+    /// }
+    ///
+    /// fn double(mut ctx: EventCtx<App>) {
+    ///     ctx.items.with_mut(|items| items.iter_mut().for_each(|item| *item *= 2));
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_mut<R>(&mut self, func: impl FnOnce(&mut T) -> R) -> R {
+        self.mark_changed();
+        func(&mut self.data)
+    }
+
+    /// Mark the signal as changed without going through `DerefMut`
+    #[inline]
+    fn mark_changed(&mut self) {
+        statics::reg_dirty_list(|| self.deps.get_mut().create_iter_and_clear());
+    }
+}
+
+/// In place list operations that only mark the signal as changed if the list was modified.
+///
+/// Like any other mutation these count as a change of the whole list, so every hook that read
+/// the list is rerun, not just the ones that read the modified items.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// #[derive(State)]
+/// struct App {
+///     todos: Signal<Vec<String>>,
+/// }
+///
+/// fn clear_done(mut ctx: EventCtx<App>) {
+///     // Only reruns the hooks reading `todos` if something was removed.
+///     ctx.todos.retain(|todo| !todo.starts_with("[x]"));
+/// }
+/// ```
+impl<T> Signal<Vec<T>> {
+    /// Append a item to the end of the list.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        self.mark_changed();
+    }
+
+    /// Remove the last item of the list,
+    /// only marking the signal as changed if the list was not empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.data.pop()?;
+        self.mark_changed();
+        Some(value)
+    }
+
+    /// Remove the item at `index`, shifting the items after it down.
+    ///
+    /// Unlike [`Vec::remove`] this returns an `Option`,
+    /// giving `None` without marking the signal as changed if `index` is out of bounds
+    /// instead of panicking.
+    /// This shadows [`Vec::remove`], so `signal.remove(i)` never reaches the panicking version
+    /// through `DerefMut`, use `(*signal).remove(i)` if you want that behaviour.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.data.len() {
+            return None;
+        }
+        let value = self.data.remove(index);
+        self.mark_changed();
+        Some(value)
+    }
+
+    /// Keep only the items matching `predicate`,
+    /// only marking the signal as changed if any items were removed.
+    #[inline]
+    pub fn retain(&mut self, predicate: impl FnMut(&T) -> bool) {
+        let before = self.data.len();
+        self.data.retain(predicate);
+        if self.data.len() != before {
+            self.mark_changed();
+        }
+    }
+
+    /// Remove all items, only marking the signal as changed if the list was not empty.
+    #[inline]
+    pub fn clear(&mut self) {
+        if self.data.is_empty() {
+            return;
+        }
+        self.data.clear();
+        self.mark_changed();
+    }
+}
+
+/// A read only view of a [`Signal`].
+///
+/// Reading the value still subscribes the current hook like reading the signal directly would.
+/// Use this in reusable render functions that only need to read a value, to make it clear at
+/// the type level that they can not modify it.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::reactivity::signal::ReadSignal;
+///
+/// fn greeting(name: ReadSignal<String>) -> String {
+///     format!("Hello {}", *name)
+/// }
+///
+/// #[derive(State)]
+/// struct App {
+///     name: Signal<String>,
+/// }
+///
+/// fn render() -> impl Element<App> {
+///     e::h1().text(|ctx: RenderCtx<App>| greeting(ctx.name.read_only()))
+/// }
+/// ```
+///
+/// It does not allow mutation:
+/// ```rust,compile_fail
+/// # use natrix::prelude::*;
+/// use natrix::reactivity::signal::ReadSignal;
+///
+/// fn clear(mut name: ReadSignal<String>) {
+///     name.clear();
+/// }
+/// ```
+pub struct ReadSignal<'a, T>(&'a Signal<T>);
+
+impl<T> Clone for ReadSignal<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ReadSignal<'_, T> {}
+
+impl<'a, T> From<&'a Signal<T>> for ReadSignal<'a, T> {
+    #[inline]
+    fn from(value: &'a Signal<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for ReadSignal<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ReadSignal<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: 'static> Signal<T> {
+    /// Create a read only value derived from the signal at `getter`.
+    ///
+    /// The value is calculated when it is rendered, and again whenever the signal changes,
+    /// so it can be passed anywhere a reactive closure is accepted, such as `.text` and `.attr`.
+    /// Reading the signal subscribes to it like any other read, so every change to the signal
+    /// counts as a change of the derived value.
+    /// If the result often stays the same use `ctx.watch` instead, which skips those updates.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     count: Signal<u32>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     let doubled = Signal::map(|ctx: Ref<App>| field!(ctx.count), |count| count * 2);
+    ///     e::div()
+    ///         .attr("data-doubled", doubled.clone())
+    ///         .text(doubled)
+    /// }
+    /// ```
+    pub fn map<C, G, F, R>(getter: G, map: F) -> DerivedSignal<T, G, F>
+    where
+        C: State,
+        G: Getter<C, Self>,
+        F: Fn(&T) -> R + Clone + 'static,
+    {
+        DerivedSignal {
+            getter,
+            map,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// A read only value derived from a signal, see [`Signal::map`].
+#[must_use]
+pub struct DerivedSignal<T, G, F> {
+    /// The path to the signal
+    getter: G,
+    /// Calculate the value from the signal
+    map: F,
+    /// The type of the signal value
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T, G: Clone, F: Clone> Clone for DerivedSignal<T, G, F> {
+    fn clone(&self) -> Self {
+        Self {
+            getter: self.getter.clone(),
+            map: self.map.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static, G: 'static, F: 'static> DerivedSignal<T, G, F> {
+    /// Calculate the value, subscribing the current hook to the signal.
+    pub fn get<C, R>(&self, ctx: &C) -> R
+    where
+        C: State,
+        G: Getter<C, Signal<T>>,
+        F: Fn(&T) -> R,
+    {
+        (self.map)(&**self.getter.call_read(ctx))
+    }
+
+    /// Convert this into a reactive closure calculating the value
+    fn into_closure<C, R>(self) -> impl Fn(RenderCtx<C>) -> R + 'static
+    where
+        C: State,
+        G: Getter<C, Signal<T>>,
+        F: Fn(&T) -> R,
+    {
+        move |ctx: RenderCtx<C>| self.get(&ctx)
+    }
+}
+
+impl<C, T, G, F, R> Element<C> for DerivedSignal<T, G, F>
+where
+    C: State,
+    T: 'static,
+    G: Getter<C, Signal<T>>,
+    F: Fn(&T) -> R + 'static,
+    R: Element<C>,
+{
+    #[inline]
+    fn render(self) -> MaybeStaticElement<C> {
+        self.into_closure().render()
+    }
+}
+
+impl<C, T, G, F, R> ToAttribute<C> for DerivedSignal<T, G, F>
+where
+    C: State,
+    T: 'static,
+    G: Getter<C, Signal<T>>,
+    F: Fn(&T) -> R + 'static,
+    R: ToAttribute<C>,
+{
+    type AttributeKind = R::AttributeKind;
+
+    #[inline]
+    fn calc_attribute(self, name: &'static str, node: &web_sys::Element) -> AttributeResult<C> {
+        self.into_closure().calc_attribute(name, node)
+    }
+}
+
 /// Trait for `Project` type whose target contains a state.
 /// Such as `Option<Signal<...>>`
 pub trait ProjectIntoState: Project {}
@@ -106,6 +472,23 @@ where
     }
 }
 
+impl<T: Snapshot> Snapshot for ProjectableSignal<Option<T>> {
+    fn snapshot(&self) -> Self {
+        Self::new(self.data.as_ref().map(Snapshot::snapshot))
+    }
+}
+
+impl<T: Snapshot, E: Snapshot> Snapshot for ProjectableSignal<Result<T, E>> {
+    fn snapshot(&self) -> Self {
+        Self::new(
+            self.data
+                .as_ref()
+                .map(Snapshot::snapshot)
+                .map_err(Snapshot::snapshot),
+        )
+    }
+}
+
 impl<T> ProjectableSignal<T>
 where
     T: ProjectIntoState,
@@ -232,6 +615,134 @@ mod tests {
         assert_eq!(second.next(), None);
     }
 
+    #[test]
+    fn reading_read_only_view_subscribes() {
+        let mut foo = Signal::new(5);
+
+        let hook = HookKey {
+            slot: 0,
+            version: 0,
+        };
+
+        statics::with_hook(hook, || {
+            let view = foo.read_only();
+            assert_eq!(*view, 5);
+        });
+
+        let (dirty, ()) = statics::with_dirty_tracking(|| {
+            *foo = 10;
+        });
+
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+    }
+
+    #[test]
+    fn update_if_changed_skips_equal_values() {
+        let mut foo = Signal::new(5);
+
+        let hook = HookKey {
+            slot: 0,
+            version: 0,
+        };
+
+        statics::with_hook(hook, || {
+            let _ = *foo;
+        });
+
+        let (dirty, changed) = statics::with_dirty_tracking(|| foo.update_if_changed(5));
+        assert!(!changed);
+        assert_eq!(dirty.into_iter().flatten().count(), 0);
+
+        let (dirty, changed) = statics::with_dirty_tracking(|| foo.update_if_changed(10));
+        assert!(changed);
+        assert_eq!(*foo, 10);
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+    }
+
+    #[test]
+    fn with_mut_checked_skips_unchanged_values() {
+        let mut foo = Signal::new(vec![1, 2]);
+
+        let hook = HookKey {
+            slot: 0,
+            version: 0,
+        };
+
+        statics::with_hook(hook, || {
+            let _ = foo.len();
+        });
+
+        let (dirty, changed) =
+            statics::with_dirty_tracking(|| foo.with_mut_checked(|items| items.sort_unstable()));
+        assert!(!changed);
+        assert_eq!(dirty.into_iter().flatten().count(), 0);
+
+        let (dirty, changed) =
+            statics::with_dirty_tracking(|| foo.with_mut_checked(|items| items.reverse()));
+        assert!(changed);
+        assert_eq!(*foo, vec![2, 1]);
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+
+        statics::with_hook(hook, || {
+            let _ = foo.len();
+        });
+        let (dirty, ()) = statics::with_dirty_tracking(|| foo.with_mut(|_| {}));
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+    }
+
+    #[test]
+    fn list_operations_only_mark_changed_on_mutation() {
+        let mut list = Signal::new(vec![1, 2, 3]);
+
+        let hook = HookKey {
+            slot: 0,
+            version: 0,
+        };
+        let subscribe = |list: &Signal<Vec<i32>>| {
+            statics::with_hook(hook, || {
+                let _ = list.len();
+            });
+        };
+
+        subscribe(&list);
+        let (dirty, ()) = statics::with_dirty_tracking(|| {
+            list.retain(|item| *item > 0);
+            assert_eq!(list.remove(10), None);
+        });
+        assert_eq!(dirty.into_iter().flatten().count(), 0);
+
+        let (dirty, ()) = statics::with_dirty_tracking(|| {
+            list.retain(|item| *item != 2);
+        });
+        assert_eq!(*list, vec![1, 3]);
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+
+        subscribe(&list);
+        let (dirty, ()) = statics::with_dirty_tracking(|| {
+            list.push(4);
+            assert_eq!(list.remove(0), Some(1));
+            assert_eq!(list.pop(), Some(4));
+        });
+        assert_eq!(*list, vec![3]);
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+
+        subscribe(&list);
+        let (dirty, ()) = statics::with_dirty_tracking(|| {
+            list.clear();
+            list.clear();
+            assert_eq!(list.pop(), None);
+        });
+        assert!(list.is_empty());
+        let hooks: HashSet<_> = dirty.into_iter().flatten().collect();
+        assert_eq!(hooks, HashSet::from([hook]));
+    }
+
     #[test]
     fn projectable_signal_modify_outer_alerts_both() {
         let mut signal = ProjectableSignal::new(Some(Signal::new(10)));
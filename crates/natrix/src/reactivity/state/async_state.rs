@@ -1,10 +1,8 @@
 //! Implementation of core async features
 
-use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
-use std::rc::Weak;
 
-use super::InnerCtx;
+use super::{InnerCtx, StateHandle};
 use crate::EventCtx;
 use crate::error_handling::log_or_panic;
 use crate::reactivity::State;
@@ -16,15 +14,14 @@ impl<T: State> EventCtx<'_, T> {
         C: FnOnce(AsyncCtxHandle<T>) -> F,
         F: Future<Output = Option<()>> + 'static,
     {
-        let handle = AsyncCtxHandle {
-            inner: self.0.this.clone(),
-        };
-        let future = func(handle);
+        let future = func(self.weak_handle());
         let future = async {
             let _ = future.await;
         };
 
         let future = PanicCheckFuture { inner: future };
+        #[cfg(feature = "test_utils")]
+        let future = crate::test_utils::track_task(future);
 
         wasm_bindgen_futures::spawn_local(future);
     }
@@ -69,23 +66,20 @@ impl<S: State> DerefMut for AsyncCtx<'_, S> {
     }
 }
 
-/// A combiend `Weak` and `RefCell` that facilities upgrading and borrowing as a shared
-/// operation and ensures you cant cause borrow errors.
-#[must_use]
-pub struct AsyncCtxHandle<T: State> {
-    /// The `Weak<RefCell<T>>` in question
-    inner: Weak<RefCell<InnerCtx<T>>>,
-}
-
-impl<T: State> Clone for AsyncCtxHandle<T> {
-    fn clone(&self) -> Self {
-        Self {
-            inner: self.inner.clone(),
-        }
+impl<S: State> AsyncCtx<'_, S> {
+    /// Run the given function once the update cycle for this `update` call has completed.
+    /// See [`EventCtx::on_next_update`]
+    #[inline]
+    pub fn on_next_update(&mut self, func: impl FnOnce(EventCtx<S>) + 'static) {
+        self.0.post_update.push(Box::new(func));
     }
 }
 
-impl<T: State> AsyncCtxHandle<T> {
+/// The handle async tasks get from [`EventCtx::use_async`], which is a [`StateHandle`] whose
+/// [`update`](StateHandle::update) method gives them a [`AsyncCtx`].
+pub type AsyncCtxHandle<T> = StateHandle<T>;
+
+impl<T: State> StateHandle<T> {
     /// Run a function on the state, returning `None` if the element was dropped.
     ///
     /// # Reactivity
@@ -93,7 +87,7 @@ impl<T: State> AsyncCtxHandle<T> {
     /// And causes a update to the UI when the closure exists.
     #[must_use]
     pub fn update<R>(&self, func: impl FnOnce(AsyncCtx<T>) -> R) -> Option<R> {
-        let rc = self.inner.upgrade()?;
+        let rc = self.upgrade()?;
         let Ok(mut borrow) = rc.try_borrow_mut() else {
             log_or_panic!("State borrowed while already borrowed.");
             return None;
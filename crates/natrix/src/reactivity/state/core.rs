@@ -7,7 +7,15 @@ use std::rc::{Rc, Weak};
 use crate::error_handling::log_or_panic;
 use crate::reactivity::State;
 use crate::reactivity::render_callbacks::RenderingState;
+use crate::reactivity::state::history::History;
 use crate::reactivity::state::hook_manager::HookStore;
+use crate::reactivity::state::watch::MemoCache;
+
+/// A callback to run once the current update cycle is done
+pub(crate) type PostUpdateCallback<T> = Box<dyn FnOnce(EventCtx<T>)>;
+
+/// The default limit of chained update cycles, see `EventCtx::set_max_update_cycles`
+const DEFAULT_MAX_UPDATE_CYCLES: usize = 100;
 
 /// The core framework state, also holds user data.
 pub(crate) struct InnerCtx<T: State> {
@@ -18,6 +26,14 @@ pub(crate) struct InnerCtx<T: State> {
     pub(crate) this: Weak<RefCell<Self>>,
     /// Reactive hooks
     pub(crate) hooks: HookStore<T>,
+    /// Callbacks to run after the next update cycle
+    pub(crate) post_update: Vec<PostUpdateCallback<T>>,
+    /// The undo/redo history, if enabled
+    pub(crate) history: Option<History<T>>,
+    /// The maximum amount of update cycles `on_next_update` callbacks can chain
+    pub(crate) max_update_cycles: usize,
+    /// The cached values of `ctx.memo` calls
+    pub(crate) memos: MemoCache,
 }
 
 impl<T: State> InnerCtx<T> {
@@ -29,6 +45,10 @@ impl<T: State> InnerCtx<T> {
             data,
             this: Weak::new(),
             hooks: HookStore::new(),
+            post_update: Vec::new(),
+            history: None,
+            max_update_cycles: DEFAULT_MAX_UPDATE_CYCLES,
+            memos: MemoCache::default(),
         }
     }
 
@@ -87,3 +107,42 @@ impl<C: State> DerefMut for EventCtx<'_, C> {
         &mut self.0.data
     }
 }
+
+impl<C: State> EventCtx<'_, C> {
+    /// Run the given function once the current update cycle has completed, i.e after the dom has
+    /// been updated to reflect the changes made in this handler.
+    ///
+    /// This is useful for things like scrolling to, or measuring, a newly added element.
+    /// Any changes made in the callback will trigger their own update cycle.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {
+    /// #     items: Signal<Vec<u8>>,
+    /// # }
+    /// # fn render() -> impl Element<App> {
+    /// e::button().on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+    ///     ctx.items.push(1);
+    ///     ctx.on_next_update(|_ctx| {
+    ///         // The new item is now in the dom.
+    ///     });
+    /// })
+    /// # }
+    /// ```
+    #[inline]
+    pub fn on_next_update(&mut self, func: impl FnOnce(EventCtx<C>) + 'static) {
+        self.0.post_update.push(Box::new(func));
+    }
+
+    /// Set how many update cycles `on_next_update` callbacks can chain before natrix gives up.
+    ///
+    /// A callback that schedules another callback (directly or through a change it makes) causes
+    /// another update cycle, if this keeps going it is most likely a accidental feedback loop.
+    /// When the limit is hit the remaining callbacks are dropped, and a error is logged (and in
+    /// debug builds it panics). The default is 100.
+    #[inline]
+    pub fn set_max_update_cycles(&mut self, cycles: usize) {
+        self.0.max_update_cycles = cycles;
+    }
+}
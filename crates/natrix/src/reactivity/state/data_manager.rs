@@ -2,10 +2,11 @@
 
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::marker::PhantomData;
 
 use smallvec::SmallVec;
 
-use super::{HookKey, InnerCtx};
+use super::{EventCtx, HookKey, InnerCtx};
 use crate::error_handling::log_or_panic;
 use crate::reactivity::render_callbacks::UpdateResult;
 use crate::reactivity::state::hook_manager::{HookStore, InsertionOrder, IterSignalList};
@@ -41,12 +42,19 @@ impl<T, O: Ord> Ord for OrderAssociatedData<T, O> {
     }
 }
 
+/// The order hooks are updated in.
+///
+/// Hooks are updated in insertion order, which means parents before their children.
+/// Insertion orders are unique unless the counter has wrapped around, so the key is used as a
+/// tiebreak to keep the order total, and as such deterministic.
+type HookOrder = Reverse<(InsertionOrder, HookKey)>;
+
 /// The queue processor of the queue
 struct HookQueue {
     /// All changed vectors
     vectors: HookDepListHolder,
     /// The queue of the next item in each vector
-    queue: BinaryHeap<OrderAssociatedData<(HookKey, usize), Reverse<InsertionOrder>>>,
+    queue: BinaryHeap<OrderAssociatedData<(HookKey, usize), HookOrder>>,
     /// A temporary next item
     next_item: Option<HookKey>,
     /// Last processed hook to avoid duplicates
@@ -78,7 +86,7 @@ impl HookQueue {
                 let (hook, ordering) = get_next_valid(hook_store, vector)?;
                 Some(OrderAssociatedData {
                     data: (hook, index),
-                    ordering: Reverse(ordering),
+                    ordering: Reverse((ordering, hook)),
                 })
             });
         queue.extend(first_items);
@@ -116,7 +124,7 @@ impl HookQueue {
                     if Some(next_hook) != self.last_hook {
                         self.queue.push(OrderAssociatedData {
                             data: (next_hook, source_index),
-                            ordering: Reverse(ordering),
+                            ordering: Reverse((ordering, next_hook)),
                         });
                         break;
                     }
@@ -150,17 +158,77 @@ impl State for () {
     fn set(&mut self, _new: Self) {}
 }
 
+/// Used by the `State` derive to reject `#[natrix(skip)]` fields that are `State`.
+///
+/// `IS_STATE` resolves to the inherent constant for `State` types, and to the
+/// [`SkippedNotState`] fallback for everything else.
+#[doc(hidden)]
+pub struct SkippedField<T>(PhantomData<T>);
+
+impl<T: State> SkippedField<T> {
+    /// The field is `State`
+    pub const IS_STATE: bool = true;
+}
+
+/// Fallback for [`SkippedField`] on types that are not `State`
+#[doc(hidden)]
+pub trait SkippedNotState {
+    /// The field is not `State`
+    const IS_STATE: bool = false;
+}
+
+impl<T> SkippedNotState for SkippedField<T> {}
+
 impl<T: State> InnerCtx<T> {
     /// Loop over signals and update any depdant hooks for changed signals
-    /// This also drains the deferred message queue
-    fn update(&mut self, dep_lists: HookDepListHolder) {
+    /// This also drains the post update queue, running further update cycles for any changes
+    /// made by the callbacks until it is empty.
+    fn update(&mut self, mut dep_lists: HookDepListHolder) {
+        let mut cycles: usize = 0;
+        loop {
+            self.update_hooks(dep_lists);
+
+            let post_update = std::mem::take(&mut self.post_update);
+            if post_update.is_empty() {
+                break;
+            }
+
+            if cycles >= self.max_update_cycles {
+                log_or_panic!(
+                    "State {} exceeded {} chained update cycles, this is likely a `on_next_update` callback that keeps scheduling itself.",
+                    std::any::type_name::<T>(),
+                    self.max_update_cycles
+                );
+                break;
+            }
+            cycles = cycles.saturating_add(1);
+
+            let (dirty_list, ()) = statics::with_dirty_tracking(|| {
+                for callback in post_update {
+                    callback(EventCtx(self));
+                }
+            });
+            dep_lists = dirty_list;
+        }
+    }
+
+    /// Run a single update cycle for the given changed signals
+    fn update_hooks(&mut self, dep_lists: HookDepListHolder) {
         log::trace!("Performing update cycle for {}", std::any::type_name::<T>());
 
         log::trace!("{} signals changed", dep_lists.len());
+        if !dep_lists.is_empty()
+            && let Some(history) = &mut self.history
+        {
+            history.record(&self.data);
+        }
+
         let mut hook_queue = HookQueue::new(&self.hooks, dep_lists);
 
         while let Some(hook_key) = hook_queue.pop(&self.hooks) {
             log::trace!("Updating hook {hook_key:?}");
+            #[cfg(feature = "test_utils")]
+            crate::test_utils::record_hook_run();
             self.run_with_hook_and_self(hook_key, |ctx, hook| match hook.update(ctx, hook_key) {
                 UpdateResult::RunHook(dep, drop) => {
                     hook_queue.push_next(dep);
@@ -198,3 +266,151 @@ impl<T: State> InnerCtx<T> {
         statics::with_hook(hook, || func(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::reactivity::signal::Signal;
+    use crate::reactivity::state::hook_manager::SignalDepList;
+
+    struct Counter {
+        value: Signal<u32>,
+    }
+
+    impl State for Counter {
+        fn set(&mut self, new: Self) {
+            self.value.set(new.value);
+        }
+    }
+
+    /// Schedule a callback that increments the counter and reschedules itself `remaining` times.
+    fn schedule_chain(ctx: &mut EventCtx<Counter>, remaining: Option<u32>) {
+        ctx.on_next_update(move |mut ctx| {
+            *ctx.value = ctx.value.saturating_add(1);
+            match remaining {
+                Some(0) => {}
+                Some(remaining) => schedule_chain(&mut ctx, Some(remaining.saturating_sub(1))),
+                None => schedule_chain(&mut ctx, None),
+            }
+        });
+    }
+
+    /// Create a dirty list containing the given hooks
+    fn dep_list(keys: &[HookKey]) -> IterSignalList {
+        let mut list = SignalDepList::new();
+        for key in keys {
+            list.insert(*key);
+        }
+        list.create_iter_and_clear()
+    }
+
+    /// Pop all the hooks from a queue over the given dirty lists
+    fn drain_queue(store: &HookStore<Counter>, lists: Vec<IterSignalList>) -> Vec<HookKey> {
+        let mut queue = HookQueue::new(store, lists.into_iter().collect());
+        std::iter::from_fn(|| queue.pop(store)).collect()
+    }
+
+    #[test]
+    fn sibling_hooks_run_in_insertion_order() {
+        let mut store = HookStore::<Counter>::new();
+        let first = store.reserve_key();
+        let second = store.reserve_key();
+        let third = store.reserve_key();
+
+        let expected = vec![first, second, third];
+        assert_eq!(
+            drain_queue(&store, vec![dep_list(&[third]), dep_list(&[first, second])]),
+            expected
+        );
+        assert_eq!(
+            drain_queue(&store, vec![dep_list(&[second]), dep_list(&[first, third])]),
+            expected
+        );
+    }
+
+    #[test]
+    fn equal_insertion_orders_are_deterministic() {
+        let mut store = HookStore::<Counter>::new();
+        let first = store.reserve_key();
+        store.set_next_insertion_order(0);
+        let second = store.reserve_key();
+
+        let expected = vec![first, second];
+        assert_eq!(
+            drain_queue(&store, vec![dep_list(&[first]), dep_list(&[second])]),
+            expected
+        );
+        assert_eq!(
+            drain_queue(&store, vec![dep_list(&[second]), dep_list(&[first])]),
+            expected
+        );
+    }
+
+    #[test]
+    fn hooks_in_multiple_lists_run_once() {
+        let mut store = HookStore::<Counter>::new();
+        let first = store.reserve_key();
+        let second = store.reserve_key();
+
+        assert_eq!(
+            drain_queue(
+                &store,
+                vec![dep_list(&[first, second]), dep_list(&[first, second])]
+            ),
+            vec![first, second]
+        );
+    }
+
+    fn counter() -> Rc<std::cell::RefCell<InnerCtx<Counter>>> {
+        InnerCtx::new(Counter {
+            value: Signal::new(0),
+        })
+    }
+
+    #[test]
+    fn chained_callbacks_run() {
+        let ctx = counter();
+        let mut ctx = ctx.borrow_mut();
+        ctx.track_changes(|ctx| schedule_chain(&mut EventCtx(ctx), Some(9)));
+        assert_eq!(*ctx.data.value, 10);
+    }
+
+    #[test]
+    fn callbacks_scheduled_together_are_one_cycle() {
+        let ctx = counter();
+        let mut ctx = ctx.borrow_mut();
+        let ran = Rc::new(Cell::new(0_u32));
+        ctx.track_changes(|ctx| {
+            let mut ctx = EventCtx(ctx);
+            ctx.set_max_update_cycles(1);
+            for _ in 0..5 {
+                let ran = ran.clone();
+                ctx.on_next_update(move |_| ran.set(ran.get().saturating_add(1)));
+            }
+        });
+        assert_eq!(ran.get(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeded 3 chained update cycles")]
+    fn respects_configured_limit() {
+        let ctx = counter();
+        let mut ctx = ctx.borrow_mut();
+        ctx.track_changes(|ctx| {
+            let mut ctx = EventCtx(ctx);
+            ctx.set_max_update_cycles(3);
+            schedule_chain(&mut ctx, Some(3));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter exceeded 100 chained update cycles")]
+    fn self_scheduling_callback_trips_limit() {
+        let ctx = counter();
+        let mut ctx = ctx.borrow_mut();
+        ctx.track_changes(|ctx| schedule_chain(&mut EventCtx(ctx), None));
+    }
+}
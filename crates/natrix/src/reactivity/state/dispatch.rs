@@ -0,0 +1,150 @@
+//! Implementation of the reducer pattern via `ctx.dispatch`
+
+#[cfg(feature = "async")]
+use super::AsyncCtx;
+use super::EventCtx;
+use crate::reactivity::State;
+
+/// A state that is updated by dispatching actions to it.
+///
+/// This allows centralizing state transitions in one place, which can be tested without any
+/// dom.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::reactivity::state::Reducer;
+///
+/// #[derive(State)]
+/// struct Counter {
+///     value: Signal<i32>,
+/// }
+///
+/// enum Action {
+///     Increment,
+///     Reset,
+/// }
+///
+/// impl Reducer<Action> for Counter {
+///     fn reduce(&mut self, action: Action) {
+///         match action {
+///             Action::Increment => *self.value += 1,
+///             Action::Reset => *self.value = 0,
+///         }
+///     }
+/// }
+///
+/// fn render() -> impl Element<Counter> {
+///     e::button()
+///         .text(|ctx: RenderCtx<Counter>| *ctx.value)
+///         .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| ctx.dispatch(Action::Increment))
+/// }
+/// ```
+pub trait Reducer<A>: State {
+    /// Apply the action to the state
+    fn reduce(&mut self, action: A);
+}
+
+impl<C: State> EventCtx<'_, C> {
+    /// Apply the given action to the state using its [`Reducer`] implementation.
+    #[inline]
+    pub fn dispatch<A>(&mut self, action: A)
+    where
+        C: Reducer<A>,
+    {
+        self.0.data.reduce(action);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<C: State> AsyncCtx<'_, C> {
+    /// Apply the given action to the state using its [`Reducer`] implementation.
+    #[inline]
+    pub fn dispatch<A>(&mut self, action: A)
+    where
+        C: Reducer<A>,
+    {
+        self.0.data.reduce(action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactivity::signal::Signal;
+    use crate::reactivity::state::InnerCtx;
+
+    struct Todos {
+        items: Signal<Vec<&'static str>>,
+        done: Signal<usize>,
+    }
+
+    impl State for Todos {
+        fn set(&mut self, new: Self) {
+            self.items.set(new.items);
+            self.done.set(new.done);
+        }
+    }
+
+    enum Action {
+        Add(&'static str),
+        Complete,
+        Clear,
+    }
+
+    impl Reducer<Action> for Todos {
+        fn reduce(&mut self, action: Action) {
+            match action {
+                Action::Add(item) => self.items.push(item),
+                Action::Complete => {
+                    if self.items.remove(0).is_some() {
+                        *self.done = self.done.saturating_add(1);
+                    }
+                }
+                Action::Clear => {
+                    self.items.clear();
+                }
+            }
+        }
+    }
+
+    fn dispatch_all(actions: Vec<Action>) -> (Vec<&'static str>, usize) {
+        let ctx = InnerCtx::new(Todos {
+            items: Signal::new(Vec::new()),
+            done: Signal::new(0),
+        });
+        let mut ctx = ctx.borrow_mut();
+        ctx.track_changes(|ctx| {
+            let mut ctx = EventCtx(ctx);
+            for action in actions {
+                ctx.dispatch(action);
+            }
+        });
+
+        (ctx.data.items.clone(), *ctx.data.done)
+    }
+
+    #[test]
+    fn dispatch_sequence() {
+        let (items, done) = dispatch_all(vec![
+            Action::Add("write code"),
+            Action::Add("write tests"),
+            Action::Complete,
+            Action::Add("write docs"),
+        ]);
+
+        assert_eq!(items, vec!["write tests", "write docs"]);
+        assert_eq!(done, 1);
+    }
+
+    #[test]
+    fn dispatch_clear() {
+        let (items, done) = dispatch_all(vec![
+            Action::Add("write code"),
+            Action::Clear,
+            Action::Complete,
+        ]);
+
+        assert!(items.is_empty());
+        assert_eq!(done, 0);
+    }
+}
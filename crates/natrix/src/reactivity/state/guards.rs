@@ -117,6 +117,172 @@ impl<C: State> RenderCtx<'_, '_, C> {
     }
 }
 
+impl<C: State> RenderCtx<'_, '_, C> {
+    /// Constrain the type of the getter given to [`guard_match!`](crate::guard_match).
+    #[doc(hidden)]
+    #[inline]
+    pub fn guard_getter<F, E>(&self, getter: F) -> F
+    where
+        F: Fn(Ref<C>) -> Ref<E> + Clone + 'static,
+    {
+        getter
+    }
+
+    /// Watch the name of the variant the getter points to, used by
+    /// [`guard_match!`](crate::guard_match).
+    #[doc(hidden)]
+    #[inline]
+    pub fn guard_variant<F, E>(
+        &mut self,
+        getter: F,
+        variant: fn(&E) -> &'static str,
+    ) -> &'static str
+    where
+        F: Fn(Ref<C>) -> Ref<E> + 'static,
+        E: 'static,
+    {
+        self.watch(move |render| {
+            let Ref::Read(value) = getter(Ref::Read(&render.ctx.data)) else {
+                unreachable!("Getter changed the variant of `Ref`");
+            };
+            variant(value)
+        })
+    }
+
+    /// Create a guard for the payload of a enum variant, used by
+    /// [`guard_match!`](crate::guard_match).
+    #[doc(hidden)]
+    #[inline]
+    pub fn guard_payload<F, E, T>(
+        &self,
+        getter: F,
+        read: fn(&E) -> Option<&T>,
+        write: fn(&mut E) -> Option<&mut T>,
+    ) -> impl Fn(Ref<C>) -> Ref<T> + Clone + use<F, E, T, C>
+    where
+        F: Fn(Ref<C>) -> Ref<E> + Clone + 'static,
+        E: 'static,
+        T: 'static,
+    {
+        create_getter(move |ctx| match getter(ctx) {
+            Ref::Read(value) => read(value).map(Ref::Read),
+            Ref::Mut(value) => write(value).map(Ref::Mut),
+            Ref::FaillableMut(None) => Some(Ref::FaillableMut(None)),
+            Ref::FaillableMut(Some(value)) => {
+                write(value).map(|value| Ref::FaillableMut(Some(value)))
+            }
+        })
+    }
+}
+
+/// Called by [`guard_match!`](crate::guard_match) if the watched variant matches none of the arms.
+#[doc(hidden)]
+pub fn guard_match_failed() -> ! {
+    log::error!("`guard_match!` got a variant not in its arms.");
+    unreachable!("`guard_match!` got a variant not in its arms.");
+}
+
+/// Match on a enum, getting guards for the payload of the matched variant.
+///
+/// This is the enum version of [`RenderCtx::guard_option`], the arms are only re-run when the
+/// variant changes, changes to the payload only re-run the closures that read it through the
+/// guards.
+///
+/// The first argument is the `RenderCtx`, followed by a getter for the enum and the arms.
+/// Each arm is a variant path, optionally followed by a single binding in parentheses for tuple
+/// variants, or field names in braces for struct variants.
+/// The bindings are guards for the respective field, not the values themselves.
+/// Arms without bindings match regardless of the payload.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// enum Shape {
+///     Circle(f32),
+///     Rectangle { width: f32, height: f32 },
+///     Empty,
+/// }
+///
+/// #[derive(State)]
+/// struct App {
+///     shape: Signal<Shape>,
+/// }
+///
+/// fn render() -> impl Element<App> {
+///     |mut ctx: RenderCtx<App>| {
+///         natrix::guard_match!(ctx, |ctx| field!(ctx.shape).deref(), {
+///             Shape::Circle(radius) => e::div()
+///                 .text(move |ctx: RenderCtx<App>| *radius.call_read(&ctx))
+///                 .generic(),
+///             Shape::Rectangle { width, height } => e::div()
+///                 .text(move |ctx: RenderCtx<App>| *width.call_read(&ctx))
+///                 .text(move |ctx: RenderCtx<App>| *height.call_read(&ctx))
+///                 .generic(),
+///             Shape::Empty => e::div().text("Nothing").generic(),
+///         })
+///     }
+/// }
+/// ```
+///
+/// Tuple variants with multiple fields can be matched with the field indices, like
+/// `Variant { 0: first, 1: second }`.
+#[macro_export]
+macro_rules! guard_match {
+    (@bindings $ctx:ident, $getter:ident, $path:path, ($($binding:ident)?), ($($field:tt $(: $field_binding:ident)?),*)) => {
+        $(
+            let $binding = $crate::guard_match!(@payload $ctx, $getter, $path, 0);
+        )?
+        $(
+            let $crate::guard_match!(@binding $field $(: $field_binding)?) =
+                $crate::guard_match!(@payload $ctx, $getter, $path, $field);
+        )*
+    };
+    (@payload $ctx:ident, $getter:ident, $path:path, $field:tt) => {
+        $ctx.guard_payload(
+            ::std::clone::Clone::clone(&$getter),
+            |value| match value {
+                $path { $field: value, .. } => ::std::option::Option::Some(value),
+                #[allow(unreachable_patterns)]
+                _ => ::std::option::Option::None,
+            },
+            |value| match value {
+                $path { $field: value, .. } => ::std::option::Option::Some(value),
+                #[allow(unreachable_patterns)]
+                _ => ::std::option::Option::None,
+            },
+        )
+    };
+    (@binding $field:ident) => { $field };
+    (@binding $field:tt : $binding:ident) => { $binding };
+    ($ctx:ident, $getter:expr, {
+        $(
+            $($segment:ident)::+
+            $(($binding:ident))?
+            $({ $($field:tt $(: $field_binding:ident)?),* $(,)? })?
+            => $body:expr
+        ),+ $(,)?
+    }) => {{
+        let __natrix_getter = $ctx.guard_getter($getter);
+        let __natrix_variant = $ctx.guard_variant(
+            ::std::clone::Clone::clone(&__natrix_getter),
+            |value| match value {
+                $($($segment)::+ { .. } => ::std::stringify!($($segment)::+),)+
+            },
+        );
+        match __natrix_variant {
+            $(
+                ::std::stringify!($($segment)::+) => {
+                    $crate::guard_match!(
+                        @bindings $ctx, __natrix_getter, $($segment)::+,
+                        ($($binding)?), ($($($field $(: $field_binding)?),*)?)
+                    );
+                    $body
+                }
+            )+
+            _ => $crate::macro_ref::guard_match_failed(),
+        }
+    }};
+}
+
 /// Create a getter that handles the logic of guard getters.
 #[inline]
 fn create_getter<S, R>(
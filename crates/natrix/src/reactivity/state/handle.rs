@@ -0,0 +1,147 @@
+//! Handles to the state for use in external callbacks
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use super::{EventCtx, InnerCtx};
+use crate::reactivity::State;
+
+impl<C: State> EventCtx<'_, C> {
+    /// Get a weak handle to the state, which can be stored in callbacks called from outside
+    /// natrix, such as events from a javascript library.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use natrix::reactivity::state::StateHandle;
+    /// #[derive(State)]
+    /// struct App {
+    ///     zoom: Signal<u8>,
+    /// }
+    ///
+    /// fn on_zoom_changed(handle: &StateHandle<App>, zoom: u8) {
+    ///     let _ = handle.with_mut(|mut ctx| *ctx.zoom = zoom);
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     e::button().on::<events::Click>(|ctx: EventCtx<App>, _| {
+    ///         let handle = ctx.weak_handle();
+    ///         // Pass `handle` to a javascript callback, which calls `on_zoom_changed`.
+    ///         # on_zoom_changed(&handle, 2);
+    ///     })
+    /// }
+    /// ```
+    pub fn weak_handle(&self) -> StateHandle<C> {
+        StateHandle {
+            inner: self.0.this.clone(),
+        }
+    }
+}
+
+/// A weak handle to the state, see [`EventCtx::weak_handle`].
+///
+/// This does not keep the state alive.
+/// Async tasks get one as a `AsyncCtxHandle`, which with the `async` feature also has `update`
+/// for use between `.await` points.
+#[must_use]
+pub struct StateHandle<C: State> {
+    /// The state in question
+    inner: Weak<RefCell<InnerCtx<C>>>,
+}
+
+impl<C: State> Clone for StateHandle<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C: State> StateHandle<C> {
+    /// Run a function with mutable access to the state, and update the dom with any changes.
+    ///
+    /// Returns `None` without calling `func` if the state has been dropped, or if the state is
+    /// already in use. The latter happens when a javascript callback is called synchronously
+    /// from within a natrix event handler, in that case make the change in the event handler
+    /// directly instead.
+    pub fn with_mut<R>(&self, func: impl FnOnce(EventCtx<C>) -> R) -> Option<R> {
+        if crate::panics::has_panicked() {
+            return None;
+        }
+
+        let rc = self.upgrade()?;
+        let Ok(mut borrow) = rc.try_borrow_mut() else {
+            log::warn!("State handle used while the state was already in use, ignoring the call");
+            return None;
+        };
+
+        Some(borrow.track_changes(|ctx| func(EventCtx(ctx))))
+    }
+
+    /// Get the state if it is still alive
+    pub(crate) fn upgrade(&self) -> Option<Rc<RefCell<InnerCtx<C>>>> {
+        self.inner.upgrade()
+    }
+
+    /// Check if the state is still alive.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.inner.strong_count() > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactivity::signal::Signal;
+
+    struct Counter {
+        value: Signal<u8>,
+    }
+
+    impl State for Counter {
+        fn set(&mut self, new: Self) {
+            self.value.set(new.value);
+        }
+    }
+
+    fn handle() -> (
+        std::rc::Rc<RefCell<InnerCtx<Counter>>>,
+        StateHandle<Counter>,
+    ) {
+        let ctx = InnerCtx::new(Counter {
+            value: Signal::new(0),
+        });
+        let handle = ctx
+            .borrow_mut()
+            .track_changes(|ctx| EventCtx(ctx).weak_handle());
+        (ctx, handle)
+    }
+
+    #[test]
+    fn updates_state() {
+        let (ctx, handle) = handle();
+        assert_eq!(handle.with_mut(|mut ctx| *ctx.value = 5), Some(()));
+        assert_eq!(*ctx.borrow().data.value, 5);
+    }
+
+    #[test]
+    fn dropped_state() {
+        let (ctx, handle) = handle();
+        assert!(handle.is_alive());
+        drop(ctx);
+        assert!(!handle.is_alive());
+        assert_eq!(handle.with_mut(|_| ()), None);
+    }
+
+    #[test]
+    fn reentrant_call_is_ignored() {
+        let (ctx, handle) = handle();
+        let inner = handle.clone();
+        let result = handle.with_mut(move |mut ctx| {
+            *ctx.value = 1;
+            inner.with_mut(|mut ctx| *ctx.value = 2)
+        });
+        assert_eq!(result, Some(None));
+        assert_eq!(*ctx.borrow().data.value, 1);
+    }
+}
@@ -0,0 +1,274 @@
+//! Undo/redo history of the state
+
+use std::collections::VecDeque;
+
+use super::{EventCtx, InnerCtx};
+use crate::reactivity::State;
+
+/// Create a untracked copy of a state, used to record the history of the state.
+///
+/// Use `#[derive(Snapshot)]` to implement this for your own state.
+/// The snapshot is a new independent state, which is why this is not just `Clone`.
+pub trait Snapshot: State {
+    /// Create a copy of this state, without any of the reactive dependencies.
+    #[must_use]
+    fn snapshot(&self) -> Self;
+}
+
+impl Snapshot for () {
+    fn snapshot(&self) -> Self {}
+}
+
+/// Snapshots of the state, recorded once per update cycle.
+pub(crate) struct History<T> {
+    /// Previous states, oldest first
+    undo: VecDeque<T>,
+    /// Undone states, most recently undone last
+    redo: Vec<T>,
+    /// The state as of the last recorded update cycle
+    current: T,
+    /// The max amount of undo steps to keep
+    capacity: usize,
+    /// Create a snapshot of the state
+    snapshot: fn(&T) -> T,
+    /// The next update cycle is caused by a undo/redo and should not be recorded
+    restoring: bool,
+}
+
+impl<T> History<T> {
+    /// Start a new history with the given state as the current state
+    fn new(data: &T, capacity: usize, snapshot: fn(&T) -> T) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            current: snapshot(data),
+            capacity,
+            snapshot,
+            restoring: false,
+        }
+    }
+
+    /// Record the state after a update cycle that changed it.
+    pub(crate) fn record(&mut self, data: &T) {
+        if std::mem::take(&mut self.restoring) {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.current, (self.snapshot)(data));
+        self.undo.push_back(previous);
+        while self.undo.len() > self.capacity {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    /// Step back in the history, returning the state to restore.
+    fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop_back()?;
+        let restore = (self.snapshot)(&previous);
+        self.redo
+            .push(std::mem::replace(&mut self.current, previous));
+        self.restoring = true;
+        Some(restore)
+    }
+
+    /// Step forward in the history, returning the state to restore.
+    fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        let restore = (self.snapshot)(&next);
+        self.undo
+            .push_back(std::mem::replace(&mut self.current, next));
+        self.restoring = true;
+        Some(restore)
+    }
+}
+
+impl<T: State> InnerCtx<T> {
+    /// Restore the state returned by the given history operation, returns `false` if there was
+    /// nothing to restore.
+    fn restore_history(&mut self, operation: impl FnOnce(&mut History<T>) -> Option<T>) -> bool {
+        let Some(restore) = self.history.as_mut().and_then(operation) else {
+            return false;
+        };
+        self.data.set(restore);
+        true
+    }
+}
+
+impl<C: State> EventCtx<'_, C> {
+    /// Start recording the history of the state, keeping at most `capacity` undo steps.
+    ///
+    /// A snapshot is recorded at the end of every update cycle that modified the state, so all
+    /// changes made in a single event handler are undone together.
+    /// The recording starts from the state as it is when this is called, calling this when
+    /// history is already enabled only changes the capacity.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::reactivity::state::Snapshot;
+    ///
+    /// #[derive(State, Snapshot)]
+    /// struct Editor {
+    ///     text: Signal<String>,
+    /// }
+    ///
+    /// fn render() -> impl Element<Editor> {
+    ///     e::div()
+    ///         .child(e::button().text("Add").on::<events::Click>(
+    ///             |mut ctx: EventCtx<Editor>, _| {
+    ///                 ctx.enable_history(100);
+    ///                 ctx.text.push('a');
+    ///             },
+    ///         ))
+    ///         .child(
+    ///             e::button()
+    ///                 .text("Undo")
+    ///                 .on::<events::Click>(|mut ctx: EventCtx<Editor>, _| {
+    ///                     ctx.undo();
+    ///                 }),
+    ///         )
+    /// }
+    /// ```
+    pub fn enable_history(&mut self, capacity: usize)
+    where
+        C: Snapshot,
+    {
+        if let Some(history) = &mut self.0.history {
+            history.capacity = capacity;
+        } else {
+            self.0.history = Some(History::new(&self.0.data, capacity, C::snapshot));
+        }
+    }
+
+    /// Restore the state to before the last recorded update cycle.
+    ///
+    /// Returns `false` if there is nothing to undo, or history is not enabled.
+    /// Other changes made in the same event handler are discarded from the history.
+    #[inline]
+    pub fn undo(&mut self) -> bool {
+        self.0.restore_history(History::undo)
+    }
+
+    /// Reapply the last undone update cycle.
+    ///
+    /// Returns `false` if there is nothing to redo, or history is not enabled.
+    /// Any recorded change clears the redo history.
+    #[inline]
+    pub fn redo(&mut self) -> bool {
+        self.0.restore_history(History::redo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactivity::signal::Signal;
+
+    struct Document {
+        text: Signal<String>,
+    }
+
+    impl State for Document {
+        fn set(&mut self, new: Self) {
+            self.text.set(new.text);
+        }
+    }
+
+    impl Snapshot for Document {
+        fn snapshot(&self) -> Self {
+            Self {
+                text: self.text.snapshot(),
+            }
+        }
+    }
+
+    /// Run the given function as a event handler, returning the resulting text.
+    fn handle(
+        ctx: &std::rc::Rc<std::cell::RefCell<InnerCtx<Document>>>,
+        func: impl FnOnce(&mut EventCtx<Document>),
+    ) -> String {
+        let mut ctx = ctx.borrow_mut();
+        ctx.track_changes(|ctx| func(&mut EventCtx(ctx)));
+        (*ctx.data.text).clone()
+    }
+
+    fn document() -> std::rc::Rc<std::cell::RefCell<InnerCtx<Document>>> {
+        let ctx = InnerCtx::new(Document {
+            text: Signal::new(String::new()),
+        });
+        handle(&ctx, |ctx| ctx.enable_history(10));
+        ctx
+    }
+
+    #[test]
+    fn undo_and_redo_edits() {
+        let ctx = document();
+        handle(&ctx, |ctx| ctx.text.push('a'));
+        handle(&ctx, |ctx| ctx.text.push('b'));
+        handle(&ctx, |ctx| ctx.text.push('c'));
+
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "ab");
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "a");
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.redo())), "ab");
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.redo())), "abc");
+        assert_eq!(handle(&ctx, |ctx| assert!(!ctx.redo())), "abc");
+    }
+
+    #[test]
+    fn handler_is_one_step() {
+        let ctx = document();
+        handle(&ctx, |ctx| {
+            ctx.text.push('a');
+            ctx.text.push('b');
+        });
+
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "");
+        assert_eq!(handle(&ctx, |ctx| assert!(!ctx.undo())), "");
+    }
+
+    #[test]
+    fn edit_clears_redo() {
+        let ctx = document();
+        handle(&ctx, |ctx| ctx.text.push('a'));
+        handle(&ctx, |ctx| ctx.text.push('b'));
+        handle(&ctx, |ctx| assert!(ctx.undo()));
+        handle(&ctx, |ctx| ctx.text.push('c'));
+
+        assert_eq!(handle(&ctx, |ctx| assert!(!ctx.redo())), "ac");
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "a");
+    }
+
+    #[test]
+    fn capacity_bounds_history() {
+        let ctx = document();
+        handle(&ctx, |ctx| ctx.enable_history(2));
+        for letter in ['a', 'b', 'c', 'd'] {
+            handle(&ctx, |ctx| ctx.text.push(letter));
+        }
+
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "abc");
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "ab");
+        assert_eq!(handle(&ctx, |ctx| assert!(!ctx.undo())), "ab");
+    }
+
+    #[test]
+    fn reads_are_not_recorded() {
+        let ctx = document();
+        handle(&ctx, |ctx| ctx.text.push('a'));
+        handle(&ctx, |ctx| {
+            let _ = ctx.text.len();
+        });
+
+        assert_eq!(handle(&ctx, |ctx| assert!(ctx.undo())), "");
+    }
+
+    #[test]
+    fn disabled_history_does_nothing() {
+        let ctx = InnerCtx::new(Document {
+            text: Signal::new(String::new()),
+        });
+        handle(&ctx, |ctx| ctx.text.push('a'));
+
+        assert_eq!(handle(&ctx, |ctx| assert!(!ctx.undo())), "a");
+    }
+}
@@ -26,7 +26,10 @@ type KeyVersion = u16;
 pub(crate) type InsertionOrder = u32;
 
 /// A key into a slotmap
-#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+///
+/// The `Ord` implementation has no meaning beyond being a stable tiebreak for hooks with the same
+/// insertion order.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 pub struct HookKey {
     /// The slot to use
     pub(crate) slot: KeySlot,
@@ -45,8 +48,10 @@ impl HookKey {
 }
 
 /// The value of a slot
+#[derive(Default)]
 enum SlotValue<T> {
     /// The slot doesnt contain a value
+    #[default]
     Empty,
     /// The slot is in use, but the value is moved out atm
     InUse,
@@ -64,12 +69,6 @@ enum SlotValue<T> {
     },
 }
 
-impl<T> Default for SlotValue<T> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 /// A slot in the slotmap
 struct Slot<T> {
     /// The version of the slot
@@ -193,8 +192,15 @@ impl<T: State> HookStore<T> {
         }
     }
 
+    /// Whether the hook still exists
+    pub(crate) fn is_alive(&self, key: HookKey) -> bool {
+        self.hooks.get(key.slot as usize).is_some_and(|slot| {
+            slot.version == key.version && matches!(slot.value, SlotValue::Occupied { .. })
+        })
+    }
+
     /// Drop the hook and all of its children
-    pub(super) fn drop_hook(&mut self, hook_key: HookKey) {
+    pub(crate) fn drop_hook(&mut self, hook_key: HookKey) {
         let mut hooks_to_drop = vec![hook_key];
         while let Some(hook_key) = hooks_to_drop.pop() {
             if let Some(slot) = self.hooks.get_mut(hook_key.slot as usize) {
@@ -218,6 +224,12 @@ impl<T: State> HookStore<T> {
         }
     }
 
+    /// Set the next insertion order, used to test insertion order ties.
+    #[cfg(test)]
+    pub(super) fn set_next_insertion_order(&mut self, order: InsertionOrder) {
+        self.next_insertion_order = order;
+    }
+
     /// Get the insertion order at a given hook, returns `None` if hook doesnt exist.
     pub(crate) fn get_insertion_order(&self, key: HookKey) -> Option<InsertionOrder> {
         if let Some(slot) = self.hooks.get(key.slot as usize) {
@@ -4,14 +4,24 @@
 mod async_state;
 mod core;
 mod data_manager;
+mod dispatch;
 pub mod guards;
+mod handle;
+mod history;
 mod hook_manager;
 mod watch;
 
+pub use natrix_macros::Snapshot;
+
 #[cfg(feature = "async")]
 pub use self::async_state::{AsyncCtx, AsyncCtxHandle};
 pub(crate) use self::core::InnerCtx;
 pub use self::core::{EventCtx, RenderCtx};
 pub(crate) use self::data_manager::HookDepListHolder;
 pub use self::data_manager::State;
+#[doc(hidden)]
+pub use self::data_manager::{SkippedField, SkippedNotState};
+pub use self::dispatch::Reducer;
+pub use self::handle::StateHandle;
+pub use self::history::Snapshot;
 pub(crate) use self::hook_manager::{HookKey, IterSignalList, SignalDepList};
@@ -1,8 +1,15 @@
-//! Implementation of `ctx.watch`
+//! Implementation of `ctx.watch`, `ctx.memo`, and `ctx.effect`
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::panic::Location;
+use std::rc::Rc;
 
 use super::{HookKey, RenderCtx};
 use crate::error_handling::log_or_panic;
-use crate::reactivity::render_callbacks::{ReactiveHook, RenderingState, UpdateResult};
+use crate::reactivity::render_callbacks::{EffectHook, ReactiveHook, RenderingState, UpdateResult};
 use crate::reactivity::state::InnerCtx;
 use crate::reactivity::{KeepAlive, State, statics};
 
@@ -53,6 +60,98 @@ where
     }
 }
 
+/// The key of a memo cache entry, the slot of the calling hook and the call site.
+type MemoKey = (u16, &'static Location<'static>);
+
+/// The cached values of `ctx.memo`.
+///
+/// Shared with the memo hooks, which remove their entry when they are dropped.
+pub(crate) type MemoCache = Rc<RefCell<HashMap<MemoKey, MemoEntry>>>;
+
+/// A cached memo value
+pub(crate) struct MemoEntry {
+    /// The hook that called the memo
+    caller: HookKey,
+    /// The memo hook, which is kept alive across re-runs of the caller
+    memo: HookKey,
+    /// The `MemoOwner` of the latest run of the caller
+    owner: Rc<Cell<HookKey>>,
+    /// The value
+    value: Rc<dyn Any>,
+    /// Set once any of the signals the value was calculated from changes
+    dirty: Rc<Cell<bool>>,
+}
+
+/// The memo hook, re-running the caller when the memoized value is out of date
+struct MemoState {
+    /// The dependency that owns us.
+    dep: HookKey,
+    /// Shared with the cache entry, marks the cached value as out of date
+    dirty: Rc<Cell<bool>>,
+    /// Keepalive
+    keep_alive: Vec<KeepAlive>,
+    /// Child hooks
+    hooks: Vec<HookKey>,
+    /// Our own key, to only remove our entry and not one that replaced it
+    me: HookKey,
+    /// The cache to remove our entry from when dropped
+    cache: MemoCache,
+    /// Our key in the cache
+    cache_key: MemoKey,
+}
+
+impl<C: State> ReactiveHook<C> for MemoState {
+    fn update(&mut self, _ctx: &mut InnerCtx<C>, _you: HookKey) -> UpdateResult {
+        // NOTE: The value is calculated again when the caller re-runs, so there is no need to
+        // calculate it here.
+        self.dirty.set(true);
+        self.keep_alive.clear();
+        UpdateResult::RunHook(self.dep, std::mem::take(&mut self.hooks))
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        if let Ok(mut cache) = self.cache.try_borrow_mut() {
+            if cache
+                .get(&self.cache_key)
+                .is_some_and(|entry| entry.memo == self.me)
+            {
+                cache.remove(&self.cache_key);
+            }
+        } else {
+            log_or_panic!("Memo cache borrowed while dropping memo");
+        }
+        self.hooks
+    }
+}
+
+/// Owns a memo hook for a single run of the caller.
+///
+/// Each run that uses the memo creates a new owner, and only the latest one drops the memo.
+/// This way the memo outlives the caller dropping the hooks of its previous run.
+struct MemoOwner {
+    /// Our own key
+    me: HookKey,
+    /// The memo hook
+    memo: HookKey,
+    /// The latest owner of the memo, shared with the cache entry
+    owner: Rc<Cell<HookKey>>,
+}
+
+impl<C: State> ReactiveHook<C> for MemoOwner {
+    fn update(&mut self, _ctx: &mut InnerCtx<C>, _you: HookKey) -> UpdateResult {
+        // NOTE: We never read any signals, so this is never called.
+        UpdateResult::DropHooks(Vec::new())
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        if self.owner.get() == self.me {
+            vec![self.memo]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 impl<C: State> RenderCtx<'_, '_, C> {
     /// Calculate the value using the function and cache it using `clone`.
     /// Then whenever any signals read in the function are modified re-run the function and check
@@ -80,7 +179,276 @@ impl<C: State> RenderCtx<'_, '_, C> {
         F: for<'c, 's> Fn(RenderCtx<'c, 's, C>) -> T + 'static,
         T: PartialEq + Clone + 'static,
     {
+        self.watch_by_key(func, T::clone)
+    }
+
+    /// Like [`watch`](Self::watch), but only caches a hash of the value for change detection.
+    ///
+    /// This avoids cloning and storing the value, which is useful for large derived values such
+    /// as long `String`s, where the parent only needs to know when it changed.
+    ///
+    /// # Collisions
+    /// If the new value happens to have the same 64 bit hash as the previous one the change is
+    /// missed and the caller wont re-run. The chance of this is negligible for practical purposes,
+    /// but use [`watch`](Self::watch) if a missed update would be a correctness issue.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {names: Signal<Vec<String>>}
+    /// #
+    /// # fn render() -> impl Element<App> {
+    /// # |mut ctx: RenderCtx<App>| {
+    /// let summary = ctx.watch_hash(|ctx| ctx.names.join(", "));
+    /// e::div().text(summary)
+    /// # }}
+    /// ```
+    #[inline]
+    pub fn watch_hash<T, F>(&mut self, func: F) -> T
+    where
+        F: for<'c, 's> Fn(RenderCtx<'c, 's, C>) -> T + 'static,
+        T: Hash + 'static,
+    {
+        self.watch_by_key(func, |value| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+
+    /// Select a part of the state, and only rerun the caller when that part changes.
+    ///
+    /// This is [`watch`](Self::watch) for projections, useful when a large struct is kept in a
+    /// single `Signal`. Any change to the struct marks the signal as changed, but the caller
+    /// only reruns if the selected value is different (as determined by `PartialEq`).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Clone, PartialEq)]
+    /// # struct Theme;
+    /// struct Settings {
+    ///     name: String,
+    ///     theme: Theme,
+    /// }
+    ///
+    /// #[derive(State)]
+    /// struct App {
+    ///     settings: Signal<Settings>,
+    /// }
+    ///
+    /// # fn render() -> impl Element<App> {
+    /// |mut ctx: RenderCtx<App>| {
+    ///     // Changing `theme` does not rerun this closure.
+    ///     let name = ctx.select(|data| &data.settings.name);
+    ///     e::h1().text(name)
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn select<T, F>(&mut self, func: F) -> T
+    where
+        F: Fn(&C) -> &T + 'static,
+        T: PartialEq + Clone + 'static,
+    {
+        self.watch(move |ctx| func(&ctx).clone())
+    }
+
+    /// Calculate a value using the function, returning it in a `Rc` that is cheap to clone
+    /// into nested reactive closures.
+    /// Whenever any signals read in the function are modified the caller is re-run, which
+    /// calculates the value again.
+    /// When the caller re-runs for any other reason the cached value is returned instead.
+    ///
+    /// Unlike [`watch`](Self::watch) the value is not compared with the previous one, so it
+    /// does not need to implement `PartialEq` or `Clone`, and the function is only run once
+    /// per change.
+    /// Use this for expensive derived values, such as a sorted or indexed copy of a list,
+    /// that nested closures read from.
+    ///
+    /// The value is cached per call site, so a memo called multiple times from the same line
+    /// (such as in a loop) is only cached for the first call.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {names: Signal<Vec<String>>, selected: Signal<usize>}
+    /// #
+    /// # fn render() -> impl Element<App> {
+    /// # |mut ctx: RenderCtx<App>| {
+    /// let sorted = ctx.memo(|ctx| {
+    ///     let mut names = ctx.names.clone();
+    ///     names.sort();
+    ///     names
+    /// });
+    /// // Changing `selected` only re-runs this closure, not the sort.
+    /// e::div().text(move |ctx: RenderCtx<App>| sorted.get(*ctx.selected).cloned())
+    /// # }}
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn memo<T, F>(&mut self, func: F) -> Rc<T>
+    where
+        F: for<'c, 's> FnOnce(RenderCtx<'c, 's, C>) -> T,
+        T: 'static,
+    {
+        let location = Location::caller();
+        let Some(dep) = statics::current_hook() else {
+            log_or_panic!("`ctx.memo` called from outside a hook");
+            let me = self.ctx.hooks.reserve_key();
+            return Rc::new(self.track_child_hook(me, func).0);
+        };
+
+        if let Some(value) = self.cached_memo(dep, location) {
+            return value;
+        }
+
         let me = self.ctx.hooks.reserve_key();
+        let (result, keep_alive, hooks) = self.track_child_hook(me, func);
+        let value = Rc::new(result);
+
+        let cache_key = (dep.slot, location);
+        let dirty = Rc::new(Cell::new(false));
+        let hook = MemoState {
+            dep,
+            dirty: Rc::clone(&dirty),
+            keep_alive,
+            hooks,
+            me,
+            cache: Rc::clone(&self.ctx.memos),
+            cache_key,
+        };
+        self.ctx.hooks.set_hook(me, Box::new(hook));
+
+        let owner = Rc::new(Cell::new(me));
+        self.own_memo(me, &owner);
+
+        let entry = MemoEntry {
+            caller: dep,
+            memo: me,
+            owner,
+            value: Rc::clone(&value) as Rc<dyn Any>,
+            dirty,
+        };
+        if let Ok(mut cache) = self.ctx.memos.try_borrow_mut() {
+            cache.insert(cache_key, entry);
+        } else {
+            log_or_panic!("Memo cache borrowed while inserting memo");
+        }
+
+        value
+    }
+
+    /// Get the cached value of the memo at `location`, if it is still up to date.
+    ///
+    /// The memo hook is handed over to the current run of the caller, so it is not dropped
+    /// along with the previous one.
+    fn cached_memo<T: 'static>(
+        &mut self,
+        dep: HookKey,
+        location: &'static Location<'static>,
+    ) -> Option<Rc<T>> {
+        let (memo, owner, value) = {
+            let cache = self.ctx.memos.try_borrow().ok()?;
+            let entry = cache.get(&(dep.slot, location))?;
+            if entry.caller != dep
+                || entry.dirty.get()
+                || !self.ctx.hooks.is_alive(entry.memo)
+                // NOTE: Already used by an earlier call in this run
+                || self.render_state.hooks.contains(&entry.owner.get())
+            {
+                return None;
+            }
+            let value = Rc::clone(&entry.value).downcast::<T>().ok()?;
+            (entry.memo, Rc::clone(&entry.owner), value)
+        };
+
+        self.own_memo(memo, &owner);
+        Some(value)
+    }
+
+    /// Make the current run of the caller the owner of the memo.
+    fn own_memo(&mut self, memo: HookKey, owner: &Rc<Cell<HookKey>>) {
+        let me = self.ctx.hooks.reserve_key();
+        owner.set(me);
+        let hook = MemoOwner {
+            me,
+            memo,
+            owner: Rc::clone(owner),
+        };
+        self.ctx.hooks.set_hook(me, Box::new(hook));
+        self.render_state.hooks.push(me);
+    }
+
+    /// Run a side effect, and run it again whenever any signals read in it are modified.
+    ///
+    /// This is for imperative work that is not tied to a dom node, such as persisting state to
+    /// `localStorage`.
+    /// The effect is run once straight away to find its dependencies, and lives as long as the
+    /// closure that created it, i.e it is dropped when the caller re-runs or is removed.
+    ///
+    /// The effect only has read access to the state, use [`EventCtx`](crate::prelude::EventCtx)
+    /// based handlers to modify it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(State)]
+    /// # struct App {value: Signal<u32>}
+    /// #
+    /// # fn render() -> impl Element<App> {
+    /// # |mut ctx: RenderCtx<App>| {
+    /// ctx.effect(|ctx| {
+    ///     natrix::log::info!("value is now {}", *ctx.value);
+    /// });
+    /// e::div().text(|ctx: RenderCtx<App>| *ctx.value)
+    /// # }}
+    /// ```
+    #[inline]
+    pub fn effect<F>(&mut self, func: F)
+    where
+        F: for<'c, 's> Fn(RenderCtx<'c, 's, C>) + 'static,
+    {
+        let me = EffectHook::init_new(Box::new(func), self.ctx);
+        self.render_state.hooks.push(me);
+    }
+
+    /// Implementation of the `watch` variants, caching `key` of the value for change detection.
+    fn watch_by_key<T, K, F>(&mut self, func: F, key: fn(&T) -> K) -> T
+    where
+        F: for<'c, 's> Fn(RenderCtx<'c, 's, C>) -> T + 'static,
+        T: 'static,
+        K: PartialEq + 'static,
+    {
+        let me = self.ctx.hooks.reserve_key();
+        let (result, keep_alive, hooks) = self.track_child_hook(me, &func);
+
+        let Some(dep) = statics::current_hook() else {
+            log_or_panic!("`ctx.watch` called from outside a hook");
+            return result;
+        };
+        let hook = WatchState {
+            calc_value: move |ctx: RenderCtx<C>| key(&func(ctx)),
+            last_value: key(&result),
+            dep,
+            keep_alive,
+            hooks,
+        };
+        self.ctx.hooks.set_hook(me, Box::new(hook));
+        self.render_state.hooks.push(me);
+
+        result
+    }
+
+    /// Run the function tracking its reads as the hook `me`, returning the result along with
+    /// the keep alive objects and hooks created in it.
+    fn track_child_hook<T>(
+        &mut self,
+        me: HookKey,
+        func: impl for<'c, 's> FnOnce(RenderCtx<'c, 's, C>) -> T,
+    ) -> (T, Vec<KeepAlive>, Vec<HookKey>) {
         let mut hooks = Vec::new();
         let mut keep_alive = Vec::new();
 
@@ -98,20 +466,95 @@ impl<C: State> RenderCtx<'_, '_, C> {
             func(render)
         });
 
-        let Some(dep) = statics::current_hook() else {
-            log_or_panic!("`ctx.watch` called from outside a hook");
-            return result;
-        };
-        let hook = WatchState {
-            calc_value: Box::new(func),
-            last_value: result.clone(),
-            dep,
-            keep_alive,
-            hooks,
-        };
-        self.ctx.hooks.set_hook(me, Box::new(hook));
-        self.render_state.hooks.push(me);
+        (result, keep_alive, hooks)
+    }
+}
 
-        result
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::reactivity::signal::Signal;
+
+    struct Pair {
+        memoized: Signal<u32>,
+        unrelated: Signal<u32>,
+    }
+
+    impl State for Pair {
+        fn set(&mut self, new: Self) {
+            self.memoized.set(new.memoized);
+            self.unrelated.set(new.unrelated);
+        }
+    }
+
+    #[test]
+    fn memo_is_cached_when_unrelated_signal_changes() {
+        let ctx = InnerCtx::new(Pair {
+            memoized: Signal::new(1),
+            unrelated: Signal::new(0),
+        });
+        let mut ctx = ctx.borrow_mut();
+
+        let runs = Rc::new(Cell::new(0_u32));
+        let seen = Rc::new(Cell::new(0_u32));
+        let (runs_effect, seen_effect) = (Rc::clone(&runs), Rc::clone(&seen));
+        EffectHook::init_new(
+            Box::new(move |mut ctx: RenderCtx<Pair>| {
+                let doubled = ctx.memo(|ctx| {
+                    runs_effect.set(runs_effect.get().saturating_add(1));
+                    ctx.memoized.saturating_mul(2)
+                });
+                seen_effect.set(doubled.saturating_add(*ctx.unrelated));
+            }),
+            &mut ctx,
+        );
+        assert_eq!((runs.get(), seen.get()), (1, 2));
+
+        ctx.track_changes(|ctx| *ctx.data.unrelated = 10);
+        assert_eq!((runs.get(), seen.get()), (1, 12));
+
+        ctx.track_changes(|ctx| *ctx.data.unrelated = 20);
+        assert_eq!((runs.get(), seen.get()), (1, 22));
+
+        ctx.track_changes(|ctx| *ctx.data.memoized = 5);
+        assert_eq!((runs.get(), seen.get()), (2, 30));
+
+        ctx.track_changes(|ctx| *ctx.data.unrelated = 0);
+        assert_eq!((runs.get(), seen.get()), (2, 10));
+    }
+
+    #[test]
+    fn memo_is_dropped_with_caller() {
+        let ctx = InnerCtx::new(Pair {
+            memoized: Signal::new(1),
+            unrelated: Signal::new(0),
+        });
+        let mut ctx = ctx.borrow_mut();
+
+        let effect = EffectHook::init_new(
+            Box::new(move |mut ctx: RenderCtx<Pair>| {
+                let doubled = ctx.memo(|ctx| ctx.memoized.saturating_mul(2));
+                let _ = doubled.saturating_add(*ctx.unrelated);
+            }),
+            &mut ctx,
+        );
+        ctx.track_changes(|ctx| *ctx.data.unrelated = 10);
+        ctx.track_changes(|ctx| *ctx.data.unrelated = 20);
+
+        let memos: Vec<_> = ctx
+            .memos
+            .borrow()
+            .values()
+            .map(|entry| entry.memo)
+            .collect();
+        assert_eq!(memos.len(), 1);
+        assert!(memos.iter().all(|memo| ctx.hooks.is_alive(*memo)));
+
+        ctx.hooks.drop_hook(effect);
+        assert!(memos.iter().all(|memo| !ctx.hooks.is_alive(*memo)));
+        assert!(ctx.memos.borrow().is_empty());
     }
 }
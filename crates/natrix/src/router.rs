@@ -0,0 +1,657 @@
+//! Client side routing using the browser history api.
+//!
+//! Put a [`Location`] in your state, and render a [`Router`] with the [`Routes`] of your app.
+//! The router renders the first route matching the current path, and updates when
+//! [`Location::navigate`] is called, a link to a page of the app is clicked, or the user uses the
+//! back and forward buttons.
+//!
+//! # Example
+//! ```rust
+//! # use natrix::prelude::*;
+//! use natrix::router::{FromRouteParams, Location, RouteParams, Router, Routes};
+//!
+//! #[derive(State)]
+//! struct App {
+//!     location: Location,
+//! }
+//!
+//! struct UserRoute {
+//!     id: u32,
+//! }
+//!
+//! impl FromRouteParams for UserRoute {
+//!     fn from_route_params(params: &RouteParams) -> Option<Self> {
+//!         Some(Self {
+//!             id: params.parse("id")?,
+//!         })
+//!     }
+//! }
+//!
+//! fn user(UserRoute { id }: UserRoute) -> impl Element<App> {
+//!     e::h1().text(format!("User {id}"))
+//! }
+//!
+//! fn render() -> impl Element<App> {
+//!     e::div()
+//!         .child(e::a().href("/users/1").text("First user"))
+//!         .child(Router::new(
+//!             |ctx: Ref<App>| field!(ctx.location),
+//!             Routes::new()
+//!                 .route("/", |()| e::h1().text("Home"))
+//!                 .route("/users/:id", user)
+//!                 .fallback(|path| e::h1().text(format!("{path} not found"))),
+//!         ))
+//! }
+//! ```
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::access::{Getter, RefClosure};
+use crate::dom::element::{DynElement, ElementRenderResult, MaybeStaticElement};
+use crate::dom::events::EventListener;
+use crate::error_handling::{log_or_panic, log_or_panic_result};
+use crate::reactivity::State;
+use crate::reactivity::render_callbacks::{ReactiveNode, RenderingState};
+use crate::reactivity::signal::Signal;
+use crate::reactivity::state::{EventCtx, InnerCtx, RenderCtx, Snapshot};
+use crate::{Element, get_document, get_window};
+
+/// The current path of the app, including the query and fragment.
+///
+/// The path is relative to the [base path](crate::base_path) the app is served under, so routes
+/// and [`Location::navigate`] do not need to include it.
+/// Reading the path is tracked like reading a signal.
+pub struct Location {
+    /// The current path
+    path: Signal<String>,
+    /// The current path without the query and fragment, so routes are not matched again when
+    /// only those change
+    pathname: Signal<String>,
+}
+
+impl State for Location {
+    fn set(&mut self, new: Self) {
+        self.path.set(new.path);
+        self.pathname.set(new.pathname);
+    }
+}
+
+impl Snapshot for Location {
+    fn snapshot(&self) -> Self {
+        Self {
+            path: self.path.snapshot(),
+            pathname: self.pathname.snapshot(),
+        }
+    }
+}
+
+impl Location {
+    /// Create a location at the path the page was loaded at
+    #[must_use]
+    pub fn current() -> Self {
+        Self::new(current_path())
+    }
+
+    /// Create a location at the given path, without changing the url.
+    ///
+    /// This is mainly useful for tests, use [`Location::current`] in your app.
+    #[must_use]
+    pub fn new(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self {
+            pathname: Signal::new(strip_query(&path).to_owned()),
+            path: Signal::new(path),
+        }
+    }
+
+    /// The current path, such as `/users/1?tab=posts`
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The current path without the query and fragment, such as `/users/1`.
+    ///
+    /// Unlike [`Location::path`] this is only tracked as changed when the path itself changes.
+    #[must_use]
+    pub fn pathname(&self) -> &str {
+        &self.pathname
+    }
+
+    /// Update the path signals, only marking the ones that changed
+    fn set_path(&mut self, path: String) {
+        self.pathname
+            .update_if_changed(strip_query(&path).to_owned());
+        self.path.update_if_changed(path);
+    }
+
+    /// Go to the given path, adding a entry to the browser history.
+    pub fn navigate(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if *self.path == path {
+            return;
+        }
+
+        if let Ok(history) = get_window().history() {
+            log_or_panic_result!(
+                history.push_state_with_url(&JsValue::NULL, "", Some(&with_base_path(&path))),
+                "Failed to push history entry"
+            );
+        } else {
+            log_or_panic!("Failed to get history");
+        }
+        self.set_path(path);
+    }
+
+    /// Go to the given path, replacing the current browser history entry.
+    ///
+    /// Use this for redirects, so the back button does not lead to the redirect again.
+    pub fn replace(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if let Ok(history) = get_window().history() {
+            log_or_panic_result!(
+                history.replace_state_with_url(&JsValue::NULL, "", Some(&with_base_path(&path))),
+                "Failed to replace history entry"
+            );
+        } else {
+            log_or_panic!("Failed to get history");
+        }
+        self.set_path(path);
+    }
+}
+
+/// The parameters matched by the `:name` segments of a route.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteParams(Vec<(&'static str, String)>);
+
+impl RouteParams {
+    /// Get the value of a parameter, percent decoded.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse the value of a parameter, returning `None` if it is missing or fails to parse.
+    #[must_use]
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+}
+
+/// Parameters of a route, parsed from the matched [`RouteParams`].
+///
+/// A route only matches if its parameters parse, so a path such as `/users/abc` for a route
+/// expecting a numeric id falls through to the next route, or the fallback.
+///
+/// `()` is for routes without parameters, and [`RouteParams`] gives the raw values.
+///
+/// # Example
+/// ```rust
+/// use natrix::router::{FromRouteParams, RouteParams};
+///
+/// struct PostRoute {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// impl FromRouteParams for PostRoute {
+///     fn from_route_params(params: &RouteParams) -> Option<Self> {
+///         Some(Self {
+///             user: params.get("user")?.to_owned(),
+///             post: params.parse("post")?,
+///         })
+///     }
+/// }
+/// ```
+pub trait FromRouteParams: Sized {
+    /// Parse the parameters, returning `None` if they are missing or invalid
+    fn from_route_params(params: &RouteParams) -> Option<Self>;
+}
+
+impl FromRouteParams for RouteParams {
+    fn from_route_params(params: &RouteParams) -> Option<Self> {
+        Some(params.clone())
+    }
+}
+
+impl FromRouteParams for () {
+    fn from_route_params(_params: &RouteParams) -> Option<Self> {
+        Some(())
+    }
+}
+
+/// A segment of a route pattern
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    /// A segment that must match exactly
+    Static(&'static str),
+    /// A `:name` segment matching anything
+    Param(&'static str),
+}
+
+/// Render function for a route, returning `None` if the parameters do not parse
+type RouteRender<C> = Box<dyn Fn(&RouteParams) -> Option<MaybeStaticElement<C>>>;
+/// Render function for paths that match no routes
+type FallbackRender<C> = Box<dyn Fn(String) -> MaybeStaticElement<C>>;
+
+/// A route of a [`Routes`]
+struct Route<C: State> {
+    /// The path pattern
+    pattern: Vec<Segment>,
+    /// Whether the matched parameters parse
+    accepts: fn(&RouteParams) -> bool,
+    /// Render the route
+    render: RouteRender<C>,
+}
+
+/// The route a path matched
+#[derive(Clone, Debug, PartialEq)]
+enum MatchedRoute {
+    /// The route at the index, with its parameters
+    Route(usize, RouteParams),
+    /// No routes matched the path
+    Fallback(String),
+}
+
+/// The routes of a [`Router`].
+///
+/// Patterns are paths such as `/users/:id`, where segments starting with `:` match any value and
+/// are parsed into the parameters of the route with [`FromRouteParams`].
+/// The first route that matches and whose parameters parse is rendered, and the query and
+/// fragment of the path are ignored.
+#[must_use]
+pub struct Routes<C: State> {
+    /// The routes in the order they were added
+    routes: Vec<Route<C>>,
+    /// Rendered when no routes match
+    fallback: FallbackRender<C>,
+}
+
+impl<C: State> Default for Routes<C> {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: Box::new(|_| None::<&'static str>.render()),
+        }
+    }
+}
+
+impl<C: State> Routes<C> {
+    /// Create a empty set of routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route, which is rendered with its parameters parsed as `P`.
+    ///
+    /// Use `()` for routes without parameters, or [`RouteParams`] for the raw values.
+    pub fn route<P: FromRouteParams, E: Element<C>>(
+        mut self,
+        pattern: &'static str,
+        render: impl Fn(P) -> E + 'static,
+    ) -> Self {
+        self.routes.push(Route {
+            pattern: parse_pattern(pattern),
+            accepts: |params| P::from_route_params(params).is_some(),
+            render: Box::new(move |params| Some(render(P::from_route_params(params)?).render())),
+        });
+        self
+    }
+
+    /// Set what to render when no routes match, it is given the current path without the query
+    /// and fragment.
+    ///
+    /// By default nothing is rendered.
+    pub fn fallback<E: Element<C>>(mut self, render: impl Fn(String) -> E + 'static) -> Self {
+        self.fallback = Box::new(move |path| render(path).render());
+        self
+    }
+
+    /// Find the first route matching the path
+    fn find(&self, path: &str) -> MatchedRoute {
+        for (index, route) in self.routes.iter().enumerate() {
+            if let Some(params) = match_pattern(&route.pattern, path)
+                && (route.accepts)(&params)
+            {
+                return MatchedRoute::Route(index, params);
+            }
+        }
+        MatchedRoute::Fallback(path.to_owned())
+    }
+
+    /// Render the matched route
+    fn render(&self, matched: MatchedRoute) -> MaybeStaticElement<C> {
+        match matched {
+            MatchedRoute::Route(index, params) => {
+                if let Some(element) = self
+                    .routes
+                    .get(index)
+                    .and_then(|route| (route.render)(&params))
+                {
+                    element
+                } else {
+                    log_or_panic!("Matched route not found");
+                    (self.fallback)(String::new())
+                }
+            }
+            MatchedRoute::Fallback(path) => (self.fallback)(path),
+        }
+    }
+}
+
+/// Renders the route matching the current [`Location`].
+///
+/// While rendered it also handles the back and forward buttons, and clicks on links to paths in
+/// the app, which navigate without reloading the page.
+/// Links that open in a new tab or window, download the file, or have a `data-natrix-external`
+/// attribute, are left alone.
+///
+/// The route is rendered from scratch when the matched route or its parameters change, so keep
+/// any state that should survive navigating away and back, such as form inputs, in your state.
+/// Changes to only the query or fragment of the url do not render the route again.
+#[must_use]
+pub struct Router<C: State, L> {
+    /// Getter for the location in the state
+    location: L,
+    /// The routes
+    routes: Routes<C>,
+    /// Whether to handle clicks on links
+    intercept_links: bool,
+}
+
+impl<C: State, L: Getter<C, Location>> Router<C, L> {
+    /// Create a router for the location returned by the getter
+    pub fn new(location: L, routes: Routes<C>) -> Self {
+        Self {
+            location,
+            routes,
+            intercept_links: true,
+        }
+    }
+
+    /// Set whether clicks on links to paths in the app are handled by the router, which is the
+    /// default.
+    ///
+    /// Disable this if something else on the page already handles link clicks, navigation then
+    /// only happens through [`Location::navigate`] and the back and forward buttons.
+    /// To leave only some links to the browser add a `data-natrix-external` attribute to them.
+    pub fn intercept_links(mut self, intercept: bool) -> Self {
+        self.intercept_links = intercept;
+        self
+    }
+}
+
+impl<C: State, L: Getter<C, Location>> Element<C> for Router<C, L> {
+    fn render(self) -> MaybeStaticElement<C> {
+        MaybeStaticElement::Dynamic(Box::new(self))
+    }
+}
+
+impl<C: State, L: Getter<C, Location>> DynElement<C> for Router<C, L> {
+    fn render(
+        self: Box<Self>,
+        ctx: &mut InnerCtx<C>,
+        render_state: &mut RenderingState,
+    ) -> ElementRenderResult {
+        let Self {
+            location,
+            routes,
+            intercept_links,
+        } = *self;
+        let handle = EventCtx(ctx).weak_handle();
+
+        let pop_handle = handle.clone();
+        let pop_location = location.clone();
+        if let Some(listener) = EventListener::new(get_window().into(), "popstate", move |_| {
+            let path = current_path();
+            pop_handle.with_mut(|mut ctx| pop_location.call_mut(&mut ctx).set_path(path));
+        }) {
+            render_state.keep_alive.push(Box::new(listener));
+        }
+
+        if intercept_links {
+            let click_location = location.clone();
+            if let Some(listener) =
+                EventListener::new(get_document().into(), "click", move |event| {
+                    let Some(path) = internal_link(&event) else {
+                        return;
+                    };
+                    event.prevent_default();
+                    handle.with_mut(|mut ctx| click_location.call_mut(&mut ctx).navigate(path));
+                })
+            {
+                render_state.keep_alive.push(Box::new(listener));
+            }
+        }
+
+        let routes = Rc::new(routes);
+        let (me, node) = ReactiveNode::create_initial(
+            Box::new(move |mut ctx: RenderCtx<C>| {
+                let matched = {
+                    let routes = Rc::clone(&routes);
+                    let location = location.clone();
+                    ctx.watch(move |ctx| routes.find(location.call_read(&ctx).pathname()))
+                };
+                routes.render(matched)
+            }),
+            ctx,
+        );
+        render_state.hooks.push(me);
+        ElementRenderResult::Node(node)
+    }
+}
+
+/// Get the path of the current url relative to the base path, including the query and fragment
+fn current_path() -> String {
+    let location = get_window().location();
+    let (Ok(path), Ok(search), Ok(hash)) =
+        (location.pathname(), location.search(), location.hash())
+    else {
+        log_or_panic!("Failed to read the current url");
+        return String::from("/");
+    };
+    let path = format!("{path}{search}{hash}");
+    match strip_base_path(&path, crate::base_path()) {
+        Some(relative) => relative.to_owned(),
+        None => path,
+    }
+}
+
+/// Prefix a path relative to the base path with the base path
+fn with_base_path(path: &str) -> String {
+    format!("{}{path}", crate::base_path())
+}
+
+/// Get the path relative to the base path, or `None` if it is not under the base path
+fn strip_base_path<'a>(path: &'a str, base_path: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(base_path)?;
+    if rest.is_empty() {
+        Some("/")
+    } else if rest.starts_with(['/', '?', '#']) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Get the path a click navigates to if it is on a link the router should handle
+fn internal_link(event: &web_sys::Event) -> Option<String> {
+    let event = event.dyn_ref::<web_sys::MouseEvent>()?;
+    if event.default_prevented()
+        || event.button() != 0
+        || event.ctrl_key()
+        || event.meta_key()
+        || event.shift_key()
+        || event.alt_key()
+    {
+        return None;
+    }
+
+    let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+    let anchor = target
+        .closest("a[href]")
+        .ok()??
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .ok()?;
+    if anchor.has_attribute("download")
+        || anchor.has_attribute("data-natrix-external")
+        || anchor
+            .get_attribute("target")
+            .is_some_and(|target| target != "_self")
+    {
+        return None;
+    }
+
+    let location = get_window().location();
+    if anchor.origin() != location.origin().ok()? {
+        return None;
+    }
+    let path = format!("{}{}", anchor.pathname(), anchor.search());
+    // NOTE: Links to a fragment on the current page are left to the browser.
+    if !anchor.hash().is_empty()
+        && path == format!("{}{}", location.pathname().ok()?, location.search().ok()?)
+    {
+        return None;
+    }
+    // NOTE: Links to other apps on the same origin are left to the browser.
+    let path = strip_base_path(&path, crate::base_path())?;
+    Some(format!("{path}{}", anchor.hash()))
+}
+
+/// Get the non empty segments of a path
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// Parse a route pattern
+fn parse_pattern(pattern: &'static str) -> Vec<Segment> {
+    segments(pattern)
+        .map(|segment| {
+            segment
+                .strip_prefix(':')
+                .map_or(Segment::Static(segment), Segment::Param)
+        })
+        .collect()
+}
+
+/// Remove the query and fragment from a path
+fn strip_query(path: &str) -> &str {
+    path.split(['?', '#']).next().unwrap_or_default()
+}
+
+/// Match the path against the pattern, returning the parameters if it matches
+fn match_pattern(pattern: &[Segment], path: &str) -> Option<RouteParams> {
+    let mut path_segments = segments(strip_query(path));
+    let mut params = Vec::new();
+    for segment in pattern {
+        let value = path_segments.next()?;
+        match segment {
+            Segment::Static(expected) => {
+                if *expected != value {
+                    return None;
+                }
+            }
+            Segment::Param(name) => params.push((*name, percent_decode(value))),
+        }
+    }
+
+    if path_segments.next().is_some() {
+        return None;
+    }
+    Some(RouteParams(params))
+}
+
+/// Decode the `%XX` escapes in a url segment
+fn percent_decode(value: &str) -> String {
+    let mut parts = value.split('%');
+    let mut bytes = parts.next().unwrap_or_default().as_bytes().to_vec();
+    for part in parts {
+        if let Some(hex) = part.get(..2)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            bytes.push(byte);
+            bytes.extend_from_slice(part.get(2..).unwrap_or_default().as_bytes());
+        } else {
+            bytes.push(b'%');
+            bytes.extend_from_slice(part.as_bytes());
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &'static str, path: &str) -> Option<Vec<(&'static str, String)>> {
+        match_pattern(&parse_pattern(pattern), path).map(|params| params.0)
+    }
+
+    #[test]
+    fn base_path() {
+        assert_eq!(strip_base_path("/about", ""), Some("/about"));
+        assert_eq!(strip_base_path("/myapp", "/myapp"), Some("/"));
+        assert_eq!(strip_base_path("/myapp/about", "/myapp"), Some("/about"));
+        assert_eq!(strip_base_path("/myapp?tab=1", "/myapp"), Some("?tab=1"));
+        assert_eq!(strip_base_path("/myapplication", "/myapp"), None);
+        assert_eq!(strip_base_path("/other", "/myapp"), None);
+    }
+
+    #[test]
+    fn static_routes() {
+        assert_eq!(matches("/", "/"), Some(vec![]));
+        assert_eq!(matches("/about", "/about"), Some(vec![]));
+        assert_eq!(matches("/about", "/about/"), Some(vec![]));
+        assert_eq!(matches("/about", "/"), None);
+        assert_eq!(matches("/", "/about"), None);
+        assert_eq!(matches("/about", "/about/team"), None);
+    }
+
+    #[test]
+    fn params() {
+        assert_eq!(
+            matches("/users/:id", "/users/42"),
+            Some(vec![("id", "42".to_owned())])
+        );
+        assert_eq!(
+            matches("/users/:id/posts/:post", "/users/1/posts/hello"),
+            Some(vec![("id", "1".to_owned()), ("post", "hello".to_owned())])
+        );
+        assert_eq!(matches("/users/:id", "/users"), None);
+        assert_eq!(matches("/users/:id", "/posts/1"), None);
+    }
+
+    #[test]
+    fn query_and_fragment_are_ignored() {
+        assert_eq!(
+            matches("/users/:id", "/users/42?tab=posts#top"),
+            Some(vec![("id", "42".to_owned())])
+        );
+        assert_eq!(matches("/", "/#top"), Some(vec![]));
+        assert_eq!(strip_query("/users/42?tab=posts#top"), "/users/42");
+        assert_eq!(strip_query("/#top"), "/");
+    }
+
+    #[test]
+    fn params_are_decoded() {
+        assert_eq!(percent_decode("john%20doe"), "john doe");
+        assert_eq!(percent_decode("%C3%B8"), "ø");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%2"), "%zz%2");
+    }
+
+    #[test]
+    fn typed_params() {
+        let params = RouteParams(vec![("id", "42".to_owned()), ("name", "bob".to_owned())]);
+        assert_eq!(params.get("name"), Some("bob"));
+        assert_eq!(params.parse::<u32>("id"), Some(42));
+        assert_eq!(params.parse::<u32>("name"), None);
+        assert_eq!(params.parse::<u32>("missing"), None);
+    }
+}
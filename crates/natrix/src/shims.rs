@@ -0,0 +1,102 @@
+//! Wrappers around nondeterministic browser apis.
+//!
+//! Use these instead of calling `Math.random`, `Date.now`, and `setTimeout` directly, as
+//! [`test_utils`](crate::test_utils) can override them to make tests reproducible.
+//! Natrix routes its own uses of time, timers, and randomness through these as well.
+
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::{self, Function};
+
+/// Get a random number in the range `0.0..1.0`, like `Math.random()`.
+///
+/// Deterministic if [`test_utils::seed_random`](crate::test_utils::seed_random) was called.
+#[must_use]
+pub fn random() -> f64 {
+    #[cfg(feature = "test_utils")]
+    if let Some(value) = crate::test_utils::fake_random() {
+        return value;
+    }
+
+    js_sys::Math::random()
+}
+
+/// Get the current time in milliseconds since the unix epoch, like `Date.now()`.
+///
+/// Returns the fake time if [`test_utils::set_fake_now`](crate::test_utils::set_fake_now) was
+/// called.
+#[must_use]
+pub fn now() -> f64 {
+    #[cfg(feature = "test_utils")]
+    if let Some(value) = crate::test_utils::fake_now() {
+        return value;
+    }
+
+    js_sys::Date::now()
+}
+
+/// Call `callback` once `milliseconds` have passed, like `setTimeout`, returning the timer id.
+///
+/// Runs on the fake clock if [`test_utils::use_fake_timers`](crate::test_utils::use_fake_timers)
+/// was called.
+///
+/// # Errors
+/// If the browser fails to set the timeout.
+pub fn set_timeout(callback: &Function, milliseconds: i32) -> Result<i32, JsValue> {
+    #[cfg(feature = "test_utils")]
+    if let Some(id) = crate::test_utils::fake_set_timeout(callback, milliseconds) {
+        return Ok(id);
+    }
+
+    crate::get_window()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(callback, milliseconds)
+}
+
+/// Cancel a timer set with [`set_timeout`], like `clearTimeout`.
+pub fn clear_timeout(id: i32) {
+    #[cfg(feature = "test_utils")]
+    if crate::test_utils::fake_clear_timeout(id) {
+        return;
+    }
+
+    crate::get_window().clear_timeout_with_handle(id);
+}
+
+#[cfg(all(test, feature = "test_utils"))]
+#[expect(clippy::float_cmp, reason = "The fakes should return exact values")]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn seeded_random_is_deterministic() {
+        test_utils::seed_random(42);
+        let first: Vec<f64> = (0..10).map(|_| random()).collect();
+        test_utils::seed_random(42);
+        let second: Vec<f64> = (0..10).map(|_| random()).collect();
+        test_utils::reset_fakes();
+
+        assert_eq!(first, second);
+        assert!(first.iter().all(|value| (0.0..1.0).contains(value)));
+        assert!(first.windows(2).any(|pair| pair.first() != pair.get(1)));
+    }
+
+    #[test]
+    fn different_seeds_differ() {
+        test_utils::seed_random(1);
+        let first = random();
+        test_utils::seed_random(2);
+        let second = random();
+        test_utils::reset_fakes();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn fake_now() {
+        test_utils::set_fake_now(1000.0);
+        assert_eq!(now(), 1000.0);
+        test_utils::advance_fake_now(500.0);
+        assert_eq!(now(), 1500.0);
+        test_utils::reset_fakes();
+    }
+}
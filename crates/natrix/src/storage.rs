@@ -0,0 +1,225 @@
+//! Typed access to `localStorage` and `sessionStorage`.
+//!
+//! Values are stored as json, and reading them back returns a error instead of panicking if the
+//! stored value does not match the expected type.
+//!
+//! # Example
+//! ```rust
+//! use natrix::storage::{LocalStorage, StorageError};
+//!
+//! fn save_volume(volume: u8) -> Result<(), StorageError> {
+//!     LocalStorage::set("volume", &volume)
+//! }
+//!
+//! fn load_volume() -> u8 {
+//!     LocalStorage::get("volume").ok().flatten().unwrap_or(50)
+//! }
+//! ```
+#![cfg(feature = "storage")]
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::access::{Getter, RefClosure};
+use crate::get_window;
+use crate::reactivity::State;
+use crate::reactivity::signal::Signal;
+use crate::reactivity::state::{EventCtx, RenderCtx};
+
+/// Why reading or writing storage failed
+#[derive(Debug)]
+pub enum StorageError {
+    /// The storage is not available, such as when the user has disabled it, or in some private
+    /// browsing modes.
+    Unavailable,
+    /// The browser refused to store the value, usually because the storage quota is exceeded.
+    Write,
+    /// The value could not be serialized
+    Serialize(serde_json::Error),
+    /// The stored value could not be parsed as the requested type
+    Deserialize(serde_json::Error),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable => f.write_str("storage is not available"),
+            Self::Write => f.write_str("failed to write to storage, is the quota exceeded?"),
+            Self::Serialize(err) => write!(f, "failed to serialize value: {err}"),
+            Self::Deserialize(err) => write!(f, "failed to parse stored value: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Unavailable | Self::Write => None,
+            Self::Serialize(err) | Self::Deserialize(err) => Some(err),
+        }
+    }
+}
+
+/// The `localStorage` of the page, which is kept across browser sessions.
+pub struct LocalStorage;
+
+impl LocalStorage {
+    /// Get the value stored under `key`, returning `Ok(None)` if there is none.
+    ///
+    /// # Errors
+    /// If the storage is not available, or the stored value can not be parsed as `T`.
+    pub fn get<T: DeserializeOwned>(key: &str) -> Result<Option<T>, StorageError> {
+        get(&local_storage()?, key)
+    }
+
+    /// Store `value` under `key`.
+    ///
+    /// # Errors
+    /// If the storage is not available, the value fails to serialize, or the browser refuses to
+    /// store it.
+    pub fn set<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<(), StorageError> {
+        set(&local_storage()?, key, value)
+    }
+
+    /// Remove the value stored under `key`.
+    ///
+    /// # Errors
+    /// If the storage is not available.
+    pub fn remove(key: &str) -> Result<(), StorageError> {
+        remove(&local_storage()?, key)
+    }
+}
+
+/// The `sessionStorage` of the page, which is cleared when the tab is closed.
+pub struct SessionStorage;
+
+impl SessionStorage {
+    /// Get the value stored under `key`, returning `Ok(None)` if there is none.
+    ///
+    /// # Errors
+    /// If the storage is not available, or the stored value can not be parsed as `T`.
+    pub fn get<T: DeserializeOwned>(key: &str) -> Result<Option<T>, StorageError> {
+        get(&session_storage()?, key)
+    }
+
+    /// Store `value` under `key`.
+    ///
+    /// # Errors
+    /// If the storage is not available, the value fails to serialize, or the browser refuses to
+    /// store it.
+    pub fn set<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<(), StorageError> {
+        set(&session_storage()?, key, value)
+    }
+
+    /// Remove the value stored under `key`.
+    ///
+    /// # Errors
+    /// If the storage is not available.
+    pub fn remove(key: &str) -> Result<(), StorageError> {
+        remove(&session_storage()?, key)
+    }
+}
+
+/// Get `localStorage`.
+///
+/// Accessing it throws in some private browsing modes, so this is a error rather than a panic.
+fn local_storage() -> Result<web_sys::Storage, StorageError> {
+    get_window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .ok_or(StorageError::Unavailable)
+}
+
+/// Get `sessionStorage`.
+///
+/// Accessing it throws in some private browsing modes, so this is a error rather than a panic.
+fn session_storage() -> Result<web_sys::Storage, StorageError> {
+    get_window()
+        .session_storage()
+        .ok()
+        .flatten()
+        .ok_or(StorageError::Unavailable)
+}
+
+/// Get and parse the value stored under `key`
+fn get<T: DeserializeOwned>(
+    storage: &web_sys::Storage,
+    key: &str,
+) -> Result<Option<T>, StorageError> {
+    let Some(value) = storage
+        .get_item(key)
+        .map_err(|_| StorageError::Unavailable)?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_str(&value)
+        .map(Some)
+        .map_err(StorageError::Deserialize)
+}
+
+/// Serialize and store the value under `key`
+fn set<T: Serialize + ?Sized>(
+    storage: &web_sys::Storage,
+    key: &str,
+    value: &T,
+) -> Result<(), StorageError> {
+    let value = serde_json::to_string(value).map_err(StorageError::Serialize)?;
+    storage
+        .set_item(key, &value)
+        .map_err(|_| StorageError::Write)
+}
+
+/// Remove the value stored under `key`
+fn remove(storage: &web_sys::Storage, key: &str) -> Result<(), StorageError> {
+    storage
+        .remove_item(key)
+        .map_err(|_| StorageError::Unavailable)
+}
+
+impl<C: State> RenderCtx<'_, '_, C> {
+    /// Keep the signal returned by the getter in [`LocalStorage`] under `key`.
+    ///
+    /// The stored value is loaded into the signal straight away, and a
+    /// [`effect`](Self::effect) saves the signal whenever it changes.
+    /// A stored value that fails to parse is ignored, leaving the signal as is, and failing to
+    /// save the value is logged, as storage might be unavailable.
+    ///
+    /// Like `effect` this lasts as long as the calling closure, so call it in your top level
+    /// render closure to persist the signal for the whole app.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(State)]
+    /// struct App {
+    ///     volume: Signal<u8>,
+    /// }
+    ///
+    /// fn render() -> impl Element<App> {
+    ///     |mut ctx: RenderCtx<App>| {
+    ///         ctx.persist_signal("volume", |ctx: Ref<App>| field!(ctx.volume));
+    ///         e::div().text(|ctx: RenderCtx<App>| *ctx.volume)
+    ///     }
+    /// }
+    /// ```
+    pub fn persist_signal<T>(&mut self, key: &'static str, signal: impl Getter<C, Signal<T>>)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + 'static,
+    {
+        match LocalStorage::get(key) {
+            Ok(Some(value)) => {
+                let mut ctx = EventCtx(&mut *self.ctx);
+                signal.call_mut(&mut ctx).update_if_changed(value);
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!("Failed to load `{key}` from storage: {err}"),
+        }
+
+        self.effect(move |ctx| {
+            if let Err(err) = LocalStorage::set(key, &**signal.call_read(&ctx)) {
+                log::warn!("Failed to save `{key}` to storage: {err}");
+            }
+        });
+    }
+}
@@ -2,10 +2,11 @@
 #![cfg(feature = "test_utils")]
 #![expect(clippy::expect_used, reason = "tests only")]
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
+use web_sys::js_sys::Function;
 
 use crate::prelude::State;
 use crate::reactivity::mount::render_state;
@@ -20,6 +21,44 @@ pub const MOUNT_POINT: &str = "__TESTING_MOUNT_POINT";
 
 thread_local! {
      static CURRENT_COMP: Cell<KeepAlive>  = Cell::new(Box::new(()));
+     /// The amount of hooks ran in the current `count_renders` call
+     static HOOK_RUNS: Cell<Option<usize>> = const { Cell::new(None) };
+     /// The state of the seeded random generator, if seeded
+     static RANDOM_STATE: Cell<Option<u64>> = const { Cell::new(None) };
+     /// The fake current time, if set
+     static FAKE_NOW: Cell<Option<f64>> = const { Cell::new(None) };
+     /// The fake timer clock, if fake timers are active
+     static FAKE_TIMERS: RefCell<Option<FakeTimers>> = const { RefCell::new(None) };
+     /// The amount of natrix spawned tasks that have not completed yet
+     #[cfg(feature = "async")]
+     static PENDING_TASKS: Cell<usize> = const { Cell::new(0) };
+     /// The amount of times natrix spawned tasks have been polled, used to detect progress
+     #[cfg(feature = "async")]
+     static TASK_POLLS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The most microtask yields `tick` does before giving up on the tasks settling
+#[cfg(feature = "async")]
+const MAX_TICKS: usize = 1000;
+
+/// A timer set while fake timers are active
+struct FakeTimer {
+    /// The id returned in place of the `setTimeout` id
+    id: i32,
+    /// The time on the fake clock the timer fires at
+    due: f64,
+    /// The function to call
+    callback: Function,
+}
+
+/// The fake clock for timers, see [`use_fake_timers`]
+struct FakeTimers {
+    /// The current time on the fake clock in milliseconds, starting at `0`
+    now: f64,
+    /// The id of the next timer
+    next_id: i32,
+    /// The timers that have not fired yet, in the order they were set
+    timers: Vec<FakeTimer>,
 }
 
 /// Has a logger be initlized?
@@ -122,3 +161,283 @@ pub fn get(id: impl Into<&'static str>) -> HtmlElement {
         .expect("Target Node wasnt a html element")
         .clone()
 }
+
+/// Record that a reactive hook was ran, if a `count_renders` call is active
+pub(crate) fn record_hook_run() {
+    if let Some(runs) = HOOK_RUNS.get() {
+        HOOK_RUNS.set(Some(runs.saturating_add(1)));
+    }
+}
+
+/// Run the given function and return how many reactive hooks (render closures, reactive
+/// attributes, `.watch` calls, etc) were re-ran as a result of it.
+///
+/// This is useful for catching over-rendering, for example asserting that updating one field only
+/// reruns the closures that read that field.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # use natrix::test_utils::{count_renders, get};
+/// # const BUTTON: Id = natrix::id!();
+/// # fn test() {
+/// let reruns = count_renders(|| get(BUTTON).click());
+/// assert_eq!(reruns, 1);
+/// # }
+/// ```
+///
+/// # Panics
+/// If called recursively.
+pub fn count_renders(func: impl FnOnce()) -> usize {
+    assert!(
+        HOOK_RUNS.replace(Some(0)).is_none(),
+        "`count_renders` called recursively"
+    );
+    func();
+    HOOK_RUNS.take().unwrap_or_default()
+}
+
+/// Make [`shims::random`](crate::shims::random) return a deterministic sequence based on the seed.
+///
+/// This lasts until [`reset_fakes`] is called, and is not reset by [`mount_test`].
+pub fn seed_random(seed: u64) {
+    RANDOM_STATE.set(Some(seed));
+}
+
+/// Make [`shims::now`](crate::shims::now) return the given time (in milliseconds since the unix
+/// epoch) until changed.
+///
+/// This lasts until [`reset_fakes`] is called, and is not reset by [`mount_test`].
+pub fn set_fake_now(milliseconds: f64) {
+    FAKE_NOW.set(Some(milliseconds));
+}
+
+/// Move the fake time forward by the given amount of milliseconds.
+///
+/// # Panics
+/// If [`set_fake_now`] hasnt been called.
+pub fn advance_fake_now(milliseconds: f64) {
+    let now = FAKE_NOW
+        .get()
+        .expect("`advance_fake_now` called without `set_fake_now`");
+    FAKE_NOW.set(Some(now + milliseconds));
+}
+
+/// Make the timers natrix uses, such as `async_utils::sleep` and debounced event handlers, run
+/// on a fake clock instead of the browser timers.
+///
+/// The timers only fire when the clock is moved forward with [`advance_timers`].
+/// This lasts until [`reset_fakes`] is called, and is not reset by [`mount_test`].
+pub fn use_fake_timers() {
+    FAKE_TIMERS.set(Some(FakeTimers {
+        now: 0.0,
+        next_id: 1,
+        timers: Vec::new(),
+    }));
+}
+
+/// Go back to using the real `Math.random`, `Date.now`, and browser timers.
+///
+/// Pending fake timers are discarded.
+pub fn reset_fakes() {
+    RANDOM_STATE.set(None);
+    FAKE_NOW.set(None);
+    FAKE_TIMERS.set(None);
+}
+
+/// Get the next seeded random number, if seeded.
+pub(crate) fn fake_random() -> Option<f64> {
+    let state = RANDOM_STATE.get()?;
+
+    // NOTE: splitmix64, which is plenty for reproducible tests.
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    RANDOM_STATE.set(Some(state));
+    let mut value = state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^= value >> 31;
+
+    // Use the top 52 bits as the mantissa of a float in `1.0..2.0`
+    let float = f64::from_bits(0x3FF0_0000_0000_0000 | (value >> 12));
+    Some(float - 1.0)
+}
+
+/// Get the fake current time, if set.
+pub(crate) fn fake_now() -> Option<f64> {
+    FAKE_NOW.get()
+}
+
+/// Set a timer on the fake clock, returning its id if fake timers are active.
+pub(crate) fn fake_set_timeout(callback: &Function, milliseconds: i32) -> Option<i32> {
+    FAKE_TIMERS.with_borrow_mut(|fake| {
+        let fake = fake.as_mut()?;
+        let id = fake.next_id;
+        fake.next_id = id.wrapping_add(1);
+        fake.timers.push(FakeTimer {
+            id,
+            due: fake.now + f64::from(milliseconds.max(0)),
+            callback: callback.clone(),
+        });
+        Some(id)
+    })
+}
+
+/// Clear a timer on the fake clock, returning `false` if it is not a pending fake timer.
+pub(crate) fn fake_clear_timeout(id: i32) -> bool {
+    FAKE_TIMERS.with_borrow_mut(|fake| {
+        let Some(fake) = fake.as_mut() else {
+            return false;
+        };
+        let before = fake.timers.len();
+        fake.timers.retain(|timer| timer.id != id);
+        fake.timers.len() != before
+    })
+}
+
+/// Move the fake timer clock to `time`, along with the fake current time if set.
+fn move_fake_clock(fake: &mut FakeTimers, time: f64) {
+    if let Some(now) = FAKE_NOW.get() {
+        FAKE_NOW.set(Some(now + (time - fake.now)));
+    }
+    fake.now = time;
+}
+
+/// A natrix spawned future that is tracked for [`tick`]
+#[cfg(feature = "async")]
+#[pin_project::pin_project]
+pub(crate) struct TrackedTask<F> {
+    /// The future to run
+    #[pin]
+    inner: F,
+}
+
+/// Track the future so [`tick`] waits for it
+#[cfg(feature = "async")]
+pub(crate) fn track_task<F: Future>(inner: F) -> TrackedTask<F> {
+    PENDING_TASKS.set(PENDING_TASKS.get().saturating_add(1));
+    TrackedTask { inner }
+}
+
+#[cfg(feature = "async")]
+impl<F: Future> Future for TrackedTask<F> {
+    type Output = F::Output;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        TASK_POLLS.set(TASK_POLLS.get().wrapping_add(1));
+        let result = self.project().inner.poll(cx);
+        if result.is_ready() {
+            PENDING_TASKS.set(PENDING_TASKS.get().saturating_sub(1));
+        }
+        result
+    }
+}
+
+/// The amount of tasks spawned with `use_async` that have not completed yet.
+///
+/// Tasks waiting on a timer or a network request are still pending after [`tick`].
+#[cfg(feature = "async")]
+#[must_use]
+pub fn pending_tasks() -> usize {
+    PENDING_TASKS.get()
+}
+
+/// Yield to the microtask queue until the tasks spawned with `use_async` stop making progress.
+///
+/// This runs every task that is ready to run, including ones spawned or woken by other tasks,
+/// so any state updates they do are applied once this returns.
+/// Tasks waiting on timers are left pending, use [`advance_timers`] to run them.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # use natrix::test_utils::{get, tick};
+/// # const BUTTON: Id = natrix::id!();
+/// # async fn test() {
+/// get(BUTTON).click();
+/// tick().await;
+/// assert_eq!(get(BUTTON).text_content().as_deref(), Some("loaded"));
+/// # }
+/// ```
+///
+/// # Panics
+/// If the tasks are still making progress after a large amount of yields,
+/// which means a task is constantly waking itself.
+#[cfg(feature = "async")]
+#[expect(clippy::panic, reason = "tests only")]
+pub async fn tick() {
+    for _ in 0..MAX_TICKS {
+        let polls = TASK_POLLS.get();
+        let resolved = web_sys::js_sys::Promise::resolve(&wasm_bindgen::JsValue::UNDEFINED);
+        wasm_bindgen_futures::JsFuture::from(resolved)
+            .await
+            .expect("Resolved promise rejected");
+        if TASK_POLLS.get() == polls {
+            return;
+        }
+    }
+    panic!("Async tasks did not settle after {MAX_TICKS} ticks");
+}
+
+/// Move the fake clock forward by `duration`, firing the timers that are due, then [`tick`].
+///
+/// Timers fire in the order they are due, and the tasks they wake are run before the next one
+/// fires, so a task that sleeps multiple times within `duration` runs to completion.
+/// If [`set_fake_now`] is active the fake time is moved forward along with the clock.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use natrix::prelude::*;
+/// # use natrix::test_utils::{advance_timers, get, use_fake_timers};
+/// # const BUTTON: Id = natrix::id!();
+/// # async fn test() {
+/// use_fake_timers();
+/// // ... mount the app ...
+/// get(BUTTON).click();
+/// advance_timers(Duration::from_millis(300)).await;
+/// assert_eq!(get(BUTTON).text_content().as_deref(), Some("debounced"));
+/// # }
+/// ```
+///
+/// # Panics
+/// If [`use_fake_timers`] hasnt been called, or a timer callback throws.
+#[cfg(feature = "async")]
+pub async fn advance_timers(duration: std::time::Duration) {
+    let target = FAKE_TIMERS.with_borrow(|fake| {
+        let fake = fake
+            .as_ref()
+            .expect("`advance_timers` called without `use_fake_timers`");
+        fake.now + duration.as_secs_f64() * 1000.0
+    });
+
+    loop {
+        let timer = FAKE_TIMERS.with_borrow_mut(|fake| {
+            let fake = fake.as_mut()?;
+            let (index, _) = fake
+                .timers
+                .iter()
+                .enumerate()
+                .filter(|(_, timer)| timer.due <= target)
+                .min_by(|(_, first), (_, second)| first.due.total_cmp(&second.due))?;
+            let timer = fake.timers.remove(index);
+            move_fake_clock(fake, timer.due);
+            Some(timer)
+        });
+        let Some(timer) = timer else {
+            break;
+        };
+
+        timer
+            .callback
+            .call0(&wasm_bindgen::JsValue::UNDEFINED)
+            .expect("Timer callback threw");
+        tick().await;
+    }
+
+    FAKE_TIMERS.with_borrow_mut(|fake| {
+        if let Some(fake) = fake.as_mut() {
+            move_fake_clock(fake, target);
+        }
+    });
+    tick().await;
+}
@@ -1,5 +1,8 @@
 #![cfg(feature = "async_utils")]
 
+use std::cell::Cell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
 use std::time::Duration;
 
 use natrix::async_utils;
@@ -64,6 +67,63 @@ async fn async_multiple() {
     assert_eq!(button.text_content(), Some("30".to_owned()));
 }
 
+#[wasm_bindgen_test]
+async fn tick_runs_ready_tasks() {
+    natrix::test_utils::use_fake_timers();
+    crate::mount_test(
+        AsyncComponent {
+            data: Signal::new(0),
+        },
+        e::button()
+            .id(BUTTON_ID)
+            .text(|ctx: RenderCtx<AsyncComponent>| *ctx.data)
+            .on::<events::Click>(|mut ctx: EventCtx<AsyncComponent>, _| {
+                ctx.use_async(async |ctx| {
+                    ctx.update(|mut ctx| {
+                        *ctx.data += 1;
+                    })?;
+                    async_utils::sleep_milliseconds(0).await;
+                    ctx.update(|mut ctx| {
+                        *ctx.data += 1;
+                    })?;
+                    Some(())
+                });
+            }),
+    );
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    assert_eq!(natrix::test_utils::pending_tasks(), 1);
+    natrix::test_utils::tick().await;
+    assert_eq!(button.text_content(), Some("1".to_owned()));
+
+    natrix::test_utils::advance_timers(Duration::ZERO).await;
+    assert_eq!(button.text_content(), Some("2".to_owned()));
+    assert_eq!(natrix::test_utils::pending_tasks(), 0);
+    natrix::test_utils::reset_fakes();
+}
+
+#[wasm_bindgen_test]
+async fn advance_timers_runs_sleeps() {
+    natrix::test_utils::use_fake_timers();
+    crate::mount_test(
+        AsyncComponent {
+            data: Signal::new(0),
+        },
+        render_async_component(),
+    );
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    natrix::test_utils::advance_timers(Duration::from_millis(9)).await;
+    assert_eq!(button.text_content(), Some("0".to_owned()));
+    natrix::test_utils::advance_timers(Duration::from_millis(1)).await;
+    assert_eq!(button.text_content(), Some("10".to_owned()));
+    natrix::test_utils::reset_fakes();
+}
+
 const BUTTON2: Id = natrix::id!();
 
 #[derive(State)]
@@ -131,3 +191,313 @@ async fn optional_async() {
     button1.click();
     assert_eq!(button1.text_content(), Some(String::from("Some(0)")));
 }
+
+#[derive(State)]
+struct FrameCounter {
+    frames: Signal<u8>,
+}
+
+fn render_frame_counter(total_frames: Rc<Cell<u8>>) -> impl Element<FrameCounter> {
+    e::button()
+        .id(BUTTON_ID)
+        .text(|ctx: RenderCtx<FrameCounter>| *ctx.frames)
+        .on::<events::Click>(move |ctx: EventCtx<FrameCounter>, _| {
+            let total_frames = total_frames.clone();
+            ctx.animation_loop(move |mut ctx, _time| {
+                total_frames.set(total_frames.get() + 1);
+                *ctx.frames += 1;
+                if *ctx.frames >= 3 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+        })
+}
+
+#[wasm_bindgen_test]
+async fn animation_loop_stops_on_break() {
+    crate::mount_test(
+        FrameCounter {
+            frames: Signal::new(0),
+        },
+        render_frame_counter(Rc::new(Cell::new(0))),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+
+    for _ in 0..6 {
+        async_utils::next_frame().await;
+    }
+    assert_eq!(button.text_content(), Some("3".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn animation_loop_stops_on_unmount() {
+    let total_frames = Rc::new(Cell::new(0));
+    crate::mount_test(
+        FrameCounter {
+            frames: Signal::new(0),
+        },
+        render_frame_counter(total_frames.clone()),
+    );
+
+    crate::get(BUTTON_ID).click();
+    async_utils::next_frame().await;
+    async_utils::next_frame().await;
+
+    crate::mount_test(
+        FrameCounter {
+            frames: Signal::new(0),
+        },
+        render_frame_counter(Rc::new(Cell::new(0))),
+    );
+    let frames_at_unmount = total_frames.get();
+
+    for _ in 0..4 {
+        async_utils::next_frame().await;
+    }
+    assert_eq!(total_frames.get(), frames_at_unmount);
+}
+
+#[derive(State)]
+struct Loader {
+    loaded: Signal<Option<(u8, u8)>>,
+}
+
+fn render_loader() -> impl Element<Loader> {
+    e::button()
+        .id(BUTTON_ID)
+        .text(|ctx: RenderCtx<Loader>| format!("{:?}", *ctx.loaded))
+        .on::<events::Click>(|ctx: EventCtx<Loader>, _| {
+            ctx.use_async(async |ctx| {
+                let (first, second) = async_utils::join2(
+                    async {
+                        async_utils::sleep_milliseconds(10).await;
+                        1
+                    },
+                    async {
+                        async_utils::sleep_milliseconds(20).await;
+                        2
+                    },
+                )
+                .await;
+                ctx.update(|mut ctx| *ctx.loaded = Some((first, second)))
+            });
+        })
+}
+
+#[wasm_bindgen_test]
+async fn join_updates_once_both_complete() {
+    crate::mount_test(
+        Loader {
+            loaded: Signal::new(None),
+        },
+        render_loader(),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+
+    async_utils::sleep_milliseconds(15).await;
+    assert_eq!(button.text_content(), Some("None".to_owned()));
+
+    async_utils::sleep_milliseconds(20).await;
+    assert_eq!(button.text_content(), Some("Some((1, 2))".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn select_resolves_with_first() {
+    let result = async_utils::select(
+        async_utils::sleep_milliseconds(50),
+        async_utils::sleep_milliseconds(5),
+    )
+    .await;
+    assert_eq!(result, async_utils::Either::Second(()));
+}
+
+#[derive(State)]
+struct Ticker {
+    ticks: Signal<u8>,
+}
+
+fn render_ticker(limit: u8) -> impl Element<Ticker> {
+    e::button()
+        .id(BUTTON_ID)
+        .text(|ctx: RenderCtx<Ticker>| *ctx.ticks)
+        .on::<events::Click>(move |ctx: EventCtx<Ticker>, _| {
+            ctx.for_each_stream(
+                async_utils::interval_milliseconds(10),
+                move |mut ctx, ()| {
+                    *ctx.ticks += 1;
+                    if *ctx.ticks >= limit {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                },
+            );
+        })
+}
+
+#[wasm_bindgen_test]
+async fn stream_updates_value() {
+    crate::mount_test(
+        Ticker {
+            ticks: Signal::new(0),
+        },
+        render_ticker(u8::MAX),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+
+    async_utils::sleep_milliseconds(55).await;
+    let ticks: u8 = button
+        .text_content()
+        .and_then(|text| text.parse().ok())
+        .unwrap_or_default();
+    assert!(ticks >= 3, "only {ticks} ticks");
+}
+
+#[wasm_bindgen_test]
+async fn stream_stops_on_break() {
+    crate::mount_test(
+        Ticker {
+            ticks: Signal::new(0),
+        },
+        render_ticker(2),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+
+    async_utils::sleep_milliseconds(60).await;
+    assert_eq!(button.text_content(), Some("2".to_owned()));
+}
+
+#[derive(State)]
+struct ToggledTicker {
+    show: Signal<bool>,
+}
+
+#[wasm_bindgen_test]
+async fn render_stream_is_dropped_with_element() {
+    crate::mount_test(
+        ToggledTicker {
+            show: Signal::new(true),
+        },
+        e::div()
+            .child(e::button().id(BUTTON_ID).on::<events::Click>(
+                |mut ctx: EventCtx<ToggledTicker>, _| {
+                    *ctx.show = false;
+                },
+            ))
+            .child(|mut ctx: RenderCtx<ToggledTicker>| {
+                if !*ctx.show {
+                    return None;
+                }
+                // NOTE: Idle for the whole test, so only cancelling can stop it
+                ctx.for_each_stream(async_utils::interval_milliseconds(100_000), |_, ()| {
+                    ControlFlow::Continue(())
+                });
+                Some(e::span())
+            }),
+    );
+
+    natrix::test_utils::tick().await;
+    assert_eq!(natrix::test_utils::pending_tasks(), 1);
+
+    crate::get(BUTTON_ID).click();
+    natrix::test_utils::tick().await;
+    assert_eq!(natrix::test_utils::pending_tasks(), 0);
+}
+
+#[derive(State)]
+struct Clock {
+    ticks: Signal<u8>,
+}
+
+#[wasm_bindgen_test]
+async fn interval_ticks_until_cancelled() {
+    let handle = Rc::new(Cell::new(None));
+    let button_handle = handle.clone();
+    crate::mount_test(
+        Clock {
+            ticks: Signal::new(0),
+        },
+        e::button()
+            .id(BUTTON_ID)
+            .text(|ctx: RenderCtx<Clock>| *ctx.ticks)
+            .on::<events::Click>(move |ctx: EventCtx<Clock>, _| {
+                button_handle.set(Some(ctx.use_interval(
+                    Duration::from_millis(10),
+                    |mut ctx| {
+                        *ctx.ticks += 1;
+                    },
+                )));
+            }),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+    async_utils::sleep_milliseconds(55).await;
+    let ticks: u8 = button
+        .text_content()
+        .and_then(|text| text.parse().ok())
+        .unwrap_or_default();
+    assert!(ticks >= 3, "only {ticks} ticks");
+
+    let handle = handle.take().expect("Interval was not started");
+    handle.cancel();
+    assert!(handle.is_cancelled());
+    let ticks_at_cancel = button.text_content();
+    async_utils::sleep_milliseconds(30).await;
+    assert_eq!(button.text_content(), ticks_at_cancel);
+}
+
+#[derive(State)]
+struct ToggledClock {
+    show: Signal<bool>,
+    ticks: Signal<u8>,
+}
+
+#[wasm_bindgen_test]
+async fn render_interval_is_cleared_with_element() {
+    crate::mount_test(
+        ToggledClock {
+            show: Signal::new(true),
+            ticks: Signal::new(0),
+        },
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .text(|ctx: RenderCtx<ToggledClock>| *ctx.ticks)
+                    .on::<events::Click>(|mut ctx: EventCtx<ToggledClock>, _| {
+                        *ctx.show = false;
+                    }),
+            )
+            .child(|mut ctx: RenderCtx<ToggledClock>| {
+                if !*ctx.show {
+                    return None;
+                }
+                ctx.use_interval(Duration::from_millis(10), |mut ctx| {
+                    *ctx.ticks += 1;
+                });
+                Some(e::span())
+            }),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    async_utils::sleep_milliseconds(35).await;
+    assert_ne!(button.text_content().as_deref(), Some("0"));
+
+    button.click();
+    natrix::test_utils::tick().await;
+    assert_eq!(natrix::test_utils::pending_tasks(), 0);
+    let ticks_at_removal = button.text_content();
+    async_utils::sleep_milliseconds(30).await;
+    assert_eq!(button.text_content(), ticks_at_removal);
+}
@@ -1,4 +1,5 @@
 use natrix::dom::ToAttribute;
+use natrix::dom::attributes::{self, Url};
 use natrix::prelude::*;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 wasm_bindgen_test_configure!(run_in_browser);
@@ -178,3 +179,197 @@ fn reactive_change_set_but_no_change() {
     button2.click();
     assert_eq!(button.get_attribute("abc"), None);
 }
+
+#[derive(State, Default)]
+struct Table {
+    span: Signal<i32>,
+}
+
+fn render_table() -> impl Element<Table> {
+    e::table().child(
+        e::tr().child(
+            e::td()
+                .id(ROOT)
+                .column_span(|ctx: RenderCtx<Table>| *ctx.span)
+                .row_span(70_000)
+                .on::<events::Click>(|mut ctx: EventCtx<Table>, _| {
+                    *ctx.span -= 1;
+                }),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn typed_integer_attributes_clamp_reactively() {
+    crate::mount_test(
+        Table {
+            span: Signal::new(2),
+        },
+        render_table(),
+    );
+
+    let cell = crate::get(ROOT);
+    assert_eq!(cell.get_attribute("colspan"), Some("2".to_owned()));
+    assert_eq!(cell.get_attribute("rowspan"), Some("65534".to_owned()));
+
+    cell.click();
+    assert_eq!(cell.get_attribute("colspan"), Some("1".to_owned()));
+
+    cell.click();
+    assert_eq!(cell.get_attribute("colspan"), Some("1".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn typed_integer_attributes() {
+    crate::mount_test(
+        Empty,
+        e::textarea::<Empty>().id(ROOT).rows(4).columns(40u16),
+    );
+
+    let textarea = crate::get(ROOT);
+    assert_eq!(textarea.get_attribute("rows"), Some("4".to_owned()));
+    assert_eq!(textarea.get_attribute("cols"), Some("40".to_owned()));
+}
+
+const PANEL: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct Modal {
+    open: Signal<bool>,
+}
+
+fn render_modal() -> impl Element<Modal> {
+    e::div()
+        .child(
+            e::div()
+                .id(ROOT)
+                .inert(|ctx: RenderCtx<Modal>| *ctx.open)
+                .child(e::button().id(BUTTON).on::<events::Click>(
+                    |mut ctx: EventCtx<Modal>, _| {
+                        *ctx.open = !*ctx.open;
+                    },
+                )),
+        )
+        .child(
+            e::div()
+                .id(PANEL)
+                .hidden(|ctx: RenderCtx<Modal>| !*ctx.open),
+        )
+}
+
+#[wasm_bindgen_test]
+fn hidden_and_inert_toggle() {
+    crate::mount_test(Modal::default(), render_modal());
+
+    let background = crate::get(ROOT);
+    let panel = crate::get(PANEL);
+    assert_eq!(background.get_attribute("inert"), None);
+    assert_eq!(panel.get_attribute("hidden"), Some("".to_owned()));
+
+    crate::get(BUTTON).click();
+    assert_eq!(background.get_attribute("inert"), Some("".to_owned()));
+    assert_eq!(panel.get_attribute("hidden"), None);
+}
+
+#[wasm_bindgen_test]
+fn inert_on_focused_subtree_still_applies() {
+    crate::mount_test(Modal::default(), render_modal());
+
+    let button = crate::get(BUTTON);
+    button.focus().expect("Failed to focus");
+    button.click();
+
+    assert_eq!(crate::get(ROOT).get_attribute("inert"), Some("".to_owned()));
+}
+
+const LINK: Id = natrix::id!();
+
+#[wasm_bindgen_test]
+fn url_attributes() {
+    crate::mount_test(
+        Empty,
+        e::div()
+            .child(e::img().id(ROOT).src(natrix::asset!("Cargo.toml")))
+            .child(
+                e::img()
+                    .id(BUTTON)
+                    .src(Url::new("https://example.com/logo.png")),
+            )
+            .child(e::a().id(LINK).href(Url::from("/docs#setup"))),
+    );
+
+    let asset = crate::get(ROOT).get_attribute("src");
+    assert!(asset.is_some_and(|src| src.starts_with('/')));
+    assert_eq!(
+        crate::get(BUTTON).get_attribute("src"),
+        Some("https://example.com/logo.png".to_owned())
+    );
+    assert_eq!(
+        crate::get(LINK).get_attribute("href"),
+        Some("/docs#setup".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn dir_asset_urls() {
+    const SOURCE: natrix::AssetDir = natrix::dir_asset!("src");
+
+    crate::mount_test(
+        Empty,
+        e::img()
+            .id(ROOT)
+            .src(SOURCE.url("lib.rs").unwrap_or_default()),
+    );
+
+    let asset = crate::get(ROOT).get_attribute("src");
+    assert!(asset.is_some_and(|src| src.starts_with('/') && src.ends_with("/lib.rs")));
+    assert!(SOURCE.files().contains(&"dom/mod.rs"));
+    assert_eq!(SOURCE.url("../Cargo.toml"), None);
+}
+
+#[wasm_bindgen_test]
+fn aria_expanded_flips() {
+    crate::mount_test(
+        Toggle::default(),
+        e::button()
+            .id(ROOT)
+            .aria_expanded(|ctx: RenderCtx<Toggle>| *ctx.value)
+            .aria("controls", "menu")
+            .aria_live(attributes::AriaLive::Polite)
+            .role(attributes::Role::Switch)
+            .on::<events::Click>(|mut ctx: EventCtx<Toggle>, _| {
+                ctx.toggle();
+            }),
+    );
+
+    let button = crate::get(ROOT);
+    assert_eq!(
+        button.get_attribute("aria-expanded"),
+        Some("false".to_owned())
+    );
+    assert_eq!(
+        button.get_attribute("aria-controls"),
+        Some("menu".to_owned())
+    );
+    assert_eq!(button.get_attribute("aria-live"), Some("polite".to_owned()));
+    assert_eq!(button.get_attribute("role"), Some("switch".to_owned()));
+
+    button.click();
+    assert_eq!(
+        button.get_attribute("aria-expanded"),
+        Some("true".to_owned())
+    );
+
+    button.click();
+    assert_eq!(
+        button.get_attribute("aria-expanded"),
+        Some("false".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn aria_bool_none_removes() {
+    crate::mount_test(Empty, e::div().id(ROOT).aria_hidden(None::<bool>));
+
+    assert_eq!(crate::get(ROOT).get_attribute("aria-hidden"), None);
+}
@@ -0,0 +1,192 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const LIST_ID: Id = natrix::id!();
+const REVERSE_ID: Id = natrix::id!();
+const ROTATE_ID: Id = natrix::id!();
+const INSERT_ID: Id = natrix::id!();
+const REMOVE_ID: Id = natrix::id!();
+const UPDATE_ID: Id = natrix::id!();
+
+#[derive(Clone, PartialEq)]
+struct User {
+    id: u32,
+    name: &'static str,
+}
+
+#[derive(State)]
+struct Users {
+    users: Signal<Vec<User>>,
+}
+
+fn render_users() -> impl Element<Users> {
+    e::div()
+        .child(
+            e::ul()
+                .id(LIST_ID)
+                .text("start")
+                .children_keyed(
+                    |ctx: &Users| &ctx.users,
+                    |user| user.id,
+                    |user| e::li().text(format!("{}: {}", user.id, user.name)),
+                )
+                .text("end"),
+        )
+        .child(
+            e::button()
+                .id(REVERSE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.reverse();
+                }),
+        )
+        .child(
+            e::button()
+                .id(ROTATE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.rotate_right(1);
+                }),
+        )
+        .child(
+            e::button()
+                .id(INSERT_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.insert(
+                        1,
+                        User {
+                            id: 4,
+                            name: "dave",
+                        },
+                    );
+                }),
+        )
+        .child(
+            e::button()
+                .id(REMOVE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.retain(|user| user.id != 2);
+                }),
+        )
+        .child(
+            e::button()
+                .id(UPDATE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    if let Some(user) = ctx.users.iter_mut().find(|user| user.id == 3) {
+                        user.name = "charlie";
+                    }
+                }),
+        )
+}
+
+fn mount() {
+    crate::mount_test(
+        Users {
+            users: Signal::new(vec![
+                User {
+                    id: 1,
+                    name: "alice",
+                },
+                User { id: 2, name: "bob" },
+                User {
+                    id: 3,
+                    name: "carol",
+                },
+            ]),
+        },
+        render_users(),
+    );
+}
+
+fn items() -> Vec<web_sys::Element> {
+    let items = crate::get(LIST_ID).children();
+    (0..items.length())
+        .map(|index| items.item(index).expect("Item not found"))
+        .collect()
+}
+
+fn texts() -> Vec<String> {
+    items()
+        .iter()
+        .map(|item| item.text_content().unwrap_or_default())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn renders_in_list_order() {
+    mount();
+    assert_eq!(texts(), vec!["1: alice", "2: bob", "3: carol"]);
+    assert_eq!(
+        crate::get(LIST_ID).text_content(),
+        Some("start1: alice2: bob3: carolend".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn reverse_moves_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(REVERSE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["3: carol", "2: bob", "1: alice"]);
+    assert!(after[0].is_same_node(Some(&before[2])));
+    assert!(after[1].is_same_node(Some(&before[1])));
+    assert!(after[2].is_same_node(Some(&before[0])));
+    assert_eq!(
+        crate::get(LIST_ID).text_content(),
+        Some("start3: carol2: bob1: aliceend".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn rotate_moves_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(ROTATE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["3: carol", "1: alice", "2: bob"]);
+    assert!(after[0].is_same_node(Some(&before[2])));
+    assert!(after[1].is_same_node(Some(&before[0])));
+    assert!(after[2].is_same_node(Some(&before[1])));
+}
+
+#[wasm_bindgen_test]
+fn insert_keeps_existing_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(INSERT_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["1: alice", "4: dave", "2: bob", "3: carol"]);
+    assert!(after[0].is_same_node(Some(&before[0])));
+    assert!(after[2].is_same_node(Some(&before[1])));
+    assert!(after[3].is_same_node(Some(&before[2])));
+}
+
+#[wasm_bindgen_test]
+fn remove_keeps_other_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(REMOVE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["1: alice", "3: carol"]);
+    assert!(after[0].is_same_node(Some(&before[0])));
+    assert!(after[1].is_same_node(Some(&before[2])));
+    assert!(!before[1].is_connected());
+}
+
+#[wasm_bindgen_test]
+fn update_only_rerenders_changed_item() {
+    mount();
+    let before = items();
+
+    crate::get(UPDATE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["1: alice", "2: bob", "3: charlie"]);
+    assert!(after[0].is_same_node(Some(&before[0])));
+    assert!(after[1].is_same_node(Some(&before[1])));
+    assert!(!after[2].is_same_node(Some(&before[2])));
+}
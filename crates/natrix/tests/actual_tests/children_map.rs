@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const LIST_ID: Id = natrix::id!();
+const INSERT_ID: Id = natrix::id!();
+const REMOVE_ID: Id = natrix::id!();
+const UPDATE_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct Users {
+    users: Signal<BTreeMap<u32, String>>,
+}
+
+fn render_users() -> impl Element<Users> {
+    e::div()
+        .child(
+            e::ul()
+                .id(LIST_ID)
+                .text("start")
+                .children_map(
+                    |ctx: &Users| &*ctx.users,
+                    |id, name| e::li().text(format!("{id}: {name}")),
+                )
+                .text("end"),
+        )
+        .child(
+            e::button()
+                .id(INSERT_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.insert(2, "bob".to_owned());
+                }),
+        )
+        .child(
+            e::button()
+                .id(REMOVE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    ctx.users.remove(&1);
+                }),
+        )
+        .child(
+            e::button()
+                .id(UPDATE_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Users>, _| {
+                    if let Some(name) = ctx.users.get_mut(&3) {
+                        *name = "charlie".to_owned();
+                    }
+                }),
+        )
+}
+
+fn mount() {
+    crate::mount_test(
+        Users {
+            users: Signal::new(BTreeMap::from([
+                (1, "alice".to_owned()),
+                (3, "carol".to_owned()),
+            ])),
+        },
+        render_users(),
+    );
+}
+
+fn items() -> Vec<web_sys::Element> {
+    let items = crate::get(LIST_ID).children();
+    (0..items.length())
+        .map(|index| items.item(index).expect("Item not found"))
+        .collect()
+}
+
+fn texts() -> Vec<String> {
+    items()
+        .iter()
+        .map(|item| item.text_content().unwrap_or_default())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn renders_in_key_order() {
+    mount();
+    assert_eq!(texts(), vec!["1: alice", "3: carol"]);
+    assert_eq!(
+        crate::get(LIST_ID).text_content(),
+        Some("start1: alice3: carolend".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn insert_keeps_existing_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(INSERT_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["1: alice", "2: bob", "3: carol"]);
+    assert!(after[0].is_same_node(Some(&before[0])));
+    assert!(after[2].is_same_node(Some(&before[1])));
+}
+
+#[wasm_bindgen_test]
+fn remove_keeps_other_nodes() {
+    mount();
+    let before = items();
+
+    crate::get(REMOVE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["3: carol"]);
+    assert!(after[0].is_same_node(Some(&before[1])));
+    assert!(!before[0].is_connected());
+}
+
+#[wasm_bindgen_test]
+fn update_only_rerenders_changed_entry() {
+    mount();
+    let before = items();
+
+    crate::get(UPDATE_ID).click();
+    let after = items();
+    assert_eq!(texts(), vec!["1: alice", "3: charlie"]);
+    assert!(after[0].is_same_node(Some(&before[0])));
+    assert!(!after[1].is_same_node(Some(&before[1])));
+    assert_eq!(
+        crate::get(LIST_ID).text_content(),
+        Some("start1: alice3: charlieend".to_owned())
+    );
+}
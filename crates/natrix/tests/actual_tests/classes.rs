@@ -8,6 +8,9 @@ const DECREMENT_ID: Id = natrix::id!();
 const CLICKED_CLASS: Class = natrix::class!();
 const NOT_CLICKED_CLASS: Class = natrix::class!();
 const CLICKED_MORE_THAN_2_CLASS: Class = natrix::class!();
+const ODD_CLASS: Class = natrix::class!();
+const STATIC_CLASS: Class = natrix::class!();
+const TOGGLE_ID: Id = natrix::id!();
 
 #[derive(State)]
 struct HelloWorld {
@@ -90,3 +93,131 @@ fn test_option() {
     decrement.click();
     assert_eq!(button.class_name(), CLICKED_CLASS.0);
 }
+
+fn render_class_if() -> impl Element<HelloWorld> {
+    e::button()
+        .id(TOGGLE_ID)
+        .class(STATIC_CLASS)
+        .class_if(
+            |ctx: RenderCtx<HelloWorld>| *ctx.counter % 2 == 1,
+            ODD_CLASS,
+        )
+        .on::<events::Click>(|mut ctx: EventCtx<HelloWorld>, _| {
+            *ctx.counter += 1;
+        })
+}
+
+#[wasm_bindgen_test]
+fn test_class_if() {
+    crate::mount_test(
+        HelloWorld {
+            counter: Signal::new(0),
+        },
+        render_class_if(),
+    );
+    let button = crate::get(TOGGLE_ID);
+    assert_eq!(button.class_name(), STATIC_CLASS.0);
+
+    button.click();
+    assert!(button.class_list().contains(ODD_CLASS.0));
+    assert!(button.class_list().contains(STATIC_CLASS.0));
+
+    button.click();
+    assert_eq!(button.class_name(), STATIC_CLASS.0);
+}
+
+const LIST_ID: Id = natrix::id!();
+const FIRST_CLASS: Class = natrix::class!();
+const SECOND_CLASS: Class = natrix::class!();
+const THIRD_CLASS: Class = natrix::class!();
+
+fn render_class_list() -> impl Element<HelloWorld> {
+    e::button()
+        .id(LIST_ID)
+        .class(STATIC_CLASS)
+        .class(|ctx: RenderCtx<HelloWorld>| match *ctx.counter {
+            0 => vec![FIRST_CLASS, SECOND_CLASS],
+            1 => vec![SECOND_CLASS, THIRD_CLASS],
+            _ => Vec::new(),
+        })
+        .class(|ctx: RenderCtx<HelloWorld>| (*ctx.counter == 1).then_some(ODD_CLASS))
+        .on::<events::Click>(|mut ctx: EventCtx<HelloWorld>, _| {
+            *ctx.counter += 1;
+        })
+}
+
+#[wasm_bindgen_test]
+fn test_class_list() {
+    crate::mount_test(
+        HelloWorld {
+            counter: Signal::new(0),
+        },
+        render_class_list(),
+    );
+    let button = crate::get(LIST_ID);
+    let classes = || {
+        let list = button.class_list();
+        [
+            STATIC_CLASS,
+            FIRST_CLASS,
+            SECOND_CLASS,
+            THIRD_CLASS,
+            ODD_CLASS,
+        ]
+        .map(|class| list.contains(class.0))
+    };
+    assert_eq!(classes(), [true, true, true, false, false]);
+
+    button.click();
+    assert_eq!(classes(), [true, false, true, true, true]);
+
+    button.click();
+    assert_eq!(classes(), [true, false, false, false, false]);
+    assert_eq!(button.class_name(), STATIC_CLASS.0);
+}
+
+const OWNED_ID: Id = natrix::id!();
+const MANUAL_CLASS: Class = Class("manual");
+
+fn render_owned_classes() -> impl Element<HelloWorld> {
+    e::button()
+        .id(OWNED_ID)
+        .class(STATIC_CLASS)
+        .class(|ctx: RenderCtx<HelloWorld>| {
+            if *ctx.counter % 2 == 0 {
+                FIRST_CLASS
+            } else {
+                SECOND_CLASS
+            }
+        })
+        .on::<events::Click>(|mut ctx: EventCtx<HelloWorld>, _| {
+            *ctx.counter += 1;
+        })
+}
+
+#[wasm_bindgen_test]
+fn reactive_class_only_touches_its_own_classes() {
+    crate::mount_test(
+        HelloWorld {
+            counter: Signal::new(0),
+        },
+        render_owned_classes(),
+    );
+    let button = crate::get(OWNED_ID);
+    let list = button.class_list();
+    list.add_1(MANUAL_CLASS.0).expect("Failed to add class");
+
+    button.click();
+    assert!(list.contains(STATIC_CLASS.0));
+    assert!(list.contains(MANUAL_CLASS.0));
+    assert!(list.contains(SECOND_CLASS.0));
+    assert!(!list.contains(FIRST_CLASS.0));
+
+    // The reactive class still updates if its class was removed outside natrix.
+    list.remove_1(SECOND_CLASS.0)
+        .expect("Failed to remove class");
+    button.click();
+    assert!(list.contains(FIRST_CLASS.0));
+    assert!(list.contains(STATIC_CLASS.0));
+    assert!(list.contains(MANUAL_CLASS.0));
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use natrix::prelude::*;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
@@ -54,3 +56,91 @@ fn works() {
     button.click();
     assert_eq!(text.text_content(), Some("5".to_owned()));
 }
+
+const NAMES_ID: Id = natrix::id!();
+const ADD_ID: Id = natrix::id!();
+const TOUCH_ID: Id = natrix::id!();
+
+thread_local! {
+    static NAME_RENDERS: Cell<u8> = const { Cell::new(0) };
+}
+
+#[derive(State)]
+struct Names {
+    names: Signal<Vec<String>>,
+}
+
+fn render_names() -> impl Element<Names> {
+    e::div()
+        .child(e::div().id(NAMES_ID).child(|mut ctx: RenderCtx<Names>| {
+            let summary = ctx.watch_hash(|ctx| ctx.names.join(", "));
+            NAME_RENDERS.set(NAME_RENDERS.get() + 1);
+            summary
+        }))
+        .child(
+            e::button()
+                .id(ADD_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Names>, _| {
+                    ctx.names.push(String::from("b"));
+                }),
+        )
+        .child(
+            e::button()
+                .id(TOUCH_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Names>, _| {
+                    // Mutable access marks the signal as changed without changing the value
+                    let _ = &mut *ctx.names;
+                }),
+        )
+}
+
+#[wasm_bindgen_test]
+fn watch_hash_only_reruns_on_change() {
+    crate::mount_test(
+        Names {
+            names: Signal::new(vec![String::from("a")]),
+        },
+        render_names(),
+    );
+
+    let names = crate::get(NAMES_ID);
+    assert_eq!(names.text_content(), Some("a".to_owned()));
+    let renders = NAME_RENDERS.get();
+
+    crate::get(TOUCH_ID).click();
+    assert_eq!(names.text_content(), Some("a".to_owned()));
+    assert_eq!(NAME_RENDERS.get(), renders);
+
+    crate::get(ADD_ID).click();
+    assert_eq!(names.text_content(), Some("a, b".to_owned()));
+    assert_eq!(NAME_RENDERS.get(), renders + 1);
+}
+
+const DERIVED: Id = natrix::id!();
+
+fn render_derived() -> impl Element<Counter> {
+    let doubled = Signal::map(|ctx: Ref<Counter>| field!(ctx.value), |value| value * 2);
+    e::button()
+        .id(DERIVED)
+        .attr("data-doubled", doubled.clone())
+        .text(doubled)
+        .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| ctx.increment())
+}
+
+#[wasm_bindgen_test]
+fn derived_signal_updates() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(1),
+        },
+        render_derived(),
+    );
+
+    let button = crate::get(DERIVED);
+    assert_eq!(button.text_content(), Some("2".to_owned()));
+    assert_eq!(button.get_attribute("data-doubled"), Some("2".to_owned()));
+
+    button.click();
+    assert_eq!(button.text_content(), Some("4".to_owned()));
+    assert_eq!(button.get_attribute("data-doubled"), Some("4".to_owned()));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "async_utils")]
+
+use std::time::Duration;
+
+use natrix::prelude::*;
+use natrix::test_utils;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const TARGET: Id = natrix::id!();
+const TOGGLE: Id = natrix::id!();
+const CALLS: Id = natrix::id!();
+
+#[derive(State)]
+struct Counter {
+    calls: Signal<u8>,
+    show: Signal<bool>,
+}
+
+fn render_debounced() -> impl Element<Counter> {
+    e::div()
+        .child(
+            e::span()
+                .id(CALLS)
+                .text(|ctx: RenderCtx<Counter>| *ctx.calls),
+        )
+        .child(
+            e::button()
+                .id(TOGGLE)
+                .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| {
+                    *ctx.show = !*ctx.show;
+                }),
+        )
+        .child(|ctx: RenderCtx<Counter>| {
+            if *ctx.show {
+                Some(e::button().id(TARGET).on_debounced::<events::Click>(
+                    Duration::from_millis(100),
+                    |mut ctx: EventCtx<Counter>, _| {
+                        *ctx.calls += 1;
+                    },
+                ))
+            } else {
+                None
+            }
+        })
+}
+
+fn mount() {
+    crate::mount_test(
+        Counter {
+            calls: Signal::new(0),
+            show: Signal::new(true),
+        },
+        render_debounced(),
+    );
+}
+
+fn calls() -> Option<String> {
+    crate::get(CALLS).text_content()
+}
+
+#[wasm_bindgen_test]
+async fn only_calls_once_events_stop() {
+    test_utils::use_fake_timers();
+    mount();
+
+    let target = crate::get(TARGET);
+    for _ in 0..3 {
+        target.click();
+        test_utils::advance_timers(Duration::from_millis(20)).await;
+    }
+    assert_eq!(calls(), Some("0".to_owned()));
+
+    // NOTE: The last click was 20ms ago
+    test_utils::advance_timers(Duration::from_millis(79)).await;
+    assert_eq!(calls(), Some("0".to_owned()));
+    test_utils::advance_timers(Duration::from_millis(1)).await;
+    assert_eq!(calls(), Some("1".to_owned()));
+
+    target.click();
+    test_utils::advance_timers(Duration::from_millis(100)).await;
+    assert_eq!(calls(), Some("2".to_owned()));
+    test_utils::reset_fakes();
+}
+
+#[wasm_bindgen_test]
+async fn not_called_after_removal() {
+    test_utils::use_fake_timers();
+    mount();
+
+    crate::get(TARGET).click();
+    crate::get(TOGGLE).click();
+
+    test_utils::advance_timers(Duration::from_millis(200)).await;
+    assert_eq!(calls(), Some("0".to_owned()));
+    test_utils::reset_fakes();
+}
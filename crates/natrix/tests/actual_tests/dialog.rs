@@ -0,0 +1,113 @@
+#![cfg(feature = "async_utils")]
+
+use natrix::async_utils;
+use natrix::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const OPEN_ID: Id = natrix::id!();
+const CLOSE_ID: Id = natrix::id!();
+const DIALOG_ID: Id = natrix::id!();
+const STATUS_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct App {
+    open: Signal<bool>,
+}
+
+fn render_dialog() -> impl Element<App> {
+    e::div()
+        .child(
+            e::button()
+                .id(OPEN_ID)
+                .text("Open")
+                .on::<events::Click>(|mut ctx: EventCtx<App>, _| *ctx.open = true),
+        )
+        .child(
+            e::div()
+                .id(STATUS_ID)
+                .text(|ctx: RenderCtx<App>| if *ctx.open { "open" } else { "closed" }),
+        )
+        .child(
+            e::dialog()
+                .id(DIALOG_ID)
+                .open_modal(|ctx: Ref<App>| field!(ctx.open).deref())
+                .child(
+                    e::button()
+                        .id(CLOSE_ID)
+                        .text("Close")
+                        .on::<events::Click>(|mut ctx: EventCtx<App>, _| *ctx.open = false),
+                ),
+        )
+}
+
+fn get_dialog() -> web_sys::HtmlDialogElement {
+    crate::get(DIALOG_ID)
+        .dyn_into()
+        .expect("Dialog was not a dialog element")
+}
+
+fn status() -> Option<String> {
+    crate::get(STATUS_ID).text_content()
+}
+
+#[wasm_bindgen_test]
+async fn open_and_close_from_state() {
+    crate::mount_test(
+        App {
+            open: Signal::new(false),
+        },
+        render_dialog(),
+    );
+
+    let dialog = get_dialog();
+    assert!(!dialog.open());
+
+    crate::get(OPEN_ID).click();
+    assert!(dialog.open());
+    assert!(dialog.matches(":modal").expect("Invalid selector"));
+
+    crate::get(CLOSE_ID).click();
+    assert!(!dialog.open());
+    async_utils::sleep_milliseconds(5).await;
+    assert_eq!(status(), Some("closed".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn browser_close_syncs_state() {
+    crate::mount_test(
+        App {
+            open: Signal::new(false),
+        },
+        render_dialog(),
+    );
+
+    let dialog = get_dialog();
+    crate::get(OPEN_ID).click();
+    assert_eq!(status(), Some("open".to_owned()));
+
+    // This is what the browser does on `Escape`
+    dialog.close();
+    async_utils::sleep_milliseconds(5).await;
+    assert_eq!(status(), Some("closed".to_owned()));
+
+    crate::get(OPEN_ID).click();
+    assert!(dialog.open());
+}
+
+#[wasm_bindgen_test]
+async fn initially_open() {
+    crate::mount_test(
+        App {
+            open: Signal::new(true),
+        },
+        render_dialog(),
+    );
+
+    async_utils::sleep_milliseconds(5).await;
+    let dialog = get_dialog();
+    assert!(dialog.open());
+    assert!(dialog.matches(":modal").expect("Invalid selector"));
+}
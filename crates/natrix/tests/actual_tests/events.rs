@@ -1,4 +1,5 @@
 use natrix::prelude::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -43,3 +44,209 @@ fn can_use_event() {
     button.click();
     assert_eq!(button.text_content(), Some("3".to_owned()));
 }
+
+const INPUT_ID: Id = natrix::id!();
+
+fn render_shortcut() -> impl Element<Counter> {
+    e::div()
+        .id(INPUT_ID)
+        .child(|ctx: RenderCtx<Counter>| *ctx.value)
+        .on::<events::KeyDown>(|mut ctx: EventCtx<Counter>, event: events::KeyEvent| {
+            if event.ctrl() && event.key() == events::Key::Character('s') {
+                event.prevent_default();
+                ctx.increment();
+            }
+        })
+}
+
+fn press(key: &str, ctrl: bool) -> bool {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_ctrl_key(ctrl);
+    init.set_cancelable(true);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+        .expect("Failed to create event");
+    crate::get(INPUT_ID)
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event")
+}
+
+#[wasm_bindgen_test]
+fn key_event_accessors() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_shortcut(),
+    );
+
+    assert!(press("s", false));
+    assert_eq!(crate::get(INPUT_ID).text_content(), Some("0".to_owned()));
+
+    // `dispatch_event` returns `false` if the default was prevented.
+    assert!(!press("s", true));
+    assert_eq!(crate::get(INPUT_ID).text_content(), Some("1".to_owned()));
+}
+
+const OUTER_ID: Id = natrix::id!();
+const INNER_ID: Id = natrix::id!();
+const TEXT_ID: Id = natrix::id!();
+const OUTPUT_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct Targets {
+    output: Signal<String>,
+}
+
+fn render_targets() -> impl Element<Targets> {
+    e::div()
+        .child(
+            e::div()
+                .id(OUTER_ID)
+                .child(e::span().id(INNER_ID).text("inner"))
+                .on::<events::Click>(|mut ctx: EventCtx<Targets>, event: web_sys::PointerEvent| {
+                    let current = event.current_target_as::<web_sys::Element>();
+                    let target = event.target_as::<web_sys::Element>();
+                    *ctx.output = format!(
+                        "{} {} {}",
+                        current
+                            .map(|element| element.tag_name())
+                            .unwrap_or_default(),
+                        target.map(|element| element.tag_name()).unwrap_or_default(),
+                        event.target_as::<web_sys::HtmlInputElement>().is_some(),
+                    );
+                }),
+        )
+        .child(e::text_input().id(TEXT_ID).on::<events::Input>(
+            |mut ctx: EventCtx<Targets>, event: web_sys::InputEvent| {
+                *ctx.output = event.target_value().unwrap_or_default();
+            },
+        ))
+        .child(
+            e::span()
+                .id(OUTPUT_ID)
+                .text(|ctx: RenderCtx<Targets>| ctx.output.clone()),
+        )
+}
+
+#[wasm_bindgen_test]
+fn event_targets() {
+    crate::mount_test(
+        Targets {
+            output: Signal::new(String::new()),
+        },
+        render_targets(),
+    );
+
+    crate::get(INNER_ID).click();
+    assert_eq!(
+        crate::get(OUTPUT_ID).text_content(),
+        Some("DIV SPAN false".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn event_target_value() {
+    crate::mount_test(
+        Targets {
+            output: Signal::new(String::new()),
+        },
+        render_targets(),
+    );
+
+    let input: web_sys::HtmlInputElement = crate::get(TEXT_ID).dyn_into().expect("Not a input");
+    input.set_value("hello");
+    let event = web_sys::InputEvent::new("input").expect("Failed to create event");
+    input
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+
+    assert_eq!(
+        crate::get(OUTPUT_ID).text_content(),
+        Some("hello".to_owned())
+    );
+}
+
+const PARENT_ID: Id = natrix::id!();
+const STOP_ID: Id = natrix::id!();
+const PASS_ID: Id = natrix::id!();
+const ORDER_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct Propagation {
+    order: Signal<Vec<&'static str>>,
+    block: Signal<bool>,
+}
+
+fn render_propagation() -> impl Element<Propagation> {
+    e::div()
+        .child(
+            e::span()
+                .id(ORDER_ID)
+                .text(|ctx: RenderCtx<Propagation>| ctx.order.join(",")),
+        )
+        .child(
+            e::div()
+                .id(PARENT_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Propagation>, _| {
+                    ctx.order.push("parent");
+                })
+                .on_capture::<events::Click>(
+                    |mut ctx: EventCtx<Propagation>, event: web_sys::PointerEvent| {
+                        ctx.order.push("capture");
+                        if *ctx.block {
+                            event.stop_propagation();
+                        }
+                    },
+                )
+                .child(e::button().id(STOP_ID).on::<events::Click>(
+                    |mut ctx: EventCtx<Propagation>, event: web_sys::PointerEvent| {
+                        ctx.order.push("stop");
+                        event.stop_propagation();
+                    },
+                ))
+                .child(e::button().id(PASS_ID).on::<events::Click>(
+                    |mut ctx: EventCtx<Propagation>, _| {
+                        ctx.order.push("pass");
+                    },
+                )),
+        )
+}
+
+fn mount_propagation(block: bool) {
+    crate::mount_test(
+        Propagation {
+            order: Signal::new(Vec::new()),
+            block: Signal::new(block),
+        },
+        render_propagation(),
+    );
+}
+
+#[wasm_bindgen_test]
+fn stop_propagation_skips_parent() {
+    mount_propagation(false);
+
+    crate::get(STOP_ID).click();
+    assert_eq!(
+        crate::get(ORDER_ID).text_content(),
+        Some("capture,stop".to_owned())
+    );
+
+    crate::get(PASS_ID).click();
+    assert_eq!(
+        crate::get(ORDER_ID).text_content(),
+        Some("capture,stop,capture,pass,parent".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn capture_runs_before_children() {
+    mount_propagation(true);
+
+    crate::get(PASS_ID).click();
+    assert_eq!(
+        crate::get(ORDER_ID).text_content(),
+        Some("capture".to_owned())
+    );
+}
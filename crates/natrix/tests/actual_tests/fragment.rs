@@ -0,0 +1,102 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const LIST: Id = natrix::id!();
+const TOGGLE: Id = natrix::id!();
+const REVERSE: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct App {
+    expanded: Signal<bool>,
+    items: Signal<Vec<u32>>,
+}
+
+fn list_text() -> String {
+    let list = crate::get(LIST);
+    let children = list.children();
+    (0..children.length())
+        .filter_map(|index| children.item(index))
+        .filter_map(|child| child.text_content())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn render_toggle() -> impl Element<App> {
+    e::div()
+        .child(
+            e::ul()
+                .id(LIST)
+                .child(e::li().text("before"))
+                .child(|ctx: RenderCtx<App>| {
+                    if *ctx.expanded {
+                        e::fragment().child(e::li().text("a")).child(
+                            e::fragment()
+                                .child(e::li().text("b"))
+                                .child(e::li().text("c")),
+                        )
+                    } else {
+                        e::fragment().child(e::li().text("collapsed"))
+                    }
+                })
+                .child(e::li().text("after")),
+        )
+        .child(
+            e::button()
+                .id(TOGGLE)
+                .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                    *ctx.expanded = !*ctx.expanded;
+                }),
+        )
+}
+
+#[wasm_bindgen_test]
+fn children_are_inserted_without_wrapper() {
+    crate::mount_test(App::default(), render_toggle());
+    assert_eq!(list_text(), "before,collapsed,after");
+}
+
+#[wasm_bindgen_test]
+fn swapping_replaces_only_own_children() {
+    crate::mount_test(App::default(), render_toggle());
+    let toggle = crate::get(TOGGLE);
+
+    toggle.click();
+    assert_eq!(list_text(), "before,a,b,c,after");
+    toggle.click();
+    assert_eq!(list_text(), "before,collapsed,after");
+    toggle.click();
+    assert_eq!(list_text(), "before,a,b,c,after");
+}
+
+#[wasm_bindgen_test]
+fn keyed_list_of_fragments() {
+    crate::mount_test(
+        App {
+            expanded: Signal::new(false),
+            items: Signal::new(vec![1, 2, 3]),
+        },
+        e::div()
+            .child(e::ul().id(LIST).children_keyed(
+                |ctx: &App| &ctx.items,
+                |item| *item,
+                |item| {
+                    e::fragment()
+                        .child(e::li().text(format!("{item}a")))
+                        .child(e::li().text(format!("{item}b")))
+                },
+            ))
+            .child(
+                e::button()
+                    .id(REVERSE)
+                    .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                        ctx.items.reverse();
+                    }),
+            ),
+    );
+    assert_eq!(list_text(), "1a,1b,2a,2b,3a,3b");
+
+    crate::get(REVERSE).click();
+    assert_eq!(list_text(), "3a,3b,2a,2b,1a,1b");
+}
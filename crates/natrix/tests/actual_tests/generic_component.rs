@@ -32,3 +32,32 @@ fn generic_str() {
     let element = crate::get(ROOT_ID);
     assert_eq!(element.text_content(), Some("Hello World".to_owned()));
 }
+
+const TITLE_ID: Id = natrix::id!();
+const COUNT_ID: Id = natrix::id!();
+
+natrix::component! {
+    fn title(text: &'static str) -> Element {
+        e::h1().id(TITLE_ID).text(text)
+    }
+
+    fn count() -> Element<Generic<u8>> {
+        e::span()
+            .id(COUNT_ID)
+            .text(|ctx: RenderCtx<Generic<u8>>| *ctx.0)
+    }
+}
+
+#[wasm_bindgen_test]
+fn component_macro() {
+    crate::mount_test(
+        Generic(Signal::new(7u8)),
+        e::div().child(title("Hello")).child(count()),
+    );
+
+    assert_eq!(
+        crate::get(TITLE_ID).text_content(),
+        Some("Hello".to_owned())
+    );
+    assert_eq!(crate::get(COUNT_ID).text_content(), Some("7".to_owned()));
+}
@@ -180,3 +180,110 @@ fn guard_nested() {
     let text = crate::get(TEXT);
     assert_eq!(text.text_content(), Some("NO VALUE".to_owned()));
 }
+
+const SWITCH: Id = natrix::id!();
+const NAME: Id = natrix::id!();
+
+enum Mode {
+    Counter(u8),
+    Named { name: &'static str, count: u8 },
+    Off,
+}
+
+#[derive(State)]
+struct TestMatch {
+    mode: Signal<Mode>,
+}
+
+fn render_test_match() -> impl Element<TestMatch> {
+    e::div()
+        .child(
+            e::button()
+                .id(SWITCH)
+                .on::<events::Click>(|mut ctx: EventCtx<TestMatch>, _| {
+                    *ctx.mode = match *ctx.mode {
+                        Mode::Counter(_) => Mode::Named {
+                            name: "natrix",
+                            count: 10,
+                        },
+                        Mode::Named { .. } => Mode::Off,
+                        Mode::Off => Mode::Counter(0),
+                    };
+                }),
+        )
+        .child(
+            e::button()
+                .id(BUTTON)
+                .on::<events::Click>(|mut ctx: EventCtx<TestMatch>, _| match &mut *ctx.mode {
+                    Mode::Counter(count) | Mode::Named { count, .. } => *count += 1,
+                    Mode::Off => {}
+                }),
+        )
+        .child(|mut ctx: RenderCtx<TestMatch>| {
+            natrix::guard_match!(ctx, |ctx| field!(ctx.mode).deref(), {
+                Mode::Counter(count) => e::div()
+                    .id(TEXT)
+                    .text(move |mut ctx: RenderCtx<TestMatch>| *count.call_read(&ctx)),
+                Mode::Named { name, count } => e::div()
+                    .id(TEXT)
+                    .child(
+                        e::span()
+                            .id(NAME)
+                            .text(move |mut ctx: RenderCtx<TestMatch>| *name.call_read(&ctx)),
+                    )
+                    .text(move |mut ctx: RenderCtx<TestMatch>| *count.call_read(&ctx)),
+                Mode::Off => e::div().id(TEXT).text("OFF"),
+            })
+        })
+}
+
+#[wasm_bindgen_test]
+fn guard_match_payload_updates() {
+    crate::mount_test(
+        TestMatch {
+            mode: Signal::new(Mode::Counter(0)),
+        },
+        render_test_match(),
+    );
+
+    let button = crate::get(BUTTON);
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("0".to_owned()));
+
+    button.click();
+    assert_eq!(text.text_content(), Some("1".to_owned()));
+
+    button.click();
+    assert_eq!(text.text_content(), Some("2".to_owned()));
+    assert!(text.is_connected());
+}
+
+#[wasm_bindgen_test]
+fn guard_match_variant_switches() {
+    crate::mount_test(
+        TestMatch {
+            mode: Signal::new(Mode::Counter(5)),
+        },
+        render_test_match(),
+    );
+
+    let switch = crate::get(SWITCH);
+    let button = crate::get(BUTTON);
+    let counter_text = crate::get(TEXT);
+
+    switch.click();
+    assert!(!counter_text.is_connected());
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("natrix10".to_owned()));
+    assert_eq!(crate::get(NAME).text_content(), Some("natrix".to_owned()));
+
+    button.click();
+    assert!(text.is_connected());
+    assert_eq!(text.text_content(), Some("natrix11".to_owned()));
+
+    switch.click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("OFF".to_owned()));
+
+    switch.click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("0".to_owned()));
+}
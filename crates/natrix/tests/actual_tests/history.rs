@@ -0,0 +1,95 @@
+use natrix::prelude::*;
+use natrix::reactivity::state::Snapshot;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const TEXT_ID: Id = natrix::id!();
+const COUNT_ID: Id = natrix::id!();
+const EDIT_ID: Id = natrix::id!();
+const UNDO_ID: Id = natrix::id!();
+const REDO_ID: Id = natrix::id!();
+
+#[derive(State, Snapshot)]
+struct Editor {
+    text: Signal<String>,
+    edits: Signal<u8>,
+}
+
+fn render_editor() -> impl Element<Editor> {
+    e::div()
+        .child(
+            e::div()
+                .id(TEXT_ID)
+                .text(|ctx: RenderCtx<Editor>| ctx.text.clone()),
+        )
+        .child(
+            e::div()
+                .id(COUNT_ID)
+                .text(|ctx: RenderCtx<Editor>| *ctx.edits),
+        )
+        .child(
+            e::button()
+                .id(EDIT_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Editor>, _| {
+                    ctx.enable_history(10);
+                    let letter = char::from(b'a' + *ctx.edits);
+                    ctx.text.push(letter);
+                    *ctx.edits += 1;
+                }),
+        )
+        .child(
+            e::button()
+                .id(UNDO_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Editor>, _| {
+                    ctx.undo();
+                }),
+        )
+        .child(
+            e::button()
+                .id(REDO_ID)
+                .on::<events::Click>(|mut ctx: EventCtx<Editor>, _| {
+                    ctx.redo();
+                }),
+        )
+}
+
+fn assert_dom(text: &str, edits: u8) {
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some(text.to_owned()));
+    assert_eq!(crate::get(COUNT_ID).text_content(), Some(edits.to_string()));
+}
+
+#[wasm_bindgen_test]
+fn undo_redo_restores_dom() {
+    crate::mount_test(
+        Editor {
+            text: Signal::new(String::new()),
+            edits: Signal::new(0),
+        },
+        render_editor(),
+    );
+
+    let edit = crate::get(EDIT_ID);
+    edit.click();
+    edit.click();
+    edit.click();
+    assert_dom("abc", 3);
+
+    crate::get(UNDO_ID).click();
+    assert_dom("ab", 2);
+    crate::get(UNDO_ID).click();
+    assert_dom("a", 1);
+
+    crate::get(REDO_ID).click();
+    assert_dom("ab", 2);
+
+    edit.click();
+    assert_dom("abc", 3);
+    crate::get(REDO_ID).click();
+    assert_dom("abc", 3);
+
+    for _ in 0..5 {
+        crate::get(UNDO_ID).click();
+    }
+    assert_dom("", 0);
+}
@@ -0,0 +1,69 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+const BUTTON: Id = natrix::id!();
+
+#[derive(State)]
+struct Markup {
+    html: Signal<String>,
+}
+
+#[wasm_bindgen_test]
+fn text_is_never_html() {
+    crate::mount_test(
+        Markup {
+            html: Signal::new(String::from("<b>bold</b>")),
+        },
+        e::div()
+            .id(ROOT)
+            .text("<b>")
+            .text(|ctx: RenderCtx<Markup>| ctx.html.clone()),
+    );
+
+    let element = crate::get(ROOT);
+    assert_eq!(element.text_content(), Some("<b><b>bold</b>".to_owned()));
+    assert_eq!(element.child_element_count(), 0);
+}
+
+#[wasm_bindgen_test]
+fn static_inner_html() {
+    crate::mount_test(
+        Markup {
+            html: Signal::new(String::new()),
+        },
+        e::div().id(ROOT).dangerous_inner_html("<b>bold</b> text"),
+    );
+
+    let element = crate::get(ROOT);
+    assert_eq!(element.inner_html(), "<b>bold</b> text");
+    assert_eq!(element.child_element_count(), 1);
+}
+
+#[wasm_bindgen_test]
+fn reactive_inner_html() {
+    crate::mount_test(
+        Markup {
+            html: Signal::new(String::from("<i>one</i>")),
+        },
+        e::div()
+            .child(
+                e::div()
+                    .id(ROOT)
+                    .dangerous_inner_html(|ctx: RenderCtx<Markup>| ctx.html.clone()),
+            )
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|mut ctx: EventCtx<Markup>, _| {
+                        *ctx.html = String::from("<b>two</b>");
+                    }),
+            ),
+    );
+
+    assert_eq!(crate::get(ROOT).inner_html(), "<i>one</i>");
+    crate::get(BUTTON).click();
+    assert_eq!(crate::get(ROOT).inner_html(), "<b>two</b>");
+}
@@ -0,0 +1,234 @@
+use natrix::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+wasm_bindgen_test_configure!(run_in_browser);
+
+const CHECKBOX: Id = natrix::id!();
+const TOGGLE: Id = natrix::id!();
+const RANGE: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct App {
+    checked: Signal<bool>,
+}
+
+fn render_checkbox() -> impl Element<App> {
+    e::div()
+        .child(
+            e::checkbox()
+                .id(CHECKBOX)
+                .checked(|ctx: RenderCtx<App>| *ctx.checked)
+                .on::<events::Change>(|mut ctx: EventCtx<App>, event: web_sys::Event| {
+                    if let Some(input) = event
+                        .target()
+                        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                    {
+                        *ctx.checked = input.checked();
+                    }
+                }),
+        )
+        .child(
+            e::button()
+                .id(TOGGLE)
+                .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                    *ctx.checked = !*ctx.checked;
+                }),
+        )
+}
+
+fn get_input(id: Id) -> web_sys::HtmlInputElement {
+    crate::get(id).dyn_into().expect("Element was not a input")
+}
+
+#[wasm_bindgen_test]
+fn checkbox_has_type() {
+    crate::mount_test(App::default(), render_checkbox());
+
+    let checkbox = get_input(CHECKBOX);
+    assert_eq!(checkbox.type_(), "checkbox");
+    assert!(!checkbox.checked());
+}
+
+#[wasm_bindgen_test]
+fn checked_follows_state() {
+    crate::mount_test(App::default(), render_checkbox());
+
+    let checkbox = get_input(CHECKBOX);
+    let toggle = crate::get(TOGGLE);
+
+    toggle.click();
+    assert!(checkbox.checked());
+    toggle.click();
+    assert!(!checkbox.checked());
+}
+
+#[wasm_bindgen_test]
+fn change_updates_state() {
+    crate::mount_test(App::default(), render_checkbox());
+
+    let checkbox = get_input(CHECKBOX);
+    checkbox.click();
+    assert!(checkbox.checked());
+    assert_eq!(checkbox.get_attribute("checked"), Some(String::new()));
+
+    checkbox.click();
+    assert!(!checkbox.checked());
+    assert_eq!(checkbox.get_attribute("checked"), None);
+}
+
+#[wasm_bindgen_test]
+fn range_attributes() {
+    crate::mount_test(
+        App::default(),
+        e::range().id(RANGE).min(0.0).max(10.0).step(0.5).value(2.5),
+    );
+
+    let range = get_input(RANGE);
+    assert_eq!(range.type_(), "range");
+    assert_eq!(range.min().parse(), Ok(0.0));
+    assert_eq!(range.max().parse(), Ok(10.0));
+    assert_eq!(range.step().parse(), Ok(0.5));
+    assert!((range.value_as_number() - 2.5).abs() < f64::EPSILON);
+}
+
+const NUMBER: Id = natrix::id!();
+const FILE: Id = natrix::id!();
+const TEXTAREA: Id = natrix::id!();
+
+#[wasm_bindgen_test]
+fn form_control_attributes() {
+    crate::mount_test(
+        App::default(),
+        e::div()
+            .child(e::number_input().id(NUMBER).min(1.0).max(5.0).step(2.0))
+            .child(e::file_input().id(FILE).accept("image/*").multiple(true))
+            .child(
+                e::textarea()
+                    .id(TEXTAREA)
+                    .rows(5)
+                    .columns(40)
+                    .min_length(2)
+                    .max_length(100),
+            ),
+    );
+
+    let number = get_input(NUMBER);
+    assert_eq!(number.type_(), "number");
+    assert_eq!(number.min(), "1");
+    assert_eq!(number.max(), "5");
+    assert_eq!(number.step(), "2");
+
+    let file = get_input(FILE);
+    assert_eq!(file.type_(), "file");
+    assert_eq!(file.accept(), "image/*");
+    assert!(file.multiple());
+
+    let textarea: web_sys::HtmlTextAreaElement = crate::get(TEXTAREA)
+        .dyn_into()
+        .expect("Element was not a textarea");
+    assert_eq!(textarea.rows(), 5);
+    assert_eq!(textarea.cols(), 40);
+    assert_eq!(textarea.min_length(), 2);
+    assert_eq!(textarea.max_length(), 100);
+}
+
+const NAME: Id = natrix::id!();
+const NAME_TEXT: Id = natrix::id!();
+const AGE: Id = natrix::id!();
+const AGE_TEXT: Id = natrix::id!();
+const RESET: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct Form {
+    name: Signal<String>,
+    age: Signal<f64>,
+}
+
+fn render_form() -> impl Element<Form> {
+    e::div()
+        .child(
+            e::text_input()
+                .id(NAME)
+                .bind(|ctx: Ref<Form>| field!(ctx.name).deref()),
+        )
+        .child(
+            e::span()
+                .id(NAME_TEXT)
+                .text(|ctx: RenderCtx<Form>| ctx.name.clone()),
+        )
+        .child(
+            e::text_input()
+                .id(AGE)
+                .bind_parsed(|ctx: Ref<Form>| field!(ctx.age).deref()),
+        )
+        .child(
+            e::span()
+                .id(AGE_TEXT)
+                .text(|ctx: RenderCtx<Form>| ctx.age.to_string()),
+        )
+        .child(
+            e::button()
+                .id(RESET)
+                .on::<events::Click>(|mut ctx: EventCtx<Form>, _| {
+                    *ctx.name = String::from("reset");
+                    *ctx.age = 0.0;
+                }),
+        )
+}
+
+fn type_into(id: Id, value: &str, composing: bool) {
+    let input = get_input(id);
+    input.set_value(value);
+
+    let init = web_sys::InputEventInit::new();
+    init.set_is_composing(composing);
+    let event = web_sys::InputEvent::new_with_event_init_dict("input", &init)
+        .expect("Failed to create event");
+    input.dispatch_event(&event).expect("Failed to dispatch");
+}
+
+fn text(id: Id) -> Option<String> {
+    crate::get(id).text_content()
+}
+
+#[wasm_bindgen_test]
+fn bind_updates_field_and_state() {
+    crate::mount_test(Form::default(), render_form());
+
+    type_into(NAME, "natrix", false);
+    type_into(AGE, "12.5", false);
+    assert_eq!(text(NAME_TEXT), Some("natrix".to_owned()));
+    assert_eq!(text(AGE_TEXT), Some("12.5".to_owned()));
+
+    crate::get(RESET).click();
+    assert_eq!(get_input(NAME).value(), "reset");
+    assert_eq!(get_input(AGE).value(), "0");
+}
+
+#[wasm_bindgen_test]
+fn bind_parsed_keeps_partial_input() {
+    crate::mount_test(Form::default(), render_form());
+
+    type_into(AGE, "1.", false);
+    assert_eq!(get_input(AGE).value(), "1.");
+    assert_eq!(text(AGE_TEXT), Some("1".to_owned()));
+
+    type_into(AGE, "1.x", false);
+    assert_eq!(get_input(AGE).value(), "1.x");
+    assert_eq!(text(AGE_TEXT), Some("1".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn bind_waits_for_composition() {
+    crate::mount_test(Form::default(), render_form());
+
+    type_into(NAME, "に", true);
+    assert_eq!(text(NAME_TEXT), Some(String::new()));
+    assert_eq!(get_input(NAME).value(), "に");
+
+    let input = get_input(NAME);
+    input.set_value("日本");
+    let event = web_sys::CompositionEvent::new("compositionend").expect("Failed to create event");
+    input.dispatch_event(&event).expect("Failed to dispatch");
+    assert_eq!(text(NAME_TEXT), Some("日本".to_owned()));
+}
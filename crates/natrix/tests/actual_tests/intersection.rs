@@ -0,0 +1,56 @@
+#![cfg(feature = "async_utils")]
+
+use natrix::async_utils;
+use natrix::dom::intersection::IntersectOptions;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const STATUS_ID: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct App {
+    visible: Signal<Option<bool>>,
+}
+
+fn render_observed(spacer_height: &'static str) -> impl Element<App> {
+    e::div()
+        .child(
+            e::div()
+                .id(STATUS_ID)
+                .text(|ctx: RenderCtx<App>| match *ctx.visible {
+                    None => "unknown",
+                    Some(true) => "visible",
+                    Some(false) => "hidden",
+                }),
+        )
+        .child(e::div().attr("style", format!("height: {spacer_height}")))
+        .child(e::div().text("observed").on_intersect(
+            |mut ctx: EventCtx<App>, entry| {
+                *ctx.visible = Some(entry.is_intersecting());
+            },
+            IntersectOptions::new().threshold(0.0),
+        ))
+}
+
+fn status() -> Option<String> {
+    crate::get(STATUS_ID).text_content()
+}
+
+#[wasm_bindgen_test]
+async fn reports_visible_element() {
+    crate::mount_test(App::default(), render_observed("0px"));
+    assert_eq!(status(), Some("unknown".to_owned()));
+
+    async_utils::sleep_milliseconds(100).await;
+    assert_eq!(status(), Some("visible".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn reports_element_outside_viewport() {
+    crate::mount_test(App::default(), render_observed("100000px"));
+
+    async_utils::sleep_milliseconds(100).await;
+    assert_eq!(status(), Some("hidden".to_owned()));
+}
@@ -0,0 +1,143 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const CONTAINER: Id = natrix::id!();
+const TAB_ONE: Id = natrix::id!();
+const TAB_TWO: Id = natrix::id!();
+const TAB_THREE: Id = natrix::id!();
+const SHOW_ONE: Id = natrix::id!();
+const SHOW_TWO: Id = natrix::id!();
+const SHOW_THREE: Id = natrix::id!();
+const INCREMENT: Id = natrix::id!();
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tab {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(State)]
+struct Tabs {
+    tab: Signal<Tab>,
+    counter: Signal<u8>,
+}
+
+fn show(id: Id, tab: Tab) -> impl Element<Tabs> {
+    e::button()
+        .id(id)
+        .on::<events::Click>(move |mut ctx: EventCtx<Tabs>, _| {
+            *ctx.tab = tab;
+        })
+}
+
+fn render_tabs(capacity: usize) -> impl Element<Tabs> {
+    e::div()
+        .child(
+            e::div()
+                .id(CONTAINER)
+                .text("start")
+                .keep_alive(
+                    |ctx: &Tabs| *ctx.tab,
+                    |tab| match tab {
+                        Tab::One => e::div()
+                            .id(TAB_ONE)
+                            .child(|ctx: RenderCtx<Tabs>| *ctx.counter),
+                        Tab::Two => e::div().id(TAB_TWO).text("two"),
+                        Tab::Three => e::div().id(TAB_THREE).text("three"),
+                    },
+                    capacity,
+                )
+                .text("end"),
+        )
+        .child(show(SHOW_ONE, Tab::One))
+        .child(show(SHOW_TWO, Tab::Two))
+        .child(show(SHOW_THREE, Tab::Three))
+        .child(
+            e::button()
+                .id(INCREMENT)
+                .on::<events::Click>(|mut ctx: EventCtx<Tabs>, _| {
+                    *ctx.counter += 1;
+                }),
+        )
+}
+
+fn mount(capacity: usize) {
+    crate::mount_test(
+        Tabs {
+            tab: Signal::new(Tab::One),
+            counter: Signal::new(0),
+        },
+        render_tabs(capacity),
+    );
+}
+
+#[wasm_bindgen_test]
+fn renders_current_tab_between_siblings() {
+    mount(4);
+    assert_eq!(
+        crate::get(CONTAINER).text_content(),
+        Some("start0end".to_owned())
+    );
+
+    crate::get(SHOW_TWO).click();
+    assert_eq!(
+        crate::get(CONTAINER).text_content(),
+        Some("starttwoend".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn switching_back_preserves_dom_state() {
+    mount(4);
+    let tab = crate::get(TAB_ONE);
+    tab.set_attribute("data-touched", "yes")
+        .expect("Failed to set attribute");
+
+    crate::get(SHOW_TWO).click();
+    assert!(!tab.is_connected());
+
+    crate::get(SHOW_ONE).click();
+    let new_tab = crate::get(TAB_ONE);
+    assert!(new_tab.is_same_node(Some(&tab)));
+    assert_eq!(
+        new_tab.get_attribute("data-touched"),
+        Some("yes".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn detached_tab_keeps_updating() {
+    mount(4);
+    crate::get(SHOW_TWO).click();
+    crate::get(INCREMENT).click();
+    crate::get(INCREMENT).click();
+
+    crate::get(SHOW_ONE).click();
+    assert_eq!(crate::get(TAB_ONE).text_content(), Some("2".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn least_recently_shown_is_evicted() {
+    mount(1);
+    let tab = crate::get(TAB_ONE);
+
+    crate::get(SHOW_TWO).click();
+    crate::get(SHOW_THREE).click();
+    crate::get(SHOW_ONE).click();
+
+    assert!(!crate::get(TAB_ONE).is_same_node(Some(&tab)));
+}
+
+#[wasm_bindgen_test]
+fn zero_capacity_drops_hidden_tabs() {
+    mount(0);
+    let tab = crate::get(TAB_ONE);
+
+    crate::get(SHOW_TWO).click();
+    crate::get(SHOW_ONE).click();
+
+    assert!(!crate::get(TAB_ONE).is_same_node(Some(&tab)));
+}
@@ -0,0 +1,108 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+use crate::{get, mount_test};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const BUTTON: Id = natrix::id!();
+const CONTENT: Id = natrix::id!();
+const MOUNTS: Id = natrix::id!();
+
+#[derive(State)]
+struct Toggle {
+    show: Signal<bool>,
+    mounts: Signal<u8>,
+}
+
+fn render_toggle(unmounts: Rc<Cell<u8>>) -> impl Element<Toggle> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON)
+                .on::<events::Click>(|mut ctx: EventCtx<Toggle>, _| {
+                    *ctx.show = !*ctx.show;
+                }),
+        )
+        .child(
+            e::span()
+                .id(MOUNTS)
+                .text(|ctx: RenderCtx<Toggle>| *ctx.mounts),
+        )
+        .child(move |ctx: RenderCtx<Toggle>| {
+            if *ctx.show {
+                let unmounts = unmounts.clone();
+                Some(
+                    e::div()
+                        .id(CONTENT)
+                        .on_mount(|mut ctx: EventCtx<Toggle>, node| {
+                            assert!(node.is_connected());
+                            *ctx.mounts += 1;
+                        })
+                        .on_unmount(move |node| {
+                            assert_eq!(node.id(), CONTENT.0);
+                            unmounts.set(unmounts.get() + 1);
+                        }),
+                )
+            } else {
+                None
+            }
+        })
+}
+
+#[wasm_bindgen_test]
+fn on_mount_runs_on_initial_render() {
+    mount_test(
+        Toggle {
+            show: Signal::new(true),
+            mounts: Signal::new(0),
+        },
+        render_toggle(Rc::default()),
+    );
+
+    assert_eq!(get(MOUNTS).text_content(), Some("1".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn on_mount_runs_once_per_node() {
+    mount_test(
+        Toggle {
+            show: Signal::new(false),
+            mounts: Signal::new(0),
+        },
+        render_toggle(Rc::default()),
+    );
+    assert_eq!(get(MOUNTS).text_content(), Some("0".to_owned()));
+
+    let button = get(BUTTON);
+    button.click();
+    assert_eq!(get(MOUNTS).text_content(), Some("1".to_owned()));
+    button.click();
+    assert_eq!(get(MOUNTS).text_content(), Some("1".to_owned()));
+    button.click();
+    assert_eq!(get(MOUNTS).text_content(), Some("2".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn on_unmount_runs_when_removed() {
+    let unmounts = Rc::new(Cell::new(0));
+    mount_test(
+        Toggle {
+            show: Signal::new(true),
+            mounts: Signal::new(0),
+        },
+        render_toggle(unmounts.clone()),
+    );
+    assert_eq!(unmounts.get(), 0);
+
+    let button = get(BUTTON);
+    button.click();
+    assert_eq!(unmounts.get(), 1);
+    button.click();
+    assert_eq!(unmounts.get(), 1);
+    button.click();
+    assert_eq!(unmounts.get(), 2);
+}
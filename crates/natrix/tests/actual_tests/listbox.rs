@@ -0,0 +1,210 @@
+use natrix::dom::listbox::Listbox;
+use natrix::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const LIST: Id = natrix::id!();
+const OUTPUT: Id = natrix::id!();
+
+#[derive(State)]
+struct Picker {
+    picked: Signal<Option<&'static str>>,
+    escaped: Signal<bool>,
+}
+
+fn render_picker() -> impl Element<Picker> {
+    e::div()
+        .child(
+            Listbox::new(|mut ctx: EventCtx<Picker>, value| *ctx.picked = Some(value))
+                .label("Fruits")
+                .item("apple", "Apple")
+                .item("banana", "Banana")
+                .item("cherry", "Cherry")
+                .on_escape(|mut ctx: EventCtx<Picker>| *ctx.escaped = true)
+                .element(|list| list.id(LIST)),
+        )
+        .child(
+            e::div()
+                .id(OUTPUT)
+                .text(|ctx: RenderCtx<Picker>| format!("{:?} {}", *ctx.picked, *ctx.escaped)),
+        )
+}
+
+fn mount() -> Vec<web_sys::HtmlElement> {
+    crate::mount_test(
+        Picker {
+            picked: Signal::new(None),
+            escaped: Signal::new(false),
+        },
+        render_picker(),
+    );
+
+    let children = crate::get(LIST).children();
+    (0..children.length())
+        .map(|index| {
+            children
+                .item(index)
+                .expect("Missing item")
+                .dyn_into()
+                .expect("Item not a html element")
+        })
+        .collect()
+}
+
+fn press(key: &str) {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_bubbles(true);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+        .expect("Failed to create event");
+
+    let document = web_sys::window()
+        .expect("Failed to get window")
+        .document()
+        .expect("Failed to get document");
+    document
+        .active_element()
+        .expect("Nothing focused")
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+}
+
+fn output() -> Option<String> {
+    crate::get(OUTPUT).text_content()
+}
+
+#[wasm_bindgen_test]
+fn roles_and_tabindex() {
+    let items = mount();
+    let list = crate::get(LIST);
+
+    assert_eq!(list.get_attribute("role"), Some("listbox".to_owned()));
+    assert_eq!(list.get_attribute("aria-label"), Some("Fruits".to_owned()));
+    assert_eq!(items.len(), 3);
+    for (index, item) in items.iter().enumerate() {
+        assert_eq!(item.get_attribute("role"), Some("option".to_owned()));
+        assert_eq!(
+            item.get_attribute("aria-selected"),
+            Some("false".to_owned())
+        );
+        let tab_index = if index == 0 { "0" } else { "-1" };
+        assert_eq!(item.get_attribute("tabindex"), Some(tab_index.to_owned()));
+    }
+}
+
+#[wasm_bindgen_test]
+fn arrow_keys_and_enter() {
+    let items = mount();
+    items[0].focus().expect("Failed to focus");
+
+    press("ArrowDown");
+    press("ArrowDown");
+    press("ArrowDown");
+    assert_eq!(items[2].get_attribute("tabindex"), Some("0".to_owned()));
+    assert_eq!(items[0].get_attribute("tabindex"), Some("-1".to_owned()));
+
+    press("ArrowUp");
+    press("Enter");
+    assert_eq!(output(), Some("Some(\"banana\") false".to_owned()));
+    assert_eq!(
+        items[1].get_attribute("aria-selected"),
+        Some("true".to_owned())
+    );
+
+    press("Home");
+    press(" ");
+    assert_eq!(output(), Some("Some(\"apple\") false".to_owned()));
+    assert_eq!(
+        items[1].get_attribute("aria-selected"),
+        Some("false".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn escape() {
+    let items = mount();
+    items[0].focus().expect("Failed to focus");
+
+    press("Escape");
+    assert_eq!(output(), Some("None true".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn click_selects() {
+    let items = mount();
+
+    items[2].click();
+    assert_eq!(output(), Some("Some(\"cherry\") false".to_owned()));
+    assert_eq!(
+        items[2].get_attribute("aria-selected"),
+        Some("true".to_owned())
+    );
+}
+
+#[derive(State)]
+struct BoundPicker {
+    index: Signal<Option<usize>>,
+}
+
+fn mount_bound(index: Option<usize>) -> Vec<web_sys::HtmlElement> {
+    crate::mount_test(
+        BoundPicker {
+            index: Signal::new(index),
+        },
+        Listbox::new(|mut ctx: EventCtx<BoundPicker>, value: usize| {
+            // NOTE: The last item is never selected, to check the state is the source of truth
+            if value != 2 {
+                *ctx.index = Some(value);
+            }
+        })
+        .item(0, "Apple")
+        .item(1, "Banana")
+        .item(2, "Cherry")
+        .selected(|ctx: Ref<BoundPicker>| field!(ctx.index).deref())
+        .element(|list| list.id(LIST)),
+    );
+
+    let children = crate::get(LIST).children();
+    (0..children.length())
+        .map(|index| {
+            children
+                .item(index)
+                .expect("Missing item")
+                .dyn_into()
+                .expect("Item not a html element")
+        })
+        .collect()
+}
+
+fn assert_selected(items: &[web_sys::HtmlElement], selected: Option<usize>) {
+    for (index, item) in items.iter().enumerate() {
+        let is_selected = Some(index) == selected;
+        assert_eq!(
+            item.get_attribute("aria-selected"),
+            Some(is_selected.to_string())
+        );
+        let is_current = index == selected.unwrap_or(0);
+        let tab_index = if is_current { "0" } else { "-1" };
+        assert_eq!(item.get_attribute("tabindex"), Some(tab_index.to_owned()));
+    }
+}
+
+#[wasm_bindgen_test]
+fn selection_follows_state() {
+    let items = mount_bound(Some(1));
+    assert_selected(&items, Some(1));
+
+    items[0].click();
+    assert_selected(&items, Some(0));
+
+    items[2].click();
+    assert_selected(&items, Some(0));
+}
+
+#[wasm_bindgen_test]
+fn out_of_range_selection_falls_back_to_first() {
+    let items = mount_bound(Some(5));
+    assert_selected(&items, None);
+}
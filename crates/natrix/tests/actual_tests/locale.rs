@@ -0,0 +1,56 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+const SWITCH: Id = natrix::id!();
+
+#[derive(State)]
+struct App {
+    locale: Signal<&'static str>,
+}
+
+fn render_app() -> impl Element<App> {
+    e::div()
+        .id(ROOT)
+        .document_lang(|ctx: RenderCtx<App>| *ctx.locale)
+        .lang(|ctx: RenderCtx<App>| *ctx.locale)
+        .child(
+            e::button()
+                .id(SWITCH)
+                .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                    *ctx.locale = "nb-NO";
+                }),
+        )
+}
+
+fn document_lang() -> Option<String> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+        .and_then(|root| root.get_attribute("lang"))
+}
+
+#[wasm_bindgen_test]
+fn changing_locale_updates_document_lang() {
+    crate::mount_test(
+        App {
+            locale: Signal::new("en"),
+        },
+        render_app(),
+    );
+
+    assert_eq!(document_lang().as_deref(), Some("en"));
+    assert_eq!(
+        crate::get(ROOT).get_attribute("lang").as_deref(),
+        Some("en")
+    );
+
+    crate::get(SWITCH).click();
+    assert_eq!(document_lang().as_deref(), Some("nb-NO"));
+    assert_eq!(
+        crate::get(ROOT).get_attribute("lang").as_deref(),
+        Some("nb-NO")
+    );
+}
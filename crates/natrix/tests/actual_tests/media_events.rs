@@ -0,0 +1,43 @@
+#![cfg(feature = "async_utils")]
+
+use natrix::async_utils;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const IMAGE: Id = natrix::id!();
+
+const BROKEN: &str = "data:image/png;base64,broken";
+const FALLBACK: &str =
+    "data:image/gif;base64,R0lGODlhAQABAIAAAAAAAP///yH5BAEAAAAALAAAAAABAAEAAAIBRAA7";
+
+#[derive(State, Default)]
+struct Avatar {
+    failed: Signal<bool>,
+    loaded: Signal<bool>,
+}
+
+fn render_avatar() -> impl Element<Avatar> {
+    e::img()
+        .id(IMAGE)
+        .src(|ctx: RenderCtx<Avatar>| if *ctx.failed { FALLBACK } else { BROKEN })
+        .on::<events::Error>(|mut ctx: EventCtx<Avatar>, _| {
+            *ctx.failed = true;
+        })
+        .attr("data-loaded", |ctx: RenderCtx<Avatar>| *ctx.loaded)
+        .on::<events::Load>(|mut ctx: EventCtx<Avatar>, _| {
+            *ctx.loaded = true;
+        })
+}
+
+#[wasm_bindgen_test]
+async fn error_handler_sets_fallback() {
+    crate::mount_test(Avatar::default(), render_avatar());
+
+    async_utils::sleep_milliseconds(100).await;
+
+    let image = crate::get(IMAGE);
+    assert_eq!(image.get_attribute("src"), Some(FALLBACK.to_owned()));
+    assert_eq!(image.get_attribute("data-loaded"), Some("".to_owned()));
+}
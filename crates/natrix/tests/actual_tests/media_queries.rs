@@ -0,0 +1,59 @@
+use natrix::css::media::MediaQuery;
+use natrix::media::MediaMatch;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const STATUS: Id = natrix::id!();
+
+#[derive(State)]
+struct App {
+    wide: MediaMatch,
+}
+
+fn render() -> impl Element<App> {
+    e::div()
+        .watch_media(|ctx: Ref<App>| field!(ctx.wide))
+        .child(e::span().id(STATUS).text(
+            |ctx: RenderCtx<App>| {
+                if ctx.wide.matches() { "wide" } else { "narrow" }
+            },
+        ))
+}
+
+#[wasm_bindgen_test]
+fn matching_query() {
+    crate::mount_test(
+        App {
+            wide: MediaMatch::new(MediaQuery::raw("(min-width: 0px)")),
+        },
+        render(),
+    );
+
+    assert_eq!(crate::get(STATUS).text_content(), Some("wide".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn not_matching_query() {
+    crate::mount_test(
+        App {
+            wide: MediaMatch::new(!MediaQuery::raw("(min-width: 0px)")),
+        },
+        render(),
+    );
+
+    assert_eq!(crate::get(STATUS).text_content(), Some("narrow".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn reduced_motion_uses_match_media() {
+    let media = MediaMatch::reduced_motion();
+    let expected = web_sys::window()
+        .expect("No window")
+        .match_media(media.query())
+        .expect("Failed to evaluate query")
+        .expect("No media query list")
+        .matches();
+    assert_eq!(media.matches(), expected);
+}
@@ -1,10 +1,39 @@
 mod async_callbacks;
 mod attributes;
+mod children_keyed;
+mod children_map;
 mod classes;
 mod computed;
+mod debounce;
+mod dialog;
 mod events;
+mod fragment;
 mod generic_component;
 mod guards;
+mod history;
+mod inner_html;
+mod inputs;
+mod intersection;
+mod keep_alive;
+mod lifecycle;
+mod listbox;
+mod locale;
+mod media_events;
+mod media_queries;
 mod nested_reactivity;
+mod post_update;
+mod props;
+mod render_counts;
+mod router;
+mod shims;
 mod simple_reactivty;
 mod simple_rendering;
+mod skip_fields;
+mod storage;
+mod styles;
+mod svg;
+mod text_template;
+mod theme;
+mod tooltip;
+mod transition;
+mod window_events;
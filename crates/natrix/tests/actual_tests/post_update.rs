@@ -0,0 +1,53 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const BUTTON_ID: Id = natrix::id!();
+const TEXT_ID: Id = natrix::id!();
+
+#[derive(State)]
+struct Counter {
+    value: Signal<u8>,
+    seen_text: Signal<Option<String>>,
+}
+
+fn render_counter() -> impl Element<Counter> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON_ID)
+                .child(|ctx: RenderCtx<Counter>| *ctx.value)
+                .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| {
+                    *ctx.value += 1;
+                    ctx.on_next_update(|mut ctx| {
+                        *ctx.seen_text = crate::get(BUTTON_ID).text_content();
+                    });
+                }),
+        )
+        .child(
+            e::div()
+                .id(TEXT_ID)
+                .child(|ctx: RenderCtx<Counter>| ctx.seen_text.clone()),
+        )
+}
+
+#[wasm_bindgen_test]
+fn callback_sees_updated_dom() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+            seen_text: Signal::new(None),
+        },
+        render_counter(),
+    );
+
+    let button = crate::get(BUTTON_ID);
+    let text = crate::get(TEXT_ID);
+
+    button.click();
+    assert_eq!(text.text_content(), Some("1".to_owned()));
+
+    button.click();
+    assert_eq!(text.text_content(), Some("2".to_owned()));
+}
@@ -0,0 +1,89 @@
+use natrix::dom::props::MergeProps;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+const TOGGLE: Id = natrix::id!();
+const BUTTON: Class = natrix::class!();
+const PRIMARY: Class = natrix::class!();
+
+#[derive(State, Default)]
+struct App {
+    primary: Signal<bool>,
+}
+
+fn button<C: State>(props: MergeProps<C>) -> impl Element<C> {
+    let own = MergeProps::new()
+        .class(BUTTON)
+        .attr("type", "button")
+        .attr("title", "Button");
+    e::button().id(ROOT).props(own.merge(props))
+}
+
+#[wasm_bindgen_test]
+fn classes_are_concatenated() {
+    crate::mount_test(
+        App::default(),
+        e::div()
+            .child(button(MergeProps::new().class(|ctx: RenderCtx<App>| {
+                if *ctx.primary { Some(PRIMARY) } else { None }
+            })))
+            .child(
+                e::button()
+                    .id(TOGGLE)
+                    .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                        *ctx.primary = !*ctx.primary;
+                    }),
+            ),
+    );
+
+    let element = crate::get(ROOT);
+    assert!(element.class_list().contains(BUTTON.0));
+    assert!(!element.class_list().contains(PRIMARY.0));
+
+    crate::get(TOGGLE).click();
+    assert!(element.class_list().contains(BUTTON.0));
+    assert!(element.class_list().contains(PRIMARY.0));
+
+    crate::get(TOGGLE).click();
+    assert!(element.class_list().contains(BUTTON.0));
+    assert!(!element.class_list().contains(PRIMARY.0));
+}
+
+#[wasm_bindgen_test]
+fn attributes_apply_in_call_order() {
+    crate::mount_test(
+        App::default(),
+        e::button()
+            .id(ROOT)
+            .attr("type", "button")
+            .props(
+                MergeProps::new()
+                    .attr("type", "submit")
+                    .attr("title", "Bundle"),
+            )
+            .attr("title", "Direct"),
+    );
+
+    let element = crate::get(ROOT);
+    assert_eq!(element.get_attribute("type"), Some("submit".to_owned()));
+    assert_eq!(element.get_attribute("title"), Some("Direct".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn attributes_are_last_wins() {
+    crate::mount_test(
+        App::default(),
+        button(
+            MergeProps::new()
+                .attr("type", "submit")
+                .attr("data-extra", "yes"),
+        ),
+    );
+
+    let element = crate::get(ROOT);
+    assert_eq!(element.get_attribute("type"), Some("submit".to_owned()));
+    assert_eq!(element.get_attribute("title"), Some("Button".to_owned()));
+    assert_eq!(element.get_attribute("data-extra"), Some("yes".to_owned()));
+}
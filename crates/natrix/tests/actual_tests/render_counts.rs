@@ -0,0 +1,181 @@
+use natrix::prelude::*;
+use natrix::test_utils::count_renders;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const BUTTON_A: Id = natrix::id!();
+const BUTTON_B: Id = natrix::id!();
+
+#[derive(State)]
+struct TwoFields {
+    a: Signal<u8>,
+    b: Signal<u8>,
+}
+
+fn render_two_fields() -> impl Element<TwoFields> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON_A)
+                .text(|ctx: RenderCtx<TwoFields>| *ctx.a)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.a += 1),
+        )
+        .child(
+            e::button()
+                .id(BUTTON_B)
+                .text(|ctx: RenderCtx<TwoFields>| *ctx.b)
+                .attr("data-b", |ctx: RenderCtx<TwoFields>| *ctx.b)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.b += 1),
+        )
+}
+
+#[wasm_bindgen_test]
+fn only_dependent_hooks_rerun() {
+    crate::mount_test(
+        TwoFields {
+            a: Signal::new(0),
+            b: Signal::new(0),
+        },
+        render_two_fields(),
+    );
+
+    assert_eq!(count_renders(|| crate::get(BUTTON_A).click()), 1);
+    assert_eq!(count_renders(|| crate::get(BUTTON_B).click()), 2);
+}
+
+#[wasm_bindgen_test]
+fn no_change_no_rerun() {
+    crate::mount_test(
+        TwoFields {
+            a: Signal::new(0),
+            b: Signal::new(0),
+        },
+        render_two_fields(),
+    );
+
+    assert_eq!(count_renders(|| {}), 0);
+}
+
+#[derive(Default)]
+struct Settings {
+    name: String,
+    volume: u8,
+}
+
+#[derive(State, Default)]
+struct SingleSignal {
+    settings: Signal<Settings>,
+}
+
+fn render_selected() -> impl Element<SingleSignal> {
+    e::div()
+        .child(e::button().id(BUTTON_A).on::<events::Click>(
+            |mut ctx: EventCtx<SingleSignal>, _| {
+                ctx.settings.name.push('a');
+            },
+        ))
+        .child(e::button().id(BUTTON_B).on::<events::Click>(
+            |mut ctx: EventCtx<SingleSignal>, _| {
+                ctx.settings.volume += 1;
+            },
+        ))
+        .child(|mut ctx: RenderCtx<SingleSignal>| {
+            let name = ctx.select(|data| &data.settings.name);
+            e::span().text(name)
+        })
+}
+
+#[wasm_bindgen_test]
+fn select_only_reruns_on_selected_change() {
+    crate::mount_test(SingleSignal::default(), render_selected());
+
+    assert_eq!(count_renders(|| crate::get(BUTTON_B).click()), 1);
+    assert_eq!(count_renders(|| crate::get(BUTTON_A).click()), 2);
+}
+
+thread_local! {
+    static MEMO_RUNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn render_memo() -> impl Element<TwoFields> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON_A)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.a += 1),
+        )
+        .child(
+            e::button()
+                .id(BUTTON_B)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.b += 1),
+        )
+        .child(|mut ctx: RenderCtx<TwoFields>| {
+            let doubled = ctx.memo(|ctx| {
+                MEMO_RUNS.set(MEMO_RUNS.get() + 1);
+                u16::from(*ctx.a) * 2
+            });
+            e::span().text(move |ctx: RenderCtx<TwoFields>| *doubled + u16::from(*ctx.b))
+        })
+}
+
+#[wasm_bindgen_test]
+fn memo_is_not_recalculated_by_nested_closures() {
+    MEMO_RUNS.set(0);
+    crate::mount_test(
+        TwoFields {
+            a: Signal::new(0),
+            b: Signal::new(0),
+        },
+        render_memo(),
+    );
+    assert_eq!(MEMO_RUNS.get(), 1);
+
+    assert_eq!(count_renders(|| crate::get(BUTTON_B).click()), 1);
+    assert_eq!(MEMO_RUNS.get(), 1);
+
+    crate::get(BUTTON_A).click();
+    assert_eq!(MEMO_RUNS.get(), 2);
+}
+
+thread_local! {
+    static EFFECT_VALUES: std::cell::RefCell<Vec<u8>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+fn render_effect() -> impl Element<TwoFields> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON_A)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.a += 1),
+        )
+        .child(
+            e::button()
+                .id(BUTTON_B)
+                .on::<events::Click>(|mut ctx: EventCtx<TwoFields>, _| *ctx.b += 1),
+        )
+        .child(|mut ctx: RenderCtx<TwoFields>| {
+            ctx.effect(|ctx| EFFECT_VALUES.with_borrow_mut(|values| values.push(*ctx.a)));
+            e::span()
+        })
+}
+
+#[wasm_bindgen_test]
+fn effect_reruns_on_its_dependencies() {
+    EFFECT_VALUES.with_borrow_mut(Vec::clear);
+    crate::mount_test(
+        TwoFields {
+            a: Signal::new(0),
+            b: Signal::new(0),
+        },
+        render_effect(),
+    );
+    assert_eq!(EFFECT_VALUES.with_borrow(Clone::clone), vec![0]);
+
+    // Only the effect reruns, not the closure that created it.
+    assert_eq!(count_renders(|| crate::get(BUTTON_A).click()), 1);
+    assert_eq!(EFFECT_VALUES.with_borrow(Clone::clone), vec![0, 1]);
+
+    crate::get(BUTTON_B).click();
+    assert_eq!(EFFECT_VALUES.with_borrow(Clone::clone), vec![0, 1]);
+}
@@ -0,0 +1,235 @@
+use natrix::prelude::*;
+use natrix::router::{FromRouteParams, Location, RouteParams, Router, Routes};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const CONTENT: Id = natrix::id!();
+const NAVIGATE: Id = natrix::id!();
+const LINK: Id = natrix::id!();
+const EXTERNAL_LINK: Id = natrix::id!();
+const OPT_OUT_LINK: Id = natrix::id!();
+const QUERY_LINK: Id = natrix::id!();
+const INPUT: Id = natrix::id!();
+
+#[derive(State)]
+struct App {
+    location: Location,
+}
+
+struct UserRoute {
+    id: u32,
+}
+
+impl FromRouteParams for UserRoute {
+    fn from_route_params(params: &RouteParams) -> Option<Self> {
+        Some(Self {
+            id: params.parse("id")?,
+        })
+    }
+}
+
+fn user(UserRoute { id }: UserRoute) -> impl Element<App> {
+    e::div()
+        .child(e::span().id(CONTENT).text(format!("user {id}")))
+        .child(e::input().id(INPUT))
+}
+
+fn render_router() -> impl Element<App> {
+    e::div()
+        .child(
+            e::button()
+                .id(NAVIGATE)
+                .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                    ctx.location.navigate("/users/7");
+                }),
+        )
+        .child(e::a().id(LINK).href("/users/3").text("user"))
+        .child(
+            e::a()
+                .id(EXTERNAL_LINK)
+                .href("/users/4")
+                .attr("target", "_blank")
+                .text("user in new tab"),
+        )
+        .child(
+            e::a()
+                .id(OPT_OUT_LINK)
+                .href("/users/5")
+                .attr("data-natrix-external", true)
+                .text("user without router"),
+        )
+        .child(
+            e::a()
+                .id(QUERY_LINK)
+                .href("/users/7?tab=posts")
+                .text("posts"),
+        )
+        .child(Router::new(
+            |ctx: Ref<App>| field!(ctx.location),
+            Routes::new()
+                .route("/", |()| e::span().id(CONTENT).text("home"))
+                .route("/users/:id", user)
+                .route("/users/:name", |params: RouteParams| {
+                    e::span()
+                        .id(CONTENT)
+                        .text(format!("named {}", params.get("name").unwrap_or_default()))
+                })
+                .fallback(|path| e::span().id(CONTENT).text(format!("missing {path}"))),
+        ))
+}
+
+fn window() -> web_sys::Window {
+    web_sys::window().expect("No window")
+}
+
+fn history() -> web_sys::History {
+    window().history().expect("No history")
+}
+
+fn content() -> Option<String> {
+    crate::get(CONTENT).text_content()
+}
+
+/// Mount the router at `path`, returning the url to restore after the test.
+fn mount(path: &str) -> String {
+    let original = window().location().href().expect("Failed to read url");
+    history()
+        .replace_state_with_url(&JsValue::NULL, "", Some(path))
+        .expect("Failed to set url");
+
+    crate::mount_test(
+        App {
+            location: Location::current(),
+        },
+        render_router(),
+    );
+    original
+}
+
+fn restore(original: &str) {
+    history()
+        .replace_state_with_url(&JsValue::NULL, "", Some(original))
+        .expect("Failed to restore url");
+}
+
+fn pathname() -> String {
+    window().location().pathname().expect("Failed to read path")
+}
+
+#[wasm_bindgen_test]
+fn renders_matching_route() {
+    let original = mount("/users/42");
+    assert_eq!(content(), Some("user 42".to_owned()));
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn renders_fallback() {
+    let original = mount("/nowhere?query=1");
+    assert_eq!(content(), Some("missing /nowhere".to_owned()));
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn navigate_updates_url_and_route() {
+    let original = mount("/");
+    assert_eq!(content(), Some("home".to_owned()));
+
+    crate::get(NAVIGATE).click();
+    assert_eq!(content(), Some("user 7".to_owned()));
+    assert_eq!(pathname(), "/users/7");
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn intercepts_internal_links() {
+    let original = mount("/");
+
+    crate::get(LINK).click();
+    assert_eq!(content(), Some("user 3".to_owned()));
+    assert_eq!(pathname(), "/users/3");
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn unparsable_params_fall_through() {
+    let original = mount("/users/bob");
+    assert_eq!(content(), Some("named bob".to_owned()));
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn query_change_keeps_route() {
+    let original = mount("/users/7");
+    let input = crate::get(INPUT)
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .expect("Not a input");
+    input.set_value("typed");
+
+    crate::get(QUERY_LINK).click();
+    assert_eq!(
+        window().location().search().expect("Failed to read query"),
+        "?tab=posts"
+    );
+    let input = crate::get(INPUT)
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .expect("Not a input");
+    assert_eq!(input.value(), "typed");
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn ignores_opted_out_links() {
+    let original = mount("/");
+
+    // NOTE: Stops the browser from actually navigating
+    let block =
+        Closure::<dyn Fn(web_sys::Event)>::new(|event: web_sys::Event| event.prevent_default());
+    window()
+        .add_event_listener_with_callback("click", block.as_ref().unchecked_ref())
+        .expect("Failed to add listener");
+
+    crate::get(OPT_OUT_LINK).click();
+    assert_eq!(content(), Some("home".to_owned()));
+    window()
+        .remove_event_listener_with_callback("click", block.as_ref().unchecked_ref())
+        .expect("Failed to remove listener");
+    restore(&original);
+}
+
+#[wasm_bindgen_test]
+fn ignores_links_opening_new_tabs() {
+    let original = mount("/");
+
+    let link = crate::get(EXTERNAL_LINK);
+    // NOTE: Stops the browser from actually opening the tab
+    let block =
+        Closure::<dyn Fn(web_sys::Event)>::new(|event: web_sys::Event| event.prevent_default());
+    window()
+        .add_event_listener_with_callback("click", block.as_ref().unchecked_ref())
+        .expect("Failed to add listener");
+
+    link.click();
+    assert_eq!(content(), Some("home".to_owned()));
+    window()
+        .remove_event_listener_with_callback("click", block.as_ref().unchecked_ref())
+        .expect("Failed to remove listener");
+    restore(&original);
+}
+
+#[cfg(feature = "async_utils")]
+#[wasm_bindgen_test]
+async fn back_button_restores_route() {
+    let original = mount("/");
+
+    crate::get(NAVIGATE).click();
+    assert_eq!(content(), Some("user 7".to_owned()));
+
+    history().back().expect("Failed to go back");
+    natrix::async_utils::sleep_milliseconds(100).await;
+    assert_eq!(content(), Some("home".to_owned()));
+    restore(&original);
+}
@@ -0,0 +1,39 @@
+use natrix::prelude::*;
+use natrix::{shims, test_utils};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+
+#[derive(State)]
+struct App;
+
+fn render_app() -> impl Element<App> {
+    e::div()
+        .id(ROOT)
+        .text(format!("{:.4}@{}", shims::random(), shims::now()))
+}
+
+#[wasm_bindgen_test]
+fn fakes_make_rendering_deterministic() {
+    test_utils::seed_random(7);
+    test_utils::set_fake_now(1234.0);
+    crate::mount_test(App, render_app());
+    let first = crate::get(ROOT).text_content();
+
+    test_utils::seed_random(7);
+    crate::mount_test(App, render_app());
+    let second = crate::get(ROOT).text_content();
+    test_utils::reset_fakes();
+
+    assert_eq!(first, second);
+    assert!(first.is_some_and(|text| text.ends_with("@1234")));
+}
+
+#[wasm_bindgen_test]
+fn real_values_without_fakes() {
+    test_utils::reset_fakes();
+    assert!(shims::now() > 0.0);
+    assert!((0.0..1.0).contains(&shims::random()));
+}
@@ -1,5 +1,6 @@
 use natrix::format_elements;
 use natrix::prelude::*;
+use natrix::reactivity::signal::ReadSignal;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -169,3 +170,32 @@ fn test_set() {
     assert_eq!(title.text_content(), Some("Natrix Guide".to_string()));
     assert_eq!(author.text_content(), Some("Viv".to_string()));
 }
+
+const READ_ONLY_ID: Id = natrix::id!();
+
+fn describe_count(count: ReadSignal<u8>) -> String {
+    format!("count is {}", *count)
+}
+
+fn render_read_only() -> impl Element<Counter> {
+    e::button()
+        .id(READ_ONLY_ID)
+        .text(|ctx: RenderCtx<Counter>| describe_count(ctx.value.read_only()))
+        .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| ctx.increment())
+}
+
+#[wasm_bindgen_test]
+fn read_only_view_is_reactive() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_read_only(),
+    );
+
+    let button = crate::get(READ_ONLY_ID);
+    assert_eq!(button.text_content(), Some("count is 0".to_owned()));
+
+    button.click();
+    assert_eq!(button.text_content(), Some("count is 1".to_owned()));
+}
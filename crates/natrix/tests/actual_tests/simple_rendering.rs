@@ -1,3 +1,4 @@
+use natrix::dom::MaybeStaticElement;
 use natrix::prelude::*;
 use wasm_bindgen_test::*;
 
@@ -80,3 +81,16 @@ mod either_test {
         assert_eq!(element.text_content(), Some("hey".to_owned()));
     }
 }
+
+#[wasm_bindgen_test]
+fn render_mixed_children() {
+    let children: Vec<MaybeStaticElement<Empty>> = vec![
+        e::b().text("a").render(),
+        "b".render(),
+        (|_ctx: RenderCtx<Empty>| "c").render(),
+    ];
+    crate::mount_test(Empty, e::div().id(HELLO_ID).children(children));
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("abc".to_owned()));
+}
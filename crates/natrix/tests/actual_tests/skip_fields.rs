@@ -0,0 +1,50 @@
+use std::rc::Rc;
+
+use natrix::prelude::*;
+use natrix::reactivity::state::Snapshot;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+
+struct Config {
+    greeting: &'static str,
+}
+
+#[derive(State, Snapshot)]
+struct App {
+    #[natrix(skip)]
+    config: Rc<Config>,
+    name: Signal<&'static str>,
+}
+
+#[wasm_bindgen_test]
+fn skipped_field_is_moved_by_set() {
+    crate::mount_test(
+        App {
+            config: Rc::new(Config { greeting: "Hello" }),
+            name: Signal::new("world"),
+        },
+        e::button()
+            .id(ROOT)
+            .text(|ctx: RenderCtx<App>| format!("{} {}", ctx.config.greeting, *ctx.name))
+            .on::<events::Click>(|mut ctx: EventCtx<App>, _| {
+                let snapshot = ctx.snapshot();
+                assert!(Rc::ptr_eq(&snapshot.config, &ctx.config));
+
+                ctx.set(App {
+                    config: Rc::new(Config {
+                        greeting: "Goodbye",
+                    }),
+                    name: Signal::new("moon"),
+                });
+            }),
+    );
+
+    let button = crate::get(ROOT);
+    assert_eq!(button.text_content(), Some("Hello world".to_owned()));
+
+    button.click();
+    assert_eq!(button.text_content(), Some("Goodbye moon".to_owned()));
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "storage")]
+use natrix::prelude::*;
+use natrix::storage::{LocalStorage, SessionStorage, StorageError};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const BUTTON: Id = natrix::id!();
+const TEXT: Id = natrix::id!();
+
+#[wasm_bindgen_test]
+fn values_round_trip() {
+    LocalStorage::set("round-trip", &vec![1, 2, 3]).expect("Failed to set value");
+    assert_eq!(
+        LocalStorage::get::<Vec<u8>>("round-trip").expect("Failed to get value"),
+        Some(vec![1, 2, 3])
+    );
+
+    LocalStorage::remove("round-trip").expect("Failed to remove value");
+    assert_eq!(
+        LocalStorage::get::<Vec<u8>>("round-trip").expect("Failed to get value"),
+        None
+    );
+}
+
+#[wasm_bindgen_test]
+fn session_storage_is_separate() {
+    SessionStorage::set("separate", "session").expect("Failed to set value");
+    LocalStorage::remove("separate").expect("Failed to remove value");
+    assert_eq!(
+        SessionStorage::get::<String>("separate").expect("Failed to get value"),
+        Some(String::from("session"))
+    );
+    assert_eq!(
+        LocalStorage::get::<String>("separate").expect("Failed to get value"),
+        None
+    );
+}
+
+#[wasm_bindgen_test]
+fn wrong_type_is_a_error() {
+    LocalStorage::set("wrong-type", "text").expect("Failed to set value");
+    assert!(matches!(
+        LocalStorage::get::<u8>("wrong-type"),
+        Err(StorageError::Deserialize(_))
+    ));
+}
+
+#[derive(State)]
+struct Counter {
+    value: Signal<u8>,
+}
+
+fn render_counter() -> impl Element<Counter> {
+    |mut ctx: RenderCtx<Counter>| {
+        ctx.persist_signal("counter", |ctx: Ref<Counter>| field!(ctx.value));
+        e::button()
+            .id(BUTTON)
+            .child(
+                e::span()
+                    .id(TEXT)
+                    .text(|ctx: RenderCtx<Counter>| *ctx.value),
+            )
+            .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn persist_signal_loads_and_saves() {
+    LocalStorage::set("counter", &5_u8).expect("Failed to set value");
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_counter(),
+    );
+    assert_eq!(crate::get(TEXT).text_content(), Some("5".to_owned()));
+
+    crate::get(BUTTON).click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("6".to_owned()));
+    assert_eq!(
+        LocalStorage::get::<u8>("counter").expect("Failed to get value"),
+        Some(6)
+    );
+}
@@ -0,0 +1,59 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+
+#[derive(State)]
+struct Styled {
+    highlighted: Signal<bool>,
+}
+
+fn render_styled() -> impl Element<Styled> {
+    e::div()
+        .id(ROOT)
+        .style_map(|ctx: RenderCtx<Styled>| {
+            let mut styles = vec![("padding", String::from("4px"))];
+            if *ctx.highlighted {
+                styles.push(("color", String::from("red")));
+            }
+            styles
+        })
+        .on::<events::Click>(|mut ctx: EventCtx<Styled>, _| {
+            *ctx.highlighted = !*ctx.highlighted;
+        })
+}
+
+#[wasm_bindgen_test]
+fn style_map_applies() {
+    crate::mount_test(
+        Styled {
+            highlighted: Signal::new(true),
+        },
+        render_styled(),
+    );
+
+    let style = crate::get(ROOT).style();
+    assert_eq!(style.get_property_value("padding"), Ok("4px".to_owned()));
+    assert_eq!(style.get_property_value("color"), Ok("red".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn style_map_removes_key() {
+    crate::mount_test(
+        Styled {
+            highlighted: Signal::new(true),
+        },
+        render_styled(),
+    );
+
+    let element = crate::get(ROOT);
+    element.click();
+
+    let style = element.style();
+    assert_eq!(style.get_property_value("padding"), Ok("4px".to_owned()));
+    assert_eq!(style.get_property_value("color"), Ok(String::new()));
+
+    element.click();
+    assert_eq!(style.get_property_value("color"), Ok("red".to_owned()));
+}
@@ -0,0 +1,75 @@
+use natrix::dom::svg_elements::SVG_NAMESPACE;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ICON: Id = natrix::id!();
+const SHAPE: Id = natrix::id!();
+
+#[derive(State)]
+struct Drawing {
+    radius: Signal<u8>,
+}
+
+fn render_drawing() -> impl Element<Drawing> {
+    e::svg().id(ICON).attr("viewBox", "0 0 100 100").child(
+        e::svg::g().child(
+            e::svg::circle()
+                .id(SHAPE)
+                .attr("cx", "50")
+                .attr("cy", "50")
+                .attr("r", |ctx: RenderCtx<Drawing>| ctx.radius.to_string())
+                .on::<events::Click>(|mut ctx: EventCtx<Drawing>, _| {
+                    *ctx.radius += 10;
+                }),
+        ),
+    )
+}
+
+fn get_element(id: Id) -> web_sys::Element {
+    web_sys::window()
+        .expect("Failed to get window")
+        .document()
+        .expect("Failed to get document")
+        .get_element_by_id(id.into())
+        .expect("Element not found")
+}
+
+#[wasm_bindgen_test]
+fn svg_elements_use_svg_namespace() {
+    crate::mount_test(
+        Drawing {
+            radius: Signal::new(10),
+        },
+        render_drawing(),
+    );
+
+    let icon = get_element(ICON);
+    assert_eq!(icon.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(icon.tag_name(), "svg");
+    assert_eq!(
+        icon.get_attribute("viewBox").as_deref(),
+        Some("0 0 100 100")
+    );
+
+    let shape = get_element(SHAPE);
+    assert_eq!(shape.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(shape.get_attribute("r").as_deref(), Some("10"));
+}
+
+#[wasm_bindgen_test]
+fn svg_attributes_are_reactive() {
+    crate::mount_test(
+        Drawing {
+            radius: Signal::new(10),
+        },
+        render_drawing(),
+    );
+
+    let shape = get_element(SHAPE);
+    let event = web_sys::MouseEvent::new("click").expect("Failed to create event");
+    shape
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+    assert_eq!(shape.get_attribute("r").as_deref(), Some("20"));
+}
@@ -0,0 +1,69 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: Id = natrix::id!();
+const BUTTON: Id = natrix::id!();
+
+#[derive(State, Default)]
+struct Progress {
+    done: Signal<u8>,
+    total: Signal<u8>,
+}
+
+fn render_progress() -> impl Element<Progress> {
+    e::div()
+        .child(natrix::text_template!(
+            e::p().id(ROOT),
+            |ctx: RenderCtx<Progress>| "Done {} of {}!",
+            *ctx.done,
+            *ctx.total
+        ))
+        .child(
+            e::button()
+                .id(BUTTON)
+                .on::<events::Click>(|mut ctx: EventCtx<Progress>, _| {
+                    *ctx.done += 1;
+                }),
+        )
+}
+
+#[wasm_bindgen_test]
+fn renders_template() {
+    crate::mount_test(
+        Progress {
+            done: Signal::new(1),
+            total: Signal::new(3),
+        },
+        render_progress(),
+    );
+
+    let element = crate::get(ROOT);
+    assert_eq!(element.text_content(), Some("Done 1 of 3!".to_owned()));
+    let mut nodes = 0;
+    let mut node = element.first_child();
+    while let Some(current) = node {
+        nodes += 1;
+        node = current.next_sibling();
+    }
+    assert_eq!(nodes, 5);
+}
+
+#[wasm_bindgen_test]
+fn only_changed_placeholder_updates() {
+    crate::mount_test(Progress::default(), render_progress());
+
+    let element = crate::get(ROOT);
+    let total_node = |element: &web_sys::HtmlElement| {
+        element
+            .last_child()
+            .and_then(|node| node.previous_sibling())
+            .expect("Missing total node")
+    };
+    let total = total_node(&element);
+
+    crate::get(BUTTON).click();
+
+    assert_eq!(element.text_content(), Some("Done 1 of 0!".to_owned()));
+    assert!(total.is_same_node(Some(&total_node(&element))));
+}
@@ -0,0 +1,47 @@
+use natrix::css::theme::{self, ThemeMode};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn root_theme() -> Option<String> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+        .and_then(|root| root.get_attribute("data-theme"))
+}
+
+fn stored_theme() -> Option<String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item("natrix-theme").ok().flatten())
+}
+
+#[wasm_bindgen_test]
+fn set_theme_sets_attribute_and_persists() {
+    theme::set_theme(ThemeMode::Dark);
+    assert_eq!(root_theme().as_deref(), Some("dark"));
+    assert_eq!(stored_theme().as_deref(), Some("dark"));
+    assert_eq!(theme::current_theme(), ThemeMode::Dark);
+
+    theme::set_theme(ThemeMode::System);
+    assert_eq!(root_theme(), None);
+    assert_eq!(stored_theme(), None);
+    assert_eq!(theme::current_theme(), ThemeMode::System);
+}
+
+#[wasm_bindgen_test]
+fn load_theme_applies_stored_choice() {
+    theme::set_theme(ThemeMode::Light);
+    let root = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element());
+    if let Some(root) = root {
+        assert!(root.remove_attribute("data-theme").is_ok());
+    }
+    assert_eq!(root_theme(), None);
+
+    assert_eq!(theme::load_theme(), ThemeMode::Light);
+    assert_eq!(root_theme().as_deref(), Some("light"));
+
+    theme::set_theme(ThemeMode::System);
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "async_utils")]
+
+use natrix::async_utils;
+use natrix::dom::tooltip::{Placement, Tooltip};
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const TARGET: Id = natrix::id!();
+
+#[derive(State)]
+struct Counter {
+    value: Signal<u8>,
+}
+
+fn render_tooltip() -> impl Element<Counter> {
+    e::button()
+        .id(TARGET)
+        .text("Hover me")
+        .tooltip(
+            Tooltip::new(|ctx: RenderCtx<Counter>| format!("Count: {}", *ctx.value))
+                .placement(Placement::Bottom),
+        )
+        .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| *ctx.value += 1)
+}
+
+fn dispatch(target: &web_sys::HtmlElement, event: &str) {
+    let event = web_sys::Event::new(event).expect("Failed to create event");
+    target
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+}
+
+fn get_tooltip(target: &web_sys::HtmlElement) -> web_sys::HtmlElement {
+    let id = target
+        .get_attribute("aria-describedby")
+        .expect("aria-describedby not set");
+    let document = web_sys::window()
+        .expect("Failed to get window")
+        .document()
+        .expect("Failed to get document");
+    wasm_bindgen::JsCast::dyn_into(
+        document
+            .get_element_by_id(&id)
+            .expect("Tooltip not in document"),
+    )
+    .expect("Tooltip not a html element")
+}
+
+#[wasm_bindgen_test]
+async fn tooltip_shows_on_hover() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_tooltip(),
+    );
+
+    let target = crate::get(TARGET);
+    let tooltip = get_tooltip(&target);
+    assert_eq!(tooltip.get_attribute("role"), Some("tooltip".to_owned()));
+    assert_eq!(
+        tooltip.style().get_property_value("display"),
+        Ok("none".to_owned())
+    );
+
+    dispatch(&target, "mouseenter");
+    async_utils::sleep_milliseconds(5).await;
+    assert_eq!(
+        tooltip.style().get_property_value("display"),
+        Ok("block".to_owned())
+    );
+
+    dispatch(&target, "mouseleave");
+    assert_eq!(
+        tooltip.style().get_property_value("display"),
+        Ok("none".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn tooltip_content_is_reactive() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_tooltip(),
+    );
+
+    let target = crate::get(TARGET);
+    let tooltip = get_tooltip(&target);
+    assert_eq!(tooltip.text_content(), Some("Count: 0".to_owned()));
+
+    target.click();
+    assert_eq!(tooltip.text_content(), Some("Count: 1".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn tooltip_removed_on_unmount() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        render_tooltip(),
+    );
+    let tooltip = get_tooltip(&crate::get(TARGET));
+
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        e::div(),
+    );
+    assert!(!tooltip.is_connected());
+}
+
+#[wasm_bindgen_test]
+async fn pending_tooltip_cleared_on_unmount() {
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        e::button()
+            .id(TARGET)
+            .tooltip(Tooltip::new("Delayed").delay_milliseconds(20)),
+    );
+    let target = crate::get(TARGET);
+    let tooltip = get_tooltip(&target);
+    dispatch(&target, "mouseenter");
+
+    crate::mount_test(
+        Counter {
+            value: Signal::new(0),
+        },
+        e::div(),
+    );
+    async_utils::sleep_milliseconds(40).await;
+    assert_eq!(
+        tooltip.style().get_property_value("display"),
+        Ok("none".to_owned())
+    );
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "async_utils")]
+
+use natrix::async_utils;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const BUTTON: Id = natrix::id!();
+const CONTENT: Id = natrix::id!();
+const ENTER: Class = natrix::class!();
+const LEAVE: Class = natrix::class!();
+const INSTANT: Class = natrix::class!();
+
+#[derive(State)]
+struct Toggle {
+    show: Signal<bool>,
+}
+
+fn render_toggle(leave: Class) -> impl Element<Toggle> {
+    e::div()
+        .child(
+            e::button()
+                .id(BUTTON)
+                .on::<events::Click>(|mut ctx: EventCtx<Toggle>, _| {
+                    *ctx.show = !*ctx.show;
+                }),
+        )
+        .child(move |ctx: RenderCtx<Toggle>| {
+            if *ctx.show {
+                Some(e::div().id(CONTENT).transition(ENTER, leave))
+            } else {
+                None
+            }
+        })
+}
+
+fn add_styles() {
+    let document = web_sys::window()
+        .expect("Failed to get window")
+        .document()
+        .expect("Failed to get document");
+    let style = document
+        .create_element("style")
+        .expect("Failed to create style");
+    style.set_text_content(Some(&format!(
+        "@keyframes natrix-test-fade {{ from {{ opacity: 0; }} }}
+        .{} {{ animation: natrix-test-fade 300ms; }}
+        .{} {{ animation: natrix-test-fade 300ms reverse; }}",
+        ENTER.0, LEAVE.0
+    )));
+    document
+        .body()
+        .expect("Failed to get body")
+        .append_child(&style)
+        .expect("Failed to add style");
+}
+
+fn content_exists() -> bool {
+    web_sys::window()
+        .expect("Failed to get window")
+        .document()
+        .expect("Failed to get document")
+        .get_element_by_id(CONTENT.0)
+        .is_some()
+}
+
+#[wasm_bindgen_test]
+fn enter_class_is_applied() {
+    add_styles();
+    crate::mount_test(
+        Toggle {
+            show: Signal::new(true),
+        },
+        render_toggle(LEAVE),
+    );
+
+    assert!(crate::get(CONTENT).class_list().contains(ENTER.0));
+}
+
+#[wasm_bindgen_test]
+async fn leave_animation_finishes_before_removal() {
+    add_styles();
+    crate::mount_test(
+        Toggle {
+            show: Signal::new(true),
+        },
+        render_toggle(LEAVE),
+    );
+
+    crate::get(BUTTON).click();
+
+    let content = crate::get(CONTENT);
+    assert!(content.class_list().contains(LEAVE.0));
+    assert!(!content.class_list().contains(ENTER.0));
+
+    async_utils::sleep_milliseconds(100).await;
+    assert!(content_exists());
+
+    async_utils::sleep_milliseconds(400).await;
+    assert!(!content_exists());
+}
+
+#[wasm_bindgen_test]
+fn removed_right_away_without_animation() {
+    add_styles();
+    crate::mount_test(
+        Toggle {
+            show: Signal::new(true),
+        },
+        render_toggle(INSTANT),
+    );
+
+    crate::get(BUTTON).click();
+    assert!(!content_exists());
+}
+
+#[derive(State)]
+struct Clicker {
+    show: Signal<bool>,
+    clicks: Signal<u32>,
+}
+
+#[wasm_bindgen_test]
+async fn leaving_element_stays_interactive() {
+    add_styles();
+    crate::mount_test(
+        Clicker {
+            show: Signal::new(true),
+            clicks: Signal::new(0),
+        },
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|mut ctx: EventCtx<Clicker>, _| {
+                        *ctx.show = false;
+                    }),
+            )
+            .child(|ctx: RenderCtx<Clicker>| {
+                if *ctx.show {
+                    Some(
+                        e::div()
+                            .id(CONTENT)
+                            .transition(ENTER, LEAVE)
+                            .on::<events::Click>(|mut ctx: EventCtx<Clicker>, _| {
+                                *ctx.clicks += 1;
+                            })
+                            .child(|ctx: RenderCtx<Clicker>| *ctx.clicks),
+                    )
+                } else {
+                    None
+                }
+            }),
+    );
+
+    crate::get(BUTTON).click();
+    let content = crate::get(CONTENT);
+    content.click();
+    assert_eq!(content.text_content(), Some("1".to_owned()));
+
+    async_utils::sleep_milliseconds(500).await;
+    assert!(!content_exists());
+}
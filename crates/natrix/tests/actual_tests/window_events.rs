@@ -0,0 +1,113 @@
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const RESIZES: Id = natrix::id!();
+const VISIBILITY: Id = natrix::id!();
+const TOGGLE: Id = natrix::id!();
+
+#[derive(State)]
+struct Counter {
+    resizes: Signal<u8>,
+    visibility: Signal<u8>,
+    show: Signal<bool>,
+}
+
+fn render_listener() -> impl Element<Counter> {
+    e::div()
+        .on_window::<events::Resize>(|mut ctx: EventCtx<Counter>, _| {
+            *ctx.resizes += 1;
+        })
+        .on_window::<events::VisibilityChange>(|mut ctx: EventCtx<Counter>, _| {
+            *ctx.visibility += 1;
+        })
+}
+
+fn render_window_events() -> impl Element<Counter> {
+    e::div()
+        .child(
+            e::span()
+                .id(RESIZES)
+                .text(|ctx: RenderCtx<Counter>| *ctx.resizes),
+        )
+        .child(
+            e::span()
+                .id(VISIBILITY)
+                .text(|ctx: RenderCtx<Counter>| *ctx.visibility),
+        )
+        .child(
+            e::button()
+                .id(TOGGLE)
+                .on::<events::Click>(|mut ctx: EventCtx<Counter>, _| {
+                    *ctx.show = !*ctx.show;
+                }),
+        )
+        .child(|ctx: RenderCtx<Counter>| {
+            if *ctx.show {
+                Some(render_listener())
+            } else {
+                None
+            }
+        })
+}
+
+fn mount() {
+    crate::mount_test(
+        Counter {
+            resizes: Signal::new(0),
+            visibility: Signal::new(0),
+            show: Signal::new(true),
+        },
+        render_window_events(),
+    );
+}
+
+fn fire(target: &web_sys::EventTarget, name: &str) {
+    let event = web_sys::Event::new(name).expect("Failed to create event");
+    target
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+}
+
+fn fire_resize() {
+    fire(&web_sys::window().expect("No window"), "resize");
+}
+
+fn fire_visibility_change() {
+    let document = web_sys::window()
+        .expect("No window")
+        .document()
+        .expect("No document");
+    fire(&document, "visibilitychange");
+}
+
+#[wasm_bindgen_test]
+fn window_event_fires() {
+    mount();
+
+    fire_resize();
+    fire_resize();
+    assert_eq!(crate::get(RESIZES).text_content(), Some("2".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn document_event_fires() {
+    mount();
+
+    fire_visibility_change();
+    assert_eq!(crate::get(VISIBILITY).text_content(), Some("1".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn listener_removed_on_unmount() {
+    mount();
+
+    fire_resize();
+    crate::get(TOGGLE).click();
+    fire_resize();
+    fire_visibility_change();
+
+    assert_eq!(crate::get(RESIZES).text_content(), Some("1".to_owned()));
+    assert_eq!(crate::get(VISIBILITY).text_content(), Some("0".to_owned()));
+}
@@ -0,0 +1,178 @@
+//! Implement the `component!` macro.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{GenericArgument, ItemFn, PathArguments, ReturnType, Type};
+
+/// The name of the state generic added to stateless components
+const STATE_GENERIC: &str = "__NatrixState";
+
+/// Input to the macro, a list of functions
+pub(crate) struct Input(Vec<ItemFn>);
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut functions = Vec::new();
+        while !input.is_empty() {
+            functions.push(input.parse()?);
+        }
+        Ok(Self(functions))
+    }
+}
+
+/// Expand all the functions in the input
+pub(crate) fn expand(input: Input) -> TokenStream {
+    input
+        .0
+        .into_iter()
+        .map(|function| expand_function(function).unwrap_or_else(syn::Error::into_compile_error))
+        .collect()
+}
+
+/// Get the state type of a `-> Element` or `-> Element<S>` return type.
+///
+/// Returns `Ok(None)` for the stateless `Element` form.
+fn element_state(output: &ReturnType) -> syn::Result<Option<TokenStream>> {
+    let error = || {
+        syn::Error::new(
+            output.span(),
+            "components must return `Element` or `Element<State>`",
+        )
+    };
+
+    let ReturnType::Type(_, return_type) = output else {
+        return Err(error());
+    };
+    let Type::Path(path) = &**return_type else {
+        return Err(error());
+    };
+    if path.qself.is_some() || path.path.segments.len() != 1 {
+        return Err(error());
+    }
+    let Some(segment) = path.path.segments.first() else {
+        return Err(error());
+    };
+    if segment.ident != "Element" {
+        return Err(error());
+    }
+
+    match &segment.arguments {
+        PathArguments::None => Ok(None),
+        PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first() {
+                Some(GenericArgument::Type(state)) => Ok(Some(quote!(#state))),
+                _ => Err(error()),
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+/// Expand a single component function
+fn expand_function(mut function: ItemFn) -> syn::Result<TokenStream> {
+    let state = if let Some(state) = element_state(&function.sig.output)? {
+        state
+    } else {
+        let generic = syn::Ident::new(STATE_GENERIC, Span::call_site());
+        function
+            .sig
+            .generics
+            .params
+            .push(syn::parse_quote!(#generic: ::natrix::macro_ref::State));
+        quote!(#generic)
+    };
+
+    function.sig.output = syn::parse_quote!(-> impl ::natrix::macro_ref::Element<#state>);
+    Ok(quote!(#function))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(input: TokenStream) -> String {
+        match syn::parse2::<Input>(input) {
+            Ok(input) => expand(input).to_string(),
+            Err(error) => error.into_compile_error().to_string(),
+        }
+    }
+
+    #[test]
+    fn stateless_component_is_generic() {
+        let result = expand_str(quote! {
+            /// Say hello
+            pub fn greeting(name: &'static str) -> Element {
+                e::h1().text(name)
+            }
+        });
+        let expected = quote! {
+            /// Say hello
+            pub fn greeting<__NatrixState: ::natrix::macro_ref::State>(name: &'static str)
+                -> impl ::natrix::macro_ref::Element<__NatrixState>
+            {
+                e::h1().text(name)
+            }
+        };
+        assert_eq!(result, expected.to_string());
+    }
+
+    #[test]
+    fn explicit_state_is_kept() {
+        let result = expand_str(quote! {
+            fn counter<T: Copy>(value: T) -> Element<App> {
+                e::div()
+            }
+        });
+        let expected = quote! {
+            fn counter<T: Copy>(value: T) -> impl ::natrix::macro_ref::Element<App> {
+                e::div()
+            }
+        };
+        assert_eq!(result, expected.to_string());
+    }
+
+    #[test]
+    fn generics_are_extended() {
+        let result = expand_str(quote! {
+            fn label<T: ToString>(value: T) -> Element where T: Clone {
+                e::span().text(value.to_string())
+            }
+        });
+        let expected = quote! {
+            fn label<T: ToString, __NatrixState: ::natrix::macro_ref::State>(value: T)
+                -> impl ::natrix::macro_ref::Element<__NatrixState>
+                where T: Clone
+            {
+                e::span().text(value.to_string())
+            }
+        };
+        assert_eq!(result, expected.to_string());
+    }
+
+    #[test]
+    fn multiple_functions() {
+        let result = expand_str(quote! {
+            fn first() -> Element { e::div() }
+            fn second() -> Element<App> { e::div() }
+        });
+        assert!(result.contains("fn first"));
+        assert!(result.contains("fn second"));
+    }
+
+    #[test]
+    fn other_return_types_are_rejected() {
+        for output in [
+            quote!(),
+            quote!(-> impl Element<App>),
+            quote!(-> Element<A, B>),
+        ] {
+            let result = expand_str(quote!(fn broken() #output { e::div() }));
+            assert!(
+                result.contains("components must return"),
+                "{output} should be rejected, got {result}"
+            );
+        }
+    }
+}
@@ -21,7 +21,8 @@ struct Input {
 impl Parse for Input {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let maybe_move = if input.peek(Token![move]) {
-            Some(input.parse()?)
+            let token = input.parse::<Token![move]>()?;
+            Some(token.to_token_stream())
         } else {
             None
         };
@@ -47,10 +48,27 @@ impl Parse for Input {
     }
 }
 
+/// Input to `text_template!`, the element followed by the normal format input
+pub(crate) struct TemplateInput {
+    /// The element to add the text to
+    element: Expr,
+    /// The format input
+    input: Input,
+}
+
+impl Parse for TemplateInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let element = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let input = input.parse()?;
+        Ok(Self { element, input })
+    }
+}
+
 /// The kind of section
 enum Kind {
-    /// Just text (already encoded as a string litral)
-    Text(TokenStream),
+    /// Just text
+    Text(String),
     /// Pop a value from the arguments
     NeedsValues,
 }
@@ -75,9 +93,8 @@ impl Input {
                 }
                 '}' => {
                     if in_bracket {
-                        result.push(Kind::Text(current_string.to_token_stream()));
+                        result.push(Kind::Text(std::mem::take(&mut current_string)));
                         result.push(Kind::NeedsValues);
-                        current_string.clear();
                         in_bracket = false;
                     } else {
                         current_string.push('}');
@@ -95,7 +112,7 @@ impl Input {
             }
         }
 
-        result.push(Kind::Text(current_string.to_token_stream()));
+        result.push(Kind::Text(current_string));
         Ok(result)
     }
 }
@@ -118,7 +135,10 @@ pub(crate) fn format_elements(raw_input: proc_macro::TokenStream) -> proc_macro:
     let elements = segments
         .into_iter()
         .map(|kind| match kind {
-            Kind::Text(value) => Ok(quote!(::natrix::macro_ref::Element::render(#value))),
+            Kind::Text(value) => {
+                let value = value.to_token_stream();
+                Ok(quote!(::natrix::macro_ref::Element::render(#value)))
+            }
             Kind::NeedsValues => {
                 let Some(expression) = expressions.next() else {
                     return Err(syn::Error::new_spanned(
@@ -142,3 +162,93 @@ pub(crate) fn format_elements(raw_input: proc_macro::TokenStream) -> proc_macro:
 
     result.into()
 }
+
+/// actual implementation of `text_template!`
+pub(crate) fn text_template(input: TemplateInput) -> TokenStream {
+    let TemplateInput { element, input } = input;
+    let segments = match input.parse_string() {
+        Ok(segments) => segments,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    let mut expressions = input.expressions.iter();
+    let closure = &input.closure;
+    let maybe_move = &input.maybe_move;
+
+    let mut result = quote!((#element));
+    for kind in segments {
+        match kind {
+            Kind::Text(value) => {
+                // NOTE: Empty text nodes would just be dead weight in the dom.
+                if !value.is_empty() {
+                    result = quote!(#result.text(#value));
+                }
+            }
+            Kind::NeedsValues => {
+                let Some(expression) = expressions.next() else {
+                    return syn::Error::new_spanned(
+                        &input.string_literal,
+                        "Expected more arguments",
+                    )
+                    .into_compile_error();
+                };
+                result = quote!(#result.child(#maybe_move |#closure| #expression));
+            }
+        }
+    }
+
+    if let Some(extra) = expressions.next() {
+        return syn::Error::new_spanned(extra, "Argument never used").into_compile_error();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(input: TokenStream) -> String {
+        match syn::parse2::<TemplateInput>(input) {
+            Ok(input) => text_template(input).to_string(),
+            Err(error) => error.into_compile_error().to_string(),
+        }
+    }
+
+    #[test]
+    fn template_expands_to_children() {
+        let result = expand_str(quote! {
+            e::h1(), |ctx: RenderCtx<App>| "Count {} of {}!", *ctx.count, *ctx.max
+        });
+        let expected = quote! {
+            (e::h1())
+                .text("Count ")
+                .child(|ctx: RenderCtx<App>| *ctx.count)
+                .text(" of ")
+                .child(|ctx: RenderCtx<App>| *ctx.max)
+                .text("!")
+        };
+        assert_eq!(result, expected.to_string());
+    }
+
+    #[test]
+    fn template_skips_empty_text() {
+        let result = expand_str(quote! {
+            e::span(), move |ctx: RenderCtx<App>| "{}{}", *ctx.a, *ctx.b
+        });
+        let expected = quote! {
+            (e::span())
+                .child(move |ctx: RenderCtx<App>| *ctx.a)
+                .child(move |ctx: RenderCtx<App>| *ctx.b)
+        };
+        assert_eq!(result, expected.to_string());
+    }
+
+    #[test]
+    fn template_argument_count_is_checked() {
+        let missing = expand_str(quote!(e::span(), |ctx: R| "{} {}", 1));
+        assert!(missing.contains("Expected more arguments"));
+        let extra = expand_str(quote!(e::span(), |ctx: R| "{}", 1, 2));
+        assert!(extra.contains("Argument never used"));
+    }
+}
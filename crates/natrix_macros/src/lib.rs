@@ -2,14 +2,17 @@
 
 extern crate proc_macro;
 
+mod component;
 mod formatting;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::{fs, io};
 
 use proc_macro2::TokenStream;
-use quote::{ToTokens, format_ident, quote};
+use quote::{ToTokens, quote, quote_spanned};
+use sha2::{Digest, Sha256};
+use syn::spanned::Spanned;
 
 /// Create a array of elements based on the format string.
 /// The start of the macro is a closure argument list, which should generally be `|ctx: R<Self>|`
@@ -23,14 +26,57 @@ pub fn format_elements(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     formatting::format_elements(input)
 }
 
+/// Like `format_elements!`, but adds the text and placeholders directly to the given element.
+///
+/// Each placeholder becomes its own reactive text node, so only the parts that changed are
+/// updated.
+///
+/// ```ignore
+/// text_template!(e::p(), |ctx: RenderCtx<App>| "progress: {}/{}", *ctx.current, *ctx.max)
+/// ```
+#[proc_macro]
+pub fn text_template(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as formatting::TemplateInput);
+    formatting::text_template(input).into()
+}
+
+/// Define stateless components without spelling out the state generic.
+///
+/// Functions returning `Element` get a generic state parameter, so they can be used in any
+/// state, and return `impl Element<State>`.
+/// Functions returning `Element<S>` are kept as is, except for returning `impl Element<S>`.
+/// Arguments, generics, and attributes are passed through unchanged.
+///
+/// ```ignore
+/// component! {
+///     /// A big title
+///     pub fn title(text: &'static str) -> Element {
+///         e::h1().text(text)
+///     }
+///
+///     fn counter() -> Element<App> {
+///         e::span().text(|ctx: RenderCtx<App>| *ctx.count)
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn component(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as component::Input);
+    component::expand(input).into()
+}
+
 /// Derive the `State` trait for a struct
 ///
 /// This mainly just asserts that each field is also a `State`, and implements the `.set` method.
-#[proc_macro_derive(State)]
+/// Fields marked `#[natrix(skip)]` must not be `State`, and are moved over as is by `.set`.
+#[proc_macro_derive(State, attributes(natrix))]
 pub fn state_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let item = syn::parse_macro_input!(item as syn::ItemStruct);
     let name = item.ident.clone();
-    let fields = get_fields(item.fields);
+    let fields = match get_fields(item.fields) {
+        Ok(fields) => fields,
+        Err(error) => return error.into_compile_error().into(),
+    };
 
     let generics = item.generics;
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
@@ -41,16 +87,40 @@ pub fn state_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
         quote! {where}
     };
     let mut set_statements = quote!();
+    let mut skip_checks = quote!();
 
     for field in &fields {
         let type_ = &field.type_;
         let access = &field.access;
 
-        where_clause = quote!(#where_clause #type_: ::natrix::macro_ref::State ,);
-        set_statements = quote!(#set_statements self.#access.set(new.#access););
+        if field.skip {
+            // NOTE: Signals in a skipped field would be tracked by the hooks reading them, but
+            // replaced rather than updated by `.set`, so those hooks would silently go stale.
+            let assertion = quote_spanned! {type_.span()=>
+                use ::natrix::macro_ref::SkippedNotState as _;
+                ::std::assert!(
+                    !::natrix::macro_ref::SkippedField::<#type_>::IS_STATE,
+                    "`#[natrix(skip)]` fields must not be `State`, remove `skip` to make them reactive",
+                );
+            };
+            // NOTE: A inline const in `set` is only evaluated if `set` is used, so it is only
+            // used when the type might refer to generics of the struct.
+            if generics.params.is_empty() {
+                skip_checks = quote!(#skip_checks const _: () = { #assertion };);
+                set_statements = quote!(#set_statements self.#access = new.#access;);
+            } else {
+                set_statements =
+                    quote!(#set_statements const { #assertion } self.#access = new.#access;);
+            }
+        } else {
+            where_clause = quote!(#where_clause #type_: ::natrix::macro_ref::State ,);
+            set_statements = quote!(#set_statements self.#access.set(new.#access););
+        }
     }
 
     quote! {
+        #skip_checks
+
         #[automatically_derived]
         impl #impl_generics ::natrix::macro_ref::State for #name #type_generics #where_clause {
             fn set(&mut self, new: Self) {
@@ -61,6 +131,52 @@ pub fn state_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     .into()
 }
 
+/// Derive the `Snapshot` trait for a struct
+///
+/// This calls `.snapshot` on each field, fields marked `#[natrix(skip)]` are cloned instead.
+#[proc_macro_derive(Snapshot, attributes(natrix))]
+pub fn snapshot_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = syn::parse_macro_input!(item as syn::ItemStruct);
+    let name = item.ident.clone();
+    let fields = match get_fields(item.fields) {
+        Ok(fields) => fields,
+        Err(error) => return error.into_compile_error().into(),
+    };
+
+    let generics = item.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let mut where_clause = if let Some(where_clause) = where_clause {
+        quote! {#where_clause , }
+    } else {
+        quote! {where}
+    };
+    let mut field_snapshots = quote!();
+
+    for field in &fields {
+        let type_ = &field.type_;
+        let access = &field.access;
+
+        if field.skip {
+            where_clause = quote!(#where_clause #type_: ::std::clone::Clone ,);
+            field_snapshots = quote!(#field_snapshots #access: self.#access.clone(),);
+        } else {
+            where_clause = quote!(#where_clause #type_: ::natrix::macro_ref::Snapshot ,);
+            field_snapshots = quote!(#field_snapshots #access: self.#access.snapshot(),);
+        }
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::natrix::macro_ref::Snapshot for #name #type_generics #where_clause {
+            fn snapshot(&self) -> Self {
+                Self { #field_snapshots }
+            }
+        }
+    }
+    .into()
+}
+
 // /// Derive the `Projectable` trait for the given enum.
 // #[proc_macro_derive(Projectable)]
 // pub fn derive_projectable(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -123,68 +239,60 @@ fn emit_file(
     fs::write(output_file, encoded).expect("Failed to write output file.");
 }
 
-/// Inform the bundling system to include the given asset
-/// Will return the url needed to fetch said asset at runtime (including the past path if set).
-///
-/// ```ignore
-/// e::img()
-///     .src(asset!("./my_cool_img.png"))
-/// ```
-#[proc_macro]
-#[expect(
-    clippy::missing_panics_doc,
-    reason = "This can only panic if its not called from cargo, or due to internal macro bugs"
-)]
-pub fn asset(file_path: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    use std::hash::{DefaultHasher, Hash, Hasher};
-
-    let file_path = syn::parse_macro_input!(file_path as syn::LitStr);
-    let file_path = file_path.value();
-
-    #[expect(
-        clippy::expect_used,
-        reason = "This only fails if not called from cargo"
-    )]
-    let package_directory =
-        std::env::var("CARGO_MANIFEST_DIR").expect("Proc macro not called from cargo");
-    let package_directory = PathBuf::from(package_directory);
-    let file_path = package_directory.join(file_path);
-
-    if !file_path.exists() {
-        let err = format!("File {} does not exist.", file_path.display());
-        return quote!(compile_error!(#err)).into();
-    }
-
-    let Ok(settings) = std::env::var(natrix_shared::MACRO_SETTINGS) else {
-        // NOTE:
-        // This is not a hard error because running without the bundler is a expected situation
-        // (cargo check, ides, etc)
-        // But all those situations are also situations where a accurate path is not required as
-        // its no runtime (building a natrix application with just `cargo build` is not supported)
-        // so we return this path that if it ends up in runtime should hopefully be helpful.
-        return quote!("/warn_no_bundler/this_expansion_was_not_via_the_natrix_bundler/as_such_a_proper_path_cant_be_given").into();
-    };
-
-    let mut hasher = DefaultHasher::default();
+/// How many bytes of the hash to include in emitted asset names
+const ASSET_HASH_LENGTH: usize = 16;
 
+/// Calculate the filename a asset is emitted as.
+///
+/// In debug builds this is based on the path, so that editing a asset doesnt require a rebuild,
+/// while release builds use the content for cache busting.
+///
+/// This uses sha256 rather than `DefaultHasher`, as the latter is not stable across toolchains
+/// and platforms, which would make asset urls differ between builds of the same code.
+fn emitted_asset_name(file_path: &Path) -> String {
     #[cfg(debug_assertions)]
-    file_path.hash(&mut hasher);
+    let hash = Sha256::digest(file_path.as_os_str().as_encoded_bytes());
     #[cfg(not(debug_assertions))]
-    if let Ok(content) = fs::read(&file_path) {
-        content.hash(&mut hasher);
+    let hash = if let Ok(content) = fs::read(file_path) {
+        Sha256::digest(content)
     } else {
-        file_path.hash(&mut hasher);
-    }
+        Sha256::digest(file_path.as_os_str().as_encoded_bytes())
+    };
 
-    let hash = hasher.finish();
-    let hash_base64 = data_encoding::BASE64URL_NOPAD.encode(&hash.to_le_bytes());
+    let hash = hash.get(..ASSET_HASH_LENGTH).unwrap_or(hash.as_slice());
+    let hash_base64 = data_encoding::BASE64URL_NOPAD.encode(hash);
 
-    let target = if let Some(file_name) = file_path.file_name() {
+    if let Some(file_name) = file_path.file_name() {
         let file_name = file_name.to_string_lossy();
         format!("{hash_base64}-{file_name}")
     } else {
         hash_base64
-    };
+    }
+}
+
+/// Placeholder url used when the macros are not expanded by the bundler.
+///
+/// This is not a hard error because running without the bundler is a expected situation
+/// (cargo check, ides, etc)
+/// But all those situations are also situations where a accurate path is not required as
+/// its no runtime (building a natrix application with just `cargo build` is not supported)
+/// so we return this path that if it ends up in runtime should hopefully be helpful.
+const NO_BUNDLER_URL: &str = "/warn_no_bundler/this_expansion_was_not_via_the_natrix_bundler/as_such_a_proper_path_cant_be_given";
+
+/// Resolve a path relative to the calling crates `Cargo.toml`
+fn package_path(path: &str) -> PathBuf {
+    #[expect(
+        clippy::expect_used,
+        reason = "This only fails if not called from cargo"
+    )]
+    let package_directory =
+        std::env::var("CARGO_MANIFEST_DIR").expect("Proc macro not called from cargo");
+    PathBuf::from(package_directory).join(path)
+}
+
+/// Read the settings set by the bundler, returning `None` if not called from the bundler.
+fn bundler_settings() -> Option<natrix_shared::macros::Settings> {
+    let settings = std::env::var(natrix_shared::MACRO_SETTINGS).ok()?;
 
     #[expect(clippy::expect_used, reason = "We should have a valid base64 string")]
     let settings = data_encoding::BASE64_NOPAD
@@ -199,10 +307,36 @@ pub fn asset(file_path: proc_macro::TokenStream) -> proc_macro::TokenStream {
         )
         .expect("Failed to decode settings");
 
+    Some(settings)
+}
+
+/// Inform the bundling system to include the given asset
+/// Will return the url needed to fetch said asset at runtime (including the past path if set).
+///
+/// ```ignore
+/// e::img()
+///     .src(asset!("./my_cool_img.png"))
+/// ```
+#[proc_macro]
+pub fn asset(file_path: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let file_path = syn::parse_macro_input!(file_path as syn::LitStr);
+    let file_path = package_path(&file_path.value());
+
+    if !file_path.exists() {
+        let err = format!("File {} does not exist.", file_path.display());
+        return quote!(compile_error!(#err)).into();
+    }
+
+    let Some(settings) = bundler_settings() else {
+        return quote!(#NO_BUNDLER_URL).into();
+    };
+
+    let target = emitted_asset_name(&file_path);
     let url = format!("{}/{target}", settings.base_path);
 
     let result = quote!(#url).into();
     let asset = natrix_shared::macros::MacroEmisson::Asset {
+        content_type: natrix_shared::macros::content_type(&file_path).to_owned(),
         path: file_path,
         emitted_path: target,
     };
@@ -211,35 +345,203 @@ pub fn asset(file_path: proc_macro::TokenStream) -> proc_macro::TokenStream {
     result
 }
 
+/// Collect the files in a directory and its sub directories, as paths relative to it.
+///
+/// The paths use `/` as the separator, and are sorted so the expansion is deterministic.
+fn collect_dir_files(directory: &Path) -> io::Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in fs::read_dir(directory.join(&relative))? {
+            let entry = entry?;
+            let relative = relative.join(entry.file_name());
+            if entry.path().is_dir() {
+                pending.push(relative);
+            } else {
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect();
+                files.push(parts.join("/"));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Inform the bundling system to include every file in the given directory.
+/// Will return a `natrix::AssetDir`, whose `.url` method returns the url of a file in it.
+///
+/// The files keep their paths relative to the directory, so urls between them (such as fonts
+/// referenced from a stylesheet) keep working.
+///
+/// ```ignore
+/// const PUBLIC: AssetDir = dir_asset!("./public");
+/// e::img()
+///     .src(PUBLIC.url("images/logo.png"))
+/// ```
+#[proc_macro]
+pub fn dir_asset(dir_path: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let dir_path = syn::parse_macro_input!(dir_path as syn::LitStr);
+    let dir_path = package_path(&dir_path.value());
+
+    if !dir_path.is_dir() {
+        let err = format!("Directory {} does not exist.", dir_path.display());
+        return quote!(compile_error!(#err)).into();
+    }
+    let files = match collect_dir_files(&dir_path) {
+        Ok(files) => files,
+        Err(err) => {
+            let err = format!("Failed to read directory {}: {err}", dir_path.display());
+            return quote!(compile_error!(#err)).into();
+        }
+    };
+
+    let Some(settings) = bundler_settings() else {
+        return quote!(::natrix::AssetDir::__new(#NO_BUNDLER_URL, &[#(#files),*])).into();
+    };
+
+    let target = emitted_asset_name(&dir_path);
+    let url = format!("{}/{target}", settings.base_path);
+
+    let result = quote!(::natrix::AssetDir::__new(#url, &[#(#files),*])).into();
+    let asset = natrix_shared::macros::MacroEmisson::AssetDir {
+        files: files
+            .into_iter()
+            .map(|relative_path| natrix_shared::macros::AssetDirFile {
+                emitted_path: format!("{target}/{relative_path}"),
+                content_type: natrix_shared::macros::content_type(Path::new(&relative_path))
+                    .to_owned(),
+                relative_path,
+            })
+            .collect(),
+        path: dir_path,
+    };
+
+    emit_file(asset, &settings);
+    result
+}
+
 /// A abstract representation of a struct field
 struct Field {
     /// The type of the field
     type_: TokenStream,
     /// How to access the field
     access: TokenStream,
+    /// Whether the field is marked `#[natrix(skip)]`, and so is not reactive
+    skip: bool,
 }
 
-/// Retrieve abstract fields from a struct, as well as a boolean indicating whether its a named
-/// struct or not (unit structs are considered named)
-pub(crate) fn get_fields(fields: syn::Fields) -> Vec<Field> {
+/// Check whether a field is marked `#[natrix(skip)]`
+fn is_skipped(attributes: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attribute in attributes {
+        if !attribute.path().is_ident("natrix") {
+            continue;
+        }
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown natrix attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Retrieve abstract fields from a struct
+pub(crate) fn get_fields(fields: syn::Fields) -> syn::Result<Vec<Field>> {
     match fields {
-        syn::Fields::Unit => vec![],
+        syn::Fields::Unit => Ok(vec![]),
         syn::Fields::Named(fields) => fields
             .named
             .into_iter()
-            .map(|field| Field {
-                type_: field.ty.into_token_stream(),
-                access: field.ident.to_token_stream(),
+            .map(|field| {
+                Ok(Field {
+                    skip: is_skipped(&field.attrs)?,
+                    type_: field.ty.into_token_stream(),
+                    access: field.ident.to_token_stream(),
+                })
             })
             .collect(),
         syn::Fields::Unnamed(fields) => fields
             .unnamed
             .into_iter()
             .enumerate()
-            .map(|(index, field)| Field {
-                type_: field.ty.to_token_stream(),
-                access: proc_macro2::Literal::usize_unsuffixed(index).to_token_stream(),
+            .map(|(index, field)| {
+                Ok(Field {
+                    skip: is_skipped(&field.attrs)?,
+                    type_: field.ty.to_token_stream(),
+                    access: proc_macro2::Literal::usize_unsuffixed(index).to_token_stream(),
+                })
             })
             .collect(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_attribute() -> syn::Result<()> {
+        let fields: syn::FieldsNamed = syn::parse_quote!({
+            #[natrix(skip)]
+            config: Rc<Config>,
+            #[doc = "Not a natrix attribute"]
+            counter: Signal<u8>,
+        });
+        let fields = get_fields(syn::Fields::Named(fields))?;
+        let skipped: Vec<bool> = fields.iter().map(|field| field.skip).collect();
+        assert_eq!(skipped, [true, false]);
+
+        let fields: syn::FieldsNamed = syn::parse_quote!({
+            #[natrix(skipp)]
+            config: Rc<Config>,
+        });
+        assert!(get_fields(syn::Fields::Named(fields)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn asset_name_is_deterministic() {
+        let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+        assert_eq!(emitted_asset_name(path), emitted_asset_name(path));
+    }
+
+    #[test]
+    fn asset_name_keeps_file_name() {
+        let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+        assert!(emitted_asset_name(path).ends_with("-Cargo.toml"));
+    }
+
+    #[test]
+    fn different_assets_get_different_names() {
+        assert_ne!(
+            emitted_asset_name(Path::new("/first/logo.png")),
+            emitted_asset_name(Path::new("/second/logo.png"))
+        );
+    }
+
+    #[test]
+    fn dir_files_are_relative_and_sorted() -> io::Result<()> {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let files = collect_dir_files(directory)?;
+
+        assert!(files.contains(&String::from("Cargo.toml")));
+        assert!(files.contains(&String::from("src/lib.rs")));
+        assert!(files.iter().all(|file| !file.starts_with('/')));
+        assert!(files.is_sorted());
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn asset_name_is_stable() {
+        let name = emitted_asset_name(Path::new("/assets/logo.png"));
+        assert_eq!(name, "QBhLes1KKUAFqW9BHI_IPw-logo.png");
+    }
+}
@@ -3,9 +3,22 @@
 /// The mount point for the auto generated `index.html` from the cli.
 pub const MOUNT_POINT: &str = "NATRIX_MOUNT";
 
+/// The id of the `<style>` element the css is injected into in dev mode, used by the dev server
+/// to hot swap the css.
+pub const DEV_STYLE_ID: &str = "NATRIX_DEV_STYLE";
+
+/// The line separating the css from the symbol definition sites in the output of bundle mode.
+///
+/// Each line after it is the symbol, kind (`class` or `id`), file, line, and column of a
+/// `class!()` or `id!()` call, separated by tabs.
+pub const SYMBOLS_MARKER: &str = "/* NATRIX_SYMBOLS */";
+
 /// The env var for setting macro settings
 pub const MACRO_SETTINGS: &str = "NATRIX_MACRO_SETTINGS";
 
+/// The env var the base path is passed to the app in, read with `option_env!` by `natrix::base_path`
+pub const BASE_PATH: &str = "NATRIX_BASE_PATH";
+
 /// Code used for macros and bundler
 #[cfg(feature = "macros")]
 pub mod macros {
@@ -22,7 +35,63 @@ pub mod macros {
             path: std::path::PathBuf,
             /// The emitted name for the asset
             emitted_path: String,
+            /// The `Content-Type` to serve the asset with
+            content_type: String,
         },
+        /// A directory of assets that need bundling
+        AssetDir {
+            /// The path to the directory
+            path: std::path::PathBuf,
+            /// The files in the directory
+            files: Vec<AssetDirFile>,
+        },
+    }
+
+    /// A file in a [`MacroEmisson::AssetDir`]
+    #[derive(bincode::Decode, bincode::Encode)]
+    pub struct AssetDirFile {
+        /// The path of the file relative to the directory, using `/` as the separator
+        pub relative_path: String,
+        /// The emitted name for the file
+        pub emitted_path: String,
+        /// The `Content-Type` to serve the file with
+        pub content_type: String,
+    }
+
+    /// Guess the `Content-Type` of a file from its extension
+    #[must_use]
+    pub fn content_type(path: &std::path::Path) -> &'static str {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("html" | "htm") => "text/html",
+            Some("js" | "mjs") => "text/javascript",
+            Some("css") => "text/css",
+            Some("wasm") => "application/wasm",
+            Some("json" | "map") => "application/json",
+            Some("txt") => "text/plain",
+            Some("xml") => "application/xml",
+            Some("pdf") => "application/pdf",
+            Some("png") => "image/png",
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            Some("avif") => "image/avif",
+            Some("svg") => "image/svg+xml",
+            Some("ico") => "image/x-icon",
+            Some("woff") => "font/woff",
+            Some("woff2") => "font/woff2",
+            Some("ttf") => "font/ttf",
+            Some("otf") => "font/otf",
+            Some("mp3") => "audio/mpeg",
+            Some("ogg") => "audio/ogg",
+            Some("wav") => "audio/wav",
+            Some("mp4") => "video/mp4",
+            Some("webm") => "video/webm",
+            None | Some(_) => "application/octet-stream",
+        }
     }
 
     /// The settings for the macros